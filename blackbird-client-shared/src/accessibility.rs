@@ -0,0 +1,202 @@
+//! Screen-reader text for controls that are otherwise only icons or
+//! painted shapes, and the platform's reduced-motion preference.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::process::Command;
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use blackbird_core::{
+    PlaybackState, TrackDisplayDetails, blackbird_state::FetchProgress, util::seconds_to_hms_string,
+};
+
+/// Describes the playing track, e.g. "Paused: Title by Artist, from Album by
+/// Album Artist, 1:05 of 3:30".
+pub fn now_playing_description(details: &TrackDisplayDetails, state: PlaybackState) -> String {
+    let prefix = match state {
+        PlaybackState::Playing => "Playing",
+        PlaybackState::Paused => "Paused",
+        PlaybackState::Stopped => "Stopped",
+    };
+    let artist = details
+        .track_artist
+        .as_deref()
+        .unwrap_or(&details.album_artist);
+    format!(
+        "{prefix}: {} by {artist}, from {} by {}, {}",
+        details.track_title,
+        details.album_name,
+        details.album_artist,
+        position_description(details.track_position, details.track_duration),
+    )
+}
+
+/// Describes a position within a track, e.g. "1:05 of 3:30", or just "1:05"
+/// if the track's length isn't known.
+pub fn position_description(position: Duration, duration: Duration) -> String {
+    let position = seconds_to_hms_string(position.as_secs() as u32, false);
+    if duration.is_zero() {
+        return position;
+    }
+    format!(
+        "{position} of {}",
+        seconds_to_hms_string(duration.as_secs() as u32, false)
+    )
+}
+
+/// What a star toggle applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StarTarget {
+    Track,
+    Album,
+}
+
+/// The label for a star toggle, describing what activating it does.
+pub fn star_label(target: StarTarget, starred: bool) -> &'static str {
+    match (target, starred) {
+        (StarTarget::Track, false) => "Star track",
+        (StarTarget::Track, true) => "Unstar track",
+        (StarTarget::Album, false) => "Star album",
+        (StarTarget::Album, true) => "Unstar album",
+    }
+}
+
+static PLATFORM_PREFERS_REDUCED_MOTION: AtomicBool = AtomicBool::new(false);
+
+/// Starts checking whether the platform asks for animations to be reduced.
+/// The check runs a subprocess, so it's done on a background thread; call
+/// this once at startup.
+pub fn detect_platform_reduced_motion() {
+    std::thread::spawn(|| {
+        if query_platform_reduced_motion() {
+            PLATFORM_PREFERS_REDUCED_MOTION.store(true, Ordering::Relaxed);
+        }
+    });
+}
+
+/// Whether the platform asks for animations to be reduced: GNOME's
+/// `enable-animations` turned off on Linux, or Reduce Motion on macOS.
+/// `false` until [`detect_platform_reduced_motion`] has finished, and where
+/// it can't be detected.
+pub fn platform_prefers_reduced_motion() -> bool {
+    PLATFORM_PREFERS_REDUCED_MOTION.load(Ordering::Relaxed)
+}
+
+#[cfg(target_os = "linux")]
+fn query_platform_reduced_motion() -> bool {
+    command_outputs(
+        Command::new("gsettings").args(["get", "org.gnome.desktop.interface", "enable-animations"]),
+        "false",
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn query_platform_reduced_motion() -> bool {
+    command_outputs(
+        Command::new("defaults").args(["read", "com.apple.universalaccess", "reduceMotion"]),
+        "1",
+    )
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn query_platform_reduced_motion() -> bool {
+    false
+}
+
+/// Whether `command` succeeds and prints `expected`.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn command_outputs(command: &mut Command, expected: &str) -> bool {
+    command
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .is_some_and(|output| String::from_utf8_lossy(&output.stdout).trim() == expected)
+}
+
+/// A single static line describing library loading progress, shown in place
+/// of the loading animation when motion is reduced.
+pub fn loading_progress_text(progress: Option<FetchProgress>) -> String {
+    match progress {
+        None => "Connecting".into(),
+        Some(p) if p.tracks_fetched == 0 => {
+            format!("Fetching albums: {} so far", p.albums_fetched)
+        }
+        Some(p) => {
+            let percent =
+                (u64::from(p.tracks_fetched) * 100 / u64::from(p.expected_tracks.max(1))).min(100);
+            format!("Fetching tracks: {percent}%")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blackbird_core::blackbird_state::{AlbumId, TrackId};
+
+    use super::*;
+
+    fn details(track_artist: Option<&str>) -> TrackDisplayDetails {
+        TrackDisplayDetails {
+            album_id: AlbumId("al".into()),
+            album_name: "Album".into(),
+            album_artist: "Album Artist".into(),
+            cover_art_id: None,
+            track_id: TrackId("t".into()),
+            track_title: "Title".into(),
+            track_artist: track_artist.map(Into::into),
+            track_duration: Duration::from_secs(210),
+            track_position: Duration::from_secs(65),
+            show_time: true,
+            starred: false,
+            play_count: None,
+        }
+    }
+
+    #[test]
+    fn now_playing_description_reads_as_a_sentence() {
+        assert_eq!(
+            now_playing_description(&details(Some("Artist")), PlaybackState::Paused),
+            "Paused: Title by Artist, from Album by Album Artist, 1:05 of 3:30"
+        );
+        assert_eq!(
+            now_playing_description(&details(None), PlaybackState::Playing),
+            "Playing: Title by Album Artist, from Album by Album Artist, 1:05 of 3:30"
+        );
+    }
+
+    #[test]
+    fn unknown_duration_is_left_out() {
+        assert_eq!(
+            position_description(Duration::from_secs(65), Duration::ZERO),
+            "1:05"
+        );
+    }
+
+    #[test]
+    fn loading_progress_text_shows_a_percentage_of_tracks() {
+        assert_eq!(loading_progress_text(None), "Connecting");
+
+        let mut progress = FetchProgress {
+            albums_fetched: 20,
+            expected_tracks: 240,
+            ..Default::default()
+        };
+        assert_eq!(
+            loading_progress_text(Some(progress)),
+            "Fetching albums: 20 so far"
+        );
+
+        progress.tracks_fetched = 100;
+        assert_eq!(
+            loading_progress_text(Some(progress)),
+            "Fetching tracks: 41%"
+        );
+    }
+
+    #[test]
+    fn star_labels_describe_the_action() {
+        assert_eq!(star_label(StarTarget::Track, false), "Star track");
+        assert_eq!(star_label(StarTarget::Album, true), "Unstar album");
+    }
+}
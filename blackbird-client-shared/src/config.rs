@@ -1,7 +1,16 @@
 /// Configuration types shared between the egui and TUI clients.
-use std::time::Duration;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    time::Duration,
+};
 
-use blackbird_core::{PlaybackMode, SortOrder, blackbird_state::TrackId};
+use blackbird_core::{
+    DEFAULT_NEXT_GROUP_PREFETCH_TRACKS, DEFAULT_PREVIOUS_RESTART_THRESHOLD, EndBehavior,
+    PlaybackErrorBehavior, PlaybackMode, PreviousBehavior, QueueEndBehavior, ScrobbleTargets,
+    SortOrder, StopBehavior,
+    blackbird_state::{Group, TrackId},
+};
 use serde::{Deserialize, Serialize};
 
 /// Controls how album art is displayed in the library view.
@@ -28,6 +37,91 @@ impl AlbumArtStyle {
     }
 }
 
+/// What is shown in place of the art of albums without any.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingArt {
+    /// The same placeholder for every album.
+    #[default]
+    Placeholder,
+    /// A block of colour derived from the artist and album, so that each
+    /// album has its own.
+    Color,
+}
+
+impl MissingArt {
+    /// All variants for UI display/cycling.
+    pub const ALL: &[MissingArt] = &[MissingArt::Placeholder, MissingArt::Color];
+
+    /// Returns a human-readable label for display in UI.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MissingArt::Placeholder => "placeholder",
+            MissingArt::Color => "album colour",
+        }
+    }
+
+    /// Whether `group` is shown as a block of colour in place of its art:
+    /// when configured to, for albums the server reports no art for.
+    pub fn shows_color(self, group: &Group) -> bool {
+        self == MissingArt::Color && !group.has_server_art()
+    }
+}
+
+/// The panel a client opens in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPanel {
+    #[default]
+    Library,
+    Search,
+    Lyrics,
+    Queue,
+    Recent,
+}
+
+/// When the library scrolls to a track that starts playing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FollowPlaying {
+    /// Scroll to every track that starts playing.
+    Always,
+    /// Stay put if the library was scrolled since the last track change, so
+    /// browsing isn't interrupted.
+    #[default]
+    UntilUserScrolls,
+    /// Only scroll to the playing track on request.
+    Never,
+}
+
+impl FollowPlaying {
+    /// All variants for UI display/cycling.
+    pub const ALL: &[FollowPlaying] = &[
+        FollowPlaying::Always,
+        FollowPlaying::UntilUserScrolls,
+        FollowPlaying::Never,
+    ];
+
+    /// Returns a human-readable label for display in UI.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FollowPlaying::Always => "always",
+            FollowPlaying::UntilUserScrolls => "until scrolled",
+            FollowPlaying::Never => "never",
+        }
+    }
+
+    /// Whether to scroll to a track that started playing, given whether the
+    /// user has scrolled the library since the last track change.
+    pub fn should_follow(self, user_scrolled: bool) -> bool {
+        match self {
+            FollowPlaying::Always => true,
+            FollowPlaying::UntilUserScrolls => !user_scrolled,
+            FollowPlaying::Never => false,
+        }
+    }
+}
+
 /// Layout configuration for the library and player UI.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
@@ -44,6 +138,35 @@ pub struct Layout {
     /// Scroll multiplier for mouse wheel scrolling.
     #[serde(default = "default_scroll_multiplier")]
     pub scroll_multiplier: f32,
+    /// Size in pixels requested from the server for the enlarged album art
+    /// view. Unset requests the original image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_art_size: Option<usize>,
+    /// Whether to show the editions of an album (e.g. the standard and
+    /// deluxe editions) as one group, with the tracks they share shown once.
+    /// Takes effect when the library is next fetched, e.g. after a rescan.
+    #[serde(default)]
+    pub merge_editions: bool,
+    /// Whether to abbreviate play counts, e.g. 1234 as "1.2k".
+    #[serde(default)]
+    pub abbreviate_play_counts: bool,
+    /// The panel opened at startup, over the library.
+    #[serde(default)]
+    pub startup_panel: StartupPanel,
+    /// When the library scrolls to a track that starts playing.
+    #[serde(default)]
+    pub follow_playing: FollowPlaying,
+    /// What is shown in place of the art of albums without any.
+    #[serde(default)]
+    pub missing_art: MissingArt,
+    /// Whether album headers show the album's ReplayGain, to spot loudly or
+    /// quietly mastered albums.
+    #[serde(default)]
+    pub show_album_gain: bool,
+    /// Whether to show static progress in place of loading animations. On
+    /// regardless if the platform asks for reduced motion.
+    #[serde(default)]
+    pub reduced_motion: bool,
 }
 impl Default for Layout {
     fn default() -> Self {
@@ -52,9 +175,24 @@ impl Default for Layout {
             album_art_style: AlbumArtStyle::default(),
             album_spacing: default_album_spacing(),
             scroll_multiplier: default_scroll_multiplier(),
+            full_art_size: None,
+            merge_editions: false,
+            abbreviate_play_counts: false,
+            startup_panel: StartupPanel::default(),
+            follow_playing: FollowPlaying::default(),
+            missing_art: MissingArt::default(),
+            show_album_gain: false,
+            reduced_motion: false,
         }
     }
 }
+impl Layout {
+    /// Whether animations should be replaced with static indicators, either
+    /// as configured or as the platform asks.
+    pub fn wants_reduced_motion(&self) -> bool {
+        self.reduced_motion || crate::accessibility::platform_prefers_reduced_motion()
+    }
+}
 
 fn default_scroll_multiplier() -> f32 {
     50.0
@@ -80,6 +218,18 @@ pub struct Config {
     /// Playback-related settings shared across clients.
     #[serde(default)]
     pub playback: Playback,
+    /// Play queue settings shared across clients.
+    #[serde(default)]
+    pub queue: Queue,
+    /// Clipboard settings shared across clients.
+    #[serde(default)]
+    pub clipboard: Clipboard,
+    /// Where downloaded tracks are saved.
+    #[serde(default)]
+    pub downloads: Downloads,
+    /// Search settings shared across clients.
+    #[serde(default)]
+    pub search: Search,
 }
 
 fn default_true() -> bool {
@@ -99,16 +249,245 @@ pub struct Playback {
     /// applies, so tracks with high peaks may be attenuated below this value.
     #[serde(default)]
     pub replaygain_preamp_db: f32,
+    /// User gains in dB for individual tracks, applied on top of ReplayGain
+    /// and volume. Intended for the odd track whose tags are wrong.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub track_gains_db: BTreeMap<TrackId, f32>,
+    /// Tracks that Next, Previous and the end of a track always step over,
+    /// e.g. intros and skits. Picking one still plays it.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub skipped_tracks: BTreeSet<TrackId>,
+    /// Whether to skip silence at the start of tracks and move on to the
+    /// next track once only silence remains. Meant for rips with long
+    /// silent lead-ins or tails.
+    #[serde(default)]
+    pub skip_silence: bool,
+    /// Level in dB below which audio counts as silence for `skip_silence`.
+    #[serde(default = "default_skip_silence_threshold_db")]
+    pub skip_silence_threshold_db: f32,
+    /// Extra output devices that play the same audio as the default device,
+    /// in sync with it (e.g. speakers in another room, or headphones for
+    /// monitoring), by device name with a volume from 0 to 1 relative to
+    /// the main volume. Unknown device names are logged along with the
+    /// available ones.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra_outputs: BTreeMap<String, f32>,
+    /// What Next and Previous do at the ends of the queue, per playback mode.
+    #[serde(default)]
+    pub end_behavior: EndBehavior,
+    /// What the stop button does: rewind and pause the current track, or
+    /// unload it.
+    #[serde(default)]
+    pub stop_behavior: StopBehavior,
+    /// What happens when a track fails to load or play: skip to the next
+    /// track, or pause so the error can be looked into.
+    #[serde(default)]
+    pub on_playback_error: PlaybackErrorBehavior,
+    /// Whether Previous always goes to the previous track, or first restarts
+    /// the current track once past `previous_restart_threshold_secs`.
+    #[serde(default)]
+    pub previous_behavior: PreviousBehavior,
+    /// How far into a track, in seconds, Previous restarts it rather than
+    /// going to the previous track.
+    #[serde(default = "default_previous_restart_threshold_secs")]
+    pub previous_restart_threshold_secs: f32,
+    /// Where plays are scrobbled: the Subsonic server, ListenBrainz, or both.
+    #[serde(default)]
+    pub scrobbling: ScrobbleTargets,
+    /// How many tracks from the end of an album to start prefetching the
+    /// next album's first track; zero disables it.
+    #[serde(default = "default_next_group_prefetch_tracks")]
+    pub next_group_prefetch_tracks: usize,
+    /// Whether selecting an album that was left partway through resumes it
+    /// from the last-played track, rather than its first.
+    #[serde(default)]
+    pub resume_albums: bool,
+    /// Minutes without any input, while playing, after which playback is
+    /// paused; zero disables it. Playback has to be resumed manually.
+    #[serde(default)]
+    pub idle_pause_minutes: u32,
+    /// Whether `idle_pause_minutes` also applies while the window is in the
+    /// background. Input is only seen while the window is focused, so this
+    /// is off by default to keep a background player going while using
+    /// other apps.
+    #[serde(default)]
+    pub idle_pause_in_background: bool,
+    /// Whether pressing Next a second time within `double_press_next_ms`
+    /// goes to the next album instead of the next track.
+    #[serde(default)]
+    pub double_press_next_group: bool,
+    /// How soon, in milliseconds, a second press of Next has to follow the
+    /// first to count as a double press.
+    #[serde(default = "default_double_press_next_ms")]
+    pub double_press_next_ms: u64,
+    /// Whether the digit keys 0-9 in the library seek to 0%-90% of the
+    /// current track. Turn this off to free the digits for other bindings.
+    #[serde(default = "default_true")]
+    pub seek_with_digits: bool,
+    /// The name of a server playlist to keep in sync with the starred
+    /// tracks, so that other clients can play them; created if it doesn't
+    /// exist. Unset by default, which leaves the server's playlists alone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favorites_playlist: Option<String>,
 }
 impl Default for Playback {
     fn default() -> Self {
         Self {
             apply_replaygain: true,
             replaygain_preamp_db: 0.0,
+            track_gains_db: BTreeMap::new(),
+            skipped_tracks: BTreeSet::new(),
+            skip_silence: false,
+            skip_silence_threshold_db: default_skip_silence_threshold_db(),
+            extra_outputs: BTreeMap::new(),
+            end_behavior: EndBehavior::default(),
+            stop_behavior: StopBehavior::default(),
+            on_playback_error: PlaybackErrorBehavior::default(),
+            previous_behavior: PreviousBehavior::default(),
+            previous_restart_threshold_secs: default_previous_restart_threshold_secs(),
+            scrobbling: ScrobbleTargets::default(),
+            next_group_prefetch_tracks: default_next_group_prefetch_tracks(),
+            resume_albums: false,
+            idle_pause_minutes: 0,
+            idle_pause_in_background: false,
+            double_press_next_group: false,
+            double_press_next_ms: default_double_press_next_ms(),
+            seek_with_digits: true,
+            favorites_playlist: None,
         }
     }
 }
 
+impl Playback {
+    /// The silence threshold to pass to `Logic`, or `None` if skipping
+    /// silence is disabled.
+    pub fn skip_silence_db(&self) -> Option<f32> {
+        self.skip_silence.then_some(self.skip_silence_threshold_db)
+    }
+
+    /// The Previous restart threshold as a `Duration`. Negative or non-finite
+    /// values are treated as zero.
+    pub fn previous_restart_threshold(&self) -> Duration {
+        Duration::try_from_secs_f32(self.previous_restart_threshold_secs).unwrap_or_default()
+    }
+
+    /// The idle time to pass to `Logic`, or `None` if idle pause is
+    /// disabled.
+    pub fn idle_pause_after(&self) -> Option<Duration> {
+        (self.idle_pause_minutes > 0)
+            .then(|| Duration::from_secs(u64::from(self.idle_pause_minutes) * 60))
+    }
+
+    /// The double press window to pass to
+    /// [`NextPress::press`](crate::double_press::NextPress::press), or `None`
+    /// if double-pressing Next is disabled.
+    pub fn double_press_next_window(&self) -> Option<Duration> {
+        self.double_press_next_group
+            .then(|| Duration::from_millis(self.double_press_next_ms))
+    }
+}
+
+fn default_skip_silence_threshold_db() -> f32 {
+    -60.0
+}
+
+fn default_previous_restart_threshold_secs() -> f32 {
+    DEFAULT_PREVIOUS_RESTART_THRESHOLD.as_secs_f32()
+}
+
+fn default_next_group_prefetch_tracks() -> usize {
+    DEFAULT_NEXT_GROUP_PREFETCH_TRACKS
+}
+
+fn default_double_press_next_ms() -> u64 {
+    300
+}
+
+/// Play queue settings shared across clients.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Queue {
+    /// What happens once the last queued track finishes: `stop`,
+    /// `continue_library` to carry on in library order, or `loop` to play
+    /// the queued tracks again.
+    #[serde(default)]
+    pub on_empty: QueueEndBehavior,
+}
+
+/// Clipboard settings shared across clients.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Clipboard {
+    /// Template for copying the playing track, e.g. `{artist} - {title}
+    /// ({album})`. See [`crate::now_playing_text::format`] for the
+    /// placeholders.
+    #[serde(default = "default_now_playing_template")]
+    pub now_playing_template: String,
+}
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self {
+            now_playing_template: default_now_playing_template(),
+        }
+    }
+}
+
+fn default_now_playing_template() -> String {
+    crate::now_playing_text::DEFAULT_TEMPLATE.to_string()
+}
+
+/// Search settings shared across clients.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Search {
+    /// Whether past queries are kept across sessions, rather than only for
+    /// the current one.
+    #[serde(default)]
+    pub persist_history: bool,
+    /// Past queries, oldest first. Only written when `persist_history` is
+    /// on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<String>,
+}
+impl Search {
+    /// The history to start the session with.
+    pub fn initial_history(&self) -> crate::search_history::SearchHistory {
+        let entries = if self.persist_history {
+            self.history.clone()
+        } else {
+            Vec::new()
+        };
+        crate::search_history::SearchHistory::new(entries)
+    }
+
+    /// Stores `history` for saving, if it is persisted.
+    pub fn store_history(&mut self, history: &crate::search_history::SearchHistory) {
+        self.history = if self.persist_history {
+            history.entries().to_vec()
+        } else {
+            Vec::new()
+        };
+    }
+}
+
+/// Download settings shared across clients.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Downloads {
+    /// The directory original track files are downloaded into. Unset uses
+    /// [`blackbird_shared::paths::downloads_dir`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<PathBuf>,
+}
+impl Downloads {
+    /// The directory to download into.
+    pub fn directory(&self) -> PathBuf {
+        self.directory
+            .clone()
+            .unwrap_or_else(blackbird_shared::paths::downloads_dir)
+    }
+}
+
 /// Last playback state, persisted across sessions.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
@@ -119,6 +498,8 @@ pub struct LastPlayback {
     pub track_position_secs: f64,
     /// The playback mode that was active.
     pub playback_mode: PlaybackMode,
+    /// The shuffle mode that toggling shuffle on switches to.
+    pub preferred_shuffle: PlaybackMode,
     /// The library sort order that was active.
     pub sort_order: SortOrder,
 }
@@ -137,7 +518,40 @@ impl Default for LastPlayback {
             track_id: None,
             track_position_secs: 0.0,
             playback_mode: PlaybackMode::default(),
+            preferred_shuffle: PlaybackMode::Shuffle,
             sort_order: SortOrder::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use blackbird_core::blackbird_state::{FetchOptions, fetch_all};
+    use blackbird_subsonic::MockClient;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn albums_without_server_art_are_coloured() {
+        let client = MockClient::fixture();
+        let output = fetch_all(&client, FetchOptions::default(), |_| {})
+            .await
+            .unwrap();
+
+        // The fixture's "Second" is the only album the server reports no art
+        // for, although the group still has IDs to request art by.
+        let coloured: Vec<_> = output
+            .groups
+            .iter()
+            .filter(|group| MissingArt::Color.shows_color(group))
+            .map(|group| group.album.as_str())
+            .collect();
+        assert_eq!(coloured, ["Second"]);
+        assert!(
+            output
+                .groups
+                .iter()
+                .all(|group| !MissingArt::Placeholder.shows_color(group))
+        );
+    }
+}
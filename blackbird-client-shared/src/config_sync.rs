@@ -0,0 +1,102 @@
+//! Keeps [`Logic`]'s playback and library settings in sync with the config,
+//! which can be edited or reloaded while the client is running.
+use blackbird_core::Logic;
+use blackbird_shared::config::Server;
+
+use crate::config::{Layout, Playback, Queue};
+
+/// Remembers the settings last applied to [`Logic`], so that they are only
+/// applied again when they change rather than on every frame.
+#[derive(Debug, Default)]
+pub struct ConfigSync {
+    applied: Option<(Playback, Queue, Server, Layout)>,
+}
+impl ConfigSync {
+    /// Applies the ReplayGain, silence skipping, extra output, end-of-queue,
+    /// stop, error, previous, prefetch, resume, idle pause, play queue,
+    /// transcoding, favorites playlist and edition merging settings to
+    /// `logic`, unless they are unchanged since the last call.
+    pub fn apply(
+        &mut self,
+        logic: &mut Logic,
+        playback: &Playback,
+        queue: &Queue,
+        server: &Server,
+        layout: &Layout,
+    ) {
+        if !self.update(playback, queue, server, layout) {
+            return;
+        }
+        logic.set_apply_replaygain(playback.apply_replaygain);
+        logic.set_replaygain_preamp_db(playback.replaygain_preamp_db);
+        logic.set_skip_silence_db(playback.skip_silence_db());
+        logic.set_extra_outputs(&playback.extra_outputs);
+        logic.set_end_behavior(playback.end_behavior);
+        logic.set_stop_behavior(playback.stop_behavior);
+        logic.set_playback_error_behavior(playback.on_playback_error);
+        logic.set_previous_behavior(
+            playback.previous_behavior,
+            playback.previous_restart_threshold(),
+        );
+        logic.set_next_group_prefetch_tracks(playback.next_group_prefetch_tracks);
+        logic.set_resume_albums(playback.resume_albums);
+        logic.set_idle_pause_after(playback.idle_pause_after());
+        logic.set_queue_end_behavior(queue.on_empty);
+        logic.set_transcode(server.transcode);
+        logic.set_transcode_format(server.transcode_format.as_deref());
+        logic.set_favorites_playlist(playback.favorites_playlist.as_deref());
+        logic.set_merge_editions(layout.merge_editions);
+    }
+
+    /// Records `playback`, `queue`, `server` and `layout` as applied,
+    /// returning whether they differ from the previously applied settings.
+    fn update(
+        &mut self,
+        playback: &Playback,
+        queue: &Queue,
+        server: &Server,
+        layout: &Layout,
+    ) -> bool {
+        if self
+            .applied
+            .as_ref()
+            .is_some_and(|(p, q, s, l)| p == playback && q == queue && s == server && l == layout)
+        {
+            return false;
+        }
+        self.applied = Some((
+            playback.clone(),
+            queue.clone(),
+            server.clone(),
+            layout.clone(),
+        ));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_are_only_reapplied_when_they_change() {
+        let mut sync = ConfigSync::default();
+        let mut playback = Playback::default();
+        let mut queue = Queue::default();
+        let server = Server::default();
+        let mut layout = Layout::default();
+
+        assert!(sync.update(&playback, &queue, &server, &layout));
+        assert!(!sync.update(&playback, &queue, &server, &layout));
+
+        playback.resume_albums = !playback.resume_albums;
+        assert!(sync.update(&playback, &queue, &server, &layout));
+        assert!(!sync.update(&playback, &queue, &server, &layout));
+
+        queue.on_empty = blackbird_core::QueueEndBehavior::Stop;
+        assert!(sync.update(&playback, &queue, &server, &layout));
+
+        layout.merge_editions = !layout.merge_editions;
+        assert!(sync.update(&playback, &queue, &server, &layout));
+    }
+}
@@ -1,18 +1,38 @@
 //! Media controls (MPRIS / Windows SMTC) shared between the egui and TUI clients.
-use std::sync::{Arc, RwLock};
+use std::{
+    io::Write as _,
+    sync::{Arc, RwLock, mpsc},
+};
 
 use blackbird_core::{
-    AppState, LogicRequestHandle, LogicRequestMessage, PlaybackState, PlaybackToLogicMessage,
-    PlaybackToLogicRx, TrackDisplayDetails,
+    AppState, Logic, LogicRequestHandle, LogicRequestMessage, PlaybackState,
+    PlaybackToLogicMessage, PlaybackToLogicRx, TrackDisplayDetails, blackbird_state::CoverArtId,
+    bs,
 };
 use souvlaki::{
     MediaControlEvent, MediaControls, MediaMetadata, MediaPlayback, PlatformConfig, SeekDirection,
 };
+use tempfile::NamedTempFile;
+
+/// The cover art size requested for lock screens and media widgets.
+const COVER_ART_SIZE: usize = 512;
 
 pub struct Controls {
     controls: MediaControls,
     playback_to_logic_rx: PlaybackToLogicRx,
     state: Arc<RwLock<AppState>>,
+    /// The last playback state reported by the playback thread.
+    playback_state: PlaybackState,
+    /// Whether a track was loading as of the last update.
+    loading: bool,
+    /// The track whose metadata is shown, if any.
+    details: Option<TrackDisplayDetails>,
+    /// The cover art written to disk for the platform to read, as platforms
+    /// take art by URL rather than as bytes. The file is deleted when
+    /// replaced or removed.
+    cover_art: Option<(CoverArtId, NamedTempFile)>,
+    /// Cover art being fetched for the track whose metadata is shown.
+    pending_cover_art: Option<(CoverArtId, mpsc::Receiver<bs::ClientResult<Vec<u8>>>)>,
 }
 
 impl Controls {
@@ -69,10 +89,118 @@ impl Controls {
             controls,
             playback_to_logic_rx,
             state,
+            playback_state: PlaybackState::Stopped,
+            loading: false,
+            details: None,
+            cover_art: None,
+            pending_cover_art: None,
+        })
+    }
+
+    /// Publishes the shown track's metadata, with its cover art if it has
+    /// been written to disk.
+    fn set_metadata(&mut self) -> Result<(), souvlaki::Error> {
+        let Some(details) = &self.details else {
+            return self.controls.set_metadata(MediaMetadata::default());
+        };
+        let cover_url = self
+            .cover_art
+            .as_ref()
+            .filter(|(id, _)| details.cover_art_id.as_ref() == Some(id))
+            .and_then(|(_, file)| url::Url::from_file_path(file.path()).ok())
+            .map(String::from);
+        self.controls.set_metadata(MediaMetadata {
+            title: Some(&details.track_title),
+            artist: Some(
+                details
+                    .track_artist
+                    .as_deref()
+                    .unwrap_or(&details.album_artist),
+            ),
+            album: Some(&details.album_name),
+            cover_url: cover_url.as_deref(),
+            duration: Some(details.track_duration).filter(|d| !d.is_zero()),
         })
     }
 
-    pub fn update(&mut self) {
+    /// Starts fetching the shown track's cover art, unless it is already
+    /// written or on its way.
+    fn request_cover_art(&mut self, logic: &Logic) {
+        let Some(id) = self.details.as_ref().and_then(|d| d.cover_art_id.clone()) else {
+            return;
+        };
+        let written = self.cover_art.as_ref().is_some_and(|(i, _)| *i == id);
+        let pending = self
+            .pending_cover_art
+            .as_ref()
+            .is_some_and(|(i, _)| *i == id);
+        if !written && !pending {
+            let rx = logic.fetch_cover_art(&id, Some(COVER_ART_SIZE));
+            self.pending_cover_art = Some((id, rx));
+        }
+    }
+
+    /// Writes fetched cover art to disk, replacing the previous image, and
+    /// republishes the metadata with it.
+    fn receive_cover_art(&mut self) -> Result<(), souvlaki::Error> {
+        let Some((id, rx)) = &self.pending_cover_art else {
+            return Ok(());
+        };
+        let bytes = match rx.try_recv() {
+            Ok(Ok(bytes)) => bytes,
+            Ok(Err(e)) => {
+                tracing::warn!("Failed to fetch cover art {id} for media controls: {e}");
+                self.pending_cover_art = None;
+                return Ok(());
+            }
+            Err(mpsc::TryRecvError::Empty) => return Ok(()),
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.pending_cover_art = None;
+                return Ok(());
+            }
+        };
+        let id = id.clone();
+        self.pending_cover_art = None;
+
+        // Each image gets its own randomly named file, as platforms may cache
+        // by URL. Replacing the previous one deletes it.
+        let file = tempfile::Builder::new()
+            .prefix("blackbird-cover-")
+            .tempfile()
+            .and_then(|mut file| file.write_all(&bytes).map(|()| file));
+        match file {
+            Ok(file) => self.cover_art = Some((id, file)),
+            Err(e) => {
+                tracing::warn!("Failed to write cover art for media controls: {e}");
+                return Ok(());
+            }
+        }
+        self.set_metadata()
+    }
+
+    /// Deletes the cover art file. Clients skip the controls' destructor on
+    /// exit, so they call this instead.
+    pub fn remove_cover_art(&mut self) {
+        self.cover_art = None;
+    }
+
+    pub fn update(&mut self, logic: &Logic) {
+        // There's no buffering status to report, so a loading track is shown
+        // as playing: that's what the user asked for, and it avoids flashing
+        // paused or stopped on the lock screen between tracks.
+        let loading = self.state.read().unwrap().started_loading_track.is_some();
+        let finished_loading = self.loading && !loading;
+        if loading != self.loading {
+            self.loading = loading;
+            if loading
+                && let Err(e) = self
+                    .controls
+                    .set_playback(MediaPlayback::Playing { progress: None })
+            {
+                tracing::warn!("Failed to update media controls: {:?}", e);
+            }
+        }
+
         while let Ok(event) = self.playback_to_logic_rx.try_recv() {
             let result = match event {
                 PlaybackToLogicMessage::TrackStarted(track_and_position) => {
@@ -81,43 +209,77 @@ impl Controls {
                         &self.state.read().unwrap(),
                     );
                     if let Some(display_details) = display_details {
-                        self.controls.set_metadata(MediaMetadata {
-                            title: Some(&display_details.track_title),
-                            artist: Some(&display_details.album_artist),
-                            album: Some(&display_details.album_name),
-                            duration: Some(display_details.track_duration),
-                            ..Default::default()
-                        })
+                        self.details = Some(display_details);
+                        self.request_cover_art(logic);
+                        self.set_metadata()
                     } else {
                         Ok(())
                     }
                 }
                 PlaybackToLogicMessage::PlaybackStateChanged(state) => {
+                    self.playback_state = state;
                     let playback_status = match state {
+                        // The previous track stopping mid-transition.
+                        _ if self.loading => MediaPlayback::Playing { progress: None },
                         PlaybackState::Playing => MediaPlayback::Playing { progress: None },
                         PlaybackState::Paused => MediaPlayback::Paused { progress: None },
                         PlaybackState::Stopped => {
-                            self.controls.set_metadata(MediaMetadata::default()).ok();
+                            self.details = None;
+                            self.set_metadata().ok();
                             MediaPlayback::Stopped
                         }
                     };
                     self.controls.set_playback(playback_status)
                 }
                 PlaybackToLogicMessage::PositionChanged(track_and_position) => {
-                    self.controls.set_playback(MediaPlayback::Playing {
-                        progress: Some(souvlaki::MediaPosition(track_and_position.position)),
+                    let progress = Some(souvlaki::MediaPosition(track_and_position.position));
+                    self.controls.set_playback(match self.playback_state {
+                        _ if self.loading => MediaPlayback::Playing { progress },
+                        PlaybackState::Playing => MediaPlayback::Playing { progress },
+                        PlaybackState::Paused => MediaPlayback::Paused { progress },
+                        PlaybackState::Stopped => MediaPlayback::Stopped,
                     })
                 }
+                PlaybackToLogicMessage::DurationResolved(track_id, duration) => {
+                    match &mut self.details {
+                        Some(details)
+                            if details.track_id == track_id && details.track_duration.is_zero() =>
+                        {
+                            details.track_duration = duration;
+                            self.set_metadata()
+                        }
+                        _ => Ok(()),
+                    }
+                }
                 PlaybackToLogicMessage::TrackEnded
                 | PlaybackToLogicMessage::FailedToPlayTrack(..) => {
                     // PlaybackStateChanged will take care of this
                     Ok(())
                 }
+                // souvlaki has no shuffle or repeat state to report.
+                PlaybackToLogicMessage::PlaybackModeChanged(_) => Ok(()),
             };
             if let Err(e) = result {
                 tracing::warn!("Failed to update media controls: {:?}", e);
             }
         }
+
+        // A track that failed to load never reports a new state, so replace
+        // the loading override with the last real one.
+        if finished_loading {
+            let playback_status = match self.playback_state {
+                PlaybackState::Playing => MediaPlayback::Playing { progress: None },
+                PlaybackState::Paused => MediaPlayback::Paused { progress: None },
+                PlaybackState::Stopped => MediaPlayback::Stopped,
+            };
+            if let Err(e) = self.controls.set_playback(playback_status) {
+                tracing::warn!("Failed to update media controls: {:?}", e);
+            }
+        }
+
+        if let Err(e) = self.receive_cover_art() {
+            tracing::warn!("Failed to update media controls: {:?}", e);
+        }
     }
 }
 
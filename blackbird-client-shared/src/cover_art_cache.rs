@@ -238,6 +238,9 @@ pub struct CoverArtCache<T: ClientData> {
     resolution_stale_timeout: Duration,
     /// How long an id must be demanded before a library-res fetch is issued.
     load_debounce: Duration,
+    /// Size requested from the server for full-res art; `None` requests the
+    /// original image.
+    full_res_size: Option<usize>,
     /// The art demanded by the client's most recent frame, rebuilt by `get`
     /// calls after each `begin_frame`. Persists between frames, so a lazily
     /// redrawing client's visible art stays demanded while it isn't drawing.
@@ -281,12 +284,34 @@ impl<T: ClientData> CoverArtCache<T> {
             cache_entry_timeout,
             resolution_stale_timeout: RESOLUTION_STALE_TIMEOUT,
             load_debounce: TIME_BEFORE_LOAD_ATTEMPT,
+            full_res_size: None,
             frame_demand: HashMap::new(),
             prefetcher: BackgroundPrefetcher::new(),
             disk_write_pool: ThreadPool::new(1),
         }
     }
 
+    /// Sets the size requested from the server for full-res art, or `None`
+    /// for the original image. Changing it drops all loaded full-res art so
+    /// it is re-fetched at the new size; the ids of the dropped slots are
+    /// returned so the client can forget any derived data.
+    pub fn set_full_res_size(&mut self, size: Option<usize>) -> Vec<CoverArtId> {
+        if self.full_res_size == size {
+            return vec![];
+        }
+        self.full_res_size = size;
+
+        let mut dropped = vec![];
+        for (id, entry) in self.cache.iter_mut() {
+            // Responses for in-flight requests would land in the wrong slot.
+            entry.loading.remove(&Resolution::Full);
+            if entry.full_res.take().is_some() {
+                dropped.push(id.clone());
+            }
+        }
+        dropped
+    }
+
     /// Start a new demand frame, clearing the previous frame's demand. Call
     /// at the start of each draw; the draw's `get` calls then rebuild the
     /// demand set from what is actually displayed.
@@ -361,10 +386,19 @@ impl<T: ClientData> CoverArtCache<T> {
             };
 
             // Determine which slot this response belongs to.
-            let resolution = match incoming.requested_size {
-                None => Resolution::Full,
-                Some(s) if s == LIBRARY_ART_SIZE => Resolution::Library,
-                Some(_) => Resolution::Library, // Fallback for other sizes.
+            let resolution = if incoming.requested_size == self.full_res_size
+                && entry.loading.contains(&Resolution::Full)
+            {
+                Resolution::Full
+            } else if incoming.requested_size == Some(LIBRARY_ART_SIZE) {
+                Resolution::Library
+            } else {
+                // A response for a full-res size that has since changed.
+                tracing::debug!(
+                    "Discarding stale full-res cover art for {}",
+                    incoming.cover_art_id
+                );
+                continue;
             };
 
             entry.loading.remove(&resolution);
@@ -485,7 +519,7 @@ impl<T: ClientData> CoverArtCache<T> {
                 && entry.full_res.is_none()
                 && !entry.loading.contains(&Resolution::Full)
             {
-                source.request_cover_art(id, None);
+                source.request_cover_art(id, self.full_res_size);
                 entry.loading.insert(Resolution::Full);
                 tracing::debug!("Requesting full-res cover art for {id}");
             }
@@ -1146,6 +1180,39 @@ mod tests {
         assert!(cache.cache.contains_key(&a));
     }
 
+    /// Changing the full-res size drops loaded full-res art and re-fetches
+    /// it at the new size, discarding responses for the old size.
+    #[test]
+    fn test_full_res_size_change_refetches() {
+        let (mut cache, tx) = test_cache("full-res-size", 10, LONG);
+        let source = MockSource::default();
+        let a = id("a");
+
+        cache.begin_frame();
+        cache.get(Some(&a), Resolution::Full, CachePriority::Visible);
+        cache.update(&source);
+        tx.send(response(&a, None)).unwrap();
+        cache.update(&source);
+        assert!(cache.is_resolution_loaded(&a, Resolution::Full));
+
+        assert_eq!(cache.set_full_res_size(Some(1024)), vec![a.clone()]);
+        assert!(cache.set_full_res_size(Some(1024)).is_empty());
+        assert!(!cache.is_resolution_loaded(&a, Resolution::Full));
+
+        // A late response at the old size is ignored.
+        tx.send(response(&a, None)).unwrap();
+        cache.update(&source);
+        assert_eq!(
+            source.requests.borrow().last(),
+            Some(&(a.clone(), Some(1024)))
+        );
+        assert!(!cache.is_resolution_loaded(&a, Resolution::Full));
+
+        tx.send(response(&a, Some(1024))).unwrap();
+        cache.update(&source);
+        assert!(cache.is_resolution_loaded(&a, Resolution::Full));
+    }
+
     /// A demand at full resolution keeps the loaded library slot alive as
     /// its fallback.
     #[test]
@@ -0,0 +1,51 @@
+//! Double-pressing Next to skip to the next album, as phone players do.
+use std::time::{Duration, Instant};
+
+use blackbird_core::Logic;
+
+/// Tracks presses of Next, upgrading a second press within the configured
+/// window to going to the next group.
+#[derive(Debug, Default)]
+pub struct NextPress {
+    /// When Next was last pressed, unless that press completed a double
+    /// press.
+    last: Option<Instant>,
+}
+impl NextPress {
+    /// Goes to the next track, or to the next group if Next was pressed less
+    /// than `window` ago. `None` disables double presses.
+    pub fn press(&mut self, logic: &Logic, window: Option<Duration>) {
+        if self.is_double_press(Instant::now(), window) {
+            logic.next_group();
+        } else {
+            logic.next();
+        }
+    }
+
+    fn is_double_press(&mut self, now: Instant, window: Option<Duration>) -> bool {
+        let double = window
+            .zip(self.last)
+            .is_some_and(|(window, last)| now.duration_since(last) <= window);
+        // A third press starts over rather than skipping another group.
+        self.last = (!double).then_some(now);
+        double
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_press_within_window_is_a_double_press() {
+        let window = Some(Duration::from_millis(300));
+        let start = Instant::now();
+        let mut press = NextPress::default();
+
+        assert!(!press.is_double_press(start, window));
+        assert!(press.is_double_press(start + Duration::from_millis(200), window));
+        assert!(!press.is_double_press(start + Duration::from_millis(300), window));
+        assert!(!press.is_double_press(start + Duration::from_millis(700), window));
+        assert!(!press.is_double_press(start + Duration::from_millis(800), None));
+    }
+}
@@ -1,16 +1,38 @@
 /// Volume adjustment step (5%).
 pub const VOLUME_STEP: f32 = 0.05;
 
+/// Per-track gain adjustment step, in dB.
+pub const TRACK_GAIN_STEP_DB: f32 = 0.5;
+
+/// Range of the per-track gain controls, in dB.
+pub const TRACK_GAIN_RANGE_DB: std::ops::RangeInclusive<f32> = -12.0..=12.0;
+
 /// Seek step in seconds.
 pub const SEEK_STEP_SECS: i64 = 5;
 
+/// Shown in place of the library when it loaded without any tracks.
+pub const EMPTY_LIBRARY_MESSAGE: &str = "No music found \u{2014} check your server or credentials.";
+
+/// Number of albums shown in the recently played panel.
+pub const RECENT_ALBUM_COUNT: usize = 50;
+
 /// Fraction of the window/terminal width used for the album art overlay.
 pub const OVERLAY_WIDTH_FRACTION: f32 = 0.9;
 
+pub mod accessibility;
 pub mod config;
+pub mod config_sync;
 pub mod cover_art_cache;
+pub mod double_press;
 pub mod library_scroll;
+pub mod listeners;
+pub mod listening_stats;
 pub mod lyrics;
+pub mod now_playing_text;
+pub mod reveal;
+pub mod search_history;
+pub mod search_suggestions;
+pub mod server_info;
 pub mod style;
 pub mod thread_pool;
 
@@ -39,6 +61,16 @@ pub fn cycle<T: Copy + PartialEq>(values: &[T], current: T, direction: Direction
     values[next]
 }
 
+/// Formats a play count for display, abbreviated (e.g. "1.2k") if
+/// `abbreviate` is set.
+pub fn format_play_count(count: u64, abbreviate: bool) -> String {
+    if abbreviate {
+        blackbird_core::util::abbreviate_count(count)
+    } else {
+        count.to_string()
+    }
+}
+
 /// Load the application icon as an RGBA image.
 pub fn load_icon() -> image::RgbaImage {
     image::load_from_memory(include_bytes!("../assets/icon.png"))
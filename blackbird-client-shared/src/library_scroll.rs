@@ -113,3 +113,100 @@ pub fn compute_item_position(entries: impl Iterator<Item = (bool, usize)>) -> Op
 
     Some(target as f32 / total_lines as f32)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(entries: &[(&'static str, usize)], threshold: f32) -> Vec<(String, f32)> {
+        compute_positions(
+            entries
+                .iter()
+                .map(|&(label, lines)| (Cow::Borrowed(label), lines)),
+            threshold,
+        )
+    }
+
+    /// Entries, cluster threshold, and the expected labels and fractions.
+    type PositionsCase = (
+        &'static [(&'static str, usize)],
+        f32,
+        &'static [(&'static str, f32)],
+    );
+
+    fn expected(labels: &[(&str, f32)]) -> Vec<(String, f32)> {
+        labels
+            .iter()
+            .map(|&(label, fraction)| (label.to_string(), fraction))
+            .collect()
+    }
+
+    #[test]
+    fn compute_positions_table() {
+        let cases: &[PositionsCase] = &[
+            // Nothing to place.
+            (&[], 0.0, &[]),
+            (&[("a", 0), ("b", 0)], 0.0, &[]),
+            (&[("", 5)], 0.0, &[]),
+            // Empty labels take up space without a label; case is merged.
+            (
+                &[("a", 2), ("", 3), ("A", 1), ("b", 4)],
+                0.0,
+                &[("A", 0.0), ("B", 0.6)],
+            ),
+            (&[("\u{e9}", 1), ("\u{c9}", 1)], 0.0, &[("\u{c9}", 0.0)]),
+            (
+                &[("2024", 3), ("2023", 1)],
+                0.0,
+                &[("2024", 0.0), ("2023", 0.75)],
+            ),
+            // Nearby labels cluster to the one with the most entries.
+            (
+                &[("a", 1), ("b", 1), ("b", 1), ("c", 1)],
+                0.3,
+                &[("B", 0.25), ("C", 0.75)],
+            ),
+            (
+                &[("a", 1), ("b", 1), ("b", 1), ("c", 1)],
+                1.0,
+                &[("B", 0.25)],
+            ),
+        ];
+        for &(entries, threshold, want) in cases {
+            assert_eq!(
+                positions(entries, threshold),
+                expected(want),
+                "entries {entries:?} at threshold {threshold}"
+            );
+        }
+    }
+
+    #[test]
+    fn cluster_labels_table() {
+        let labels = [
+            ("a", 0.0, 1),
+            ("b", 0.1, 2),
+            ("c", 0.12, 1),
+            ("d", 0.5, 3),
+            ("e", 0.9, 1),
+        ]
+        .map(|(label, fraction, count)| (label.to_string(), fraction, count));
+        let cases: &[(f32, &[(&str, f32)])] = &[
+            (
+                0.0,
+                &[("a", 0.0), ("b", 0.1), ("c", 0.12), ("d", 0.5), ("e", 0.9)],
+            ),
+            (0.05, &[("a", 0.0), ("b", 0.1), ("d", 0.5), ("e", 0.9)]),
+            (0.2, &[("b", 0.1), ("d", 0.5), ("e", 0.9)]),
+            (0.5, &[("b", 0.1), ("d", 0.5)]),
+            (1.0, &[("d", 0.5)]),
+        ];
+        for &(threshold, want) in cases {
+            assert_eq!(
+                cluster_labels(labels.to_vec(), threshold),
+                expected(want),
+                "threshold {threshold}"
+            );
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Shared logic for the "listeners" view: what every user of the server is
+//! playing, as reported by `getNowPlaying`.
+use std::time::{Duration, Instant};
+
+use blackbird_core::bs;
+
+/// How often the listeners view re-fetches from the server while open.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// One row of the listeners view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListenerRow {
+    pub username: String,
+    /// Whether this is the user blackbird is logged in as.
+    pub is_you: bool,
+    /// "Artist - Title" for the track being played.
+    pub track: String,
+    /// The player and how long ago the track started, e.g. "feishin, 2m ago".
+    pub detail: String,
+}
+
+/// Builds the rows for `entries`, with other users first and the user
+/// logged in as `own_username` last.
+pub fn rows(entries: &[bs::NowPlayingEntry], own_username: &str) -> Vec<ListenerRow> {
+    let mut rows: Vec<ListenerRow> = entries
+        .iter()
+        .map(|entry| {
+            let track = match &entry.child.artist {
+                Some(artist) => format!("{artist} - {}", entry.child.title),
+                None => entry.child.title.clone(),
+            };
+            let ago = if entry.minutes_ago == 0 {
+                "just now".to_string()
+            } else {
+                format!("{}m ago", entry.minutes_ago)
+            };
+            let detail = match &entry.player_name {
+                Some(player) if !player.is_empty() => format!("{player}, {ago}"),
+                _ => ago,
+            };
+            ListenerRow {
+                username: entry.username.clone(),
+                is_you: entry.username == own_username,
+                track,
+                detail,
+            }
+        })
+        .collect();
+    rows.sort_by_key(|row| row.is_you);
+    rows
+}
+
+/// The message to show alongside `rows`, if any: when nothing is playing, or
+/// when the only listener is the logged-in user.
+pub fn status_message(rows: &[ListenerRow]) -> Option<&'static str> {
+    if rows.is_empty() {
+        Some("Nobody is playing anything.")
+    } else if rows.iter().all(|row| row.is_you) {
+        Some("No one else is listening.")
+    } else {
+        None
+    }
+}
+
+/// Decides when the listeners view should re-fetch from the server.
+#[derive(Debug, Default)]
+pub struct RefreshTimer {
+    last_refresh: Option<Instant>,
+}
+
+impl RefreshTimer {
+    /// Returns `true`, and restarts the timer, if a refresh is due. The first
+    /// call after [`reset`](Self::reset) is always due.
+    pub fn due(&mut self) -> bool {
+        if self
+            .last_refresh
+            .is_some_and(|last| last.elapsed() < REFRESH_INTERVAL)
+        {
+            return false;
+        }
+        self.last_refresh = Some(Instant::now());
+        true
+    }
+
+    /// Makes the next [`due`](Self::due) call return `true`, e.g. when the
+    /// view is reopened.
+    pub fn reset(&mut self) {
+        self.last_refresh = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(username: &str, artist: Option<&str>, minutes_ago: u32) -> bs::NowPlayingEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": "t1",
+            "isDir": false,
+            "title": "Title",
+            "artist": artist,
+            "username": username,
+            "minutesAgo": minutes_ago,
+            "playerName": "player",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn rows_put_the_logged_in_user_last() {
+        let entries = [entry("me", Some("Artist"), 0), entry("them", None, 3)];
+        let rows = rows(&entries, "me");
+        assert_eq!(rows[0].username, "them");
+        assert_eq!(rows[0].track, "Title");
+        assert_eq!(rows[0].detail, "player, 3m ago");
+        assert!(rows[1].is_you);
+        assert_eq!(rows[1].track, "Artist - Title");
+        assert_eq!(rows[1].detail, "player, just now");
+        assert_eq!(status_message(&rows), None);
+    }
+
+    #[test]
+    fn status_message_covers_empty_and_single_user_servers() {
+        assert!(status_message(&[]).is_some());
+        let rows = rows(&[entry("me", None, 0)], "me");
+        assert_eq!(status_message(&rows), Some("No one else is listening."));
+    }
+}
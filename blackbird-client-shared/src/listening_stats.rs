@@ -0,0 +1,102 @@
+//! Shared logic for the listening stats panel, which summarizes the local
+//! listen history.
+use std::time::Duration;
+
+use blackbird_core::ListeningStats;
+
+/// Shown in place of the stats when nothing has been played yet.
+pub const EMPTY_MESSAGE: &str =
+    "No plays recorded yet. Listens are added to the history once they're scrobbled and end.";
+
+/// Shown in place of the stats while the history is being read.
+pub const LOADING_MESSAGE: &str = "Reading the listen history\u{2026}";
+
+/// Builds the `(label, value)` summary rows of the stats panel.
+pub fn summary_rows(stats: &ListeningStats) -> Vec<(&'static str, String)> {
+    let days = |count: u32| {
+        if count == 1 {
+            "1 day".to_string()
+        } else {
+            format!("{count} days")
+        }
+    };
+    vec![
+        (
+            "This week",
+            format_listening_time(stats.week_listening_time),
+        ),
+        (
+            "This month",
+            format_listening_time(stats.month_listening_time),
+        ),
+        ("Total plays", stats.total_plays.to_string()),
+        ("Streak", days(stats.streak_days)),
+    ]
+}
+
+/// Builds the titled top lists of the stats panel, each line being a play
+/// count followed by what was played.
+pub fn top_sections(stats: &ListeningStats) -> Vec<(&'static str, Vec<String>)> {
+    let pair = |(name, artist): &(_, _)| format!("{name} \u{2014} {artist}");
+    vec![
+        (
+            "Top artists",
+            stats
+                .top_artists
+                .iter()
+                .map(|(artist, count)| format!("{count:>4}  {artist}"))
+                .collect(),
+        ),
+        (
+            "Top albums",
+            stats
+                .top_albums
+                .iter()
+                .map(|(album, count)| format!("{count:>4}  {}", pair(album)))
+                .collect(),
+        ),
+        (
+            "Top tracks",
+            stats
+                .top_tracks
+                .iter()
+                .map(|(track, count)| format!("{count:>4}  {}", pair(track)))
+                .collect(),
+        ),
+    ]
+}
+
+/// Formats a listening time as hours and minutes, e.g. "3h 05m" or "42m".
+pub fn format_listening_time(time: Duration) -> String {
+    let minutes = time.as_secs() / 60;
+    let (hours, minutes) = (minutes / 60, minutes % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listening_time_is_formatted_as_hours_and_minutes() {
+        assert_eq!(format_listening_time(Duration::ZERO), "0m");
+        assert_eq!(format_listening_time(Duration::from_secs(42 * 60)), "42m");
+        assert_eq!(
+            format_listening_time(Duration::from_secs(3 * 3600 + 5 * 60 + 59)),
+            "3h 05m"
+        );
+    }
+
+    #[test]
+    fn summary_rows_describe_an_empty_history() {
+        let rows = summary_rows(&ListeningStats::default());
+        let value = |label| &rows.iter().find(|(l, _)| *l == label).unwrap().1;
+        assert_eq!(value("This week"), "0m");
+        assert_eq!(value("Total plays"), "0");
+        assert_eq!(value("Streak"), "0 days");
+    }
+}
@@ -23,6 +23,36 @@ pub fn find_current_lyrics_line(
         .unwrap_or(0)
 }
 
+/// The playback position at which the synced lyrics line `direction` lines
+/// from the current one starts (1 for the next line, -1 for the previous).
+/// Before the first line, the next line is the first. Returns `None` for
+/// unsynced lyrics or if there is no such line.
+pub fn adjacent_line_start(
+    lyrics: &StructuredLyrics,
+    playback_position: Option<Duration>,
+    direction: isize,
+) -> Option<Duration> {
+    if !lyrics.synced {
+        return None;
+    }
+    let offset = lyrics.offset.unwrap_or(0);
+    let current_ms = playback_position.map(|d| d.as_millis() as i64).unwrap_or(0);
+    let adjusted_ms = current_ms + offset;
+    // Unlike `find_current_lyrics_line`, there is no current line before the
+    // first one starts.
+    let current = lyrics
+        .line
+        .iter()
+        .rposition(|line| line.start.unwrap_or(0) <= adjusted_ms);
+    let target = match current {
+        Some(current) => current.checked_add_signed(direction)?,
+        None if direction > 0 => 0,
+        None => return None,
+    };
+    let start_ms = lyrics.line.get(target)?.start? - offset;
+    Some(Duration::from_millis(start_ms.max(0) as u64))
+}
+
 /// Shared lyrics data state used by both the egui and TUI clients.
 ///
 /// Centralizes lyrics data management and fetch-decision logic so that both
@@ -93,6 +123,22 @@ impl LyricsState {
             .is_some_and(|l| l.synced && !l.line.is_empty())
     }
 
+    /// The playback position at which the loaded lyrics line `direction`
+    /// lines from the current one starts; see [`adjacent_line_start`].
+    /// Returns `None` unless the lyrics are for `playing_track_id`, as they
+    /// are left loaded for the previous track while no lyrics are shown.
+    pub fn adjacent_line_start(
+        &self,
+        playing_track_id: Option<&TrackId>,
+        position: Option<Duration>,
+        direction: isize,
+    ) -> Option<Duration> {
+        if playing_track_id.is_none() || self.track_id.as_ref() != playing_track_id {
+            return None;
+        }
+        adjacent_line_start(self.data.as_ref()?, position, direction)
+    }
+
     /// Returns the current synced lyric line, or `None` if the current line's
     /// text is empty (instrumental break, etc.). Callers should check
     /// [`has_synced_lyrics`](Self::has_synced_lyrics) first to decide whether
@@ -114,3 +160,71 @@ impl LyricsState {
         Some(line)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use blackbird_core::bs::LyricLine;
+
+    use super::*;
+
+    fn lyrics(starts: &[i64], offset: Option<i64>) -> StructuredLyrics {
+        StructuredLyrics {
+            display_artist: None,
+            display_title: None,
+            lang: None,
+            offset,
+            synced: true,
+            line: starts
+                .iter()
+                .map(|&start| LyricLine {
+                    start: Some(start),
+                    value: String::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn adjacent_line_start_steps_between_lines() {
+        let lyrics = lyrics(&[1_000, 5_000, 9_000], None);
+        let at = |ms| Some(Duration::from_millis(ms));
+
+        assert_eq!(adjacent_line_start(&lyrics, at(0), 1), at(1_000));
+        assert_eq!(adjacent_line_start(&lyrics, at(0), -1), None);
+        assert_eq!(adjacent_line_start(&lyrics, at(6_000), 1), at(9_000));
+        assert_eq!(adjacent_line_start(&lyrics, at(6_000), -1), at(1_000));
+        assert_eq!(adjacent_line_start(&lyrics, at(9_500), 1), None);
+    }
+
+    #[test]
+    fn lyrics_of_a_previous_track_are_not_seeked_by() {
+        let previous = TrackId("previous".into());
+        let mut state = LyricsState::new();
+        assert!(state.on_track_started(&previous, true, false));
+        state.on_lyrics_loaded(&LyricsData {
+            track_id: previous.clone(),
+            lyrics: Some(lyrics(&[1_000, 5_000], None)),
+        });
+        let at = |ms| Some(Duration::from_millis(ms));
+        assert_eq!(
+            state.adjacent_line_start(Some(&previous), at(0), 1),
+            at(1_000)
+        );
+
+        // With no lyrics shown, the next track's aren't fetched.
+        let next = TrackId("next".into());
+        assert!(!state.on_track_started(&next, false, false));
+        assert_eq!(state.adjacent_line_start(Some(&next), at(0), 1), None);
+        assert_eq!(state.adjacent_line_start(None, at(0), 1), None);
+    }
+
+    #[test]
+    fn adjacent_line_start_applies_the_offset() {
+        // Lines show 500ms early, so seeking lands 500ms before each start.
+        let lyrics = lyrics(&[1_000, 5_000], Some(500));
+        assert_eq!(
+            adjacent_line_start(&lyrics, Some(Duration::from_millis(600)), 1),
+            Some(Duration::from_millis(4_500))
+        );
+    }
+}
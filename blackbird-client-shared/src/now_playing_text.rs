@@ -0,0 +1,95 @@
+//! Plain-text descriptions of the playing track for copying elsewhere, e.g.
+//! into a chat message.
+use blackbird_core::{
+    TrackDisplayDetails,
+    util::{UNKNOWN_DURATION, seconds_to_hms_string},
+};
+
+/// The template used when none is configured.
+pub const DEFAULT_TEMPLATE: &str = "{artist} - {title} ({album})";
+
+/// Fills in `template` from `details`. Supported placeholders are `{title}`,
+/// `{artist}` (the track artist, or the album artist if unset), `{album}`,
+/// `{album_artist}`, `{position}` and `{duration}`; anything else is copied
+/// as written.
+pub fn format(template: &str, details: &TrackDisplayDetails) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        match placeholder(&rest[1..end], details) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn placeholder(name: &str, details: &TrackDisplayDetails) -> Option<String> {
+    Some(match name {
+        "title" => details.track_title.to_string(),
+        "artist" => details
+            .track_artist
+            .as_ref()
+            .unwrap_or(&details.album_artist)
+            .to_string(),
+        "album" => details.album_name.to_string(),
+        "album_artist" => details.album_artist.to_string(),
+        "position" => seconds_to_hms_string(details.track_position.as_secs() as u32, false),
+        "duration" if details.track_duration.is_zero() => UNKNOWN_DURATION.to_string(),
+        "duration" => seconds_to_hms_string(details.track_duration.as_secs() as u32, false),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use blackbird_core::blackbird_state::{AlbumId, TrackId};
+
+    use super::*;
+
+    fn details(track_artist: Option<&str>) -> TrackDisplayDetails {
+        TrackDisplayDetails {
+            album_id: AlbumId("al".into()),
+            album_name: "Album".into(),
+            album_artist: "Album Artist".into(),
+            cover_art_id: None,
+            track_id: TrackId("t".into()),
+            track_title: "Title".into(),
+            track_artist: track_artist.map(Into::into),
+            track_duration: Duration::from_secs(210),
+            track_position: Duration::from_secs(65),
+            show_time: true,
+            starred: false,
+            play_count: None,
+        }
+    }
+
+    #[test]
+    fn default_template_uses_the_track_artist() {
+        assert_eq!(
+            format(DEFAULT_TEMPLATE, &details(Some("Artist"))),
+            "Artist - Title (Album)"
+        );
+        assert_eq!(
+            format(DEFAULT_TEMPLATE, &details(None)),
+            "Album Artist - Title (Album)"
+        );
+    }
+
+    #[test]
+    fn unknown_placeholders_and_stray_braces_are_kept() {
+        assert_eq!(
+            format("{title} [{position}/{duration}] {mood} {", &details(None)),
+            "Title [1:05/3:30] {mood} {"
+        );
+    }
+}
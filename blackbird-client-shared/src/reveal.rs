@@ -0,0 +1,34 @@
+//! Showing a track's file in the platform's file manager.
+#[cfg(windows)]
+use std::ffi::OsString;
+use std::{io, path::Path, process::Command};
+
+/// Opens the folder containing `path` in the file manager, selecting the file
+/// where the platform supports it.
+pub fn reveal_in_file_manager(path: &Path) -> io::Result<()> {
+    reveal_command(path).spawn().map(|_| ())
+}
+
+#[cfg(target_os = "macos")]
+fn reveal_command(path: &Path) -> Command {
+    let mut command = Command::new("open");
+    command.arg("-R").arg(path);
+    command
+}
+
+#[cfg(windows)]
+fn reveal_command(path: &Path) -> Command {
+    let mut select = OsString::from("/select,");
+    select.push(path);
+    let mut command = Command::new("explorer");
+    command.arg(select);
+    command
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn reveal_command(path: &Path) -> Command {
+    let folder = path.parent().unwrap_or(path);
+    let mut command = Command::new("xdg-open");
+    command.arg(folder);
+    command
+}
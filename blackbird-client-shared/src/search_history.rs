@@ -0,0 +1,122 @@
+//! A bounded history of past search queries, recalled with Up/Down in the
+//! search input as a shell does.
+use crate::search_suggestions::MIN_QUERY_LEN;
+
+/// How many queries are remembered; the oldest are dropped first.
+pub const CAPACITY: usize = 50;
+
+/// Past queries, oldest first, and the position of the one being recalled.
+#[derive(Debug, Default)]
+pub struct SearchHistory {
+    entries: Vec<String>,
+    /// The recalled entry, while browsing.
+    cursor: Option<usize>,
+    /// The query that was being typed before browsing started, restored when
+    /// browsing past the newest entry.
+    draft: String,
+}
+impl SearchHistory {
+    /// Creates a history from persisted entries, oldest first.
+    pub fn new(mut entries: Vec<String>) -> Self {
+        entries.drain(..entries.len().saturating_sub(CAPACITY));
+        Self {
+            entries,
+            cursor: None,
+            draft: String::new(),
+        }
+    }
+
+    /// The remembered queries, oldest first.
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Remembers `query`, unless it is too short to search for or repeats the
+    /// newest entry. Also stops browsing.
+    pub fn record(&mut self, query: &str) {
+        self.stop_browsing();
+        let query = query.trim();
+        if query.len() < MIN_QUERY_LEN || self.entries.last().is_some_and(|last| last == query) {
+            return;
+        }
+        if self.entries.len() == CAPACITY {
+            self.entries.remove(0);
+        }
+        self.entries.push(query.to_string());
+    }
+
+    /// Whether an entry is being recalled.
+    pub fn is_browsing(&self) -> bool {
+        self.cursor.is_some()
+    }
+
+    /// Stops browsing, keeping whatever query is in the input.
+    pub fn stop_browsing(&mut self) {
+        self.cursor = None;
+        self.draft.clear();
+    }
+
+    /// Steps back to the next older entry. `current` is the query in the
+    /// input, kept to be restored when browsing ends. Returns `None` if there
+    /// is nothing older.
+    pub fn older(&mut self, current: &str) -> Option<String> {
+        let index = match self.cursor {
+            Some(0) => return None,
+            Some(cursor) => cursor - 1,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len().checked_sub(1)?
+            }
+        };
+        self.cursor = Some(index);
+        Some(self.entries[index].clone())
+    }
+
+    /// Steps forward to the next newer entry, or back to the draft past the
+    /// newest one. Returns `None` if not browsing.
+    pub fn newer(&mut self) -> Option<String> {
+        let cursor = self.cursor?;
+        if cursor + 1 < self.entries.len() {
+            self.cursor = Some(cursor + 1);
+            return Some(self.entries[cursor + 1].clone());
+        }
+        let draft = std::mem::take(&mut self.draft);
+        self.stop_browsing();
+        Some(draft)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn browses_entries_and_restores_the_draft() {
+        let mut history = SearchHistory::default();
+        history.record("beatles");
+        history.record("beatles");
+        history.record("ab");
+        history.record("  radiohead ");
+
+        assert_eq!(history.entries(), ["beatles", "radiohead"]);
+        assert_eq!(history.older("blu").as_deref(), Some("radiohead"));
+        assert_eq!(history.older("radiohead").as_deref(), Some("beatles"));
+        assert_eq!(history.older("beatles"), None);
+        assert_eq!(history.newer().as_deref(), Some("radiohead"));
+        assert_eq!(history.newer().as_deref(), Some("blu"));
+        assert!(!history.is_browsing());
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn drops_the_oldest_entries_beyond_capacity() {
+        let mut history =
+            SearchHistory::new((0..CAPACITY + 5).map(|i| format!("query {i}")).collect());
+        assert_eq!(history.entries().len(), CAPACITY);
+        assert_eq!(history.entries()[0], "query 5");
+
+        history.record("another");
+        assert_eq!(history.entries().len(), CAPACITY);
+        assert_eq!(history.entries()[0], "query 6");
+    }
+}
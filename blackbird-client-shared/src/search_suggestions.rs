@@ -0,0 +1,150 @@
+//! Shared handling of server search suggestions: debouncing requests while
+//! the user types, picking the suggestion to complete to, and merging the
+//! server's track results with local ones.
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use blackbird_core::{
+    Library, SearchSuggestion,
+    blackbird_state::{Track, TrackId},
+};
+
+/// How long the query must stay unchanged before suggestions are requested.
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Queries shorter than this don't get suggestions, matching local search.
+pub const MIN_QUERY_LEN: usize = 3;
+
+/// Tracks the search query so suggestions are requested once per query,
+/// after it has settled.
+#[derive(Debug, Default)]
+pub struct SuggestionDebouncer {
+    query: String,
+    changed_at: Option<Instant>,
+    requested: bool,
+}
+
+impl SuggestionDebouncer {
+    /// Call with the current query on every frame or tick. Returns the query
+    /// to request suggestions for once it has been unchanged for [`DEBOUNCE`].
+    pub fn poll(&mut self, query: &str) -> Option<&str> {
+        self.poll_at(query, Instant::now())
+    }
+
+    /// Whether a request will be due once the debounce elapses, so the
+    /// client should keep polling.
+    pub fn is_waiting(&self) -> bool {
+        !self.requested && self.query.len() >= MIN_QUERY_LEN
+    }
+
+    fn poll_at(&mut self, query: &str, now: Instant) -> Option<&str> {
+        if query != self.query {
+            self.query = query.to_string();
+            self.changed_at = Some(now);
+            self.requested = false;
+        }
+        if !self.is_waiting()
+            || self
+                .changed_at
+                .is_some_and(|changed_at| now.duration_since(changed_at) < DEBOUNCE)
+        {
+            return None;
+        }
+        self.requested = true;
+        Some(&self.query)
+    }
+}
+
+/// The suggestion to complete `query` to: the first one that would change it.
+pub fn completion<'a>(
+    suggestions: &'a [SearchSuggestion],
+    query: &str,
+) -> Option<&'a SearchSuggestion> {
+    suggestions
+        .iter()
+        .find(|s| !s.text().eq_ignore_ascii_case(query))
+}
+
+/// Appends the server's track results to the local ones, skipping tracks
+/// local search already found. Returns the index the server's results start
+/// at, so they can be shown as their own section.
+pub fn append_server_results(
+    results: &mut Vec<TrackId>,
+    server: impl IntoIterator<Item = TrackId>,
+) -> usize {
+    let start = results.len();
+    let local: HashSet<TrackId> = results.iter().cloned().collect();
+    results.extend(server.into_iter().filter(|id| !local.contains(id)));
+    start
+}
+
+/// The server's results that aren't in the local library, by ID, so that
+/// they can be shown from the server's details and marked as remote.
+pub fn remote_tracks(server: &[Track], library: &Library) -> HashMap<TrackId, Track> {
+    server
+        .iter()
+        .filter(|track| !library.track_map.contains_key(&track.id))
+        .map(|track| (track.id.clone(), track.clone()))
+        .collect()
+}
+
+/// A short label for a suggestion, e.g. "artist: Boards of Canada".
+pub fn label(suggestion: &SearchSuggestion) -> String {
+    match suggestion {
+        SearchSuggestion::Artist(name) => format!("artist: {name}"),
+        SearchSuggestion::Album { name, artist } if !artist.is_empty() => {
+            format!("album: {name} ({artist})")
+        }
+        SearchSuggestion::Album { name, .. } => format!("album: {name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_once_after_the_query_settles() {
+        let mut debouncer = SuggestionDebouncer::default();
+        let start = Instant::now();
+        assert_eq!(debouncer.poll_at("bo", start), None);
+        assert_eq!(debouncer.poll_at("boa", start), None);
+        assert!(debouncer.is_waiting());
+
+        let later = start + DEBOUNCE;
+        assert_eq!(debouncer.poll_at("boa", later), Some("boa"));
+        assert_eq!(debouncer.poll_at("boa", later + DEBOUNCE), None);
+        assert!(!debouncer.is_waiting());
+
+        // Typing restarts the debounce.
+        assert_eq!(debouncer.poll_at("boar", later), None);
+        assert_eq!(debouncer.poll_at("boar", later + DEBOUNCE), Some("boar"));
+    }
+
+    #[test]
+    fn server_results_are_appended_without_duplicates() {
+        let id = |s: &str| TrackId(s.into());
+        let mut results = vec![id("a"), id("b")];
+        let start = append_server_results(&mut results, [id("b"), id("c"), id("d")]);
+        assert_eq!(start, 2);
+        assert_eq!(results, [id("a"), id("b"), id("c"), id("d")]);
+    }
+
+    #[test]
+    fn completion_skips_suggestions_matching_the_query() {
+        let suggestions = [
+            SearchSuggestion::Artist("Boards".into()),
+            SearchSuggestion::Album {
+                name: "Boards of Canada".into(),
+                artist: "".into(),
+            },
+        ];
+        assert_eq!(
+            completion(&suggestions, "boards").map(SearchSuggestion::text),
+            Some("Boards of Canada")
+        );
+        assert_eq!(label(&suggestions[1]), "album: Boards of Canada");
+    }
+}
@@ -0,0 +1,93 @@
+//! Shared logic for the server info panel: what the server is and which
+//! features it supports, to explain why a feature is or isn't available.
+use blackbird_core::bs;
+
+/// Shown in place of the rows until the server info has been collected.
+pub const UNAVAILABLE_MESSAGE: &str = "Server details haven't been collected yet.";
+
+/// Builds the `(label, value)` rows of the server info panel.
+pub fn rows(info: &bs::ServerInfo, username: &str) -> Vec<(&'static str, String)> {
+    let yes_no = |supported: bool| if supported { "yes" } else { "no" }.to_string();
+
+    let server = match (&info.server_type, &info.server_version) {
+        (Some(server_type), Some(version)) => format!("{server_type} {version}"),
+        (Some(server_type), None) => server_type.clone(),
+        (None, _) => "unknown".to_string(),
+    };
+    let api_version = if info.open_subsonic {
+        format!("{} (OpenSubsonic)", info.api_version)
+    } else {
+        info.api_version.clone()
+    };
+    let license = match info.license_valid {
+        Some(true) => "valid",
+        Some(false) => "invalid",
+        None => "not reported",
+    }
+    .to_string();
+    let extensions = if info.extensions.is_empty() {
+        "none".to_string()
+    } else {
+        info.extensions
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    vec![
+        ("Server", server),
+        ("API version", api_version),
+        ("Logged in as", username.to_string()),
+        ("License", license),
+        ("Lyrics", yes_no(info.supports_lyrics())),
+        ("Bookmarks", yes_no(info.supports_bookmarks())),
+        (
+            "Transcoded seeking",
+            yes_no(info.supports_transcode_offset()),
+        ),
+        ("Extensions", extensions),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_describe_an_opensubsonic_server() {
+        let info = bs::ServerInfo {
+            api_version: "1.16.1".to_string(),
+            server_type: Some("navidrome".to_string()),
+            server_version: Some("0.53.3".to_string()),
+            open_subsonic: true,
+            extensions: vec![bs::OpenSubsonicExtension {
+                name: "songLyrics".to_string(),
+                versions: vec![1],
+            }],
+            license_valid: Some(true),
+        };
+        let rows = rows(&info, "me");
+        let value = |label| &rows.iter().find(|(l, _)| *l == label).unwrap().1;
+        assert_eq!(value("Server"), "navidrome 0.53.3");
+        assert_eq!(value("API version"), "1.16.1 (OpenSubsonic)");
+        assert_eq!(value("Lyrics"), "yes");
+        assert_eq!(value("Bookmarks"), "yes");
+        assert_eq!(value("Transcoded seeking"), "no");
+        assert_eq!(value("Extensions"), "songLyrics");
+    }
+
+    #[test]
+    fn rows_describe_a_plain_subsonic_server() {
+        let info = bs::ServerInfo {
+            api_version: "1.8.0".to_string(),
+            ..Default::default()
+        };
+        let rows = rows(&info, "me");
+        let value = |label| &rows.iter().find(|(l, _)| *l == label).unwrap().1;
+        assert_eq!(value("Server"), "unknown");
+        assert_eq!(value("License"), "not reported");
+        assert_eq!(value("Bookmarks"), "no");
+        assert_eq!(value("Extensions"), "none");
+    }
+}
@@ -31,6 +31,12 @@ pub fn string_to_hsv(s: &str) -> Hsv {
     [hue, 0.75, 0.75]
 }
 
+/// The colour shown in place of the art of an album without any, when
+/// [`MissingArt::Color`](crate::config::MissingArt::Color) is configured.
+pub fn missing_art_hsv(artist: &str, album: &str) -> Hsv {
+    string_to_hsv(&format!("{artist}{album}"))
+}
+
 macro_rules! style_fields {
     ($(($field:ident, $fn_name:ident, $default:expr)),* $(,)?) => {
         /// Style configuration with HSV colors for various UI elements.
@@ -27,15 +27,14 @@ pub struct TrayMenu {
     playback_mode_items: Vec<(bc::PlaybackMode, CheckMenuItem)>,
     quit_item: MenuItem,
     last_track_display: Option<String>,
-    last_playback_mode: bc::PlaybackMode,
     last_starred: Option<bool>,
+    /// Playback events, for following changes to the playback mode.
+    playback_to_logic_rx: bc::PlaybackToLogicRx,
 }
 
 impl TrayMenu {
-    pub fn new(
-        icon: image::RgbaImage,
-        current_playback_mode: bc::PlaybackMode,
-    ) -> (TrayIcon, Self) {
+    pub fn new(icon: image::RgbaImage, logic: &bc::Logic) -> (TrayIcon, Self) {
+        let current_playback_mode = logic.get_playback_mode();
         let menu = Menu::new();
 
         // Current track (disabled, non-clickable).
@@ -69,6 +68,7 @@ impl TrayMenu {
             bc::PlaybackMode::LikedShuffle,
             bc::PlaybackMode::GroupShuffle,
             bc::PlaybackMode::LikedGroupShuffle,
+            bc::PlaybackMode::ArtistShuffle,
         ];
 
         let playback_mode_items: Vec<(bc::PlaybackMode, CheckMenuItem)> = playback_modes
@@ -96,8 +96,8 @@ impl TrayMenu {
             playback_mode_items,
             quit_item,
             last_track_display: None,
-            last_playback_mode: current_playback_mode,
             last_starred: None,
+            playback_to_logic_rx: logic.subscribe_events(),
         };
 
         let tray_icon = Self::build_tray_icon(icon, &menu);
@@ -202,13 +202,19 @@ impl TrayMenu {
             self.last_starred = current_starred;
         }
 
-        // Update menu playback mode checkmarks.
-        let current_mode = logic.get_playback_mode();
-        if current_mode != self.last_playback_mode {
+        // Update menu playback mode checkmarks as the mode changes. The
+        // events are bounded, so a mode change may have been dropped while
+        // they weren't being read; the mode is then read afresh.
+        loop {
+            let current_mode = match self.playback_to_logic_rx.try_recv() {
+                Ok(bc::PlaybackToLogicMessage::PlaybackModeChanged(mode)) => mode,
+                Ok(_) => continue,
+                Err(bc::PlaybackToLogicTryRecvError::Lagged(_)) => logic.get_playback_mode(),
+                Err(_) => break,
+            };
             for (mode, item) in &self.playback_mode_items {
                 item.set_checked(*mode == current_mode);
             }
-            self.last_playback_mode = current_mode;
         }
     }
 }
@@ -0,0 +1,101 @@
+//! A minimal headless consumer of `blackbird-core`: connects to a server,
+//! waits for the library to load, plays its first track, and prints playback
+//! events until the track ends.
+//!
+//! Usage: `cargo run -p blackbird-core --example embed -- <url> <username> <password>`
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::mpsc,
+    time::Duration,
+};
+
+use blackbird_core::{self as bc, PlaybackToLogicMessage};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let (Some(base_url), Some(username), Some(password)) = (args.next(), args.next(), args.next())
+    else {
+        eprintln!("usage: embed <url> <username> <password>");
+        std::process::exit(1);
+    };
+
+    // The clients use these to redraw when something arrives; a headless
+    // consumer can poll instead.
+    let (cover_art_loaded_tx, _cover_art_loaded_rx) = mpsc::channel();
+    let (lyrics_loaded_tx, _lyrics_loaded_rx) = mpsc::channel();
+    let (library_populated_tx, _library_populated_rx) = mpsc::channel();
+    let (track_updated_tx, _track_updated_rx) = mpsc::channel();
+
+    let mut logic = bc::Logic::new(bc::LogicArgs {
+        base_url,
+        username,
+        password,
+        client_id: format!("blackbird-embed/{}", env!("CARGO_PKG_VERSION")),
+        transcode: false,
+        transcode_format: None,
+        api_version: None,
+        fetch_options: Default::default(),
+        scrobble_targets: Default::default(),
+        volume: 1.0,
+        apply_replaygain: true,
+        replaygain_preamp_db: 0.0,
+        track_gains: HashMap::new(),
+        skipped_tracks: HashSet::new(),
+        skip_silence_db: None,
+        sort_order: Default::default(),
+        playback_mode: Default::default(),
+        preferred_shuffle: bc::PlaybackMode::Shuffle,
+        end_behavior: Default::default(),
+        stop_behavior: Default::default(),
+        previous_behavior: Default::default(),
+        previous_restart_threshold: bc::DEFAULT_PREVIOUS_RESTART_THRESHOLD,
+        last_playback: None,
+        cover_art_loaded_tx,
+        lyrics_loaded_tx,
+        library_populated_tx,
+        track_updated_tx,
+    });
+    let mut events = logic.subscribe_events();
+    let mut started = false;
+
+    while !logic.should_shutdown() {
+        logic.update();
+
+        if let Some(error) = logic.get_error() {
+            eprintln!("error: {error:?}");
+            break;
+        }
+
+        if !started && logic.has_loaded_all_tracks() {
+            let first = logic
+                .get_state()
+                .read()
+                .unwrap()
+                .library
+                .track_ids
+                .first()
+                .cloned();
+            let Some(track_id) = first else {
+                println!("the library is empty");
+                break;
+            };
+            println!("playing {track_id}");
+            logic.request_play_track(&track_id);
+            started = true;
+        }
+
+        while let Ok(event) = events.try_recv() {
+            match event {
+                PlaybackToLogicMessage::PositionChanged(_) => {}
+                PlaybackToLogicMessage::TrackEnded => {
+                    println!("track ended");
+                    return;
+                }
+                event => println!("{event:?}"),
+            }
+        }
+
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
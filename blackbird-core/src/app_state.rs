@@ -1,9 +1,16 @@
-use std::time::Duration;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
 
-use blackbird_state::{AlbumId, CoverArtId, TrackId};
+use blackbird_state::{AlbumId, CoverArtId, FetchProgress, Group, Track, TrackId};
 use serde::{Deserialize, Serialize};
 
-use crate::{Library, PlaybackState, TrackDisplayDetails, queue::QueueState};
+use crate::{
+    Library, ListeningStats, PlaybackState, Scrobble, TrackDisplayDetails, bs, queue::QueueState,
+    search_suggestions::SuggestionCache,
+};
 
 /// The sort order for displaying albums in the library.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -63,11 +70,13 @@ pub enum PlaybackMode {
     GroupShuffle,
     /// Shuffles groups with liked tracks and plays them in order.
     LikedGroupShuffle,
+    /// Shuffles all tracks by the current track's album artist.
+    ArtistShuffle,
 }
 
 impl PlaybackMode {
     /// All playback modes in cycle order.
-    pub const ALL: [PlaybackMode; 7] = [
+    pub const ALL: [PlaybackMode; 8] = [
         PlaybackMode::Sequential,
         PlaybackMode::RepeatOne,
         PlaybackMode::GroupRepeat,
@@ -75,8 +84,33 @@ impl PlaybackMode {
         PlaybackMode::LikedShuffle,
         PlaybackMode::GroupShuffle,
         PlaybackMode::LikedGroupShuffle,
+        PlaybackMode::ArtistShuffle,
     ];
 
+    /// The shuffle modes, in the order the shuffle scope cycles through them.
+    pub const SHUFFLES: [PlaybackMode; 5] = [
+        PlaybackMode::Shuffle,
+        PlaybackMode::GroupShuffle,
+        PlaybackMode::LikedShuffle,
+        PlaybackMode::LikedGroupShuffle,
+        PlaybackMode::ArtistShuffle,
+    ];
+
+    /// Returns whether this mode shuffles.
+    pub fn is_shuffle(&self) -> bool {
+        Self::SHUFFLES.contains(self)
+    }
+
+    /// Returns the shuffle mode after this one in [`Self::SHUFFLES`],
+    /// wrapping around. Modes that don't shuffle start from the first.
+    pub fn next_shuffle_scope(&self) -> PlaybackMode {
+        let index = Self::SHUFFLES
+            .iter()
+            .position(|mode| mode == self)
+            .map_or(0, |index| (index + 1) % Self::SHUFFLES.len());
+        Self::SHUFFLES[index]
+    }
+
     /// Returns whether this mode organizes playback by groups (albums).
     pub fn is_group_mode(&self) -> bool {
         matches!(
@@ -110,6 +144,7 @@ impl PlaybackMode {
             PlaybackMode::LikedShuffle => "liked shuffle",
             PlaybackMode::GroupShuffle => "group shuffle",
             PlaybackMode::LikedGroupShuffle => "liked group shuffle",
+            PlaybackMode::ArtistShuffle => "artist shuffle",
         }
     }
 }
@@ -120,6 +155,139 @@ impl std::fmt::Display for PlaybackMode {
     }
 }
 
+/// How far [`Logic::play_from`](crate::Logic::play_from) plays on from the
+/// selected track before stopping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayScope {
+    /// The rest of the library, in library order.
+    Library,
+    /// The rest of the selected track's album.
+    Album,
+}
+
+/// What happens when advancing past either end of the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BoundaryBehavior {
+    /// Continue from the other end of the queue.
+    #[default]
+    Wrap,
+    /// Stay on the current track; playback stops once it ends.
+    Stop,
+}
+
+/// Per-mode policy for what Next and Previous do at the ends of the queue.
+///
+/// Every mode wraps by default. For shuffle modes, wrapping also reshuffles
+/// the queue so the next pass plays a fresh order. `RepeatOne` and
+/// `GroupRepeat` only reach a boundary through explicit Next/Previous, as
+/// their queue is the current track or group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct EndBehavior {
+    pub sequential: BoundaryBehavior,
+    pub repeat_one: BoundaryBehavior,
+    pub group_repeat: BoundaryBehavior,
+    pub shuffle: BoundaryBehavior,
+    pub liked_shuffle: BoundaryBehavior,
+    pub group_shuffle: BoundaryBehavior,
+    pub liked_group_shuffle: BoundaryBehavior,
+    pub artist_shuffle: BoundaryBehavior,
+}
+
+impl EndBehavior {
+    /// Returns the boundary behavior for `mode`.
+    pub fn for_mode(&self, mode: PlaybackMode) -> BoundaryBehavior {
+        match mode {
+            PlaybackMode::Sequential => self.sequential,
+            PlaybackMode::RepeatOne => self.repeat_one,
+            PlaybackMode::GroupRepeat => self.group_repeat,
+            PlaybackMode::Shuffle => self.shuffle,
+            PlaybackMode::LikedShuffle => self.liked_shuffle,
+            PlaybackMode::GroupShuffle => self.group_shuffle,
+            PlaybackMode::LikedGroupShuffle => self.liked_group_shuffle,
+            PlaybackMode::ArtistShuffle => self.artist_shuffle,
+        }
+    }
+
+    /// Sets the boundary behavior for `mode`.
+    pub fn set_for_mode(&mut self, mode: PlaybackMode, behavior: BoundaryBehavior) {
+        let slot = match mode {
+            PlaybackMode::Sequential => &mut self.sequential,
+            PlaybackMode::RepeatOne => &mut self.repeat_one,
+            PlaybackMode::GroupRepeat => &mut self.group_repeat,
+            PlaybackMode::Shuffle => &mut self.shuffle,
+            PlaybackMode::LikedShuffle => &mut self.liked_shuffle,
+            PlaybackMode::GroupShuffle => &mut self.group_shuffle,
+            PlaybackMode::LikedGroupShuffle => &mut self.liked_group_shuffle,
+            PlaybackMode::ArtistShuffle => &mut self.artist_shuffle,
+        };
+        *slot = behavior;
+    }
+}
+
+/// What Previous does partway through a track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviousBehavior {
+    /// Always go to the previous track.
+    AlwaysPrevious,
+    /// Restart the current track if past the restart threshold, otherwise go
+    /// to the previous track.
+    #[default]
+    RestartThenPrevious,
+}
+
+impl PreviousBehavior {
+    /// Returns whether Previous at `position` should restart the current
+    /// track rather than go to the previous one.
+    pub fn restarts_track(&self, position: Duration, threshold: Duration) -> bool {
+        match self {
+            PreviousBehavior::AlwaysPrevious => false,
+            PreviousBehavior::RestartThenPrevious => position > threshold,
+        }
+    }
+}
+
+/// The default position past which Previous restarts the current track.
+pub const DEFAULT_PREVIOUS_RESTART_THRESHOLD: Duration = Duration::from_secs(3);
+
+/// The default number of tracks from the end of an album within which the
+/// next album's first track is prefetched.
+pub const DEFAULT_NEXT_GROUP_PREFETCH_TRACKS: usize = 2;
+
+/// What the stop button does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum StopBehavior {
+    /// Pause and rewind the current track to its start, keeping it loaded.
+    #[default]
+    ResetToStart,
+    /// Unload the current track entirely.
+    Clear,
+}
+
+/// What happens once the last track taken from the play queue finishes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueEndBehavior {
+    /// Stop playback, e.g. after a deliberately built queue.
+    Stop,
+    /// Carry on in library order after the last queued track.
+    #[default]
+    ContinueLibrary,
+    /// Queue the tracks played from the queue again, in the same order.
+    Loop,
+}
+
+/// What happens when a track fails to load or play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PlaybackErrorBehavior {
+    /// Move on to the next track.
+    #[default]
+    SkipNext,
+    /// Pause, leaving the error shown, e.g. to wait out a server issue.
+    Pause,
+}
+
 pub struct AppState {
     pub library: Library,
 
@@ -129,6 +297,29 @@ pub struct AppState {
     pub last_requested_track_for_ui_scroll: Option<TrackId>,
     pub playback_state: PlaybackState,
     pub playback_mode: PlaybackMode,
+    /// The shuffle mode that toggling shuffle on switches to: the last one
+    /// used.
+    pub preferred_shuffle: PlaybackMode,
+    /// What Next and Previous do at the ends of the queue, per mode.
+    pub end_behavior: EndBehavior,
+    /// What the stop button does.
+    pub stop_behavior: StopBehavior,
+    /// What happens once the play queue runs out.
+    pub queue_end_behavior: QueueEndBehavior,
+    /// What happens when a track fails to load or play.
+    pub playback_error_behavior: PlaybackErrorBehavior,
+    /// What Previous does partway through a track.
+    pub previous_behavior: PreviousBehavior,
+    /// The position past which Previous restarts the current track, when
+    /// `previous_behavior` is [`PreviousBehavior::RestartThenPrevious`].
+    pub previous_restart_threshold: Duration,
+    /// Once the current track is this many tracks or fewer from the end of
+    /// its group, the next group's first track is prefetched; zero disables
+    /// it.
+    pub next_group_prefetch_tracks: usize,
+    /// Whether re-selecting an album resumes it from where it was left off,
+    /// rather than starting from its first track.
+    pub resume_albums: bool,
     pub sort_order: SortOrder,
     pub queue: QueueState,
     pub volume: f32,
@@ -136,10 +327,51 @@ pub struct AppState {
     pub apply_replaygain: bool,
     /// Preamp added on top of the ReplayGain-computed gain, in dB.
     pub replaygain_preamp_db: f32,
+    /// User gains in dB for individual tracks, applied on top of ReplayGain.
+    pub track_gains: HashMap<TrackId, f32>,
+    /// Tracks that Next, Previous and the end of a track step over. They can
+    /// still be played by picking them.
+    pub skipped_tracks: HashSet<TrackId>,
+    /// Level in dB below which leading and trailing silence is skipped, or
+    /// `None` to play tracks in full.
+    pub skip_silence_db: Option<f32>,
+    /// Extra output devices that mirror playback, by name, with their
+    /// volumes (0 to 1) relative to the main volume.
+    pub extra_outputs: BTreeMap<String, f32>,
+    /// The number of files scanned so far while a server-side library scan
+    /// started by [`Logic::trigger_server_scan`](crate::Logic::trigger_server_scan)
+    /// is running.
+    pub server_scan_count: Option<u64>,
+    /// Progress of the initial library fetch, once the first page arrives.
+    pub fetch_progress: Option<FetchProgress>,
+    /// Tracks that have started playing this session, oldest first, capped
+    /// at [`PLAY_HISTORY_LEN`].
+    pub play_history: VecDeque<TrackId>,
+    /// What every user of the server is playing, as of the last
+    /// [`Logic::refresh_server_now_playing`](crate::Logic::refresh_server_now_playing).
+    pub server_now_playing: Vec<bs::NowPlayingEntry>,
+    /// The stats aggregated by the last
+    /// [`Logic::refresh_listening_stats`](crate::Logic::refresh_listening_stats),
+    /// or `None` while they are being aggregated.
+    pub listening_stats: Option<ListeningStats>,
+    /// Details about the server, collected after the initial connection.
+    pub server_info: Option<bs::ServerInfo>,
+    /// The length of the playing track according to its decoder, used when
+    /// the library doesn't know it.
+    pub resolved_duration: Option<(TrackId, Duration)>,
+    pub(crate) search_suggestions: SuggestionCache,
+    /// Library tracks the server found for recent queries.
+    pub(crate) server_search: SuggestionCache<Vec<Track>>,
 
     pub scrobble_state: ScrobbleState,
+    /// The playlist the starred tracks are mirrored into, if enabled.
+    pub favorites_playlist: Option<String>,
 
     pub error: Option<AppStateError>,
+    /// Whether the server has rejected the credentials. Automatic requests
+    /// are held off until new credentials are given to
+    /// [`Logic::reconfigure_server`](crate::Logic::reconfigure_server).
+    pub credentials_rejected: bool,
 }
 
 impl Default for AppState {
@@ -151,17 +383,143 @@ impl Default for AppState {
             last_requested_track_for_ui_scroll: None,
             playback_state: PlaybackState::Stopped,
             playback_mode: PlaybackMode::default(),
+            preferred_shuffle: PlaybackMode::Shuffle,
+            end_behavior: EndBehavior::default(),
+            stop_behavior: StopBehavior::default(),
+            queue_end_behavior: QueueEndBehavior::default(),
+            playback_error_behavior: PlaybackErrorBehavior::default(),
+            previous_behavior: PreviousBehavior::default(),
+            previous_restart_threshold: DEFAULT_PREVIOUS_RESTART_THRESHOLD,
+            next_group_prefetch_tracks: DEFAULT_NEXT_GROUP_PREFETCH_TRACKS,
+            resume_albums: false,
             sort_order: SortOrder::default(),
             queue: QueueState::new(),
             volume: 0.0,
             apply_replaygain: false,
             replaygain_preamp_db: 0.0,
+            track_gains: HashMap::new(),
+            skipped_tracks: HashSet::new(),
+            skip_silence_db: None,
+            extra_outputs: BTreeMap::new(),
+            server_scan_count: None,
+            fetch_progress: None,
+            play_history: VecDeque::new(),
+            server_now_playing: vec![],
+            listening_stats: None,
+            server_info: None,
+            resolved_duration: None,
+            search_suggestions: SuggestionCache::default(),
+            server_search: SuggestionCache::default(),
             scrobble_state: ScrobbleState::default(),
+            favorites_playlist: None,
             error: None,
+            credentials_rejected: false,
+        }
+    }
+}
+
+impl AppState {
+    /// How long `track_id` is: its tagged duration, or the decoder's if it
+    /// has none (or it's zero). `None` if neither is known, e.g. for streams.
+    pub fn track_duration(&self, track_id: &TrackId) -> Option<Duration> {
+        self.library
+            .track_map
+            .get(track_id)
+            .and_then(|track| track.duration)
+            .filter(|&secs| secs > 0)
+            .map(|secs| Duration::from_secs(u64::from(secs)))
+            .or_else(|| {
+                self.resolved_duration
+                    .as_ref()
+                    .filter(|(id, _)| id == track_id)
+                    .map(|(_, duration)| *duration)
+            })
+    }
+
+    /// Records that `track_id` started playing. Restarts of the track that
+    /// was already playing are not recorded again.
+    pub(crate) fn record_play(&mut self, track_id: &TrackId) {
+        if self.play_history.back() == Some(track_id) {
+            return;
+        }
+        if self.play_history.len() == PLAY_HISTORY_LEN {
+            self.play_history.pop_front();
+        }
+        self.play_history.push_back(track_id.clone());
+    }
+
+    /// Forgets the current track and drops the audio cache, for
+    /// [`Logic::clear_playback`](crate::Logic::clear_playback). The queue's
+    /// order is kept.
+    pub(crate) fn clear_playback(&mut self) {
+        self.current_track_and_position = None;
+        self.started_loading_track = None;
+        self.scrobble_state = Default::default();
+
+        let queue = &mut self.queue;
+        queue.audio_cache = HashMap::new();
+        queue.pending_audio_requests.clear();
+        // Invalidate any in-flight load so it doesn't start playing.
+        queue.request_counter = queue.request_counter.wrapping_add(1);
+        queue.current_target = None;
+        queue.current_target_request_id = None;
+        queue.pending_skip_after_error = false;
+        queue.next_track_appended = None;
+    }
+
+    /// Records a failed server request as the error built by `to_app_error`.
+    /// If the server rejected the credentials, a single
+    /// [`AppStateError::AuthenticationFailed`] is raised instead, however
+    /// many requests fail, and [`credentials_rejected`](Self::credentials_rejected)
+    /// is set. Returns whether the credentials were rejected.
+    pub(crate) fn report_client_error(
+        &mut self,
+        error: &bs::ClientError,
+        to_app_error: impl FnOnce(String) -> AppStateError,
+    ) -> bool {
+        if !error.is_auth_failure() {
+            self.error = Some(to_app_error(error.to_string()));
+            return false;
         }
+        if !self.credentials_rejected {
+            self.credentials_rejected = true;
+            self.error = Some(AppStateError::AuthenticationFailed {
+                error: error.to_string(),
+            });
+        }
+        true
+    }
+
+    /// Returns up to `n` distinct albums from the play history, most recently
+    /// played first.
+    pub fn recent_albums(&self, n: usize) -> Vec<RecentAlbum> {
+        let mut seen = HashSet::new();
+        self.play_history
+            .iter()
+            .rev()
+            .filter_map(|track_id| {
+                let group = self.library.group_for_track(track_id)?;
+                seen.insert(group.album_id.clone()).then(|| RecentAlbum {
+                    group: group.clone(),
+                    track_id: track_id.clone(),
+                })
+            })
+            .take(n)
+            .collect()
     }
 }
 
+/// The maximum number of tracks kept in [`AppState::play_history`].
+pub const PLAY_HISTORY_LEN: usize = 500;
+
+/// An album from the play history. See [`AppState::recent_albums`].
+#[derive(Debug, Clone)]
+pub struct RecentAlbum {
+    pub group: Arc<Group>,
+    /// The album's most recently played track.
+    pub track_id: TrackId,
+}
+
 /// Tracks scrobbling state for the currently playing track.
 #[derive(Debug, Default, Clone)]
 pub struct ScrobbleState {
@@ -173,6 +531,9 @@ pub struct ScrobbleState {
     pub accumulated_listening_time: Duration,
     /// The last position we observed (to detect seeks backward)
     pub last_position: Duration,
+    /// The scrobble made for this listen, recorded in the listen history
+    /// with the final listening time once the listen ends.
+    pub scrobble: Option<Scrobble>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -208,6 +569,30 @@ pub enum AppStateError {
         album_id: AlbumId,
         error: String,
     },
+    ServerScanFailed {
+        error: String,
+    },
+    LibraryRefreshFailed {
+        error: String,
+    },
+    AlbumRadioFailed {
+        album_id: AlbumId,
+        error: String,
+    },
+    AuthenticationFailed {
+        error: String,
+    },
+    DownloadTrackFailed {
+        track_id: TrackId,
+        error: String,
+    },
+    FavoritesPlaylistSyncFailed {
+        playlist: String,
+        error: String,
+    },
+    ListeningStatsFailed {
+        error: String,
+    },
 }
 impl AppStateError {
     /// Should be paired with [`Self::display_message`]
@@ -221,6 +606,15 @@ impl AppStateError {
             AppStateError::UnstarTrackFailed { .. } => "Failed to unstar track",
             AppStateError::StarAlbumFailed { .. } => "Failed to star album",
             AppStateError::UnstarAlbumFailed { .. } => "Failed to unstar album",
+            AppStateError::ServerScanFailed { .. } => "Failed to scan server library",
+            AppStateError::LibraryRefreshFailed { .. } => "Failed to refresh library",
+            AppStateError::AlbumRadioFailed { .. } => "Failed to start album radio",
+            AppStateError::AuthenticationFailed { .. } => "Server rejected credentials",
+            AppStateError::DownloadTrackFailed { .. } => "Failed to download track",
+            AppStateError::FavoritesPlaylistSyncFailed { .. } => {
+                "Failed to sync favorites playlist"
+            }
+            AppStateError::ListeningStatsFailed { .. } => "Failed to load listening stats",
         }
     }
 
@@ -262,6 +656,36 @@ impl AppStateError {
             AppStateError::UnstarAlbumFailed { album_id, error } => {
                 format!("Failed to unstar album `{}`: {error}", album_id,)
             }
+            AppStateError::ServerScanFailed { error } => {
+                format!("The server could not scan its library: {error}")
+            }
+            AppStateError::LibraryRefreshFailed { error } => {
+                format!("Failed to fetch the updated library: {error}")
+            }
+            AppStateError::AlbumRadioFailed { album_id, error } => {
+                let album = state
+                    .library
+                    .albums
+                    .get(album_id)
+                    .map_or_else(|| album_id.to_string(), |a| a.name.to_string());
+                format!("Could not find tracks similar to `{album}`: {error}")
+            }
+            AppStateError::AuthenticationFailed { error } => format!(
+                "The server no longer accepts your credentials ({error}). \
+                 Re-enter them in the settings to reconnect."
+            ),
+            AppStateError::DownloadTrackFailed { track_id, error } => {
+                format!(
+                    "Failed to download `{}`: {error}",
+                    TrackDisplayDetails::string_report_without_time(track_id, state)
+                )
+            }
+            AppStateError::FavoritesPlaylistSyncFailed { playlist, error } => {
+                format!("Failed to sync starred tracks to the playlist `{playlist}`: {error}")
+            }
+            AppStateError::ListeningStatsFailed { error } => {
+                format!("Could not read the listen history: {error}")
+            }
         }
     }
 }
@@ -271,3 +695,198 @@ pub struct TrackAndPosition {
     pub track_id: TrackId,
     pub position: Duration,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn always_previous_never_restarts() {
+        let threshold = DEFAULT_PREVIOUS_RESTART_THRESHOLD;
+        for secs in [1, 30] {
+            assert!(
+                !PreviousBehavior::AlwaysPrevious
+                    .restarts_track(Duration::from_secs(secs), threshold)
+            );
+        }
+    }
+
+    #[test]
+    fn restart_then_previous_restarts_past_threshold() {
+        let behavior = PreviousBehavior::RestartThenPrevious;
+        let threshold = DEFAULT_PREVIOUS_RESTART_THRESHOLD;
+        assert!(!behavior.restarts_track(Duration::from_secs(1), threshold));
+        assert!(behavior.restarts_track(Duration::from_secs(30), threshold));
+        assert!(!behavior.restarts_track(Duration::from_secs(30), Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn shuffle_scope_cycles_through_shuffle_modes() {
+        assert_eq!(
+            PlaybackMode::Shuffle.next_shuffle_scope(),
+            PlaybackMode::GroupShuffle
+        );
+        assert_eq!(
+            PlaybackMode::ArtistShuffle.next_shuffle_scope(),
+            PlaybackMode::Shuffle
+        );
+        assert_eq!(
+            PlaybackMode::Sequential.next_shuffle_scope(),
+            PlaybackMode::Shuffle
+        );
+        assert!(!PlaybackMode::GroupRepeat.is_shuffle());
+    }
+
+    /// A state whose library has one track, tagged with a zero duration.
+    fn state_with_zero_duration_track() -> AppState {
+        let mut library = crate::test_util::make_library(1, 1);
+        for track in library.track_map.values_mut() {
+            track.duration = Some(0);
+        }
+        AppState {
+            library,
+            ..AppState::default()
+        }
+    }
+
+    #[test]
+    fn zero_duration_falls_back_to_the_decoded_duration() {
+        let mut state = state_with_zero_duration_track();
+        let track_id = TrackId("t0".into());
+        let track_and_position = crate::TrackAndPosition {
+            track_id: track_id.clone(),
+            position: Duration::from_secs(5),
+        };
+
+        assert_eq!(state.track_duration(&track_id), None);
+        let details =
+            TrackDisplayDetails::from_track_and_position(&track_and_position, &state).unwrap();
+        assert_eq!(details.track_duration, Duration::ZERO);
+        let scrobble = crate::Scrobble::from_state(&state, &track_id, 0).unwrap();
+        assert_eq!(scrobble.duration_secs, None);
+
+        // A duration decoded for another track is ignored.
+        state.resolved_duration = Some((TrackId("other".into()), Duration::from_secs(60)));
+        assert_eq!(state.track_duration(&track_id), None);
+
+        state.resolved_duration = Some((track_id.clone(), Duration::from_secs(200)));
+        assert_eq!(
+            state.track_duration(&track_id),
+            Some(Duration::from_secs(200))
+        );
+        let details =
+            TrackDisplayDetails::from_track_and_position(&track_and_position, &state).unwrap();
+        assert_eq!(details.track_duration, Duration::from_secs(200));
+        let scrobble = crate::Scrobble::from_state(&state, &track_id, 0).unwrap();
+        assert_eq!(scrobble.duration_secs, Some(200));
+    }
+
+    #[test]
+    fn recent_albums_are_distinct_and_most_recent_first() {
+        // Album 0 has t0, album 1 has t1, and album 2 has t2 and t3.
+        let mut state = AppState {
+            library: crate::test_util::make_library(4, 3),
+            ..AppState::default()
+        };
+        for track in ["t2", "t0", "t3", "t3", "t1"] {
+            state.record_play(&TrackId(track.into()));
+        }
+        assert_eq!(state.play_history.len(), 4);
+
+        let recent = state.recent_albums(10);
+        let albums: Vec<_> = recent.iter().map(|r| r.group.album.as_str()).collect();
+        assert_eq!(albums, ["Album 1", "Album 2", "Album 0"]);
+        assert_eq!(recent[1].track_id, TrackId("t3".into()));
+        assert_eq!(state.recent_albums(1).len(), 1);
+    }
+
+    #[test]
+    fn play_history_is_capped() {
+        let mut state = AppState::default();
+        for idx in 0..PLAY_HISTORY_LEN + 10 {
+            state.record_play(&TrackId(format!("t{idx}")));
+        }
+        assert_eq!(state.play_history.len(), PLAY_HISTORY_LEN);
+        assert_eq!(state.play_history.front(), Some(&TrackId("t10".into())));
+    }
+
+    #[test]
+    fn clearing_playback_unloads_the_track_and_cache() {
+        let track = TrackId("t1".into());
+        let mut state = AppState {
+            current_track_and_position: Some(TrackAndPosition {
+                track_id: track.clone(),
+                position: Duration::from_secs(30),
+            }),
+            ..Default::default()
+        };
+        state.started_loading_track = Some(std::time::Instant::now());
+        state.scrobble_state.track_id = Some(track.clone());
+        state.queue.audio_cache.insert(track.clone(), vec![0; 16]);
+        state
+            .queue
+            .pending_audio_requests
+            .insert(TrackId("t2".into()), 3);
+        state.queue.current_target = Some(track.clone());
+        state.queue.current_target_request_id = Some(3);
+        state.queue.next_track_appended = Some(TrackId("t2".into()));
+        state.queue.ordered_tracks = vec![track.clone(), TrackId("t2".into())];
+        let request_counter = state.queue.request_counter;
+
+        state.clear_playback();
+
+        assert_eq!(state.current_track_and_position, None);
+        assert!(state.started_loading_track.is_none());
+        assert_eq!(state.scrobble_state.track_id, None);
+        assert!(state.queue.audio_cache.is_empty());
+        assert!(state.queue.pending_audio_requests.is_empty());
+        assert_eq!(state.queue.current_target, None);
+        assert_eq!(state.queue.current_target_request_id, None);
+        assert_eq!(state.queue.next_track_appended, None);
+        // In-flight loads are stale, but the queue itself is kept.
+        assert_ne!(state.queue.request_counter, request_counter);
+        assert_eq!(state.queue.ordered_tracks.len(), 2);
+    }
+
+    #[test]
+    fn clearing_playback_without_a_track_is_harmless() {
+        let mut state = AppState::default();
+        state.clear_playback();
+        state.clear_playback();
+        assert_eq!(state.current_track_and_position, None);
+        assert!(state.queue.audio_cache.is_empty());
+    }
+
+    #[test]
+    fn rejected_credentials_are_reported_once() {
+        let mut state = AppState::default();
+        let auth_error = || bs::ClientError::SubsonicError {
+            code: 40,
+            message: Some("Wrong username or password".into()),
+        };
+        let scan_failed = |error| AppStateError::ServerScanFailed { error };
+
+        assert!(state.report_client_error(&auth_error(), scan_failed));
+        assert!(state.credentials_rejected);
+        assert!(matches!(
+            state.error,
+            Some(AppStateError::AuthenticationFailed { .. })
+        ));
+
+        // Further rejections don't raise the error again once dismissed.
+        state.error = None;
+        assert!(state.report_client_error(&auth_error(), scan_failed));
+        assert_eq!(state.error, None);
+
+        // Other failures are still reported as usual.
+        let not_found = bs::ClientError::SubsonicError {
+            code: 70,
+            message: None,
+        };
+        assert!(!state.report_client_error(&not_found, scan_failed));
+        assert!(matches!(
+            state.error,
+            Some(AppStateError::ServerScanFailed { .. })
+        ));
+    }
+}
@@ -0,0 +1,81 @@
+//! Saving the original files of tracks, as served by the server's `download`
+//! endpoint rather than transcoded by `stream`, for archiving or exporting
+//! them.
+use std::path::{Path, PathBuf};
+
+use blackbird_shared::track_path::TrackPath;
+use blackbird_state::TrackId;
+
+use crate::{AppStateError, Logic, bs};
+
+/// The extension used when the server doesn't report the original file's.
+const UNKNOWN_EXTENSION: &str = "bin";
+
+impl<C: bs::SubsonicApi> Logic<C> {
+    /// Downloads the original file of a track into `directory`, laid out as
+    /// `Album Artist/Album/NN - Title.ext` like `blackbird-id3mover` would.
+    pub fn download_track_to(&self, track_id: &TrackId, directory: &Path) {
+        let Some(path) = self.download_path(track_id) else {
+            return;
+        };
+        let path = directory.join(path);
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let track_id = track_id.clone();
+
+        self.tokio_thread.spawn(async move {
+            let bytes = match client.download(&track_id.0).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    state.write().unwrap().report_client_error(&e, |error| {
+                        AppStateError::DownloadTrackFailed { track_id, error }
+                    });
+                    return;
+                }
+            };
+
+            if let Err(e) = save(&path, &bytes).await {
+                state.write().unwrap().error = Some(AppStateError::DownloadTrackFailed {
+                    track_id,
+                    error: format!("could not write {}: {e}", path.display()),
+                });
+                return;
+            }
+            tracing::info!("Downloaded {track_id} to {}", path.display());
+        });
+    }
+
+    /// The path of a track's download relative to the download directory,
+    /// falling back to the track's own artist and title like
+    /// `blackbird-id3mover` does for missing tags.
+    fn download_path(&self, track_id: &TrackId) -> Option<PathBuf> {
+        let st = self.read_state();
+        let track = st.library.track_map.get(track_id)?;
+        let album = track
+            .album_id
+            .as_ref()
+            .and_then(|id| st.library.albums.get(id));
+        let album_artist = album
+            .map(|a| a.artist.as_str())
+            .or(track.artist.as_deref())
+            .unwrap_or("Unknown Artist");
+        Some(
+            TrackPath {
+                album_artist,
+                album: album.map_or(track.title.as_str(), |a| a.name.as_str()),
+                title: &track.title,
+                track_number: track.track,
+                disc_number: track.disc_number,
+                extension: track.suffix.as_deref().unwrap_or(UNKNOWN_EXTENSION),
+            }
+            .relative_path(),
+        )
+    }
+}
+
+async fn save(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, bytes).await
+}
@@ -0,0 +1,275 @@
+//! Mirroring the starred tracks into a server playlist, so that other Subsonic
+//! clients can play them as an ordinary playlist. The sync is opt-in: it only
+//! runs once a playlist name has been set with
+//! [`Logic::set_favorites_playlist`](crate::Logic::set_favorites_playlist).
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock, atomic::Ordering},
+    time::Duration,
+};
+
+use blackbird_state::TrackId;
+
+use crate::{AppState, AppStateError, Logic, bs, library::Library};
+
+/// How long to wait after a star or unstar before syncing, so that a burst of
+/// changes results in a single round of requests.
+pub(crate) const SYNC_DELAY: Duration = Duration::from_secs(10);
+
+/// How many songs are sent per request, keeping the query string of the
+/// `GET` requests to a reasonable length.
+const BATCH_SIZE: usize = 100;
+
+/// The changes that bring a playlist in line with the starred tracks.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct PlaylistDiff {
+    /// Tracks to append, in library order.
+    pub add: Vec<TrackId>,
+    /// Positions of the entries to remove, in descending order.
+    pub remove: Vec<usize>,
+}
+impl PlaylistDiff {
+    /// Diffs the playlist's `entries` against the `starred` tracks. Entries
+    /// that are no longer starred or that repeat an earlier entry are
+    /// removed; starred tracks missing from the playlist are appended, so
+    /// that an unchanged playlist needs no requests at all.
+    pub fn new(entries: &[TrackId], starred: &[TrackId]) -> Self {
+        let starred_set: HashSet<&TrackId> = starred.iter().collect();
+        let mut kept = HashSet::new();
+        let mut remove: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| !starred_set.contains(id) || !kept.insert(*id))
+            .map(|(index, _)| index)
+            .collect();
+        remove.reverse();
+
+        let add = starred
+            .iter()
+            .filter(|id| !kept.contains(id))
+            .cloned()
+            .collect();
+
+        Self { add, remove }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+}
+
+/// Returns the starred tracks in library order, or `None` if the library is
+/// still loading and the set would be incomplete.
+pub(crate) fn starred_tracks(library: &Library) -> Option<Vec<TrackId>> {
+    library.has_loaded_all_tracks.then(|| {
+        library
+            .iter_tracks_in_order()
+            .filter(|t| t.starred)
+            .map(|t| t.id.clone())
+            .collect()
+    })
+}
+
+/// Brings the playlist called `name` in line with `starred`, creating it if
+/// it doesn't exist yet.
+pub(crate) async fn sync(
+    client: &impl bs::SubsonicApi,
+    name: &str,
+    starred: &[TrackId],
+) -> bs::ClientResult<()> {
+    let existing = client
+        .get_playlists()
+        .await?
+        .into_iter()
+        .find(|playlist| playlist.name == name);
+
+    let Some(playlist) = existing else {
+        if starred.is_empty() {
+            return Ok(());
+        }
+        tracing::info!(
+            "Creating favorites playlist `{name}` with {} tracks",
+            starred.len()
+        );
+        let mut batches = starred.chunks(BATCH_SIZE);
+        let first = batches.next().unwrap_or_default();
+        client
+            .create_playlist(name, first.iter().map(|id| id.0.clone()).collect())
+            .await?;
+        if batches.len() == 0 {
+            return Ok(());
+        }
+        // Older servers don't return the created playlist, so look it up to
+        // append the remaining tracks.
+        let Some(playlist) = client
+            .get_playlists()
+            .await?
+            .into_iter()
+            .find(|playlist| playlist.name == name)
+        else {
+            return Ok(());
+        };
+        for batch in batches {
+            add(client, &playlist.id, batch).await?;
+        }
+        return Ok(());
+    };
+
+    let entries: Vec<TrackId> = client
+        .get_playlist(&playlist.id)
+        .await?
+        .entry
+        .into_iter()
+        .map(|child| TrackId(child.id))
+        .collect();
+    let diff = PlaylistDiff::new(&entries, starred);
+    if diff.is_empty() {
+        return Ok(());
+    }
+    tracing::info!(
+        "Syncing favorites playlist `{name}`: adding {}, removing {}",
+        diff.add.len(),
+        diff.remove.len()
+    );
+
+    // Removals go first and in descending order, so each batch's indices are
+    // unaffected by the batches before it.
+    for batch in diff.remove.chunks(BATCH_SIZE) {
+        client
+            .update_playlist(&playlist.id, vec![], batch.to_vec())
+            .await?;
+    }
+    for batch in diff.add.chunks(BATCH_SIZE) {
+        add(client, &playlist.id, batch).await?;
+    }
+    Ok(())
+}
+
+impl<C: bs::SubsonicApi> Logic<C> {
+    /// Sets the name of the playlist the starred tracks are mirrored into;
+    /// `None` turns mirroring off. Schedules a sync when it changes.
+    pub fn set_favorites_playlist(&self, name: Option<&str>) {
+        {
+            let mut st = self.write_state();
+            if st.favorites_playlist.as_deref() == name {
+                return;
+            }
+            st.favorites_playlist = name.map(str::to_string);
+        }
+        self.schedule_favorites_sync();
+    }
+
+    /// Brings the playlist called `name` in line with the starred tracks
+    /// right away, creating it if needed. Does nothing while the library is
+    /// still loading.
+    pub fn sync_favorites_playlist(&self, name: &str) {
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let name = name.to_string();
+        self.tokio_thread.spawn(async move {
+            sync_and_report(client.as_ref(), &state, &name).await;
+        });
+    }
+
+    /// Syncs the configured favorites playlist after [`SYNC_DELAY`], unless
+    /// a sync is already waiting, in which case it picks up this change too.
+    pub(crate) fn schedule_favorites_sync(&self) {
+        if self.read_state().favorites_playlist.is_none()
+            || self.favorites_sync_pending.swap(true, Ordering::SeqCst)
+        {
+            return;
+        }
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let pending = self.favorites_sync_pending.clone();
+        self.tokio_thread.spawn(async move {
+            // Wait out the delay, and for the library if it's still loading,
+            // as syncing against a partial library would empty the playlist.
+            loop {
+                tokio::time::sleep(SYNC_DELAY).await;
+                if state.read().unwrap().library.has_loaded_all_tracks {
+                    break;
+                }
+            }
+            pending.store(false, Ordering::SeqCst);
+            let name = state.read().unwrap().favorites_playlist.clone();
+            if let Some(name) = name {
+                sync_and_report(client.as_ref(), &state, &name).await;
+            }
+        });
+    }
+}
+
+async fn sync_and_report(client: &impl bs::SubsonicApi, state: &Arc<RwLock<AppState>>, name: &str) {
+    let starred = {
+        let st = state.read().unwrap();
+        if st.credentials_rejected {
+            return;
+        }
+        starred_tracks(&st.library)
+    };
+    let Some(starred) = starred else {
+        return;
+    };
+    if let Err(e) = sync(client, name, &starred).await {
+        state.write().unwrap().report_client_error(&e, |error| {
+            AppStateError::FavoritesPlaylistSyncFailed {
+                playlist: name.to_string(),
+                error,
+            }
+        });
+    }
+}
+
+async fn add(
+    client: &impl bs::SubsonicApi,
+    playlist_id: &str,
+    tracks: &[TrackId],
+) -> bs::ClientResult<()> {
+    client
+        .update_playlist(
+            playlist_id,
+            tracks.iter().map(|id| id.0.clone()).collect(),
+            vec![],
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<TrackId> {
+        ids.iter().map(|id| TrackId((*id).into())).collect()
+    }
+
+    #[test]
+    fn unchanged_playlist_needs_no_changes() {
+        let tracks = ids(&["a", "b", "c"]);
+        assert!(PlaylistDiff::new(&tracks, &tracks).is_empty());
+    }
+
+    #[test]
+    fn existing_order_is_kept_when_appending() {
+        let diff = PlaylistDiff::new(&ids(&["c", "a"]), &ids(&["a", "b", "c"]));
+        assert_eq!(
+            diff,
+            PlaylistDiff {
+                add: ids(&["b"]),
+                remove: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn unstarred_and_duplicate_entries_are_removed_last_first() {
+        let diff = PlaylistDiff::new(&ids(&["a", "x", "b", "a", "y"]), &ids(&["a", "b", "c"]));
+        assert_eq!(
+            diff,
+            PlaylistDiff {
+                add: ids(&["c"]),
+                remove: vec![4, 3, 1],
+            }
+        );
+    }
+}
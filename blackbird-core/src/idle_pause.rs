@@ -0,0 +1,104 @@
+//! Pausing playback after a stretch without user input, so it doesn't play
+//! to an empty room. Clients report input with [`Logic::note_user_activity`];
+//! the idle time only counts while playing, so starting playback after a
+//! long break doesn't pause it straight away.
+use std::time::{Duration, Instant};
+
+use crate::{Logic, PlaybackState, bs};
+
+/// Tracks how long playback has gone without user input.
+#[derive(Debug)]
+pub(crate) struct IdleTimer {
+    after: Option<Duration>,
+    last_activity: Instant,
+}
+
+impl IdleTimer {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            after: None,
+            last_activity: now,
+        }
+    }
+
+    pub fn note_activity(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    /// Returns whether playback should be paused at `now`. Time spent not
+    /// playing counts as activity.
+    pub fn poll(&mut self, now: Instant, playing: bool) -> bool {
+        if !playing {
+            self.last_activity = now;
+            return false;
+        }
+        let Some(after) = self.after else {
+            return false;
+        };
+        if now.duration_since(self.last_activity) < after {
+            return false;
+        }
+        self.last_activity = now;
+        true
+    }
+}
+
+impl<C: bs::SubsonicApi> Logic<C> {
+    /// Sets how long playback can go without user input before it is
+    /// paused; `None` disables it.
+    pub fn set_idle_pause_after(&mut self, after: Option<Duration>) {
+        self.idle_timer.after = after;
+    }
+
+    /// Records user input, restarting the idle countdown.
+    pub fn note_user_activity(&mut self) {
+        self.idle_timer.note_activity(Instant::now());
+    }
+
+    /// Pauses playback if the user has been idle for too long. Returns
+    /// whether it did.
+    pub(crate) fn check_idle_pause(&mut self) -> bool {
+        let playing = self.read_state().playback_state == PlaybackState::Playing;
+        if !self.idle_timer.poll(Instant::now(), playing) {
+            return false;
+        }
+        tracing::info!("No input for {:?}; pausing playback", self.idle_timer.after);
+        self.pause_current();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn pauses_once_after_idling_while_playing() {
+        let start = Instant::now();
+        let mut timer = IdleTimer::new(start);
+        assert!(
+            !timer.poll(start + 10 * MINUTE, true),
+            "disabled by default"
+        );
+
+        timer.after = Some(5 * MINUTE);
+        timer.note_activity(start + 10 * MINUTE);
+        assert!(!timer.poll(start + 14 * MINUTE, true));
+        assert!(timer.poll(start + 15 * MINUTE, true));
+        // Once paused, the countdown starts over.
+        assert!(!timer.poll(start + 16 * MINUTE, true));
+    }
+
+    #[test]
+    fn time_spent_not_playing_counts_as_activity() {
+        let start = Instant::now();
+        let mut timer = IdleTimer::new(start);
+        timer.after = Some(5 * MINUTE);
+
+        assert!(!timer.poll(start + 60 * MINUTE, false));
+        assert!(!timer.poll(start + 61 * MINUTE, true));
+        assert!(timer.poll(start + 65 * MINUTE, true));
+    }
+}
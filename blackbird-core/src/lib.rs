@@ -1,23 +1,47 @@
+//! The client-independent core of blackbird: the library, the play queue,
+//! playback, and talking to the server. Both clients are built on it, and it
+//! can be embedded in other tools the same way:
+//!
+//! - Construct a [`Logic`] with [`LogicArgs`], which carries the server
+//!   credentials, the initial settings, and the senders for the
+//!   notifications the embedder wants to receive. The library starts loading
+//!   in the background straight away.
+//! - Call [`Logic::update`] regularly from one thread, e.g. once per frame or
+//!   tick. It applies playback events and requests; nothing happens to the
+//!   queue without it.
+//! - Control playback from anywhere with a [`LogicRequestHandle`] from
+//!   [`Logic::request_handle`], or by calling [`Logic`]'s methods directly on
+//!   the updating thread.
+//! - Observe playback with [`Logic::subscribe_events`], and read everything
+//!   else from [`Logic::get_state`].
+//!
+//! See `examples/embed.rs` for a minimal headless consumer.
 pub mod util;
 
 pub use blackbird_state;
-use blackbird_state::{AlbumId, CoverArtId, Track, TrackId};
+use blackbird_state::{AlbumId, CoverArtId, FetchProgress, Group, Track, TrackId};
 pub use blackbird_subsonic as bs;
 use smol_str::SmolStr;
 
 use std::{
-    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
-    time::Duration,
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, atomic::AtomicBool},
+    time::{Duration, Instant},
 };
 
 mod render;
 pub use render::VisibleGroupSet;
 
+#[cfg(feature = "audio")]
+mod output_fanout;
 #[cfg(feature = "audio")]
 mod playback_source;
 mod playback_thread;
 use playback_thread::{LogicToPlaybackMessage, PlaybackThread, TrackLoadMode, TrackPlayback};
-pub use playback_thread::{PlaybackState, PlaybackToLogicMessage, PlaybackToLogicRx};
+pub use playback_thread::{
+    PlaybackState, PlaybackToLogicMessage, PlaybackToLogicRx, PlaybackToLogicTryRecvError,
+};
 
 mod tokio_thread;
 use tokio_thread::TokioThread;
@@ -26,13 +50,45 @@ pub(crate) mod queue;
 
 mod app_state;
 pub use app_state::{
-    AppState, AppStateError, PlaybackMode, ScrobbleState, SortOrder, TrackAndPosition,
+    AppState, AppStateError, BoundaryBehavior, DEFAULT_NEXT_GROUP_PREFETCH_TRACKS,
+    DEFAULT_PREVIOUS_RESTART_THRESHOLD, EndBehavior, PLAY_HISTORY_LEN, PlayScope,
+    PlaybackErrorBehavior, PlaybackMode, PreviousBehavior, QueueEndBehavior, RecentAlbum,
+    ScrobbleState, SortOrder, StopBehavior, TrackAndPosition,
 };
 
 mod library;
-pub use library::Library;
+pub use library::{GroupGain, Library, LibraryDelta, TrackNumber};
+
+mod search_suggestions;
+pub use search_suggestions::SearchSuggestion;
+
+mod radio;
+
+mod download;
+
+mod idle_pause;
+use idle_pause::IdleTimer;
+
+mod position;
+use position::{LivePosition, PositionThrottle};
+
+mod favorites_playlist;
 
-pub struct Logic {
+mod listen_history;
+pub use listen_history::{HistoryEntry, HistoryError, ListeningStats};
+
+mod scrobble;
+pub use scrobble::{
+    ListenBrainzScrobbler, Scrobble, ScrobbleError, ScrobbleFuture, ScrobbleTargets, Scrobbler,
+    SubsonicScrobbler,
+};
+
+#[cfg(test)]
+mod test_util;
+
+/// The client-independent player: the library, the play queue and playback,
+/// backed by a Subsonic server through `C`, which is only replaced for tests.
+pub struct Logic<C = bs::Client> {
     // N.B. `playback_thread` must be declared before `tokio_thread` so that it
     // drops first. `TokioThread` drop blocks while spawned tasks (which hold
     // `PlaybackThreadSendHandle` clones) complete; if that runs before
@@ -62,10 +118,33 @@ pub struct Logic {
 
     /// Guards against duplicate in-flight lyrics requests for the same track.
     last_requested_lyrics_track: std::sync::Mutex<Option<TrackId>>,
+    /// Cover art requests that haven't completed yet, so that the same art
+    /// at the same size is only fetched once at a time.
+    cover_art_in_flight: Arc<std::sync::Mutex<HashSet<CoverArtRequest>>>,
+
+    /// The latest reported position, read by the UI every frame.
+    live_position: LivePosition,
+    /// Coalesces position reports into fewer state writes.
+    position_throttle: PositionThrottle,
+    /// Pauses playback after too long without user input.
+    idle_timer: IdleTimer,
 
     state: Arc<RwLock<AppState>>,
-    client: Arc<bs::Client>,
+    client: Arc<C>,
+    /// Identifies this client to the server; kept for reconnecting.
+    client_id: String,
     transcode: bool,
+    /// Format to transcode to instead of the one picked for the server.
+    transcode_format: Option<String>,
+    /// API version to use instead of negotiating one with the server.
+    api_version: Option<String>,
+    fetch_options: blackbird_state::FetchOptions,
+    scrobble_targets: ScrobbleTargets,
+    /// Built from `scrobble_targets`; rebuilt whenever the client changes.
+    scrobblers: Vec<Arc<dyn Scrobbler>>,
+    /// Set while a favorites playlist sync is waiting to run, so that a burst
+    /// of stars only schedules one.
+    favorites_sync_pending: Arc<AtomicBool>,
 }
 #[derive(Debug, Clone)]
 pub enum LogicRequestMessage {
@@ -95,6 +174,16 @@ pub struct CoverArt {
     /// The size that was requested from the server, or `None` for full resolution.
     pub requested_size: Option<usize>,
 }
+/// A request for cover art at a given size. Each size is fetched and cached
+/// separately, so a thumbnail and an overlay of the same art don't replace
+/// each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CoverArtRequest {
+    /// The art to fetch; any fallback IDs are tried under this one.
+    pub(crate) cover_art_id: CoverArtId,
+    /// The edge length to request in pixels, or `None` for full resolution.
+    pub(crate) size: Option<usize>,
+}
 
 /// How many albums either side of the next track's album have their cover
 /// art kept warm, approximating a page of albums in either client.
@@ -115,6 +204,7 @@ pub struct TrackDisplayDetails {
     pub track_id: TrackId,
     pub track_title: SmolStr,
     pub track_artist: Option<SmolStr>,
+    /// Zero if the track's length isn't known.
     pub track_duration: Duration,
     pub track_position: Duration,
     pub show_time: bool,
@@ -132,11 +222,11 @@ impl TrackDisplayDetails {
             album_id: album.id.clone(),
             album_name: album.name.clone(),
             album_artist: album.artist.clone(),
-            cover_art_id: album.cover_art_id.clone(),
+            cover_art_id: album.cover_art_ids().next().cloned(),
             track_id: track.id.clone(),
             track_title: track.title.clone(),
             track_artist: track.artist.clone(),
-            track_duration: Duration::from_secs(track.duration.unwrap_or(1) as u64),
+            track_duration: state.track_duration(&track.id).unwrap_or_default(),
             track_position: track_and_position.position,
             show_time: true,
             starred: track.starred,
@@ -197,12 +287,35 @@ pub struct LogicArgs {
     pub base_url: String,
     pub username: String,
     pub password: String,
+    /// Identifies the client to the server (the Subsonic `c` parameter), e.g.
+    /// `blackbird/0.1.0`.
+    pub client_id: String,
     pub transcode: bool,
+    /// Format to transcode to instead of the one picked for the server.
+    pub transcode_format: Option<String>,
+    /// API version to use instead of negotiating one with the server.
+    pub api_version: Option<String>,
+    pub fetch_options: blackbird_state::FetchOptions,
+    pub scrobble_targets: ScrobbleTargets,
     pub volume: f32,
     pub apply_replaygain: bool,
     pub replaygain_preamp_db: f32,
+    /// User gains in dB for individual tracks, applied on top of ReplayGain.
+    pub track_gains: HashMap<TrackId, f32>,
+    /// Tracks that are always skipped over when advancing.
+    pub skipped_tracks: HashSet<TrackId>,
+    /// Level in dB below which leading and trailing silence is skipped, or
+    /// `None` to play tracks in full.
+    pub skip_silence_db: Option<f32>,
     pub sort_order: SortOrder,
     pub playback_mode: PlaybackMode,
+    /// The shuffle mode that toggling shuffle on switches to.
+    pub preferred_shuffle: PlaybackMode,
+    pub end_behavior: EndBehavior,
+    pub stop_behavior: StopBehavior,
+    pub previous_behavior: PreviousBehavior,
+    /// The position past which Previous restarts the current track.
+    pub previous_restart_threshold: Duration,
     pub last_playback: Option<(TrackId, Duration)>,
     pub cover_art_loaded_tx: std::sync::mpsc::Sender<CoverArt>,
     pub lyrics_loaded_tx: std::sync::mpsc::Sender<LyricsData>,
@@ -211,17 +324,44 @@ pub struct LogicArgs {
 }
 
 impl Logic {
-    pub fn new(
+    pub fn new(args: LogicArgs) -> Self {
+        let client = bs::Client::new(
+            args.base_url.clone(),
+            args.username.clone(),
+            args.password.clone(),
+            args.client_id.clone(),
+        );
+        Self::with_client(client, args)
+    }
+}
+impl<C: bs::SubsonicApi> Logic<C> {
+    /// Like [`Logic::new`], but talks to `client` rather than connecting with
+    /// the server settings in the arguments, e.g. to run against a mock.
+    pub fn with_client(
+        client: C,
         LogicArgs {
-            base_url,
-            username,
-            password,
+            base_url: _,
+            username: _,
+            password: _,
+            client_id,
             transcode,
+            transcode_format,
+            api_version,
+            fetch_options,
+            scrobble_targets,
             volume,
             apply_replaygain,
             replaygain_preamp_db,
+            track_gains,
+            skipped_tracks,
+            skip_silence_db,
             sort_order,
             playback_mode,
+            preferred_shuffle,
+            end_behavior,
+            stop_behavior,
+            previous_behavior,
+            previous_restart_threshold,
             last_playback,
             cover_art_loaded_tx,
             lyrics_loaded_tx,
@@ -233,16 +373,20 @@ impl Logic {
             volume,
             apply_replaygain,
             replaygain_preamp_db,
+            track_gains,
+            skipped_tracks,
+            skip_silence_db,
             sort_order,
             playback_mode,
+            preferred_shuffle,
+            end_behavior,
+            stop_behavior,
+            previous_behavior,
+            previous_restart_threshold,
             ..AppState::default()
         }));
-        let client = Arc::new(bs::Client::new(
-            base_url,
-            username,
-            password,
-            "blackbird".to_string(),
-        ));
+        let client = Arc::new(client);
+        let scrobblers = scrobble::build_scrobblers(&scrobble_targets, &client);
 
         let tokio_thread = TokioThread::new();
 
@@ -277,10 +421,22 @@ impl Logic {
             track_updated_tx,
 
             last_requested_lyrics_track: std::sync::Mutex::new(None),
+            cover_art_in_flight: Default::default(),
+
+            live_position: LivePosition::default(),
+            position_throttle: PositionThrottle::default(),
+            idle_timer: IdleTimer::new(Instant::now()),
 
             state,
             client,
+            client_id,
             transcode,
+            transcode_format,
+            api_version,
+            fetch_options,
+            scrobble_targets,
+            scrobblers,
+            favorites_sync_pending: Arc::new(AtomicBool::new(false)),
         };
         logic.initial_fetch(last_playback);
         logic
@@ -313,9 +469,14 @@ impl Logic {
                     );
                     self.ensure_cache_window();
 
+                    // Reports held back for the previous track are stale.
+                    self.position_throttle.discard();
+                    self.live_position.set(track_and_position.position);
+
                     let mut st = self.write_state();
                     st.current_track_and_position = Some(track_and_position.clone());
                     st.started_loading_track = None;
+                    st.record_play(&track_and_position.track_id);
 
                     // Sync current_target with the actual current track.
                     // This is important for detecting pending track changes in gapless logic.
@@ -325,35 +486,57 @@ impl Logic {
                     // playback thread moved to the next track without going through
                     // schedule_next_track, so the index is stale).
                     let ordered = &st.queue.ordered_tracks;
-                    if !ordered.is_empty() {
-                        let next_index = (st.queue.current_index + 1) % ordered.len();
+                    if st.queue.manual_queue.front() == Some(&track_and_position.track_id) {
+                        // The front of the play queue was appended gaplessly.
+                        let finished = ordered.get(st.queue.current_index).cloned();
+                        let on_empty = st.queue_end_behavior;
+                        queue::take_manual_next(&mut st.queue, on_empty);
+                        if let Some(finished) = finished {
+                            queue::clear_finished_album(&mut st, &finished);
+                        }
+                    } else if !ordered.is_empty() {
+                        let next_index = queue::unskipped_neighbour_index(&st, 1)
+                            .unwrap_or((st.queue.current_index + 1) % ordered.len());
                         if ordered[next_index] == track_and_position.track_id {
+                            let finished = ordered[st.queue.current_index].clone();
                             st.queue.current_index = next_index;
+                            queue::clear_finished_album(&mut st, &finished);
                         }
                     }
+                    queue::note_track_started(&mut st.queue, &track_and_position.track_id);
+                    queue::mark_album_progress(&mut st, &track_and_position.track_id);
 
                     // Reset next track append tracking for gapless playback.
                     st.queue.next_track_appended = None;
 
                     // Reset scrobble state for new track
-                    st.scrobble_state = ScrobbleState {
-                        track_id: Some(track_and_position.track_id.clone()),
-                        has_scrobbled: false,
-                        accumulated_listening_time: Duration::ZERO,
-                        last_position: Duration::ZERO,
-                    };
+                    let finished = std::mem::replace(
+                        &mut st.scrobble_state,
+                        ScrobbleState {
+                            track_id: Some(track_and_position.track_id.clone()),
+                            ..Default::default()
+                        },
+                    );
                     tracing::debug!(
                         "Scrobble state reset for track: {}",
                         track_and_position.track_id.0
                     );
+                    drop(st);
+                    self.record_listen(finished);
+
+                    self.report_now_playing(&track_and_position.track_id);
                 }
-                PlaybackToLogicMessage::PositionChanged(track_and_duration) => {
-                    self.write_state().current_track_and_position =
-                        Some(track_and_duration.clone());
-                    self.update_scrobble_state(&track_and_duration);
+                PlaybackToLogicMessage::PositionChanged(track_and_position) => {
+                    // Readers see every report; the state gets the latest one
+                    // once per write interval, below.
+                    self.live_position.set(track_and_position.position);
+                    self.position_throttle.push(track_and_position);
                 }
                 PlaybackToLogicMessage::TrackEnded => {
                     tracing::debug!("TrackEnded: scheduling advance to next track");
+                    if let Some(track_id) = self.get_playing_track_id() {
+                        queue::clear_finished_album(&mut self.write_state(), &track_id);
+                    }
                     self.handle_track_end_advance();
                 }
                 PlaybackToLogicMessage::FailedToPlayTrack(track_id, error) => {
@@ -364,24 +547,52 @@ impl Logic {
                             &self.state.read().unwrap()
                         )
                     );
-                    self.write_state().error =
-                        Some(AppStateError::DecodeTrackFailed { track_id, error });
-                    self.schedule_next_track();
+                    {
+                        let mut st = self.write_state();
+                        st.started_loading_track = None;
+                        st.error = Some(AppStateError::DecodeTrackFailed { track_id, error });
+                    }
+                    self.recover_from_playback_error();
                 }
                 PlaybackToLogicMessage::PlaybackStateChanged(s) => {
                     self.write_state().playback_state = s;
                 }
+                PlaybackToLogicMessage::DurationResolved(track_id, duration) => {
+                    self.write_state().resolved_duration = Some((track_id, duration));
+                }
+                // Sent by `set_playback_mode`, which has already applied it.
+                PlaybackToLogicMessage::PlaybackModeChanged(_) => {}
             }
         }
 
-        // Handle deferred auto-skip after load error.
+        if let Some(track_and_position) = self.position_throttle.take_due(Instant::now()) {
+            self.apply_position(track_and_position);
+        }
+
+        // Handle deferred recovery after load error.
         let should_skip = self.read_state().queue.pending_skip_after_error;
         if should_skip {
-            self.schedule_next_track();
+            self.recover_from_playback_error();
             self.write_state().queue.pending_skip_after_error = false;
             changed = true;
         }
 
+        changed |= self.check_idle_pause();
+
+        // Queue a radio mix fetched by `start_album_radio`.
+        let radio = self.write_state().queue.pending_radio.take();
+        if let Some(tracks) = radio {
+            self.insert_next(tracks);
+            changed = true;
+        }
+
+        // Play a track loaded by `load_and_play_track`.
+        let pending_play = self.write_state().queue.pending_play.take();
+        if let Some(track_id) = pending_play {
+            self.request_play_track(&track_id);
+            changed = true;
+        }
+
         while let Ok(event) = self.logic_request_rx.try_recv() {
             changed = true;
             match event {
@@ -431,12 +642,13 @@ impl Logic {
 
             // Don't append if we're in the middle of changing tracks
             if !pending_track_change && let Some(next_id) = self.compute_next_track_id() {
-                let (already_appended, audio_data, replaygain) = {
+                let (already_appended, audio_data, replaygain, gain_db) = {
                     let st = self.read_state();
                     (
                         st.queue.next_track_appended.as_ref() == Some(&next_id),
                         st.queue.audio_cache.get(&next_id).cloned(),
                         queue::replaygain_for_track(&st, &next_id),
+                        queue::track_gain_db(&st, &next_id),
                     )
                 };
 
@@ -446,6 +658,7 @@ impl Logic {
                         track_id: next_id.clone(),
                         data,
                         replaygain,
+                        gain_db,
                     }));
                     self.write_state().queue.next_track_appended = Some(next_id);
                 }
@@ -455,7 +668,7 @@ impl Logic {
         changed
     }
 }
-impl Logic {
+impl<C: bs::SubsonicApi> Logic<C> {
     pub fn play_current(&self) {
         self.send_to_playback(LogicToPlaybackMessage::Play);
     }
@@ -468,8 +681,43 @@ impl Logic {
         self.send_to_playback(LogicToPlaybackMessage::TogglePlayback);
     }
 
+    /// Skips to the next track or pauses after a track fails to load or play,
+    /// according to the configured [`PlaybackErrorBehavior`].
+    fn recover_from_playback_error(&self) {
+        let behavior = self.read_state().playback_error_behavior;
+        match behavior {
+            PlaybackErrorBehavior::SkipNext => self.schedule_next_track(),
+            PlaybackErrorBehavior::Pause => self.pause_current(),
+        }
+    }
+
+    /// Stops playback according to the configured [`StopBehavior`]: either
+    /// rewinding the current track to its start and pausing, or unloading it.
     pub fn stop_current(&self) {
+        let stop_behavior = self.read_state().stop_behavior;
+        match stop_behavior {
+            StopBehavior::ResetToStart => {
+                self.apply_seek_to_state(Duration::ZERO);
+                self.send_to_playback(LogicToPlaybackMessage::ResetToStart);
+            }
+            StopBehavior::Clear => {
+                self.send_to_playback(LogicToPlaybackMessage::StopPlayback);
+            }
+        }
+    }
+
+    /// Stops playback and unloads the current track, dropping the audio cache
+    /// to free memory. Unlike [`stop_current`](Self::stop_current), nothing is
+    /// kept around to resume from; the UI shows the empty now-playing state.
+    pub fn clear_playback(&self) {
         self.send_to_playback(LogicToPlaybackMessage::StopPlayback);
+        let finished = {
+            let mut st = self.write_state();
+            let finished = std::mem::take(&mut st.scrobble_state);
+            st.clear_playback();
+            finished
+        };
+        self.record_listen(finished);
     }
 
     pub fn seek_current(&self, position: Duration) {
@@ -484,6 +732,20 @@ impl Logic {
         self.send_to_playback(LogicToPlaybackMessage::SeekImmediate(position));
     }
 
+    /// Seeks to `fraction` (0 to 1) of the way through the current track. Does
+    /// nothing if no track is loaded or its length isn't known.
+    pub fn seek_fraction(&self, fraction: f32) {
+        let duration = {
+            let st = self.read_state();
+            st.current_track_and_position
+                .as_ref()
+                .and_then(|tap| st.track_duration(&tap.track_id))
+        };
+        if let Some(duration) = duration {
+            self.seek_current(duration.mul_f32(fraction.clamp(0.0, 1.0)));
+        }
+    }
+
     /// Optimistically updates the displayed position so the UI reflects the
     /// seek target immediately, even if the playback thread's debounce delays
     /// the actual hardware seek.
@@ -493,6 +755,7 @@ impl Logic {
     /// this, a rewound track that had already scrobbled would never scrobble
     /// again, as the seek does not go through `TrackStarted`.
     fn apply_seek_to_state(&self, position: Duration) {
+        self.live_position.set(position);
         let mut st = self.write_state();
         let Some(tap) = &mut st.current_track_and_position else {
             return;
@@ -500,10 +763,15 @@ impl Logic {
         tap.position = position;
         let track_id = tap.track_id.clone();
         if position == Duration::ZERO {
-            st.scrobble_state = ScrobbleState {
-                track_id: Some(track_id),
-                ..Default::default()
-            };
+            let finished = std::mem::replace(
+                &mut st.scrobble_state,
+                ScrobbleState {
+                    track_id: Some(track_id),
+                    ..Default::default()
+                },
+            );
+            drop(st);
+            self.record_listen(finished);
         }
     }
 
@@ -512,11 +780,16 @@ impl Logic {
     }
 
     pub fn previous(&self) {
-        // If we're past 5 seconds of the track, seek to the start instead of going to previous track
+        // Depending on the configured behavior, restart the track instead of going to the previous
+        // track if we're far enough into it.
         // I bristle a little at pulling in unnecessary information just to get the position / duration,
         // but this is getting called once every few minutes, so I'll get over it.
+        let (behavior, threshold) = {
+            let st = self.read_state();
+            (st.previous_behavior, st.previous_restart_threshold)
+        };
         if let Some(details) = self.get_track_display_details()
-            && details.track_position > Duration::from_secs(5)
+            && behavior.restarts_track(details.track_position, threshold)
         {
             self.seek_current(Duration::from_secs(0));
             return;
@@ -532,22 +805,64 @@ impl Logic {
         self.schedule_previous_group();
     }
 }
-impl Logic {
+impl<C: bs::SubsonicApi> Logic<C> {
     pub fn request_handle(&self) -> LogicRequestHandle {
         self.logic_request_tx.clone()
     }
-    pub fn subscribe_to_playback_events(&self) -> PlaybackToLogicRx {
+    /// Subscribes to playback events. Subscriptions made before the library
+    /// loads (and the playback thread starts) stay valid.
+    pub fn subscribe_events(&self) -> PlaybackToLogicRx {
         self.playback_event_tx.subscribe()
     }
 }
-impl Logic {
+impl<C: bs::SubsonicApi> Logic<C> {
+    /// Fetches cover art for use outside the cover art cache (e.g. by media
+    /// controls), delivering the bytes or an error on the returned channel.
+    pub fn fetch_cover_art(
+        &self,
+        cover_art_id: &CoverArtId,
+        size: Option<usize>,
+    ) -> std::sync::mpsc::Receiver<bs::ClientResult<Vec<u8>>> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let client = self.client.clone();
+        let cover_art_ids = self.cover_art_ids(cover_art_id);
+        self.tokio_thread.spawn(async move {
+            let result = get_cover_art_with_fallbacks(client.as_ref(), &cover_art_ids, size).await;
+            let _ = tx.send(result);
+        });
+        rx
+    }
+
+    /// Requests cover art into the cover art cache. If it fails to load, any
+    /// fallback IDs are tried in turn, with the art still delivered under
+    /// `cover_art_id`. Does nothing if the same art at the same size is
+    /// already being fetched.
     pub fn request_cover_art(&self, cover_art_id: &CoverArtId, size: Option<usize>) {
+        if self.read_state().credentials_rejected {
+            return;
+        }
+        let request = CoverArtRequest {
+            cover_art_id: cover_art_id.clone(),
+            size,
+        };
+        if !self
+            .cover_art_in_flight
+            .lock()
+            .unwrap()
+            .insert(request.clone())
+        {
+            return;
+        }
         let client = self.client.clone();
         let state = self.state.clone();
+        let in_flight = self.cover_art_in_flight.clone();
+        let cover_art_ids = self.cover_art_ids(cover_art_id);
         let cover_art_id = cover_art_id.clone();
         let cover_art_loaded_tx = self.cover_art_loaded_tx.clone();
         self.tokio_thread.spawn(async move {
-            match client.get_cover_art(cover_art_id.0.as_str(), size).await {
+            let result = get_cover_art_with_fallbacks(client.as_ref(), &cover_art_ids, size).await;
+            in_flight.lock().unwrap().remove(&request);
+            match result {
                 Ok(cover_art) => {
                     cover_art_loaded_tx
                         .send(CoverArt {
@@ -557,11 +872,17 @@ impl Logic {
                         })
                         .unwrap();
                 }
+                Err(e) if cover_art_ids.len() > 1 && e.is_not_found() => {
+                    // The server reported no cover art for the album, and
+                    // none of the fallbacks had any either.
+                    tracing::debug!("No cover art found for {cover_art_id}: {e}");
+                }
                 Err(e) => {
-                    let mut state = state.write().unwrap();
-                    state.error = Some(AppStateError::CoverArtFetchFailed {
-                        cover_art_id: cover_art_id.clone(),
-                        error: e.to_string(),
+                    state.write().unwrap().report_client_error(&e, |error| {
+                        AppStateError::CoverArtFetchFailed {
+                            cover_art_id: cover_art_id.clone(),
+                            error,
+                        }
                     });
                 }
             }
@@ -598,9 +919,11 @@ impl Logic {
             let _ = track_updated_tx.send(());
 
             let operation = if starred {
-                client.star([track_id.0.clone()], [], []).await
+                client.star(vec![track_id.0.clone()], vec![], vec![]).await
             } else {
-                client.unstar([track_id.0.clone()], [], []).await
+                client
+                    .unstar(vec![track_id.0.clone()], vec![], vec![])
+                    .await
             };
 
             let Err(e) = operation else {
@@ -608,7 +931,6 @@ impl Logic {
             };
 
             let track_id = track_id.clone();
-            let error = e.to_string();
 
             if let Some(old_starred) = old_starred {
                 state
@@ -618,16 +940,19 @@ impl Logic {
                     .set_track_starred(&track_id, old_starred);
             }
 
-            state.write().unwrap().error = Some(if starred {
-                AppStateError::StarTrackFailed { track_id, error }
-            } else {
-                AppStateError::UnstarTrackFailed { track_id, error }
+            state.write().unwrap().report_client_error(&e, |error| {
+                if starred {
+                    AppStateError::StarTrackFailed { track_id, error }
+                } else {
+                    AppStateError::UnstarTrackFailed { track_id, error }
+                }
             });
 
             // The optimistic update was just rolled back; notify clients so they
             // show the reverted state.
             let _ = track_updated_tx.send(());
         });
+        self.schedule_favorites_sync();
     }
 
     pub fn set_album_starred(&self, album_id: &AlbumId, starred: bool) {
@@ -657,9 +982,13 @@ impl Logic {
             let _ = track_updated_tx.send(());
 
             let operation = if starred {
-                client.star([], [album_id.0.to_string()], []).await
+                client
+                    .star(vec![], vec![album_id.0.to_string()], vec![])
+                    .await
             } else {
-                client.unstar([], [album_id.0.to_string()], []).await
+                client
+                    .unstar(vec![], vec![album_id.0.to_string()], vec![])
+                    .await
             };
 
             let Err(e) = operation else {
@@ -667,7 +996,6 @@ impl Logic {
             };
 
             let album_id = album_id.clone();
-            let error = e.to_string();
 
             if let Some(old_starred) = old_starred {
                 state
@@ -677,10 +1005,12 @@ impl Logic {
                     .set_album_starred(&album_id, old_starred);
             }
 
-            state.write().unwrap().error = Some(if starred {
-                AppStateError::StarAlbumFailed { album_id, error }
-            } else {
-                AppStateError::UnstarAlbumFailed { album_id, error }
+            state.write().unwrap().report_client_error(&e, |error| {
+                if starred {
+                    AppStateError::StarAlbumFailed { album_id, error }
+                } else {
+                    AppStateError::UnstarAlbumFailed { album_id, error }
+                }
             });
 
             // The optimistic update was just rolled back; notify clients so they
@@ -739,9 +1069,21 @@ impl Logic {
         });
     }
 }
-impl Logic {
+impl<C: bs::SubsonicApi> Logic<C> {
     pub fn get_playing_track_and_position(&self) -> Option<TrackAndPosition> {
-        self.read_state().current_track_and_position.clone()
+        self.read_state()
+            .current_track_and_position
+            .clone()
+            .map(|tp| self.with_live_position(tp))
+    }
+
+    /// Replaces the position in `tp`, which may lag behind by up to a write
+    /// interval, with the latest reported one.
+    fn with_live_position(&self, mut tp: TrackAndPosition) -> TrackAndPosition {
+        if let Some(position) = self.live_position.get() {
+            tp.position = position;
+        }
+        tp
     }
 
     pub fn get_playing_track_id(&self) -> Option<TrackId> {
@@ -751,11 +1093,41 @@ impl Logic {
             .map(|tp| tp.track_id.clone())
     }
 
+    /// The group (album) of the playing track, if any.
+    pub fn current_group(&self) -> Option<Arc<Group>> {
+        let st = self.read_state();
+        let track_id = &st.current_track_and_position.as_ref()?.track_id;
+        st.library.group_for_track(track_id).cloned()
+    }
+
+    /// The album of the playing track, if any.
+    pub fn get_playing_album_id(&self) -> Option<AlbumId> {
+        let st = self.read_state();
+        let track_id = &st.current_track_and_position.as_ref()?.track_id;
+        st.library.track_map.get(track_id)?.album_id.clone()
+    }
+
+    /// The local path of `track_id`'s file, if the server reports an
+    /// absolute path that exists on this machine, as when it shares the
+    /// filesystem. Paths relative to the server's music folder can't be
+    /// resolved and give `None`.
+    pub fn track_path(&self, track_id: &TrackId) -> Option<PathBuf> {
+        let path = PathBuf::from(
+            self.read_state()
+                .library
+                .track_map
+                .get(track_id)?
+                .path
+                .as_deref()?,
+        );
+        (path.is_absolute() && path.exists()).then_some(path)
+    }
+
     pub fn get_playing_position(&self) -> Option<Duration> {
         self.read_state()
             .current_track_and_position
             .as_ref()
-            .map(|tp| tp.position)
+            .map(|tp| self.live_position.get().unwrap_or(tp.position))
     }
 
     pub fn is_track_loaded(&self) -> bool {
@@ -766,21 +1138,88 @@ impl Logic {
             .started_loading_track
             .is_some_and(|t| t.elapsed() > Duration::from_millis(100))
     }
+    /// Progress of the initial library fetch, if any pages have arrived.
+    pub fn get_fetch_progress(&self) -> Option<FetchProgress> {
+        self.read_state().fetch_progress
+    }
     pub fn has_loaded_all_tracks(&self) -> bool {
         self.read_state().library.has_loaded_all_tracks
     }
+    /// Whether the library has loaded and has no tracks, e.g. because the
+    /// server is empty. See [`Library::is_loaded_and_empty`].
+    pub fn is_library_empty(&self) -> bool {
+        self.read_state().library.is_loaded_and_empty()
+    }
 
     pub fn get_track_display_details(&self) -> Option<TrackDisplayDetails> {
-        let track_and_position = self.read_state().current_track_and_position.clone()?;
+        let track_and_position = self.get_playing_track_and_position()?;
         TrackDisplayDetails::from_track_and_position(
             &track_and_position,
             &self.state.read().unwrap(),
         )
     }
 
+    /// Aggregates the local listen history into [`ListeningStats`] in the
+    /// background, as it reads the whole history file. The stats are
+    /// available from [`get_listening_stats`](Self::get_listening_stats) once
+    /// they are ready.
+    pub fn refresh_listening_stats(&self) {
+        self.write_state().listening_stats = None;
+        let state = self.state.clone();
+        let track_updated_tx = self.track_updated_tx.clone();
+        self.tokio_thread.spawn(async move {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            match listen_history::read(&listen_history::path()).await {
+                Ok(entries) => {
+                    state.write().unwrap().listening_stats =
+                        Some(ListeningStats::aggregate(&entries, now));
+                    let _ = track_updated_tx.send(());
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to read the listen history: {e}");
+                    state.write().unwrap().error = Some(AppStateError::ListeningStatsFailed {
+                        error: e.to_string(),
+                    });
+                }
+            }
+        });
+    }
+
+    /// Returns the stats aggregated by the last
+    /// [`refresh_listening_stats`](Self::refresh_listening_stats), or `None`
+    /// while they are being aggregated.
+    pub fn get_listening_stats(&self) -> Option<ListeningStats> {
+        self.read_state().listening_stats.clone()
+    }
+
+    /// Records a finished listen in the local listen history, if it was
+    /// scrobbled and the history is enabled.
+    fn record_listen(&self, finished: ScrobbleState) {
+        if !self.scrobble_targets.history {
+            return;
+        }
+        let Some(scrobble) = finished.scrobble else {
+            return;
+        };
+        let entry = HistoryEntry::new(&scrobble, finished.accumulated_listening_time);
+        self.tokio_thread.spawn(async move {
+            if let Err(e) = listen_history::append(&listen_history::path(), &entry).await {
+                tracing::warn!("Failed to record listen of {}: {e}", entry.track_id.0);
+            }
+        });
+    }
+
     pub fn get_error(&self) -> Option<AppStateError> {
         self.read_state().error.clone()
     }
+    /// Whether the server has rejected the credentials. See
+    /// [`AppState::credentials_rejected`].
+    pub fn has_rejected_credentials(&self) -> bool {
+        self.read_state().credentials_rejected
+    }
     pub fn clear_error(&self) {
         self.write_state().error = None;
     }
@@ -795,6 +1234,9 @@ impl Logic {
             let mut st = self.write_state();
             let mode_changed = st.playback_mode != mode;
             st.playback_mode = mode;
+            if mode.is_shuffle() {
+                st.preferred_shuffle = mode;
+            }
 
             // Reset gapless playback state since the next track may be different in the new mode
             st.queue.next_track_appended = None;
@@ -804,6 +1246,10 @@ impl Logic {
             // one left behind by the previous visit.
             if mode_changed {
                 st.queue.bump_shuffle_seed_for_mode(mode);
+                st.queue.stop_after = None;
+                let _ = self
+                    .playback_event_tx
+                    .send(PlaybackToLogicMessage::PlaybackModeChanged(mode));
             }
 
             st.current_track_and_position
@@ -822,6 +1268,103 @@ impl Logic {
         }
     }
 
+    /// Returns what Next and Previous do at the ends of the queue.
+    pub fn get_end_behavior(&self) -> EndBehavior {
+        self.read_state().end_behavior
+    }
+
+    /// Sets what Next and Previous do at the ends of the queue. No-op if the
+    /// value is unchanged.
+    pub fn set_end_behavior(&self, end_behavior: EndBehavior) {
+        {
+            let mut st = self.write_state();
+            if st.end_behavior == end_behavior {
+                return;
+            }
+            tracing::debug!("End behavior set to {end_behavior:?}");
+            st.end_behavior = end_behavior;
+            // The staged gapless track may no longer be the next one.
+            st.queue.next_track_appended = None;
+        }
+        self.send_to_playback(LogicToPlaybackMessage::ClearQueuedNextTracks);
+    }
+
+    /// Returns what the stop button does.
+    pub fn get_stop_behavior(&self) -> StopBehavior {
+        self.read_state().stop_behavior
+    }
+
+    /// Sets what the stop button does.
+    pub fn set_stop_behavior(&self, stop_behavior: StopBehavior) {
+        self.write_state().stop_behavior = stop_behavior;
+    }
+
+    /// Returns what happens once the play queue runs out.
+    pub fn get_queue_end_behavior(&self) -> QueueEndBehavior {
+        self.read_state().queue_end_behavior
+    }
+
+    /// Sets what happens once the play queue runs out.
+    pub fn set_queue_end_behavior(&self, queue_end_behavior: QueueEndBehavior) {
+        {
+            let mut st = self.write_state();
+            if st.queue_end_behavior == queue_end_behavior {
+                return;
+            }
+            st.queue_end_behavior = queue_end_behavior;
+            // The staged gapless track may no longer be the next one.
+            st.queue.next_track_appended = None;
+        }
+        self.send_to_playback(LogicToPlaybackMessage::ClearQueuedNextTracks);
+    }
+
+    /// Returns what happens when a track fails to load or play.
+    pub fn get_playback_error_behavior(&self) -> PlaybackErrorBehavior {
+        self.read_state().playback_error_behavior
+    }
+
+    /// Sets what happens when a track fails to load or play.
+    pub fn set_playback_error_behavior(&self, behavior: PlaybackErrorBehavior) {
+        self.write_state().playback_error_behavior = behavior;
+    }
+
+    /// Returns what Previous does partway through a track.
+    pub fn get_previous_behavior(&self) -> PreviousBehavior {
+        self.read_state().previous_behavior
+    }
+
+    /// Sets what Previous does partway through a track, and the position past
+    /// which it restarts the track.
+    pub fn set_previous_behavior(&self, behavior: PreviousBehavior, restart_threshold: Duration) {
+        let mut st = self.write_state();
+        st.previous_behavior = behavior;
+        st.previous_restart_threshold = restart_threshold;
+    }
+
+    /// Sets how many tracks from the end of an album the next album's first
+    /// track starts being prefetched, so that skipping to it is instant.
+    pub fn set_next_group_prefetch_tracks(&self, tracks: usize) {
+        self.write_state().next_group_prefetch_tracks = tracks;
+    }
+
+    /// Returns whether re-selecting an album resumes it from where it was
+    /// left off.
+    pub fn get_resume_albums(&self) -> bool {
+        self.read_state().resume_albums
+    }
+
+    /// Sets whether re-selecting an album resumes it from where it was left
+    /// off, rather than starting from its first track.
+    pub fn set_resume_albums(&self, resume_albums: bool) {
+        self.write_state().resume_albums = resume_albums;
+    }
+
+    /// Sets whether the editions of an album are shown as one group. Takes
+    /// effect when the library is next fetched.
+    pub fn set_merge_editions(&mut self, merge_editions: bool) {
+        self.fetch_options.merge_editions = merge_editions;
+    }
+
     pub fn get_playback_state(&self) -> PlaybackState {
         self.read_state().playback_state
     }
@@ -830,6 +1373,38 @@ impl Logic {
         self.read_state().playback_mode
     }
 
+    /// Returns the shuffle mode that [`Logic::toggle_shuffle`] switches to.
+    pub fn get_preferred_shuffle(&self) -> PlaybackMode {
+        self.read_state().preferred_shuffle
+    }
+
+    /// Switches between the preferred shuffle mode and sequential playback.
+    pub fn toggle_shuffle(&self) {
+        let mode = {
+            let st = self.read_state();
+            if st.playback_mode.is_shuffle() {
+                PlaybackMode::Sequential
+            } else {
+                st.preferred_shuffle
+            }
+        };
+        self.set_playback_mode(mode);
+    }
+
+    /// Switches to the next shuffle scope, which becomes the preferred one.
+    /// When not shuffling, switches to the preferred shuffle mode instead.
+    pub fn cycle_shuffle_scope(&self) {
+        let mode = {
+            let st = self.read_state();
+            if st.playback_mode.is_shuffle() {
+                st.playback_mode.next_shuffle_scope()
+            } else {
+                st.preferred_shuffle
+            }
+        };
+        self.set_playback_mode(mode);
+    }
+
     pub fn set_sort_order(&self, order: SortOrder) {
         tracing::debug!("Sort order set to {order:?}");
         let current_track = {
@@ -847,6 +1422,19 @@ impl Logic {
         self.read_state().sort_order
     }
 
+    /// Sets whether the library shows only starred tracks, keeping the
+    /// headers of groups that have any. This only affects what is shown, not
+    /// what is played.
+    pub fn set_hide_unstarred(&self, hide_unstarred: bool) {
+        self.write_state()
+            .library
+            .set_hide_unstarred(hide_unstarred);
+    }
+
+    pub fn get_hide_unstarred(&self) -> bool {
+        self.read_state().library.hide_unstarred()
+    }
+
     pub fn get_volume(&self) -> f32 {
         self.read_state().volume
     }
@@ -895,6 +1483,196 @@ impl Logic {
         }
     }
 
+    /// Returns the level in dB below which leading and trailing silence is
+    /// skipped, or `None` if tracks are played in full.
+    pub fn get_skip_silence_db(&self) -> Option<f32> {
+        self.read_state().skip_silence_db
+    }
+
+    /// Sets the level in dB below which leading and trailing silence is
+    /// skipped, or disables skipping with `None`. Takes effect from the next
+    /// track, as silence is found when a track is decoded. No-op if the value
+    /// is unchanged.
+    pub fn set_skip_silence_db(&self, threshold_db: Option<f32>) {
+        let changed = {
+            let mut st = self.write_state();
+            let changed = st.skip_silence_db != threshold_db;
+            st.skip_silence_db = threshold_db;
+            // The staged gapless track was decoded with the old setting.
+            if changed {
+                st.queue.next_track_appended = None;
+            }
+            changed
+        };
+        if changed {
+            self.send_to_playback(LogicToPlaybackMessage::SetSkipSilence(threshold_db));
+            self.send_to_playback(LogicToPlaybackMessage::ClearQueuedNextTracks);
+        }
+    }
+
+    /// Returns whether tracks are streamed transcoded.
+    pub fn get_transcode(&self) -> bool {
+        self.transcode
+    }
+
+    /// Sets whether tracks are streamed transcoded, e.g. to save data on a
+    /// metered connection. Cached and in-flight tracks were fetched in the
+    /// other format, so the cache is dropped and refilled; the playing track
+    /// carries on as it was loaded. No-op if the value is unchanged.
+    pub fn set_transcode(&mut self, transcode: bool) {
+        if self.transcode == transcode {
+            return;
+        }
+        tracing::info!(
+            "Transcoding turned {}",
+            if transcode { "on" } else { "off" }
+        );
+        self.transcode = transcode;
+        self.refill_audio_cache();
+    }
+
+    /// Returns the format `track_id` is transcoded to: the configured one if
+    /// set, otherwise the one the server reports for the track if it can be
+    /// played, falling back to MP3.
+    pub fn get_transcode_format(&self, track_id: &TrackId) -> String {
+        let st = self.read_state();
+        pick_transcode_format(
+            self.transcode_format.as_deref(),
+            st.library
+                .track_map
+                .get(track_id)
+                .and_then(|t| t.transcoded_suffix.as_deref()),
+        )
+    }
+
+    /// Sets the format to transcode to, or `None` to pick one for the
+    /// server. Drops the audio cache if tracks are being transcoded. No-op if
+    /// the value is unchanged.
+    pub fn set_transcode_format(&mut self, format: Option<&str>) {
+        if self.transcode_format.as_deref() == format {
+            return;
+        }
+        self.transcode_format = format.map(str::to_string);
+        tracing::info!(
+            "Transcoding to {}",
+            format.unwrap_or("the format the server reports for each track")
+        );
+        if self.transcode {
+            self.refill_audio_cache();
+        }
+    }
+
+    /// The format to request from `stream` for `track_id`, or `None` for the
+    /// original file.
+    fn stream_format(&self, track_id: &TrackId) -> Option<String> {
+        self.transcode.then(|| self.get_transcode_format(track_id))
+    }
+
+    /// Drops the cached and in-flight tracks after the stream format changed,
+    /// so that they are fetched again in the new format.
+    fn refill_audio_cache(&self) {
+        {
+            let mut st = self.write_state();
+            st.queue.audio_cache.clear();
+            st.queue.pending_audio_requests.clear();
+            st.queue.next_track_appended = None;
+        }
+        self.send_to_playback(LogicToPlaybackMessage::ClearQueuedNextTracks);
+        self.ensure_cache_window();
+    }
+
+    /// Returns the extra output devices mirroring playback, by name, with
+    /// their volumes.
+    pub fn get_extra_outputs(&self) -> BTreeMap<String, f32> {
+        self.read_state().extra_outputs.clone()
+    }
+
+    /// Sets the extra output devices that play the same audio as the main
+    /// output, in sync with it, by name with their volumes (0 to 1) relative
+    /// to the main volume. Devices are opened and closed as needed; unknown
+    /// devices are logged and skipped. No-op if the outputs are unchanged.
+    pub fn set_extra_outputs(&self, outputs: &BTreeMap<String, f32>) {
+        let previous = {
+            let mut st = self.write_state();
+            if st.extra_outputs == *outputs {
+                return;
+            }
+            std::mem::replace(&mut st.extra_outputs, outputs.clone())
+        };
+        for device in previous.keys().filter(|d| !outputs.contains_key(*d)) {
+            self.send_to_playback(LogicToPlaybackMessage::RemoveOutput(device.clone()));
+        }
+        for (device, &volume) in outputs {
+            let old_volume = previous.get(device);
+            if old_volume.is_none() {
+                self.send_to_playback(LogicToPlaybackMessage::AddOutput(device.clone()));
+            }
+            if old_volume != Some(&volume) {
+                self.send_to_playback(LogicToPlaybackMessage::SetOutputVolume {
+                    device: device.clone(),
+                    volume,
+                });
+            }
+        }
+    }
+
+    /// Returns the user gain for `track_id` in dB, or zero if none is set.
+    pub fn get_track_gain(&self, track_id: &TrackId) -> f32 {
+        queue::track_gain_db(&self.read_state(), track_id)
+    }
+
+    /// Returns every user gain that has been set, in dB.
+    pub fn get_track_gains(&self) -> HashMap<TrackId, f32> {
+        self.read_state().track_gains.clone()
+    }
+
+    /// Sets the user gain for `track_id` in dB, applied on top of ReplayGain
+    /// and volume. The gain is rounded to a tenth of a decibel, and a gain of
+    /// zero removes the entry. Takes effect immediately if the track is
+    /// playing or staged as the next track.
+    pub fn set_track_gain(&self, track_id: &TrackId, gain_db: f32) {
+        let gain_db = (gain_db * 10.0).round() / 10.0;
+        {
+            let mut st = self.write_state();
+            if gain_db == 0.0 {
+                st.track_gains.remove(track_id);
+            } else {
+                st.track_gains.insert(track_id.clone(), gain_db);
+            }
+        }
+        self.send_to_playback(LogicToPlaybackMessage::SetTrackGain {
+            track_id: track_id.clone(),
+            gain_db,
+        });
+    }
+
+    /// Returns whether `track_id` is always skipped over when advancing.
+    pub fn is_track_skipped(&self, track_id: &TrackId) -> bool {
+        self.read_state().skipped_tracks.contains(track_id)
+    }
+
+    /// Returns every track that is always skipped over.
+    pub fn get_skipped_tracks(&self) -> HashSet<TrackId> {
+        self.read_state().skipped_tracks.clone()
+    }
+
+    /// Sets whether `track_id` is always skipped over by Next, Previous and
+    /// the end of the track before it. Picking the track still plays it.
+    pub fn set_track_skipped(&self, track_id: &TrackId, skipped: bool) {
+        {
+            let mut st = self.write_state();
+            if skipped {
+                st.skipped_tracks.insert(track_id.clone());
+            } else {
+                st.skipped_tracks.remove(track_id);
+            }
+            // The staged gapless track may no longer be the next one.
+            st.queue.next_track_appended = None;
+        }
+        self.send_to_playback(LogicToPlaybackMessage::ClearQueuedNextTracks);
+        self.ensure_cache_window();
+    }
+
     /// The cover art ID for the album containing the next track in the
     /// queue. Returns `None` if there is no next track or if the library is
     /// not populated.
@@ -903,8 +1681,10 @@ impl Logic {
     pub fn get_next_track_cover_art_id(&self) -> Option<CoverArtId> {
         let st = self.read_state();
         let next_track_id = self.compute_next_track_id()?;
-        let &next_group_idx = st.library.track_to_group_index.get(&next_track_id)?;
-        st.library.groups[next_group_idx].cover_art_id.clone()
+        st.library
+            .group_for_track(&next_track_id)?
+            .cover_art_id
+            .clone()
     }
 
     /// Get cover art IDs for the albums within
@@ -936,11 +1716,29 @@ impl Logic {
         self.write_state().last_requested_track_for_ui_scroll = Some(track_id.clone());
     }
 
+    /// Requests that the library scroll to the playing track, returning it.
+    ///
+    /// If the track is hidden because unstarred tracks are, they are shown
+    /// again so that there is somewhere to scroll to. Clients are responsible
+    /// for dismissing anything covering the library, such as search results
+    /// or lyrics.
+    pub fn goto_playing(&self) -> Option<TrackId> {
+        let track_id = self.get_playing_track_id()?;
+        {
+            let mut st = self.write_state();
+            if !st.library.is_track_shown(&track_id) {
+                st.library.set_hide_unstarred(false);
+            }
+        }
+        self.set_scroll_target(&track_id);
+        Some(track_id)
+    }
+
     pub fn should_shutdown(&self) -> bool {
         self.tokio_thread.should_shutdown()
     }
 }
-impl Logic {
+impl<C: bs::SubsonicApi> Logic<C> {
     pub fn request_play_track(&self, track_id: &TrackId) {
         // Public API used by UI: keep current playing until new track is ready.
         self.schedule_play_track(track_id);
@@ -949,28 +1747,93 @@ impl Logic {
         // current mode, so the rest of the queue around the new anchor is
         // reshuffled rather than continuing the previous permutation.
         let mode = self.read_state().playback_mode;
-        self.write_state().queue.bump_shuffle_seed_for_mode(mode);
+        let dropped_group = {
+            let mut st = self.write_state();
+            st.queue.bump_shuffle_seed_for_mode(mode);
+            st.queue.stop_after = None;
+            queue::drop_group_queued(&mut st.queue)
+        };
 
         self.recompute_queue(Some(track_id));
+        if dropped_group {
+            self.manual_queue_changed();
+        }
+    }
+
+    /// Plays the album `album_id` from its first track or, if album resuming
+    /// is enabled, from the track it was left off at.
+    ///
+    /// The rest of the album goes to the front of the play queue, so it plays
+    /// through in order whatever the playback mode; once it has, the mode's
+    /// own order resumes. Playing another track, or another album, takes the
+    /// album's remaining tracks back out of the queue. Only the tracks the
+    /// library has loaded so far are queued: tracks of the album that arrive
+    /// later are left to the mode.
+    pub fn play_group(&self, album_id: &AlbumId) {
+        let track_id = {
+            let st = self.read_state();
+            queue::album_start_track(&st, album_id, st.resume_albums)
+        };
+        let Some(track_id) = track_id else {
+            return;
+        };
+        self.request_play_track(&track_id);
+        queue::queue_rest_of_album(&mut self.write_state(), album_id, &track_id);
+        self.manual_queue_changed();
+    }
+
+    /// Plays the track of the album `album_id` with the given number.
+    /// Returns whether the album has such a track.
+    pub fn play_track_number(&self, album_id: &AlbumId, number: TrackNumber) -> bool {
+        let track_id = self
+            .read_state()
+            .library
+            .track_by_number(album_id, number)
+            .cloned();
+        if let Some(track_id) = &track_id {
+            self.request_play_track(track_id);
+        }
+        track_id.is_some()
+    }
+
+    /// Plays `track_id`, then the tracks after it in `scope` in library
+    /// order, stopping once the last of them ends. Unlike
+    /// [`request_play_track`](Self::request_play_track), playback doesn't
+    /// carry on past the end of the scope; playing another track or changing
+    /// the mode lifts it.
+    pub fn play_from(&self, track_id: &TrackId, scope: PlayScope) {
+        self.request_play_track(track_id);
+        queue::apply_play_scope(&mut self.write_state(), track_id, scope);
+        self.ensure_cache_window();
+    }
+
+    /// Writes a position report to the state and updates the scrobble state,
+    /// under a single lock.
+    fn apply_position(&self, track_and_position: TrackAndPosition) {
+        let mut st = self.write_state();
+        // Playback was cleared; ignore the final position report from the
+        // stop so the track isn't restored.
+        if st.current_track_and_position.is_none() && st.queue.current_target.is_none() {
+            return;
+        }
+        st.current_track_and_position = Some(track_and_position.clone());
+        self.update_scrobble_state(st, &track_and_position);
     }
 
     /// Updates the scrobble state based on current playback position.
     /// Scrobbles the track when criteria are met:
     /// - Minimum 10 seconds of listening time
     /// - Either 30 seconds OR 50% of track duration (whichever comes first)
-    fn update_scrobble_state(&self, track_and_position: &TrackAndPosition) {
-        let mut state = self.write_state();
-
+    ///
+    /// Takes the state lock from the caller so the position write and the
+    /// scrobble update share it.
+    fn update_scrobble_state(
+        &self,
+        mut state: RwLockWriteGuard<'_, AppState>,
+        track_and_position: &TrackAndPosition,
+    ) {
         // Get track duration first (before taking mutable borrow)
-        let Some(track_duration) = state
-            .library
-            .track_map
-            .get(&track_and_position.track_id)
-            .and_then(|track| track.duration)
-            .map(|duration| Duration::from_secs(duration as u64))
-        else {
-            return;
-        };
+        let track_duration = state.track_duration(&track_and_position.track_id);
 
         let scrobble_state = &mut state.scrobble_state;
 
@@ -984,11 +1847,6 @@ impl Logic {
             return;
         }
 
-        // If already scrobbled, nothing to do
-        if scrobble_state.has_scrobbled {
-            return;
-        }
-
         let current_position = track_and_position.position;
         let last_position = scrobble_state.last_position;
 
@@ -1012,6 +1870,12 @@ impl Logic {
         }
         scrobble_state.last_position = current_position;
 
+        // Once scrobbled, the listening time is only kept for the listen
+        // history.
+        if scrobble_state.has_scrobbled {
+            return;
+        }
+
         let accumulated_time = scrobble_state.accumulated_listening_time;
 
         // Check scrobble criteria:
@@ -1027,15 +1891,13 @@ impl Logic {
         }
 
         // 2. Either 30 seconds OR 50% of track (whichever comes first)
-        const SCROBBLE_TIME_THRESHOLD: Duration = Duration::from_secs(30);
-        let half_duration = track_duration / 2;
-        let scrobble_threshold = SCROBBLE_TIME_THRESHOLD.min(half_duration);
+        let scrobble_threshold = scrobble::threshold(track_duration);
 
         tracing::debug!(
-            "Scrobble: checking threshold - accumulated: {:.1}s, threshold: {:.1}s (50% of {:.1}s)",
+            "Scrobble: checking threshold - accumulated: {:.1}s, threshold: {:.1}s (duration: {:?})",
             accumulated_time.as_secs_f32(),
             scrobble_threshold.as_secs_f32(),
-            track_duration.as_secs_f32()
+            track_duration
         );
 
         if accumulated_time >= scrobble_threshold {
@@ -1049,94 +1911,423 @@ impl Logic {
                 .as_millis() as u64;
 
             tracing::info!(
-                "Scrobbling track: {} (listened: {:.1}s / {:.1}s)",
+                "Scrobbling track: {} (listened: {:.1}s / {:?})",
                 track_and_position.track_id.0,
                 accumulated_time.as_secs_f32(),
-                track_duration.as_secs_f32()
+                track_duration
             );
 
-            // Make async API call.
-            self.tokio_thread.spawn({
-                let client = self.client.clone();
-                let state = self.state.clone();
-                let track_id = track_and_position.track_id.clone();
-                let track_updated_tx = self.track_updated_tx.clone();
-
-                async move {
-                    if let Err(e) = client
-                        .scrobble(&track_id.0, Some(timestamp), Some(true))
-                        .await
-                    {
-                        tracing::error!("Failed to scrobble track {}: {}", track_id.0, e);
-                        // Note: We don't update the UI error state for scrobble failures
-                        // as they're not critical to the user experience
-                    }
-
-                    // Reload track from API to update play count
-                    match client.get_song(&track_id.0).await {
-                        Ok(child) => {
-                            let updated_track: Track = child.into();
-                            if let Ok(mut state) = state.write() {
-                                state
-                                    .library
-                                    .track_map
-                                    .insert(track_id.clone(), updated_track);
-                                tracing::debug!(
-                                    "Updated track {} from API after scrobble",
-                                    track_id.0
-                                );
-                            }
-                            let _ = track_updated_tx.send(());
-                        }
-                        Err(e) => {
-                            tracing::warn!(
-                                "Failed to reload track {} to update play count: {}",
+            let Some(scrobble) =
+                Scrobble::from_state(&state, &track_and_position.track_id, timestamp)
+            else {
+                return;
+            };
+            state.scrobble_state.scrobble = Some(scrobble.clone());
+            drop(state);
+
+            for scrobbler in &self.scrobblers {
+                self.tokio_thread.spawn({
+                    let scrobbler = scrobbler.clone();
+                    let scrobble = scrobble.clone();
+                    let client = self.client.clone();
+                    let state = self.state.clone();
+                    let track_updated_tx = self.track_updated_tx.clone();
+
+                    async move {
+                        let track_id = &scrobble.track_id;
+                        if let Err(e) = scrobbler.scrobble(&scrobble).await {
+                            tracing::error!(
+                                "Failed to scrobble track {} to {}: {}",
                                 track_id.0,
+                                scrobbler.name(),
                                 e
                             );
+                            // Note: We don't update the UI error state for scrobble failures
+                            // as they're not critical to the user experience
+                        }
+                        if !scrobbler.updates_play_count() {
+                            return;
+                        }
+
+                        // Reload track from API to update play count
+                        match client.get_song(&track_id.0).await {
+                            Ok(child) => {
+                                let updated_track: Track = child.into();
+                                if let Ok(mut state) = state.write() {
+                                    state
+                                        .library
+                                        .track_map
+                                        .insert(track_id.clone(), updated_track);
+                                    tracing::debug!(
+                                        "Updated track {} from API after scrobble",
+                                        track_id.0
+                                    );
+                                }
+                                let _ = track_updated_tx.send(());
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    "Failed to reload track {} to update play count: {}",
+                                    track_id.0,
+                                    e
+                                );
+                            }
                         }
                     }
-                }
-            });
+                });
+            }
         }
     }
 }
 impl Logic {
-    /// Reloads the library with new server credentials. Stops playback,
-    /// replaces the client, clears the library and queue, and re-fetches.
-    pub fn reload_library(
-        &mut self,
-        base_url: String,
-        username: String,
-        password: String,
-        transcode: bool,
-    ) {
+    /// Reconnects with new server settings, e.g. after they were edited or to
+    /// retry a failed initial fetch. Stops playback, replaces the client,
+    /// clears the library and queue, and re-fetches.
+    pub fn reconfigure_server(&mut self, server: &blackbird_shared::config::Server) {
         // Shut down the playback thread (closes the audio device).
         self.playback_thread = None;
 
         // Create a new client with the new credentials.
         self.client = Arc::new(bs::Client::new(
-            base_url,
-            username,
-            password,
-            "blackbird".to_string(),
+            server.base_url.clone(),
+            server.username.clone(),
+            server.password.clone(),
+            self.client_id.clone(),
         ));
-        self.transcode = transcode;
+        self.transcode = server.transcode;
+        self.transcode_format = server.transcode_format.clone();
+        self.api_version = server.api_version.clone();
+        self.fetch_options.album_page_size = server.album_page_size;
+        self.fetch_options.track_page_size = server.track_page_size;
+        self.scrobblers = scrobble::build_scrobblers(&self.scrobble_targets, &self.client);
 
         // Clear the library, queue, and any previous connection error.
-        {
+        let finished = {
             let mut st = self.write_state();
             st.library = Default::default();
             st.queue = Default::default();
             st.current_track_and_position = None;
             st.started_loading_track = None;
-            st.scrobble_state = Default::default();
+            st.fetch_progress = None;
             st.error = None;
-        }
+            st.credentials_rejected = false;
+            std::mem::take(&mut st.scrobble_state)
+        };
+        self.record_listen(finished);
 
         // Re-fetch the library without restoring a track.
         self.initial_fetch(None);
     }
+}
+impl<C: bs::SubsonicApi> Logic<C> {
+    /// Re-fetches the library without interrupting playback. The queue stays
+    /// anchored on the current track.
+    pub fn refresh_library(&self) {
+        self.tokio_thread.spawn(refresh_library(
+            self.client.clone(),
+            self.state.clone(),
+            self.fetch_options,
+            self.library_populated_tx.clone(),
+        ));
+    }
+
+    /// Makes sure a track is in the library so that it can be shown and
+    /// played, e.g. for a track ID from outside the library view. Returns
+    /// whether it already was; if not, it's fetched in the background (along
+    /// with its album, if that's missing too) and the library is marked as
+    /// populated once it's inserted. Tracks the server doesn't know are
+    /// logged and left out.
+    pub fn ensure_track_loaded(&self, track_id: &TrackId) -> bool {
+        self.load_track(track_id, false)
+    }
+
+    /// Plays a track that may not be in the library yet, such as one found
+    /// by [`search_server`](Self::search_server). If it isn't, it's loaded as
+    /// by [`ensure_track_loaded`](Self::ensure_track_loaded) and played once
+    /// it's inserted.
+    pub fn load_and_play_track(&self, track_id: &TrackId) {
+        if self.load_track(track_id, true) {
+            self.request_play_track(track_id);
+        }
+    }
+
+    /// Loads a track into the library if it isn't there yet, queueing it to
+    /// be played once inserted if `play` is set. Returns whether it already
+    /// was in the library.
+    fn load_track(&self, track_id: &TrackId, play: bool) -> bool {
+        if self.read_state().library.track_map.contains_key(track_id) {
+            return true;
+        }
+
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let track_id = track_id.clone();
+        let library_populated_tx = self.library_populated_tx.clone();
+        let merge_editions = self.fetch_options.merge_editions;
+        self.tokio_thread.spawn(async move {
+            let track = match client.get_song(track_id.0.as_str()).await {
+                Ok(song) => Track::from(song),
+                Err(e) => {
+                    tracing::warn!("Failed to load track {}: {e}", track_id.0);
+                    return;
+                }
+            };
+            let missing_album = track
+                .album_id
+                .clone()
+                .filter(|id| !state.read().unwrap().library.albums.contains_key(id));
+            // A new album arrives with the rest of its tracks, so that its
+            // group isn't left with just the one.
+            let (tracks, album) = match missing_album {
+                Some(album_id) => match client.get_album_with_songs(album_id.0.as_str()).await {
+                    Ok(album) => {
+                        let mut tracks: Vec<Track> = album
+                            .song
+                            .into_iter()
+                            .map(Track::from)
+                            .filter(|t| !t.is_video && t.id != track.id)
+                            .collect();
+                        tracks.push(track);
+                        (tracks, Some(blackbird_state::Album::from(album.album)))
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to load album {} of track {}: {e}",
+                            album_id.0,
+                            track_id.0
+                        );
+                        return;
+                    }
+                },
+                None => (vec![track], None),
+            };
+
+            {
+                let mut st = state.write().unwrap();
+                let order = st.sort_order;
+                st.library
+                    .insert_tracks(tracks, album, merge_editions, order);
+                if play && st.library.track_map.contains_key(&track_id) {
+                    st.queue.pending_play = Some(track_id);
+                }
+            }
+            let _ = library_populated_tx.send(());
+        });
+        false
+    }
+
+    /// Asks the server to scan its media library, then polls the scan's
+    /// progress (see [`get_server_scan_count`](Self::get_server_scan_count))
+    /// and refreshes the library once it completes. No-op if a scan started
+    /// here is already running.
+    pub fn trigger_server_scan(&self) {
+        {
+            let mut st = self.write_state();
+            if st.server_scan_count.is_some() {
+                return;
+            }
+            st.server_scan_count = Some(0);
+        }
+
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let fetch_options = self.fetch_options;
+        let library_populated_tx = self.library_populated_tx.clone();
+        let track_updated_tx = self.track_updated_tx.clone();
+        self.tokio_thread.spawn(async move {
+            const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+            let mut status = client.start_scan().await;
+            loop {
+                match status {
+                    Ok(bs::ScanStatus {
+                        scanning: true,
+                        count,
+                    }) => {
+                        state.write().unwrap().server_scan_count = Some(count.unwrap_or(0));
+                        let _ = track_updated_tx.send(());
+                    }
+                    Ok(bs::ScanStatus {
+                        scanning: false, ..
+                    }) => break,
+                    Err(e) => {
+                        // Most likely a server without the scan endpoints.
+                        tracing::warn!("Server scan failed: {e}");
+                        let mut st = state.write().unwrap();
+                        st.server_scan_count = None;
+                        st.report_client_error(&e, |error| AppStateError::ServerScanFailed {
+                            error,
+                        });
+                        let _ = track_updated_tx.send(());
+                        return;
+                    }
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+                status = client.get_scan_status().await;
+            }
+
+            tracing::info!("Server scan complete; refreshing library");
+            state.write().unwrap().server_scan_count = None;
+            refresh_library(client, state, fetch_options, library_populated_tx).await;
+        });
+    }
+
+    /// Returns the number of files scanned so far if a server scan started by
+    /// [`trigger_server_scan`](Self::trigger_server_scan) is running.
+    pub fn get_server_scan_count(&self) -> Option<u64> {
+        self.read_state().server_scan_count
+    }
+
+    /// Fetches what every user of the server is currently playing. The result
+    /// is available from [`get_server_now_playing`](Self::get_server_now_playing)
+    /// once the fetch completes.
+    pub fn refresh_server_now_playing(&self) {
+        if self.read_state().credentials_rejected {
+            return;
+        }
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let track_updated_tx = self.track_updated_tx.clone();
+        self.tokio_thread.spawn(async move {
+            match client.get_now_playing().await {
+                Ok(entries) => {
+                    state.write().unwrap().server_now_playing = entries;
+                    let _ = track_updated_tx.send(());
+                }
+                Err(e) => tracing::warn!("Failed to fetch now playing from server: {e}"),
+            }
+        });
+    }
+
+    /// Returns what every user of the server was playing as of the last
+    /// [`refresh_server_now_playing`](Self::refresh_server_now_playing).
+    pub fn get_server_now_playing(&self) -> Vec<bs::NowPlayingEntry> {
+        self.read_state().server_now_playing.clone()
+    }
+
+    /// Fetches artist and album name suggestions for `query` from the server,
+    /// unless they are already cached or being fetched. The result is
+    /// available from [`get_search_suggestions`](Self::get_search_suggestions)
+    /// once the fetch completes.
+    pub fn request_search_suggestions(&self, query: &str) {
+        if !self.write_state().search_suggestions.begin(query) {
+            return;
+        }
+
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let track_updated_tx = self.track_updated_tx.clone();
+        let query = query.to_string();
+        self.tokio_thread.spawn(async move {
+            let request = bs::Search3Request {
+                query: query.clone(),
+                artist_count: Some(search_suggestions::SUGGESTION_COUNT),
+                album_count: Some(search_suggestions::SUGGESTION_COUNT),
+                song_count: Some(0),
+                ..Default::default()
+            };
+            match client.search3(&request).await {
+                Ok(response) => {
+                    state
+                        .write()
+                        .unwrap()
+                        .search_suggestions
+                        .finish(&query, SearchSuggestion::from_response(response));
+                    let _ = track_updated_tx.send(());
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to fetch search suggestions for {query:?}: {e}");
+                    state.write().unwrap().search_suggestions.abandon(&query);
+                }
+            }
+        });
+    }
+
+    /// Returns the suggestions for `query` if they have been fetched by
+    /// [`request_search_suggestions`](Self::request_search_suggestions).
+    pub fn get_search_suggestions(&self, query: &str) -> Option<Vec<SearchSuggestion>> {
+        self.read_state().search_suggestions.get(query).cloned()
+    }
+
+    /// Searches the server's own index for tracks matching `query`, unless
+    /// the query is already cached or being searched for. This can find
+    /// tracks that local search misses, as servers match differently. The
+    /// result is available from [`get_server_search`](Self::get_server_search)
+    /// once the search completes.
+    ///
+    /// Tracks missing from the loaded library are kept too, so that they can
+    /// be shown from the server's details and played with
+    /// [`load_and_play_track`](Self::load_and_play_track).
+    pub fn search_server(&self, query: &str) {
+        if !self.write_state().server_search.begin(query) {
+            return;
+        }
+
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let track_updated_tx = self.track_updated_tx.clone();
+        let query = query.to_string();
+        self.tokio_thread.spawn(async move {
+            let request = bs::Search3Request {
+                query: query.clone(),
+                artist_count: Some(0),
+                album_count: Some(0),
+                song_count: Some(search_suggestions::SERVER_SEARCH_TRACK_COUNT),
+                ..Default::default()
+            };
+            match client.search3(&request).await {
+                Ok(response) => {
+                    let tracks = response.song.into_iter().map(Track::from).collect();
+                    state.write().unwrap().server_search.finish(&query, tracks);
+                    let _ = track_updated_tx.send(());
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to search the server for {query:?}: {e}");
+                    state.write().unwrap().server_search.abandon(&query);
+                }
+            }
+        });
+    }
+
+    /// Returns the tracks the server found for `query`, best match first, if
+    /// [`search_server`](Self::search_server) has completed. They may not be
+    /// in the library.
+    pub fn get_server_search(&self, query: &str) -> Option<Vec<Track>> {
+        self.read_state().server_search.get(query).cloned()
+    }
+
+    /// The user blackbird is logged in to the server as.
+    pub fn get_server_username(&self) -> String {
+        self.client.username().to_string()
+    }
+
+    /// Details about the server (its software, version, and supported
+    /// features), once collected after the initial connection.
+    pub fn server_info(&self) -> Option<bs::ServerInfo> {
+        self.read_state().server_info.clone()
+    }
+
+    /// Tells the server that `track_id` is now playing, so it shows up in
+    /// other clients' now playing lists. Only done when scrobbling to the
+    /// server is enabled.
+    fn report_now_playing(&self, track_id: &TrackId) {
+        if !self.scrobble_targets.server {
+            return;
+        }
+        let client = self.client.clone();
+        let track_id = track_id.clone();
+        self.tokio_thread.spawn(async move {
+            if let Err(e) = client.scrobble(&track_id.0, None, Some(false)).await {
+                tracing::warn!("Failed to report now playing for {}: {e}", track_id.0);
+            }
+        });
+    }
+
+    /// Returns up to `n` distinct albums played this session, most recently
+    /// played first.
+    pub fn recent_albums(&self, n: usize) -> Vec<RecentAlbum> {
+        self.read_state().recent_albums(n)
+    }
 
     fn initial_fetch(&self, restore_track: Option<(TrackId, Duration)>) {
         let client = self.client.clone();
@@ -1145,23 +2336,73 @@ impl Logic {
         let playback_event_tx = self.playback_event_tx.clone();
         let playback_thread_slot = self.playback_thread_slot.clone();
         let transcode = self.transcode;
+        let transcode_format = self.transcode_format.clone();
+        let api_version = self.api_version.clone();
+        let fetch_options = self.fetch_options;
         self.tokio_thread.spawn(async move {
             let future = {
                 let client = client.clone();
                 let state = state.clone();
                 let library_populated_tx = library_populated_tx.clone();
                 async move {
-                    client.ping().await?;
+                    match api_version {
+                        Some(api_version) => {
+                            client.set_api_version(api_version);
+                            let server_version = client.ping().await?;
+                            tracing::info!(
+                                "Using configured API version {} (server reports {server_version})",
+                                client.api_version()
+                            );
+                        }
+                        None => {
+                            let version = client.negotiate_api_version().await?;
+                            tracing::info!("Negotiated API version {version}");
+                        }
+                    }
 
-                    let result = blackbird_state::fetch_all(&client, |batch_count, total_count| {
-                        tracing::info!("Fetched {batch_count} tracks, total {total_count} tracks");
-                    })
-                    .await?;
+                    let last_progress = std::sync::Mutex::new(FetchProgress::default());
+                    let fetch = blackbird_state::fetch_all(client.as_ref(), fetch_options, |progress| {
+                        if progress.batch_tracks == 0 {
+                            tracing::info!(
+                                "Fetched {} albums ({} tracks expected)",
+                                progress.albums_fetched,
+                                progress.expected_tracks
+                            );
+                        } else {
+                            tracing::info!(
+                                "Fetched {} tracks, total {}/{} tracks",
+                                progress.batch_tracks,
+                                progress.tracks_fetched,
+                                progress.expected_tracks
+                            );
+                        }
+                        *last_progress.lock().unwrap() = progress;
+                        state.write().unwrap().fetch_progress = Some(progress);
+                    });
+                    // The server info is fetched alongside the library rather
+                    // than holding it up, and is only informational, so the
+                    // library is loaded regardless.
+                    let (server_info, result) = tokio::join!(client.get_server_info(), fetch);
+                    match server_info {
+                        Ok(info) => state.write().unwrap().server_info = Some(info),
+                        Err(e) => tracing::warn!("Failed to get server info: {e}"),
+                    }
+                    let result = result?;
+                    let last_progress = *last_progress.lock().unwrap();
+                    if last_progress.is_incomplete() {
+                        tracing::warn!(
+                            "Fetched {} tracks, but albums report {}; the library may be incomplete",
+                            last_progress.tracks_fetched,
+                            last_progress.expected_tracks
+                        );
+                    }
 
                     let req_id;
                     let volume;
                     let apply_replaygain;
                     let replaygain_preamp_db;
+                    let skip_silence_db;
+                    let extra_outputs;
                     {
                         let mut st = state.write().unwrap();
                         let sort_order = st.sort_order;
@@ -1190,6 +2431,8 @@ impl Logic {
                         volume = st.volume;
                         apply_replaygain = st.apply_replaygain;
                         replaygain_preamp_db = st.replaygain_preamp_db;
+                        skip_silence_db = st.skip_silence_db;
+                        extra_outputs = st.extra_outputs.clone();
                     }
 
                     // Server connection succeeded — start the playback thread
@@ -1199,8 +2442,13 @@ impl Logic {
                         volume,
                         apply_replaygain,
                         replaygain_preamp_db,
+                        skip_silence_db,
                         playback_event_tx,
                     );
+                    for (device, volume) in extra_outputs {
+                        pt.send(LogicToPlaybackMessage::AddOutput(device.clone()));
+                        pt.send(LogicToPlaybackMessage::SetOutputVolume { device, volume });
+                    }
                     let playback_tx = pt.send_handle();
                     *playback_thread_slot.lock().unwrap() = Some(pt);
 
@@ -1216,9 +2464,18 @@ impl Logic {
                             track_id.0,
                             position.as_secs_f64()
                         );
-                        let response = client
-                            .stream(&track_id.0, transcode.then(|| "mp3".to_string()), None)
-                            .await;
+                        // Picked now that the track's details are known.
+                        let format = transcode.then(|| {
+                            let st = state.read().unwrap();
+                            pick_transcode_format(
+                                transcode_format.as_deref(),
+                                st.library
+                                    .track_map
+                                    .get(&track_id)
+                                    .and_then(|t| t.transcoded_suffix.as_deref()),
+                            )
+                        });
+                        let response = client.stream(&track_id.0, format, None).await;
                         queue::handle_load_response(
                             response,
                             state,
@@ -1234,9 +2491,14 @@ impl Logic {
             };
 
             if let Err(error) = future.await {
-                state.write().unwrap().error = Some(AppStateError::InitialFetchFailed {
-                    error: error.to_string(),
-                });
+                // Rejected credentials are reported as such, so that clients
+                // prompt for new ones rather than offering to retry.
+                state
+                    .write()
+                    .unwrap()
+                    .report_client_error(&error, |error| AppStateError::InitialFetchFailed {
+                        error,
+                    });
                 // Notify clients so they leave the loading state and render
                 // the connection error instead of staying on a frozen loading
                 // screen. Nothing else sets `changed` during loading (no
@@ -1261,4 +2523,221 @@ impl Logic {
     fn read_state(&'_ self) -> RwLockReadGuard<'_, AppState> {
         self.state.read().unwrap()
     }
+
+    /// `cover_art_id` followed by its fallbacks.
+    fn cover_art_ids(&self, cover_art_id: &CoverArtId) -> Vec<CoverArtId> {
+        let st = self.read_state();
+        std::iter::once(cover_art_id)
+            .chain(st.library.cover_art_fallbacks(cover_art_id))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Fetches cover art by each of `cover_art_ids` in turn until one loads,
+/// returning the last error if none do.
+async fn get_cover_art_with_fallbacks(
+    client: &impl bs::SubsonicApi,
+    cover_art_ids: &[CoverArtId],
+    size: Option<usize>,
+) -> bs::ClientResult<Vec<u8>> {
+    let (last, earlier) = cover_art_ids
+        .split_last()
+        .expect("at least one cover art ID");
+    for cover_art_id in earlier {
+        match client.get_cover_art(cover_art_id.0.as_str(), size).await {
+            Ok(cover_art) => return Ok(cover_art),
+            Err(e) => tracing::debug!("No cover art for {cover_art_id}, trying the next ID: {e}"),
+        }
+    }
+    client.get_cover_art(last.0.as_str(), size).await
+}
+
+/// Re-fetches the library and swaps it in, keeping playback and the queue
+/// position intact. Notifies clients through `library_populated_tx`.
+async fn refresh_library(
+    client: Arc<impl bs::SubsonicApi>,
+    state: Arc<RwLock<AppState>>,
+    fetch_options: blackbird_state::FetchOptions,
+    library_populated_tx: std::sync::mpsc::Sender<()>,
+) {
+    match blackbird_state::fetch_all(client.as_ref(), fetch_options, |_| {}).await {
+        Ok(result) => {
+            let mut st = state.write().unwrap();
+            let sort_order = st.sort_order;
+            st.library.populate(
+                result.track_ids,
+                result.track_map,
+                result.groups,
+                result.albums,
+                sort_order,
+            );
+            let current = st.queue.current_target.clone();
+            queue::recompute_queue_on_state(&mut st, current.as_ref());
+            tracing::info!("Library refreshed: {} tracks", st.library.track_ids.len());
+        }
+        Err(e) => {
+            tracing::warn!("Library refresh failed: {e}");
+            state
+                .write()
+                .unwrap()
+                .report_client_error(&e, |error| AppStateError::LibraryRefreshFailed { error });
+        }
+    }
+    let _ = library_populated_tx.send(());
+}
+
+/// The transcode formats the decoder can play, as reported in a track's
+/// `transcodedSuffix`.
+const PLAYABLE_TRANSCODE_FORMATS: &[&str] = &["mp3", "ogg", "oga"];
+
+/// Picks the format to transcode to: `configured` if set, otherwise the one
+/// the server `recommended` for the track if the decoder can play it, falling
+/// back to MP3, which every server can produce.
+fn pick_transcode_format(configured: Option<&str>, recommended: Option<&str>) -> String {
+    configured
+        .or_else(|| {
+            recommended.filter(|format| {
+                PLAYABLE_TRANSCODE_FORMATS
+                    .iter()
+                    .any(|playable| playable.eq_ignore_ascii_case(format))
+            })
+        })
+        .unwrap_or("mp3")
+        .to_string()
+}
+
+#[cfg(test)]
+impl Logic<bs::MockClient> {
+    /// A `Logic` serving `client`'s library, returned once the library has
+    /// been fetched. The playback thread is left out, as it needs an audio
+    /// device, so tests report playback with [`Self::simulate_playback`].
+    pub(crate) fn with_mock(client: bs::MockClient) -> Self {
+        let (library_populated_tx, library_populated_rx) = std::sync::mpsc::channel();
+        let logic = Logic::with_client(
+            client,
+            LogicArgs {
+                base_url: String::new(),
+                username: String::new(),
+                password: String::new(),
+                client_id: "blackbird-test".to_string(),
+                transcode: false,
+                transcode_format: None,
+                api_version: None,
+                fetch_options: blackbird_state::FetchOptions::default(),
+                scrobble_targets: ScrobbleTargets {
+                    history: false,
+                    ..Default::default()
+                },
+                volume: 1.0,
+                apply_replaygain: false,
+                replaygain_preamp_db: 0.0,
+                track_gains: HashMap::new(),
+                skipped_tracks: HashSet::new(),
+                skip_silence_db: None,
+                sort_order: SortOrder::default(),
+                playback_mode: PlaybackMode::default(),
+                preferred_shuffle: PlaybackMode::Shuffle,
+                end_behavior: EndBehavior::default(),
+                stop_behavior: StopBehavior::default(),
+                previous_behavior: PreviousBehavior::default(),
+                previous_restart_threshold: Duration::from_secs(3),
+                last_playback: None,
+                cover_art_loaded_tx: std::sync::mpsc::channel().0,
+                lyrics_loaded_tx: std::sync::mpsc::channel().0,
+                library_populated_tx,
+                track_updated_tx: std::sync::mpsc::channel().0,
+            },
+        );
+        library_populated_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("the library should be fetched");
+        assert!(logic.read_state().error.is_none());
+        // The playback thread is deposited before the library is reported as
+        // populated, so it can be taken out before `update` picks it up.
+        logic.playback_thread_slot.lock().unwrap().take();
+        logic
+    }
+
+    /// Reports `message` as if it came from the playback thread, and processes
+    /// it.
+    pub(crate) fn simulate_playback(&mut self, message: PlaybackToLogicMessage) {
+        self.playback_event_tx.send(message).unwrap();
+        self.update();
+    }
+
+    /// Reports `track_and_position` as the playback position, writing it to
+    /// the state straight away rather than once the write interval elapses.
+    pub(crate) fn simulate_position(&mut self, track_and_position: TrackAndPosition) {
+        self.position_throttle.expire();
+        self.simulate_playback(PlaybackToLogicMessage::PositionChanged(track_and_position));
+    }
+
+    /// The track most recently asked to play.
+    pub(crate) fn current_target(&self) -> Option<TrackId> {
+        self.read_state().queue.current_target.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::wait_until;
+
+    #[test]
+    fn configured_transcode_format_wins() {
+        assert_eq!(pick_transcode_format(Some("opus"), Some("ogg")), "opus");
+        assert_eq!(pick_transcode_format(Some("opus"), None), "opus");
+    }
+
+    #[test]
+    fn playable_server_transcode_format_is_used() {
+        assert_eq!(pick_transcode_format(None, Some("ogg")), "ogg");
+        assert_eq!(pick_transcode_format(None, Some("mp3")), "mp3");
+    }
+
+    #[test]
+    fn cover_art_is_fetched_once_per_size_at_a_time() {
+        let mut client = bs::MockClient::fixture();
+        client.hold_cover_art = true;
+        let logic = Logic::with_mock(client.clone());
+        let cover_art_id = CoverArtId("al-debut".into());
+
+        logic.request_cover_art(&cover_art_id, Some(64));
+        logic.request_cover_art(&cover_art_id, Some(64));
+        logic.request_cover_art(&cover_art_id, Some(512));
+
+        wait_until(|| client.cover_art_requests().len() >= 2);
+        let mut requests = client.cover_art_requests();
+        requests.sort();
+        assert_eq!(
+            requests,
+            [
+                ("al-debut".to_string(), Some(64)),
+                ("al-debut".to_string(), Some(512)),
+            ]
+        );
+    }
+
+    #[test]
+    fn failures_other_than_missing_cover_art_are_reported() {
+        let logic = Logic::with_mock(bs::MockClient::fixture());
+        // The album has no art of its own, so its track's art is tried too;
+        // the mock fails both with a generic error rather than "not found".
+        logic.request_cover_art(&CoverArtId("al-second".into()), Some(64));
+
+        wait_until(|| logic.read_state().error.is_some());
+        assert!(matches!(
+            logic.read_state().error,
+            Some(AppStateError::CoverArtFetchFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn transcode_format_falls_back_to_mp3() {
+        assert_eq!(pick_transcode_format(None, None), "mp3");
+        // The decoder can't play Opus, so the server's recommendation is
+        // ignored.
+        assert_eq!(pick_transcode_format(None, Some("opus")), "mp3");
+    }
 }
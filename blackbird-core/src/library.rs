@@ -1,10 +1,11 @@
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     ops::Bound,
     sync::Arc,
 };
 
-use blackbird_state::{Album, AlbumId, Group, Track, TrackId};
+use blackbird_state::{Album, AlbumId, CoverArtId, Group, Track, TrackId};
 use icu_normalizer::DecomposingNormalizer;
 use icu_properties::{CodePointMapData, props::CanonicalCombiningClass};
 use smallvec::SmallVec;
@@ -27,6 +28,14 @@ pub struct Library {
     pub track_to_group_index: HashMap<TrackId, usize>,
     pub track_to_group_track_index: HashMap<TrackId, usize>,
 
+    /// Whether [`shown_groups`](Self::shown_groups) leaves out unstarred tracks.
+    hide_unstarred: bool,
+    /// The groups cut down to their starred tracks, kept up to date while
+    /// `hide_unstarred` is set.
+    starred_groups: Vec<Arc<Group>>,
+    /// The index of each group in `starred_groups`, by album.
+    album_to_starred_group_index: HashMap<AlbumId, usize>,
+
     /// Inverted search index: normalized word → track indices (into `track_ids`).
     /// Each posting list is sorted and deduplicated.
     word_index: BTreeMap<SmolStr, Vec<u32>>,
@@ -54,12 +63,76 @@ impl Library {
         self.has_loaded_all_tracks = true;
     }
 
+    /// Whether the library has finished loading without any tracks, as
+    /// opposed to still loading.
+    pub fn is_loaded_and_empty(&self) -> bool {
+        self.has_loaded_all_tracks && self.track_ids.is_empty()
+    }
+
+    /// The group (album) that `track_id` is in, if it's in the library.
+    pub fn group_for_track(&self, track_id: &TrackId) -> Option<&Arc<Group>> {
+        self.groups.get(*self.track_to_group_index.get(track_id)?)
+    }
+
+    /// Iterates over the groups in sorted order.
+    pub fn iter_groups(&self) -> impl Iterator<Item = &Group> {
+        self.groups.iter().map(|group| group.as_ref())
+    }
+
+    /// Iterates over the tracks in sorted order: group by group, each in
+    /// disc and track order.
+    pub fn iter_tracks_in_order(&self) -> impl Iterator<Item = &Track> {
+        self.track_ids
+            .iter()
+            .filter_map(|track_id| self.track_map.get(track_id))
+    }
+
+    /// The track of the album `album_id` with the given number. Without a
+    /// disc, the first track with that number is used.
+    pub fn track_by_number(&self, album_id: &AlbumId, number: TrackNumber) -> Option<&TrackId> {
+        let group = self.groups.get(*self.album_to_group_index.get(album_id)?)?;
+        group.tracks.iter().find(|id| {
+            self.track_map.get(*id).is_some_and(|track| {
+                track.track == Some(number.track)
+                    && number
+                        .disc
+                        .is_none_or(|disc| track.disc_number.unwrap_or(1) == disc)
+            })
+        })
+    }
+
+    /// The ReplayGain of `group`: its album gain if any track reports one,
+    /// or otherwise the range of its tracks' gains. `None` if none of its
+    /// tracks have gain data.
+    pub fn group_gain(&self, group: &Group) -> Option<GroupGain> {
+        let gains = group
+            .tracks
+            .iter()
+            .filter_map(|id| self.track_map.get(id)?.replay_gain.as_ref());
+        let mut range: Option<(f32, f32)> = None;
+        for gain in gains {
+            if let Some(album_gain) = gain.album_gain {
+                return Some(GroupGain::Album(album_gain));
+            }
+            if let Some(track_gain) = gain.track_gain {
+                let (min, max) = range.get_or_insert((track_gain, track_gain));
+                *min = min.min(track_gain);
+                *max = max.max(track_gain);
+            }
+        }
+        range.map(|(min, max)| GroupGain::Tracks { min, max })
+    }
+
     pub fn set_track_starred(&mut self, track_id: &TrackId, starred: bool) -> Option<bool> {
         let mut old_starred = None;
         if let Some(track) = self.track_map.get_mut(track_id) {
             old_starred = Some(track.starred);
             track.starred = starred;
+            if !starred {
+                track.starred_at = None;
+            }
         }
+        self.rebuild_starred_groups();
         old_starred
     }
 
@@ -69,6 +142,9 @@ impl Library {
         if let Some(album) = self.albums.get_mut(album_id) {
             old_starred = Some(album.starred);
             album.starred = starred;
+            if !starred {
+                album.starred_at = None;
+            }
         }
         if let Some(group_idx) = self.album_to_group_index.get(album_id)
             && let Some(group) = self.groups.get(*group_idx)
@@ -79,10 +155,63 @@ impl Library {
             };
             self.groups[*group_idx] = Arc::new(group);
         }
+        self.rebuild_starred_groups();
 
         old_starred
     }
 
+    /// Sets whether [`shown_groups`](Self::shown_groups) leaves out
+    /// unstarred tracks.
+    pub fn set_hide_unstarred(&mut self, hide_unstarred: bool) {
+        if self.hide_unstarred != hide_unstarred {
+            self.hide_unstarred = hide_unstarred;
+            self.rebuild_starred_groups();
+        }
+    }
+
+    pub fn hide_unstarred(&self) -> bool {
+        self.hide_unstarred
+    }
+
+    /// The groups to display. While unstarred tracks are hidden, each group
+    /// only has its starred tracks, and groups without any are left out.
+    pub fn shown_groups(&self) -> &[Arc<Group>] {
+        if self.hide_unstarred {
+            &self.starred_groups
+        } else {
+            &self.groups
+        }
+    }
+
+    /// The index of the album's group in [`shown_groups`](Self::shown_groups),
+    /// if it is shown.
+    pub fn shown_group_index(&self, album_id: &AlbumId) -> Option<usize> {
+        if self.hide_unstarred {
+            self.album_to_starred_group_index.get(album_id).copied()
+        } else {
+            self.album_to_group_index.get(album_id).copied()
+        }
+    }
+
+    /// Whether the track is in [`shown_groups`](Self::shown_groups).
+    pub fn is_track_shown(&self, track_id: &TrackId) -> bool {
+        self.track_map
+            .get(track_id)
+            .is_some_and(|track| !self.hide_unstarred || track.starred)
+            && self.track_to_group_index.contains_key(track_id)
+    }
+
+    /// The IDs to request cover art by if `cover_art_id` fails to load.
+    pub fn cover_art_fallbacks(&self, cover_art_id: &CoverArtId) -> &[CoverArtId] {
+        // Only albums without cover art of their own have fallbacks, and
+        // those are keyed by their album ID.
+        self.album_to_group_index
+            .get(&AlbumId(cover_art_id.0.clone()))
+            .and_then(|idx| self.groups.get(*idx))
+            .filter(|group| group.cover_art_id.as_ref() == Some(cover_art_id))
+            .map_or(&[], |group| &group.cover_art_fallbacks)
+    }
+
     pub fn search(&mut self, query: &str) -> Vec<TrackId> {
         let cache_key = query.to_lowercase();
 
@@ -163,170 +292,523 @@ impl Library {
 
     /// Resorts the library groups based on the given sort order and rebuilds all lookup structures.
     pub fn resort(&mut self, order: SortOrder) {
-        use std::cmp::Ordering;
-
-        /// Compare by artist name (case-insensitive, ascending).
-        fn cmp_artist(a: &Group, b: &Group) -> Ordering {
-            a.artist.to_lowercase().cmp(&b.artist.to_lowercase())
+        {
+            let cmp = group_ordering(order, &self.albums, &self.track_map);
+            self.groups.sort_by(|a, b| cmp(a, b));
         }
 
-        /// Compare by year (descending, newest first; None values sort last).
-        fn cmp_year_desc(a: &Group, b: &Group) -> Ordering {
-            match (a.year, b.year) {
-                (Some(y1), Some(y2)) => y2.cmp(&y1),
-                (Some(_), None) => Ordering::Less,
-                (None, Some(_)) => Ordering::Greater,
-                (None, None) => Ordering::Equal,
+        self.rebuild_lookups();
+
+        // Clear search cache since the order has changed.
+        self.search_cache.clear();
+        self.search_cache_order.clear();
+
+        // Rebuild the inverted word index to match the new track order.
+        self.word_index.clear();
+        for (idx, track_id) in self.track_ids.iter().enumerate() {
+            let idx = idx as u32;
+            let track = self.track_map.get(track_id).unwrap();
+            for word in search_words(track, &self.albums) {
+                // Tracks are iterated in ascending order, so the posting
+                // list for a word grows monotonically. Checking `last()` is
+                // enough to avoid duplicates without a post-pass.
+                let postings = self.word_index.entry(word).or_default();
+                if postings.last() != Some(&idx) {
+                    postings.push(idx);
+                }
             }
         }
+    }
 
-        /// Compare by year (ascending, oldest first; None values sort last).
-        fn cmp_year_asc(a: &Group, b: &Group) -> Ordering {
-            match (a.year, b.year) {
-                (Some(y1), Some(y2)) => y1.cmp(&y2),
-                (Some(_), None) => Ordering::Less,
-                (None, Some(_)) => Ordering::Greater,
-                (None, None) => Ordering::Equal,
+    /// Applies `delta` in place. Only the groups it touches are placed anew
+    /// in `order`; the others keep their positions without a full re-sort,
+    /// so the UI's selection and scroll position stay put. The lookup maps
+    /// are rebuilt, and only the touched tracks are re-indexed for search.
+    ///
+    /// Groups that tie with others under `order` may end up in a different
+    /// position than a full [`resort`](Self::resort) would put them in.
+    pub fn apply_delta(&mut self, delta: LibraryDelta, order: SortOrder) {
+        let LibraryDelta {
+            albums,
+            tracks,
+            groups,
+            removed_albums,
+            removed_tracks,
+        } = delta;
+
+        // Albums whose groups need to be rebuilt or placed anew, and tracks
+        // whose position or search words may have changed.
+        let mut touched_albums: HashSet<AlbumId> = HashSet::new();
+        let mut touched_tracks: HashSet<TrackId> = HashSet::new();
+
+        for album_id in removed_albums {
+            self.albums.remove(&album_id);
+            if let Some(group) = self
+                .album_to_group_index
+                .get(&album_id)
+                .and_then(|idx| self.groups.get(*idx))
+            {
+                for track_id in &group.tracks {
+                    self.track_map.remove(track_id);
+                }
             }
+            touched_albums.insert(album_id);
         }
-
-        /// Compare by album name (case-insensitive, ascending).
-        fn cmp_album(a: &Group, b: &Group) -> Ordering {
-            a.album.to_lowercase().cmp(&b.album.to_lowercase())
+        for track_id in removed_tracks {
+            if let Some(track) = self.track_map.remove(&track_id) {
+                touched_albums.extend(track.album_id);
+            }
+            touched_tracks.insert(track_id);
         }
-
-        /// Compare by (artist, year asc, album).
-        fn cmp_artist_year_album(a: &Group, b: &Group) -> Ordering {
-            cmp_artist(a, b)
-                .then_with(|| cmp_year_asc(a, b))
-                .then_with(|| cmp_album(a, b))
+        for album in albums {
+            touched_albums.insert(album.id.clone());
+            self.albums.insert(album.id.clone(), album);
         }
-
-        match order {
-            SortOrder::Alphabetical => {
-                // Sort by (artist, year desc, album).
-                self.groups.sort_by(|a, b| cmp_artist_year_album(a, b));
+        for track in tracks {
+            // A track that moved albums touches both of them.
+            if let Some(old) = self.track_map.get(&track.id) {
+                touched_albums.extend(old.album_id.clone());
             }
-            SortOrder::NewestFirst => {
-                // Sort by (year desc, artist, album).
-                self.groups.sort_by(|a, b| {
-                    cmp_year_desc(a, b)
-                        .then_with(|| cmp_artist(a, b))
-                        .then_with(|| cmp_album(a, b))
-                });
-            }
-            SortOrder::RecentlyAdded => {
-                // Sort by (added desc, artist, year desc, album).
-                let albums = &self.albums;
-                self.groups.sort_by(|a, b| {
-                    let created_a = albums.get(&a.album_id).map(|album| album.created.as_str());
-                    let created_b = albums.get(&b.album_id).map(|album| album.created.as_str());
-                    // Reverse comparison for descending order (most recent first).
-                    created_b
-                        .cmp(&created_a)
-                        .then_with(|| cmp_artist_year_album(a, b))
-                });
+            touched_albums.extend(track.album_id.clone());
+            touched_tracks.insert(track.id.clone());
+            self.track_map.insert(track.id.clone(), track);
+        }
+        let mut replacements: HashMap<AlbumId, Arc<Group>> = groups
+            .into_iter()
+            .map(|group| (group.album_id.clone(), group))
+            .collect();
+        touched_albums.extend(replacements.keys().cloned());
+
+        // Take the touched groups out, leaving the rest in order.
+        let mut placed = vec![];
+        let mut kept = Vec::with_capacity(self.groups.len());
+        for group in std::mem::take(&mut self.groups) {
+            if !touched_albums.contains(&group.album_id) {
+                kept.push(group);
+                continue;
             }
-            SortOrder::MostPlayed => {
-                // Sort by average playcount per listened track (descending).
-                // Groups with no listened tracks sort last.
-                let track_map = &self.track_map;
-                self.groups.sort_by(|a, b| {
-                    let avg_playcount = |group: &Group| -> Option<f64> {
-                        let mut total: u64 = 0;
-                        let mut count: u64 = 0;
-                        for track_id in &group.tracks {
-                            if let Some(track) = track_map.get(track_id)
-                                && let Some(pc) = track.play_count
-                                && pc > 0
-                            {
-                                total += pc;
-                                count += 1;
-                            }
-                        }
-                        if count > 0 {
-                            Some(total as f64 / count as f64)
-                        } else {
-                            None
-                        }
-                    };
-                    let avg_a = avg_playcount(a);
-                    let avg_b = avg_playcount(b);
-                    match (avg_a, avg_b) {
-                        (Some(a_val), Some(b_val)) => b_val
-                            .partial_cmp(&a_val)
-                            .unwrap_or(Ordering::Equal)
-                            .then_with(|| cmp_artist_year_album(a, b)),
-                        (Some(_), None) => Ordering::Less,
-                        (None, Some(_)) => Ordering::Greater,
-                        (None, None) => cmp_artist_year_album(a, b),
-                    }
-                });
+            touched_tracks.extend(group.tracks.iter().cloned());
+            let group = replacements.remove(&group.album_id).unwrap_or_else(|| {
+                // Without a new group, keep the tracks that still belong to
+                // the album, and pick up any changes to the album itself.
+                let tracks = group
+                    .tracks
+                    .iter()
+                    .filter(|id| {
+                        self.track_map
+                            .get(*id)
+                            .is_some_and(|t| t.album_id.as_ref() == Some(&group.album_id))
+                    })
+                    .cloned()
+                    .collect();
+                let mut group = Group {
+                    tracks,
+                    ..(*group).clone()
+                };
+                if let Some(album) = self.albums.get(&group.album_id) {
+                    group.artist = album.artist.clone();
+                    group.album = album.name.clone();
+                    group.year = album.year;
+                    group.duration = album.duration;
+                    group.starred = album.starred;
+                }
+                Arc::new(group)
+            });
+            placed.push(group);
+        }
+        self.groups = kept;
+        // The remaining replacements are for albums new to the library.
+        placed.extend(replacements.into_values());
+        placed
+            .retain(|group| !group.tracks.is_empty() && self.albums.contains_key(&group.album_id));
+
+        {
+            let cmp = group_ordering(order, &self.albums, &self.track_map);
+            for group in placed {
+                touched_tracks.extend(group.tracks.iter().cloned());
+                let idx = self
+                    .groups
+                    .partition_point(|g| cmp(g, &group) != Ordering::Greater);
+                self.groups.insert(idx, group);
             }
         }
 
-        // Rebuild track_ids from reordered groups.
-        self.track_ids.clear();
-        for group in &self.groups {
-            for track_id in &group.tracks {
-                self.track_ids.push(track_id.clone());
+        let old_track_ids = std::mem::take(&mut self.track_ids);
+        self.rebuild_lookups();
+        self.search_cache.clear();
+        self.search_cache_order.clear();
+        self.reindex_words(&old_track_ids, &touched_tracks);
+    }
+
+    /// Adds tracks fetched outside of a library fetch, along with their album
+    /// if the library doesn't have it yet. The groups the tracks join are
+    /// rebuilt the way a library fetch builds them, so that they sort, merge
+    /// editions and are timed as they would be after a refresh. Tracks without
+    /// an album, or whose album isn't known, are ignored.
+    pub fn insert_tracks(
+        &mut self,
+        tracks: Vec<Track>,
+        album: Option<Album>,
+        merge_editions: bool,
+        order: SortOrder,
+    ) {
+        let album = album.filter(|album| !self.albums.contains_key(&album.id));
+        let known_album = |id: &AlbumId| {
+            self.albums.contains_key(id) || album.as_ref().is_some_and(|a| a.id == *id)
+        };
+        let tracks: Vec<Track> = tracks
+            .into_iter()
+            .filter(|track| track.album_id.as_ref().is_some_and(known_album))
+            .collect();
+        if tracks.is_empty() {
+            return;
+        }
+
+        // The groups the tracks may join. A new album may be an edition of any
+        // of its artist's albums, so all of them are rebuilt when merging.
+        let new_album_ids: HashSet<&AlbumId> =
+            tracks.iter().filter_map(|t| t.album_id.as_ref()).collect();
+        let new_artists: HashSet<String> = new_album_ids
+            .iter()
+            .filter_map(|id| self.albums.get(*id).or(album.as_ref()))
+            .map(|a| a.artist.to_lowercase())
+            .collect();
+        let old_groups: Vec<Arc<Group>> = self
+            .groups
+            .iter()
+            .filter(|group| {
+                group.album_ids().any(|id| new_album_ids.contains(id))
+                    || (merge_editions
+                        && self
+                            .albums
+                            .get(&group.album_id)
+                            .is_some_and(|a| new_artists.contains(&a.artist.to_lowercase())))
+            })
+            .cloned()
+            .collect();
+
+        let mut albums: HashMap<AlbumId, Album> = old_groups
+            .iter()
+            .flat_map(|group| group.album_ids())
+            .chain(new_album_ids.iter().copied())
+            .filter_map(|id| self.albums.get(id))
+            .map(|a| (a.id.clone(), a.clone()))
+            .collect();
+        albums.extend(album.iter().map(|a| (a.id.clone(), a.clone())));
+        let mut track_map: HashMap<TrackId, Track> = old_groups
+            .iter()
+            .flat_map(|group| &group.tracks)
+            .filter_map(|id| self.track_map.get(id))
+            .map(|t| (t.id.clone(), t.clone()))
+            .collect();
+        let inserted_ids: Vec<TrackId> = tracks.iter().map(|t| t.id.clone()).collect();
+        track_map.extend(tracks.into_iter().map(|t| (t.id.clone(), t)));
+
+        let output = blackbird_state::collate(albums, track_map, &HashMap::new(), merge_editions);
+
+        // Without artist sort names to hand, keep the sort name the library
+        // already has for the artist.
+        let sort_artists: HashMap<&SmolStr, &SmolStr> = self
+            .groups
+            .iter()
+            .map(|group| (&group.artist, &group.sort_artist))
+            .collect();
+        let mut groups: Vec<Arc<Group>> = output
+            .groups
+            .into_iter()
+            .map(|group| match sort_artists.get(&group.artist) {
+                Some(&sort_artist) if *sort_artist != group.sort_artist => Arc::new(Group {
+                    sort_artist: sort_artist.clone(),
+                    ..(*group).clone()
+                }),
+                _ => group,
+            })
+            .collect();
+        // Groups merged into another edition are emptied, which removes them.
+        for old in &old_groups {
+            if !groups.iter().any(|g| g.album_id == old.album_id) {
+                groups.push(Arc::new(Group {
+                    tracks: vec![],
+                    ..(**old).clone()
+                }));
             }
         }
+        // Tracks that turned out to be duplicates of another edition's are
+        // dropped, as a fetch would.
+        let removed_tracks = old_groups
+            .iter()
+            .flat_map(|group| &group.tracks)
+            .chain(&inserted_ids)
+            .filter(|id| !output.track_map.contains_key(*id) && self.track_map.contains_key(*id))
+            .cloned()
+            .collect();
 
-        // Rebuild reverse lookup maps.
+        self.apply_delta(
+            LibraryDelta {
+                albums: album.into_iter().collect(),
+                tracks: output.track_map.into_values().collect(),
+                groups,
+                removed_tracks,
+                ..Default::default()
+            },
+            order,
+        );
+    }
+
+    /// Rebuilds `track_ids` and the reverse lookup maps from the groups.
+    fn rebuild_lookups(&mut self) {
+        self.track_ids.clear();
         self.track_to_group_index.clear();
         self.track_to_group_track_index.clear();
         self.album_to_group_index.clear();
         for (group_idx, group) in self.groups.iter().enumerate() {
             for (track_idx, track_id) in group.tracks.iter().enumerate() {
+                self.track_ids.push(track_id.clone());
                 self.track_to_group_index
                     .insert(track_id.clone(), group_idx);
                 self.track_to_group_track_index
                     .insert(track_id.clone(), track_idx);
             }
-            self.album_to_group_index
-                .insert(group.album_id.clone(), group_idx);
+            for album_id in group.album_ids() {
+                self.album_to_group_index
+                    .insert(album_id.clone(), group_idx);
+            }
         }
+        self.rebuild_starred_groups();
+    }
 
-        // Clear search cache since the order has changed.
-        self.search_cache.clear();
-        self.search_cache_order.clear();
+    fn rebuild_starred_groups(&mut self) {
+        if !self.hide_unstarred {
+            self.starred_groups.clear();
+            self.album_to_starred_group_index.clear();
+            return;
+        }
+        self.starred_groups = self
+            .groups
+            .iter()
+            .filter_map(|group| {
+                let tracks: Vec<_> = group
+                    .tracks
+                    .iter()
+                    .filter(|id| self.track_map.get(*id).is_some_and(|t| t.starred))
+                    .cloned()
+                    .collect();
+                if tracks.is_empty() {
+                    None
+                } else if tracks.len() == group.tracks.len() {
+                    Some(group.clone())
+                } else {
+                    Some(Arc::new(Group {
+                        tracks,
+                        ..(**group).clone()
+                    }))
+                }
+            })
+            .collect();
+        self.album_to_starred_group_index = self
+            .starred_groups
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, group)| {
+                group
+                    .album_ids()
+                    .map(move |album_id| (album_id.clone(), idx))
+            })
+            .collect();
+    }
 
-        // Rebuild the inverted word index to match the new track order.
-        self.word_index.clear();
+    /// Updates the word index once the `touched` tracks have been added,
+    /// removed, changed or moved, given the track order beforehand. The
+    /// other tracks keep their relative order, so their postings only need
+    /// renumbering.
+    fn reindex_words(&mut self, old_track_ids: &[TrackId], touched: &HashSet<TrackId>) {
+        let mut group_starts = Vec::with_capacity(self.groups.len());
+        let mut start = 0;
+        for group in &self.groups {
+            group_starts.push(start);
+            start += group.tracks.len() as u32;
+        }
+        let new_indices: Vec<Option<u32>> = old_track_ids
+            .iter()
+            .map(|id| {
+                if touched.contains(id) {
+                    return None;
+                }
+                let group_idx = *self.track_to_group_index.get(id)?;
+                let track_idx = *self.track_to_group_track_index.get(id)?;
+                Some(group_starts[group_idx] + track_idx as u32)
+            })
+            .collect();
+
+        self.word_index.retain(|_, postings| {
+            *postings = postings
+                .iter()
+                .filter_map(|&idx| new_indices[idx as usize])
+                .collect();
+            !postings.is_empty()
+        });
+
+        let mut grown = HashSet::new();
         for (idx, track_id) in self.track_ids.iter().enumerate() {
-            let idx = idx as u32;
-            let track = self.track_map.get(track_id).unwrap();
-            let album = track.album_id.as_ref().and_then(|id| self.albums.get(id));
-            let artist = track
-                .artist
-                .as_deref()
-                .or(album.as_ref().map(|a| a.artist.as_str()));
-
-            let mut raw = String::new();
-            if let Some(artist) = artist {
-                raw.push_str(artist);
-                raw.push(' ');
+            if !touched.contains(track_id) {
+                continue;
             }
-            if let Some(album) = album {
-                raw.push_str(&album.name);
-                raw.push(' ');
+            let Some(track) = self.track_map.get(track_id) else {
+                continue;
+            };
+            for word in search_words(track, &self.albums) {
+                self.word_index
+                    .entry(word.clone())
+                    .or_default()
+                    .push(idx as u32);
+                grown.insert(word);
             }
-            raw.push_str(&track.title);
-
-            for variant in normalize_variants(&raw) {
-                for word in variant.split_whitespace() {
-                    // Tracks are iterated in ascending order, so the posting
-                    // list for a word grows monotonically. Checking `last()` is
-                    // enough to avoid duplicates without a post-pass.
-                    let postings = self.word_index.entry(SmolStr::new(word)).or_default();
-                    if postings.last() != Some(&idx) {
-                        postings.push(idx);
-                    }
-                }
+        }
+        for word in grown {
+            if let Some(postings) = self.word_index.get_mut(&word) {
+                postings.sort_unstable();
+                postings.dedup();
+            }
+        }
+    }
+}
+
+/// Changes to the library's contents, for [`Library::apply_delta`].
+#[derive(Debug, Default)]
+pub struct LibraryDelta {
+    /// Albums that were added or changed.
+    pub albums: Vec<Album>,
+    /// Tracks that were added or changed.
+    pub tracks: Vec<Track>,
+    /// Groups for added albums, or for albums whose tracks were added or
+    /// reordered or whose artist changed, built as `fetch_all` builds them.
+    /// Each replaces the album's current group.
+    pub groups: Vec<Arc<Group>>,
+    /// Albums that were removed, along with their tracks.
+    pub removed_albums: Vec<AlbumId>,
+    /// Tracks that were removed.
+    pub removed_tracks: Vec<TrackId>,
+}
+
+/// Compares groups for the given sort order.
+fn group_ordering<'a>(
+    order: SortOrder,
+    albums: &'a HashMap<AlbumId, Album>,
+    track_map: &'a HashMap<TrackId, Track>,
+) -> impl Fn(&Group, &Group) -> Ordering + 'a {
+    /// Compare by artist name (case-insensitive, ascending).
+    fn cmp_artist(a: &Group, b: &Group) -> Ordering {
+        a.artist.to_lowercase().cmp(&b.artist.to_lowercase())
+    }
+
+    /// Compare by year (descending, newest first; None values sort last).
+    fn cmp_year_desc(a: &Group, b: &Group) -> Ordering {
+        match (a.year, b.year) {
+            (Some(y1), Some(y2)) => y2.cmp(&y1),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    /// Compare by year (ascending, oldest first; None values sort last).
+    fn cmp_year_asc(a: &Group, b: &Group) -> Ordering {
+        match (a.year, b.year) {
+            (Some(y1), Some(y2)) => y1.cmp(&y2),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    /// Compare by album name (case-insensitive, ascending).
+    fn cmp_album(a: &Group, b: &Group) -> Ordering {
+        a.album.to_lowercase().cmp(&b.album.to_lowercase())
+    }
+
+    /// Compare by (artist, year asc, album).
+    fn cmp_artist_year_album(a: &Group, b: &Group) -> Ordering {
+        cmp_artist(a, b)
+            .then_with(|| cmp_year_asc(a, b))
+            .then_with(|| cmp_album(a, b))
+    }
+
+    // Average playcount per listened track; `None` if no track was listened to.
+    let avg_playcount = move |group: &Group| -> Option<f64> {
+        let mut total: u64 = 0;
+        let mut count: u64 = 0;
+        for track_id in &group.tracks {
+            if let Some(track) = track_map.get(track_id)
+                && let Some(pc) = track.play_count
+                && pc > 0
+            {
+                total += pc;
+                count += 1;
             }
         }
+        if count > 0 {
+            Some(total as f64 / count as f64)
+        } else {
+            None
+        }
+    };
+
+    move |a: &Group, b: &Group| match order {
+        // Sort by (artist, year desc, album).
+        SortOrder::Alphabetical => cmp_artist_year_album(a, b),
+        // Sort by (year desc, artist, album).
+        SortOrder::NewestFirst => cmp_year_desc(a, b)
+            .then_with(|| cmp_artist(a, b))
+            .then_with(|| cmp_album(a, b)),
+        SortOrder::RecentlyAdded => {
+            // Sort by (added desc, artist, year desc, album).
+            let created_a = albums.get(&a.album_id).map(|album| album.created.as_str());
+            let created_b = albums.get(&b.album_id).map(|album| album.created.as_str());
+            // Reverse comparison for descending order (most recent first).
+            created_b
+                .cmp(&created_a)
+                .then_with(|| cmp_artist_year_album(a, b))
+        }
+        SortOrder::MostPlayed => {
+            // Sort by average playcount per listened track (descending).
+            // Groups with no listened tracks sort last.
+            match (avg_playcount(a), avg_playcount(b)) {
+                (Some(a_val), Some(b_val)) => b_val
+                    .partial_cmp(&a_val)
+                    .unwrap_or(Ordering::Equal)
+                    .then_with(|| cmp_artist_year_album(a, b)),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => cmp_artist_year_album(a, b),
+            }
+        }
+    }
+}
+
+/// The normalized words a track can be searched by: its artist (or its
+/// album's), album name and title. May contain duplicates.
+fn search_words(track: &Track, albums: &HashMap<AlbumId, Album>) -> Vec<SmolStr> {
+    let album = track.album_id.as_ref().and_then(|id| albums.get(id));
+    let artist = track
+        .artist
+        .as_deref()
+        .or(album.as_ref().map(|a| a.artist.as_str()));
+
+    let mut raw = String::new();
+    if let Some(artist) = artist {
+        raw.push_str(artist);
+        raw.push(' ');
     }
+    if let Some(album) = album {
+        raw.push_str(&album.name);
+        raw.push(' ');
+    }
+    raw.push_str(&track.title);
+
+    normalize_variants(&raw)
+        .iter()
+        .flat_map(|variant| variant.split_whitespace().map(SmolStr::new))
+        .collect()
 }
 
 /// Maps typographic Unicode characters to their ASCII equivalents.
@@ -425,9 +907,51 @@ fn normalize_variants(s: &str) -> SmallVec<[SmolStr; 2]> {
     variants
 }
 
+/// A track's number within its album, as typed to jump to it: `7`, or `2.7`
+/// for the seventh track of the second disc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackNumber {
+    pub disc: Option<u32>,
+    pub track: u32,
+}
+impl TrackNumber {
+    /// Parses `track` or `disc.track`.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.split_once('.') {
+            Some((disc, track)) => Some(Self {
+                disc: Some(disc.parse().ok()?),
+                track: track.parse().ok()?,
+            }),
+            None => Some(Self {
+                disc: None,
+                track: s.parse().ok()?,
+            }),
+        }
+    }
+}
+
+/// The ReplayGain of a group, as found by [`Library::group_gain`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupGain {
+    /// The album gain, in dB.
+    Album(f32),
+    /// The range of the track gains, in dB, for groups without album gain.
+    Tracks { min: f32, max: f32 },
+}
+impl std::fmt::Display for GroupGain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            GroupGain::Album(gain) => write!(f, "{gain:+.1} dB"),
+            GroupGain::Tracks { min, max } if max - min < 0.05 => write!(f, "{min:+.1} dB"),
+            GroupGain::Tracks { min, max } => write!(f, "{min:+.1} to {max:+.1} dB"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{make_album_group, make_named_album, make_named_track};
 
     fn variants(s: &str) -> Vec<String> {
         normalize_variants(s)
@@ -436,6 +960,21 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn empty_library_is_only_reported_once_loaded() {
+        let mut library = Library::default();
+        assert!(!library.is_loaded_and_empty());
+
+        library.populate(
+            vec![],
+            HashMap::new(),
+            vec![],
+            HashMap::new(),
+            SortOrder::default(),
+        );
+        assert!(library.is_loaded_and_empty());
+    }
+
     #[test]
     fn normalize_variants_collapses_when_equal() {
         // No punctuation: one variant.
@@ -507,61 +1046,23 @@ mod tests {
     fn build_library(specs: &[TrackSpec]) -> Library {
         let mut track_map: HashMap<TrackId, Track> = HashMap::new();
         let mut albums: HashMap<AlbumId, Album> = HashMap::new();
-        let mut group_tracks: HashMap<AlbumId, Vec<TrackId>> = HashMap::new();
+        let mut group_tracks: HashMap<AlbumId, Vec<&str>> = HashMap::new();
 
         for (tid, title, artist, aid, aname) in specs {
-            let track_id = TrackId((*tid).into());
             let album_id = AlbumId((*aid).into());
-
             track_map.insert(
-                track_id.clone(),
-                Track {
-                    id: track_id.clone(),
-                    title: (*title).into(),
-                    artist: Some((*artist).into()),
-                    track: None,
-                    year: None,
-                    _genre: None,
-                    duration: None,
-                    disc_number: None,
-                    album_id: Some(album_id.clone()),
-                    starred: false,
-                    play_count: None,
-                    replay_gain: None,
-                },
+                TrackId((*tid).into()),
+                make_named_track(tid, title, artist, aid),
             );
-            albums.entry(album_id.clone()).or_insert_with(|| Album {
-                id: album_id.clone(),
-                name: (*aname).into(),
-                artist: (*artist).into(),
-                artist_id: None,
-                cover_art_id: None,
-                track_count: 0,
-                duration: 0,
-                year: None,
-                _genre: None,
-                starred: false,
-                created: "".into(),
-            });
-            group_tracks.entry(album_id).or_default().push(track_id);
+            albums
+                .entry(album_id.clone())
+                .or_insert_with(|| make_named_album(aid, aname, artist));
+            group_tracks.entry(album_id).or_default().push(tid);
         }
 
         let groups: Vec<Arc<Group>> = group_tracks
             .into_iter()
-            .map(|(album_id, tracks)| {
-                let album = &albums[&album_id];
-                Arc::new(Group {
-                    artist: album.artist.clone(),
-                    sort_artist: album.artist.clone(),
-                    album: album.name.clone(),
-                    year: None,
-                    duration: 0,
-                    tracks,
-                    cover_art_id: None,
-                    album_id,
-                    starred: false,
-                })
-            })
+            .map(|(album_id, tracks)| make_album_group(&albums[&album_id], &tracks))
             .collect();
 
         let mut library = Library::default();
@@ -569,6 +1070,174 @@ mod tests {
         library
     }
 
+    #[test]
+    fn merged_editions_map_to_their_group() {
+        let standard = make_named_album("a1", "Record", "Band");
+        let deluxe = make_named_album("a2", "Record (Deluxe)", "Band");
+        let track_map = HashMap::from([
+            (
+                TrackId("t1".into()),
+                make_named_track("t1", "One", "Band", "a1"),
+            ),
+            (
+                TrackId("t2".into()),
+                make_named_track("t2", "Bonus", "Band", "a2"),
+            ),
+        ]);
+        let merged = Arc::new(Group {
+            edition_album_ids: vec![deluxe.id.clone()],
+            ..(*make_album_group(&standard, &["t1", "t2"])).clone()
+        });
+        let albums = HashMap::from([
+            (standard.id.clone(), standard.clone()),
+            (deluxe.id.clone(), deluxe.clone()),
+        ]);
+
+        let mut lib = Library::default();
+        lib.populate(
+            vec![],
+            track_map,
+            vec![merged],
+            albums,
+            SortOrder::Alphabetical,
+        );
+
+        assert_eq!(lib.album_to_group_index.get(&standard.id), Some(&0));
+        assert_eq!(lib.album_to_group_index.get(&deluxe.id), Some(&0));
+        assert_eq!(lib.shown_group_index(&deluxe.id), Some(0));
+        lib.set_hide_unstarred(true);
+        assert_eq!(lib.shown_group_index(&deluxe.id), None);
+    }
+
+    #[test]
+    fn group_gain_prefers_album_gain_over_track_gains() {
+        let mut lib = build_library(&[
+            ("t1", "One", "Artist", "a1", "Album One"),
+            ("t2", "Two", "Artist", "a1", "Album One"),
+        ]);
+        let set_gain = |lib: &mut Library, tid: &str, track_gain, album_gain| {
+            lib.track_map
+                .get_mut(&TrackId(tid.into()))
+                .unwrap()
+                .replay_gain = Some(blackbird_subsonic::ReplayGain {
+                track_gain,
+                album_gain,
+                ..Default::default()
+            });
+        };
+        let group = lib.groups[0].clone();
+        assert_eq!(lib.group_gain(&group), None);
+
+        set_gain(&mut lib, "t1", Some(-9.14), None);
+        set_gain(&mut lib, "t2", Some(-6.0), None);
+        let gain = lib.group_gain(&group).unwrap();
+        assert_eq!(
+            gain,
+            GroupGain::Tracks {
+                min: -9.14,
+                max: -6.0
+            }
+        );
+        assert_eq!(gain.to_string(), "-9.1 to -6.0 dB");
+
+        set_gain(&mut lib, "t2", Some(-6.0), Some(-7.5));
+        let gain = lib.group_gain(&group).unwrap();
+        assert_eq!(gain, GroupGain::Album(-7.5));
+        assert_eq!(gain.to_string(), "-7.5 dB");
+    }
+
+    #[test]
+    fn iterators_follow_the_sorted_order() {
+        let lib = build_library(&[
+            ("t1", "One", "Beta", "a2", "Second"),
+            ("t2", "Two", "Alpha", "a1", "First"),
+            ("t3", "Three", "Alpha", "a1", "First"),
+        ]);
+
+        let albums: Vec<&str> = lib.iter_groups().map(|g| g.album.as_str()).collect();
+        assert_eq!(albums, ["First", "Second"]);
+        let titles: Vec<&str> = lib
+            .iter_tracks_in_order()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Two", "Three", "One"]);
+    }
+
+    #[test]
+    fn track_by_number_matches_the_disc_when_given() {
+        let mut lib = build_library(&[
+            ("t1", "One", "Artist", "a1", "Album One"),
+            ("t2", "Two", "Artist", "a1", "Album One"),
+            ("t3", "Three", "Artist", "a1", "Album One"),
+        ]);
+        for (tid, disc, number) in [("t1", None, 1), ("t2", Some(1), 2), ("t3", Some(2), 2)] {
+            let track = lib.track_map.get_mut(&TrackId(tid.into())).unwrap();
+            track.disc_number = disc;
+            track.track = Some(number);
+        }
+
+        let album_id = AlbumId("a1".into());
+        let find = |s: &str| {
+            lib.track_by_number(&album_id, TrackNumber::parse(s)?)
+                .map(|id| id.0.as_str())
+        };
+        assert_eq!(find("1"), Some("t1"));
+        assert_eq!(find("2"), Some("t2"));
+        assert_eq!(find("2.2"), Some("t3"));
+        assert_eq!(find("1.1"), Some("t1"));
+        assert_eq!(find("3"), None);
+        assert_eq!(find("2."), None);
+    }
+
+    #[test]
+    fn group_for_track_finds_the_album_of_a_track() {
+        let lib = build_library(&[
+            ("t1", "One", "Artist", "a1", "Album One"),
+            ("t2", "Two", "Artist", "a2", "Album Two"),
+            ("t3", "Three", "Artist", "a2", "Album Two"),
+        ]);
+
+        let album_of = |tid: &str| {
+            lib.group_for_track(&TrackId(tid.into()))
+                .map(|group| group.album_id.0.as_str())
+        };
+        assert_eq!(album_of("t1"), Some("a1"));
+        assert_eq!(album_of("t3"), Some("a2"));
+        assert_eq!(album_of("missing"), None);
+    }
+
+    #[test]
+    fn hiding_unstarred_tracks_keeps_only_groups_with_starred_tracks() {
+        let mut lib = build_library(&[
+            ("t1", "One", "Artist", "a1", "Album One"),
+            ("t2", "Two", "Artist", "a2", "Album Two"),
+            ("t3", "Three", "Artist", "a2", "Album Two"),
+        ]);
+        lib.set_track_starred(&TrackId("t3".into()), true);
+        let shown = |lib: &Library| -> Vec<Vec<String>> {
+            lib.shown_groups()
+                .iter()
+                .map(|g| g.tracks.iter().map(|id| id.0.to_string()).collect())
+                .collect()
+        };
+        assert_eq!(shown(&lib), [vec!["t1"], vec!["t2", "t3"]]);
+
+        lib.set_hide_unstarred(true);
+        assert_eq!(shown(&lib), [vec!["t3"]]);
+        assert_eq!(lib.shown_group_index(&AlbumId("a1".into())), None);
+        assert_eq!(lib.shown_group_index(&AlbumId("a2".into())), Some(0));
+        assert!(!lib.is_track_shown(&TrackId("t2".into())));
+        assert!(lib.is_track_shown(&TrackId("t3".into())));
+
+        // Starring a track while hidden brings its group back.
+        lib.set_track_starred(&TrackId("t1".into()), true);
+        assert_eq!(shown(&lib), [vec!["t1"], vec!["t3"]]);
+        assert_eq!(lib.shown_group_index(&AlbumId("a2".into())), Some(1));
+
+        lib.set_hide_unstarred(false);
+        assert_eq!(shown(&lib), [vec!["t1"], vec!["t2", "t3"]]);
+    }
+
     fn search_ids(library: &mut Library, query: &str) -> Vec<String> {
         library.search(query).into_iter().map(|id| id.0).collect()
     }
@@ -684,4 +1353,256 @@ mod tests {
         let mut lib = build_library(&[("t1", "Hello World", "Artist", "a1", "Album")]);
         assert!(search_ids(&mut lib, "xyz").is_empty());
     }
+
+    #[test]
+    fn cover_art_fallbacks_are_found_by_album_id() {
+        let mut lib = build_library(&[("t1", "Hello World", "Artist", "a1", "Album")]);
+        let album_art = CoverArtId("a1".into());
+        let track_art = CoverArtId("t1".into());
+        lib.groups[0] = Arc::new(Group {
+            cover_art_id: Some(album_art.clone()),
+            cover_art_fallbacks: vec![track_art.clone()],
+            ..(*lib.groups[0]).clone()
+        });
+
+        assert_eq!(
+            lib.cover_art_fallbacks(&album_art),
+            std::slice::from_ref(&track_art)
+        );
+        // Only the first ID in the chain has fallbacks.
+        assert!(lib.cover_art_fallbacks(&track_art).is_empty());
+        assert!(
+            lib.cover_art_fallbacks(&CoverArtId("other".into()))
+                .is_empty()
+        );
+    }
+
+    /// Checks that the incrementally updated structures match those built
+    /// from scratch for the same groups.
+    fn assert_matches_full_rebuild(library: &Library) {
+        let mut rebuilt = Library::default();
+        rebuilt.populate(
+            vec![],
+            library.track_map.clone(),
+            library.groups.clone(),
+            library.albums.clone(),
+            SortOrder::Alphabetical,
+        );
+        let albums = |lib: &Library| -> Vec<AlbumId> {
+            lib.groups.iter().map(|g| g.album_id.clone()).collect()
+        };
+        assert_eq!(albums(library), albums(&rebuilt), "group order");
+        assert_eq!(library.track_ids, rebuilt.track_ids);
+        assert_eq!(library.album_to_group_index, rebuilt.album_to_group_index);
+        assert_eq!(library.track_to_group_index, rebuilt.track_to_group_index);
+        assert_eq!(
+            library.track_to_group_track_index,
+            rebuilt.track_to_group_track_index
+        );
+        assert_eq!(library.word_index, rebuilt.word_index);
+    }
+
+    fn group_artists(library: &Library) -> Vec<&str> {
+        library.groups.iter().map(|g| g.artist.as_str()).collect()
+    }
+
+    fn delta_library() -> Library {
+        build_library(&[
+            ("t1", "First", "Alpha", "a1", "Album A"),
+            ("t2", "Second", "Alpha", "a1", "Album A"),
+            ("t3", "Third", "Charlie", "a3", "Album C"),
+            ("t4", "Fourth", "Echo", "a5", "Album E"),
+        ])
+    }
+
+    #[test]
+    fn apply_delta_adds_albums_in_sorted_position() {
+        let mut lib = delta_library();
+        let new_album = make_named_album("a4", "Album D", "Delta");
+        lib.apply_delta(
+            LibraryDelta {
+                groups: vec![make_album_group(&new_album, &["t5", "t6"])],
+                albums: vec![new_album],
+                tracks: vec![
+                    make_named_track("t5", "Fifth", "Delta", "a4"),
+                    make_named_track("t6", "Sixth", "Delta", "a4"),
+                ],
+                ..Default::default()
+            },
+            SortOrder::Alphabetical,
+        );
+
+        assert_eq!(group_artists(&lib), ["Alpha", "Charlie", "Delta", "Echo"]);
+        assert_matches_full_rebuild(&lib);
+        assert_eq!(search_ids(&mut lib, "sixth"), vec!["t6"]);
+        assert_eq!(search_ids(&mut lib, "fourth"), vec!["t4"]);
+    }
+
+    #[test]
+    fn apply_delta_removes_albums_and_tracks() {
+        let mut lib = delta_library();
+        lib.apply_delta(
+            LibraryDelta {
+                removed_albums: vec![AlbumId("a3".into())],
+                removed_tracks: vec![TrackId("t1".into())],
+                ..Default::default()
+            },
+            SortOrder::Alphabetical,
+        );
+
+        assert_eq!(group_artists(&lib), ["Alpha", "Echo"]);
+        assert_eq!(lib.groups[0].tracks, [TrackId("t2".into())]);
+        assert!(!lib.track_map.contains_key(&TrackId("t3".into())));
+        assert!(!lib.albums.contains_key(&AlbumId("a3".into())));
+        assert_matches_full_rebuild(&lib);
+        assert!(search_ids(&mut lib, "third").is_empty());
+        assert!(search_ids(&mut lib, "first").is_empty());
+
+        // Removing an album's last track removes its group.
+        lib.apply_delta(
+            LibraryDelta {
+                removed_tracks: vec![TrackId("t2".into())],
+                ..Default::default()
+            },
+            SortOrder::Alphabetical,
+        );
+        assert_eq!(group_artists(&lib), ["Echo"]);
+        assert_matches_full_rebuild(&lib);
+    }
+
+    #[test]
+    fn apply_delta_renames_tracks_and_albums() {
+        let mut lib = delta_library();
+        lib.apply_delta(
+            LibraryDelta {
+                tracks: vec![make_named_track("t3", "Renamed", "Charlie", "a3")],
+                ..Default::default()
+            },
+            SortOrder::Alphabetical,
+        );
+        assert!(search_ids(&mut lib, "third").is_empty());
+        assert_eq!(search_ids(&mut lib, "renamed"), vec!["t3"]);
+        assert_matches_full_rebuild(&lib);
+
+        // Renaming an album's artist moves its group.
+        let renamed = make_named_album("a1", "Album A", "Foxtrot");
+        lib.apply_delta(
+            LibraryDelta {
+                groups: vec![make_album_group(&renamed, &["t1", "t2"])],
+                albums: vec![renamed],
+                tracks: vec![
+                    make_named_track("t1", "First", "Foxtrot", "a1"),
+                    make_named_track("t2", "Second", "Foxtrot", "a1"),
+                ],
+                ..Default::default()
+            },
+            SortOrder::Alphabetical,
+        );
+        assert_eq!(group_artists(&lib), ["Charlie", "Echo", "Foxtrot"]);
+        assert_matches_full_rebuild(&lib);
+        let mut found = search_ids(&mut lib, "foxtrot");
+        found.sort();
+        assert_eq!(found, vec!["t1", "t2"]);
+        assert!(search_ids(&mut lib, "alpha").is_empty());
+    }
+
+    #[test]
+    fn insert_tracks_joins_their_album_or_adds_it() {
+        let mut lib = delta_library();
+        lib.insert_tracks(
+            vec![make_named_track("t0", "Zeroth", "Alpha", "a1")],
+            None,
+            false,
+            SortOrder::Alphabetical,
+        );
+        assert_eq!(lib.groups.len(), 3);
+        assert_eq!(lib.groups[0].tracks.len(), 3);
+        assert_eq!(lib.track_to_group_index[&TrackId("t0".into())], 0);
+
+        lib.insert_tracks(
+            vec![
+                make_named_track("t5", "Fifth", "Delta", "a4"),
+                make_named_track("t6", "Sixth", "Delta", "a4"),
+            ],
+            Some(make_named_album("a4", "Album D", "Delta")),
+            false,
+            SortOrder::Alphabetical,
+        );
+        assert_eq!(group_artists(&lib), ["Alpha", "Charlie", "Delta", "Echo"]);
+        assert_eq!(lib.groups[2].tracks.len(), 2);
+        assert_matches_full_rebuild(&lib);
+        assert_eq!(search_ids(&mut lib, "fifth"), vec!["t5"]);
+
+        // Without its album, a track has nowhere to go.
+        lib.insert_tracks(
+            vec![make_named_track("t7", "Seventh", "Golf", "a6")],
+            None,
+            false,
+            SortOrder::Alphabetical,
+        );
+        assert!(!lib.track_map.contains_key(&TrackId("t7".into())));
+    }
+
+    #[test]
+    fn insert_tracks_keeps_the_artists_sort_name() {
+        let mut lib = delta_library();
+        lib.groups = lib
+            .groups
+            .iter()
+            .map(|group| {
+                Arc::new(Group {
+                    sort_artist: format!("{} sort", group.artist).into(),
+                    ..(**group).clone()
+                })
+            })
+            .collect();
+        lib.insert_tracks(
+            vec![make_named_track("t5", "Fifth", "Charlie", "a4")],
+            Some(make_named_album("a4", "Album D", "Charlie")),
+            false,
+            SortOrder::Alphabetical,
+        );
+        let group = &lib.groups[lib.album_to_group_index[&AlbumId("a4".into())]];
+        assert_eq!(group.sort_artist, "Charlie sort");
+    }
+
+    #[test]
+    fn insert_tracks_merges_a_new_edition_into_its_group() {
+        let timed = |tid, title, aid, number, duration| Track {
+            track: Some(number),
+            duration: Some(duration),
+            ..make_named_track(tid, title, "Band", aid)
+        };
+        let standard = make_named_album("a1", "Record", "Band");
+        let mut lib = Library::default();
+        lib.populate(
+            vec![],
+            HashMap::from([(TrackId("t1".into()), timed("t1", "One", "a1", 1, 100))]),
+            vec![Arc::new(Group {
+                duration: 100,
+                ..(*make_album_group(&standard, &["t1"])).clone()
+            })],
+            HashMap::from([(standard.id.clone(), standard)]),
+            SortOrder::Alphabetical,
+        );
+
+        lib.insert_tracks(
+            vec![
+                timed("t2", "One", "a2", 1, 100),
+                timed("t3", "Bonus", "a2", 2, 50),
+            ],
+            Some(make_named_album("a2", "Record (Deluxe)", "Band")),
+            true,
+            SortOrder::Alphabetical,
+        );
+
+        assert_eq!(lib.groups.len(), 1);
+        let group = &lib.groups[0];
+        assert_eq!(group.album_id, AlbumId("a1".into()));
+        assert_eq!(group.edition_album_ids, [AlbumId("a2".into())]);
+        assert_eq!(group.tracks, [TrackId("t1".into()), TrackId("t3".into())]);
+        assert_eq!(group.duration, 150);
+        assert!(!lib.track_map.contains_key(&TrackId("t2".into())));
+        assert_eq!(lib.album_to_group_index[&AlbumId("a2".into())], 0);
+    }
 }
@@ -0,0 +1,296 @@
+//! The local listen history: once a scrobbled listen ends, it is appended to
+//! an NDJSON file in the data directory along with how long it was listened
+//! to. [`ListeningStats`] are aggregated from it.
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use blackbird_state::TrackId;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use tokio::io::AsyncWriteExt as _;
+
+use crate::scrobble::Scrobble;
+
+/// How many entries each of the top lists holds.
+const TOP_COUNT: usize = 10;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Returns the path of the listen history file.
+pub fn path() -> PathBuf {
+    blackbird_shared::paths::data_dir().join("history.ndjson")
+}
+
+/// A single listen, as stored in the history file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub track_id: TrackId,
+    pub title: SmolStr,
+    pub artist: SmolStr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub album: Option<SmolStr>,
+    /// How long the track was listened to, skipping any parts seeked past.
+    pub listened_secs: u32,
+    /// When the listen started, in seconds since the Unix epoch.
+    pub played_at: u64,
+}
+impl HistoryEntry {
+    /// Builds the entry for a listen of `scrobble` that lasted `listened`.
+    pub fn new(scrobble: &Scrobble, listened: Duration) -> Self {
+        Self {
+            track_id: scrobble.track_id.clone(),
+            title: scrobble.title.clone(),
+            artist: scrobble.artist.clone(),
+            album: scrobble.release.clone(),
+            listened_secs: listened.as_secs() as u32,
+            played_at: scrobble.timestamp_ms / 1000,
+        }
+    }
+}
+
+/// Why the listen history could not be read or written.
+#[derive(Debug)]
+pub enum HistoryError {
+    /// An entry could not be serialized.
+    Serialize(serde_json::Error),
+    /// The history file could not be read or written.
+    Io(std::io::Error),
+}
+impl std::fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryError::Serialize(e) => write!(f, "serialize listen: {e}"),
+            HistoryError::Io(e) => write!(f, "access listen history: {e}"),
+        }
+    }
+}
+impl std::error::Error for HistoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HistoryError::Serialize(e) => Some(e),
+            HistoryError::Io(e) => Some(e),
+        }
+    }
+}
+impl From<serde_json::Error> for HistoryError {
+    fn from(e: serde_json::Error) -> Self {
+        HistoryError::Serialize(e)
+    }
+}
+impl From<std::io::Error> for HistoryError {
+    fn from(e: std::io::Error) -> Self {
+        HistoryError::Io(e)
+    }
+}
+
+/// Reads the listen history at `path`, skipping any lines that can't be
+/// parsed. A missing file is an empty history.
+pub async fn read(path: &Path) -> Result<Vec<HistoryEntry>, HistoryError> {
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Appends `entry` to the listen history at `path`, creating it if needed.
+pub async fn append(path: &Path, entry: &HistoryEntry) -> Result<(), HistoryError> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(line.as_bytes()).await?;
+    Ok(())
+}
+
+/// Totals aggregated from the listen history.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListeningStats {
+    pub total_plays: usize,
+    /// Time spent listening over the last 7 days.
+    pub week_listening_time: Duration,
+    /// Time spent listening over the last 30 days.
+    pub month_listening_time: Duration,
+    /// The most played artists, with their play counts.
+    pub top_artists: Vec<(SmolStr, usize)>,
+    /// The most played albums as `(album, artist)`, with their play counts.
+    pub top_albums: Vec<((SmolStr, SmolStr), usize)>,
+    /// The most played tracks as `(title, artist)`, with their play counts.
+    pub top_tracks: Vec<((SmolStr, SmolStr), usize)>,
+    /// How many consecutive days, ending today or yesterday, had a play.
+    pub streak_days: u32,
+}
+impl ListeningStats {
+    /// Aggregates `entries` as of `now`, in seconds since the Unix epoch. Days
+    /// are counted in UTC.
+    pub fn aggregate(entries: &[HistoryEntry], now: u64) -> Self {
+        let listening_time_since = |since: u64| {
+            Duration::from_secs(
+                entries
+                    .iter()
+                    .filter(|e| e.played_at >= since)
+                    .map(|e| u64::from(e.listened_secs))
+                    .sum(),
+            )
+        };
+
+        let mut artists = HashMap::new();
+        let mut albums = HashMap::new();
+        let mut tracks = HashMap::new();
+        for entry in entries {
+            *artists.entry(entry.artist.clone()).or_default() += 1;
+            if let Some(album) = &entry.album {
+                *albums
+                    .entry((album.clone(), entry.artist.clone()))
+                    .or_default() += 1;
+            }
+            *tracks
+                .entry((entry.title.clone(), entry.artist.clone()))
+                .or_default() += 1;
+        }
+
+        Self {
+            total_plays: entries.len(),
+            week_listening_time: listening_time_since(now.saturating_sub(7 * SECS_PER_DAY)),
+            month_listening_time: listening_time_since(now.saturating_sub(30 * SECS_PER_DAY)),
+            top_artists: top(artists),
+            top_albums: top(albums),
+            top_tracks: top(tracks),
+            streak_days: streak_days(entries, now),
+        }
+    }
+}
+
+/// The [`TOP_COUNT`] most played keys, breaking ties by key.
+fn top<K: Ord>(counts: HashMap<K, usize>) -> Vec<(K, usize)> {
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|(a_key, a), (b_key, b)| b.cmp(a).then_with(|| a_key.cmp(b_key)));
+    counts.truncate(TOP_COUNT);
+    counts
+}
+
+/// Counts the consecutive days with a play, ending today or, if nothing has
+/// been played yet today, yesterday.
+fn streak_days(entries: &[HistoryEntry], now: u64) -> u32 {
+    let days: HashSet<u64> = entries.iter().map(|e| e.played_at / SECS_PER_DAY).collect();
+    let today = now / SECS_PER_DAY;
+    let Some(mut day) = [today, today.saturating_sub(1)]
+        .into_iter()
+        .find(|day| days.contains(day))
+    else {
+        return 0;
+    };
+    let mut streak = 0;
+    while days.contains(&day) {
+        streak += 1;
+        let Some(previous) = day.checked_sub(1) else {
+            break;
+        };
+        day = previous;
+    }
+    streak
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn entry(title: &str, artist: &str, album: &str, days_ago: u64) -> HistoryEntry {
+        HistoryEntry {
+            track_id: TrackId(title.into()),
+            title: title.into(),
+            artist: artist.into(),
+            album: Some(album.into()),
+            listened_secs: 100,
+            played_at: NOW - days_ago * SECS_PER_DAY,
+        }
+    }
+
+    #[test]
+    fn empty_history_has_empty_stats() {
+        assert_eq!(
+            ListeningStats::aggregate(&[], NOW),
+            ListeningStats::default()
+        );
+    }
+
+    #[test]
+    fn aggregates_listening_time_top_lists_and_streak() {
+        let entries = [
+            entry("a", "Artist 1", "Album 1", 0),
+            entry("a", "Artist 1", "Album 1", 1),
+            entry("b", "Artist 1", "Album 1", 2),
+            entry("c", "Artist 2", "Album 2", 10),
+            entry("c", "Artist 2", "Album 2", 60),
+        ];
+        let stats = ListeningStats::aggregate(&entries, NOW);
+
+        assert_eq!(stats.total_plays, 5);
+        assert_eq!(stats.week_listening_time, Duration::from_secs(300));
+        assert_eq!(stats.month_listening_time, Duration::from_secs(400));
+        assert_eq!(
+            stats.top_artists,
+            vec![("Artist 1".into(), 3), ("Artist 2".into(), 2)]
+        );
+        assert_eq!(
+            stats.top_albums[0],
+            (("Album 1".into(), "Artist 1".into()), 3)
+        );
+        assert_eq!(
+            stats.top_tracks,
+            vec![
+                (("a".into(), "Artist 1".into()), 2),
+                (("c".into(), "Artist 2".into()), 2),
+                (("b".into(), "Artist 1".into()), 1),
+            ]
+        );
+        assert_eq!(stats.streak_days, 3);
+    }
+
+    #[tokio::test]
+    async fn appended_entries_read_back_skipping_bad_lines() {
+        let dir = std::env::temp_dir().join(format!(
+            "blackbird-listen-history-test-{}",
+            std::process::id()
+        ));
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        let path = dir.join("history.ndjson");
+        assert!(read(&path).await.unwrap().is_empty());
+
+        let first = entry("a", "A", "A", 1);
+        let second = entry("b", "B", "B", 0);
+        append(&path, &first).await.unwrap();
+        let mut file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap();
+        file.write_all(b"not json\n").await.unwrap();
+        append(&path, &second).await.unwrap();
+        assert_eq!(read(&path).await.unwrap(), [first, second]);
+
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[test]
+    fn streak_survives_until_the_end_of_the_next_day() {
+        let entries = [entry("a", "A", "A", 1), entry("a", "A", "A", 2)];
+        assert_eq!(streak_days(&entries, NOW), 2);
+        assert_eq!(streak_days(&entries, NOW + 2 * SECS_PER_DAY), 0);
+    }
+}
@@ -0,0 +1,378 @@
+//! Fan-out of the decoded stream to extra output devices, e.g. to monitor
+//! through headphones while also playing on speakers.
+//!
+//! The primary device pulls from [`FanOut`], which copies every sample into
+//! a [`MirrorBuffer`] per extra output. Each extra device pulls from its own
+//! [`MirrorSource`]. Device clocks drift and pull in differently sized
+//! callbacks, so every mirror keeps roughly [`MIRROR_LATENCY`] of audio
+//! buffered, skipping ahead when it falls behind and re-priming after an
+//! underrun. While any mirror is attached, the primary output is delayed by
+//! the same amount so that all outputs stay in sync; attaching the first
+//! mirror therefore inserts a short gap, and detaching the last one skips
+//! ahead by the same amount.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
+
+use rodio::{ChannelCount, SampleRate, Source, source::SeekError};
+
+/// How far behind the decoded stream every output plays while mirroring.
+/// Large enough to absorb differences in device callback sizes.
+pub const MIRROR_LATENCY: Duration = Duration::from_millis(100);
+
+/// A mirror that has buffered this many multiples of [`MIRROR_LATENCY`] has
+/// fallen behind and skips ahead.
+const MAX_LATENCY_FACTOR: usize = 3;
+
+/// Frames copied to the mirrors at a time, to avoid taking their locks on
+/// every sample.
+const BATCH_FRAMES: usize = 256;
+
+/// Samples from the primary output queued for one extra output.
+pub struct MirrorBuffer {
+    samples: VecDeque<f32>,
+    channels: usize,
+    /// Buffer length, in samples, to fill to before playing.
+    target_len: usize,
+    /// Samples still to emit for the current frame.
+    in_frame: usize,
+    /// Whether the current frame is silence because the buffer ran dry.
+    silent_frame: bool,
+    /// Whether the buffer is filling back up to `target_len`.
+    priming: bool,
+}
+
+impl MirrorBuffer {
+    pub fn new(channels: ChannelCount, sample_rate: SampleRate) -> Self {
+        let channels = channels.get() as usize;
+        let frames = (sample_rate.get() as f32 * MIRROR_LATENCY.as_secs_f32()) as usize;
+        Self {
+            samples: VecDeque::new(),
+            channels,
+            target_len: frames.max(1) * channels,
+            in_frame: 0,
+            silent_frame: false,
+            priming: true,
+        }
+    }
+
+    /// Queues whole frames of samples.
+    pub fn push(&mut self, samples: &[f32]) {
+        self.samples.extend(samples);
+        if self.samples.len() > self.target_len * MAX_LATENCY_FACTOR {
+            // Fallen behind the primary output: skip ahead, keeping whole
+            // frames and the rest of the frame being played.
+            let start = if self.silent_frame { 0 } else { self.in_frame };
+            let excess = self.samples.len() - self.target_len;
+            let excess = excess - excess % self.channels;
+            self.samples.drain(start..start + excess);
+        }
+    }
+
+    /// The next sample to play, or silence while priming.
+    pub fn pop(&mut self) -> f32 {
+        if self.in_frame == 0 {
+            if self.samples.len() >= self.target_len {
+                self.priming = false;
+            } else if self.samples.len() < self.channels {
+                // Underrun: wait until the latency is built back up.
+                self.priming = true;
+            }
+            self.silent_frame = self.priming;
+            self.in_frame = self.channels;
+        }
+        self.in_frame -= 1;
+        if self.silent_frame {
+            0.0
+        } else {
+            self.samples.pop_front().unwrap_or(0.0)
+        }
+    }
+}
+
+/// One extra output: its queued samples and volume.
+pub struct OutputTap {
+    buffer: Mutex<MirrorBuffer>,
+    /// Linear volume, stored as `f32::to_bits`.
+    volume_bits: AtomicU32,
+}
+
+impl OutputTap {
+    /// Sets the linear volume of this output, relative to the main volume.
+    pub fn set_volume(&self, volume: f32) {
+        self.volume_bits.store(volume.to_bits(), Ordering::Relaxed);
+    }
+
+    fn volume(&self) -> f32 {
+        f32::from_bits(self.volume_bits.load(Ordering::Relaxed))
+    }
+}
+
+/// The extra outputs attached to a [`FanOut`]. Cheap to clone.
+#[derive(Clone, Default)]
+pub struct OutputTaps(Arc<Mutex<Vec<Arc<OutputTap>>>>);
+
+impl OutputTaps {
+    /// Attaches a new output fed with `channels`/`sample_rate` audio, and
+    /// returns the source for its device to play.
+    pub fn add(&self, channels: ChannelCount, sample_rate: SampleRate) -> MirrorSource {
+        let tap = Arc::new(OutputTap {
+            buffer: Mutex::new(MirrorBuffer::new(channels, sample_rate)),
+            volume_bits: AtomicU32::new(1.0f32.to_bits()),
+        });
+        self.0.lock().unwrap().push(tap.clone());
+        MirrorSource {
+            tap,
+            channels,
+            sample_rate,
+        }
+    }
+
+    /// Detaches the output fed to `source`.
+    pub fn remove(&self, source: &Arc<OutputTap>) {
+        self.0
+            .lock()
+            .unwrap()
+            .retain(|tap| !Arc::ptr_eq(tap, source));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+}
+
+/// Wraps the primary output's source, copying its samples to every attached
+/// [`OutputTaps`] entry. `input` must have a fixed channel count and sample
+/// rate, e.g. by being converted to the primary device's format first.
+pub struct FanOut<S> {
+    input: S,
+    taps: OutputTaps,
+    batch: Vec<f32>,
+    /// Whether any mirror was attached at the last batch.
+    mirroring: bool,
+    /// Delays the primary output by [`MIRROR_LATENCY`] while mirroring, so
+    /// it plays in step with the extra outputs.
+    delay: VecDeque<f32>,
+    delay_len: usize,
+}
+
+impl<S: Source<Item = f32>> FanOut<S> {
+    pub fn new(input: S, taps: OutputTaps) -> Self {
+        let frames = (input.sample_rate().get() as f32 * MIRROR_LATENCY.as_secs_f32()) as usize;
+        let delay_len = frames * input.channels().get() as usize;
+        let mirroring = !taps.is_empty();
+        Self {
+            input,
+            taps,
+            batch: vec![],
+            mirroring,
+            delay: VecDeque::new(),
+            delay_len,
+        }
+    }
+
+    fn flush_batch(&mut self) {
+        for tap in self.taps.0.lock().unwrap().iter() {
+            tap.buffer.lock().unwrap().push(&self.batch);
+        }
+        self.batch.clear();
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for FanOut<S> {
+    type Item = f32;
+
+    #[inline]
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.input.next()?;
+        let batch_len = BATCH_FRAMES * self.input.channels().get() as usize;
+        self.batch.push(sample);
+        if self.batch.len() >= batch_len {
+            // Mirrors are only picked up once per batch; the delay line
+            // follows along.
+            self.mirroring = !self.taps.is_empty();
+            self.flush_batch();
+            if !self.mirroring {
+                self.delay.clear();
+            }
+        }
+        if !self.mirroring {
+            return Some(sample);
+        }
+        self.delay.push_back(sample);
+        if self.delay.len() > self.delay_len {
+            self.delay.pop_front()
+        } else {
+            Some(0.0)
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Source for FanOut<S> {
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        self.input.current_span_len()
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    #[inline]
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.input.try_seek(pos)
+    }
+}
+
+/// The source played by an extra output device.
+pub struct MirrorSource {
+    tap: Arc<OutputTap>,
+    channels: ChannelCount,
+    sample_rate: SampleRate,
+}
+
+impl MirrorSource {
+    /// The tap feeding this source, for [`OutputTaps::remove`] and volume
+    /// changes.
+    pub fn tap(&self) -> Arc<OutputTap> {
+        self.tap.clone()
+    }
+}
+
+impl Iterator for MirrorSource {
+    type Item = f32;
+
+    #[inline]
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.tap.buffer.lock().unwrap().pop();
+        Some(sample * self.tap.volume())
+    }
+}
+
+impl Source for MirrorSource {
+    #[inline]
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline]
+    fn channels(&self) -> ChannelCount {
+        self.channels
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    #[inline]
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    #[inline]
+    fn try_seek(&mut self, _pos: Duration) -> Result<(), SeekError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rodio::{buffer::SamplesBuffer, math::nz};
+
+    use super::*;
+
+    /// A mirror at 100Hz keeps a 10-frame latency.
+    fn buffer(channels: ChannelCount) -> MirrorBuffer {
+        MirrorBuffer::new(channels, nz!(100))
+    }
+
+    #[test]
+    fn mirror_primes_to_the_target_latency_before_playing() {
+        let mut mirror = buffer(nz!(1));
+        let samples: Vec<f32> = (1..=10).map(|i| i as f32).collect();
+        mirror.push(&samples[..5]);
+        assert_eq!(mirror.pop(), 0.0, "still priming");
+        mirror.push(&samples[5..]);
+        for expected in 1..=10 {
+            assert_eq!(mirror.pop(), expected as f32);
+        }
+        // Underrun: silence until primed again.
+        assert_eq!(mirror.pop(), 0.0);
+        mirror.push(&[11.0]);
+        assert_eq!(mirror.pop(), 0.0);
+    }
+
+    #[test]
+    fn mirror_skips_ahead_in_whole_frames_when_behind() {
+        let mut mirror = buffer(nz!(2));
+        let samples: Vec<f32> = (1..=20).map(|i| i as f32).collect();
+        mirror.push(&samples);
+        // Start playing a frame, then fall far behind.
+        assert_eq!(mirror.pop(), 1.0);
+        let more: Vec<f32> = (21..=80).map(|i| i as f32).collect();
+        mirror.push(&more);
+        // The rest of the current frame is kept...
+        assert_eq!(mirror.pop(), 2.0);
+        // ...then playback resumes at the target latency, frame-aligned.
+        assert_eq!(mirror.samples.len(), 20);
+        assert_eq!(mirror.pop(), 61.0);
+        assert_eq!(mirror.pop(), 62.0);
+    }
+
+    #[test]
+    fn fan_out_keeps_two_outputs_in_step() {
+        // At 10kHz the latency is 1000 samples.
+        let samples: Vec<f32> = (1..=8000).map(|i| i as f32).collect();
+        let taps = OutputTaps::default();
+        let mut mirror = taps.add(nz!(1), nz!(10_000));
+        let mut primary = FanOut::new(SamplesBuffer::new(nz!(1), nz!(10_000), samples), taps);
+
+        // Both devices pull in small callbacks, taking turns.
+        let (mut primary_out, mut mirror_out) = (vec![], vec![]);
+        for _ in 0..100 {
+            primary_out.extend(primary.by_ref().take(64));
+            mirror_out.extend(mirror.by_ref().take(64));
+        }
+
+        // The primary is delayed by the latency...
+        assert!(primary_out[..1000].iter().all(|s| *s == 0.0));
+        assert_eq!(primary_out[1000], 1.0);
+        // ...and the mirror plays the same samples within a batch of it.
+        let start = mirror_out.iter().position(|s| *s != 0.0).unwrap();
+        assert!(
+            start.abs_diff(1000) <= BATCH_FRAMES,
+            "mirror starts at {start}"
+        );
+        assert_eq!(
+            mirror_out[start..start + 1000],
+            primary_out[1000..2000],
+            "mirror plays the primary's samples"
+        );
+    }
+
+    #[test]
+    fn fan_out_passes_through_without_mirrors() {
+        let taps = OutputTaps::default();
+        let mut primary = FanOut::new(SamplesBuffer::new(nz!(1), nz!(1000), vec![1.0, 2.0]), taps);
+        assert_eq!(primary.next(), Some(1.0));
+        assert_eq!(primary.next(), Some(2.0));
+    }
+}
@@ -35,6 +35,10 @@ use crate::playback_thread::{
 /// `UniformSourceIterator` rebootstraps quickly when a track is loaded.
 const SILENCE_SPAN_LEN: usize = 1024;
 
+/// How far into either end of a track, in seconds, silence is looked for
+/// when it is loaded. Bounds the decoding done up front.
+const MAX_SILENCE_SECS: usize = 30;
+
 /// A boxed source of `f32` samples that can cross thread boundaries.
 type BoxedSource = Box<dyn Source<Item = f32> + Send>;
 
@@ -44,6 +48,12 @@ type BoxedSource = Box<dyn Source<Item = f32> + Send>;
 struct LoadedTrack {
     track_id: TrackId,
     inner: rodio::source::TrackPosition<BoxedSource>,
+    /// Linear user gain for this track, applied on top of ReplayGain and
+    /// volume. Updated live via [`PlaybackController::set_track_gain`].
+    gain: f32,
+    /// The part of the track that is played when silence is skipped; the
+    /// whole track otherwise.
+    audible: AudibleSpan,
 }
 
 impl LoadedTrack {
@@ -55,6 +65,11 @@ impl LoadedTrack {
         self.inner.sample_rate()
     }
 
+    /// The track's length according to the decoder, if it can tell.
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration().filter(|d| !d.is_zero())
+    }
+
     fn position(&self) -> Duration {
         self.inner.get_pos()
     }
@@ -62,6 +77,31 @@ impl LoadedTrack {
     fn current_span_len(&self) -> Option<usize> {
         self.inner.current_span_len()
     }
+
+    /// Seeks to `pos`, or to the start of the audible part of the track if
+    /// that is later.
+    fn seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.inner.try_seek(pos.max(self.audible.start))
+    }
+
+    /// Pulls the next sample, or `None` once the source is exhausted or the
+    /// audible part of the track has been played out.
+    fn next_sample(&mut self) -> Option<f32> {
+        if self.past_audible_end() {
+            return None;
+        }
+        self.inner.next()
+    }
+
+    /// Whether every sample up to the end of the audible part of the track
+    /// has been emitted. Compared in whole frames, as the positions are
+    /// derived from sample counts through floating point.
+    fn past_audible_end(&self) -> bool {
+        self.audible.end.is_some_and(|end| {
+            let rate = f64::from(self.sample_rate().get());
+            (self.position().as_secs_f64() * rate).round() >= (end.as_secs_f64() * rate).round()
+        })
+    }
 }
 
 /// Shared mutable state between [`PlaybackController`] (mutated from the
@@ -78,6 +118,9 @@ struct State {
     /// Linear volume; squared from the user-facing 0..1 scale at the
     /// caller. Applied per sample.
     volume: f32,
+    /// Linear amplitude below which samples count as silence to be skipped
+    /// in tracks loaded from now on, or `None` to play everything.
+    silence_threshold: Option<f32>,
     /// Channel count and sample rate to report when no source is loaded,
     /// so `UniformSourceIterator` has plausible metadata for its silence
     /// span. Updated whenever a real source becomes current.
@@ -121,6 +164,7 @@ impl PlaybackController {
             paused: false,
             seek_request: None,
             volume,
+            silence_threshold: None,
             silence_channels: target_channels,
             silence_sample_rate: target_sample_rate,
             event_tx,
@@ -140,7 +184,8 @@ impl PlaybackController {
     /// `TrackStarted` and `PlaybackStateChanged` so the logic layer
     /// updates its UI.
     pub fn load_track(&self, track: TrackPlayback, mode: TrackLoadMode) -> Result<(), DecodeError> {
-        let loaded = decode_track(track, &self.replaygain)?;
+        let loaded = decode_track(track, &self.replaygain, self.silence_threshold())?;
+        let duration = loaded.total_duration();
         let (track_id, position, broadcast) = {
             let mut state = self.state.lock().unwrap();
             state.silence_channels = loaded.channels();
@@ -158,9 +203,12 @@ impl PlaybackController {
             (track_id, position, state.event_tx.clone())
         };
         let _ = broadcast.send(PlaybackToLogicMessage::TrackStarted(TrackAndPosition {
-            track_id,
+            track_id: track_id.clone(),
             position,
         }));
+        if let Some(duration) = duration {
+            let _ = broadcast.send(PlaybackToLogicMessage::DurationResolved(track_id, duration));
+        }
         let new_state = match mode {
             TrackLoadMode::Play => PlaybackState::Playing,
             TrackLoadMode::Paused(_) => PlaybackState::Paused,
@@ -172,7 +220,7 @@ impl PlaybackController {
     /// Stages `track` as the gapless next track. Replaces any previously
     /// staged next. Has no effect on the currently playing track.
     pub fn append_next(&self, track: TrackPlayback) -> Result<(), DecodeError> {
-        let loaded = decode_track(track, &self.replaygain)?;
+        let loaded = decode_track(track, &self.replaygain, self.silence_threshold())?;
         let mut state = self.state.lock().unwrap();
         state.next = Some(loaded);
         Ok(())
@@ -244,6 +292,24 @@ impl PlaybackController {
         }
     }
 
+    /// Pauses and rewinds the current track to its start, keeping it (and
+    /// any staged next track) loaded. Does nothing if no track is loaded.
+    pub fn reset_to_start(&self) {
+        let (track_id, broadcast) = {
+            let mut state = self.state.lock().unwrap();
+            let Some(track_id) = state.current.as_ref().map(|t| t.track_id.clone()) else {
+                return;
+            };
+            state.seek_request = Some(Duration::ZERO);
+            (track_id, state.event_tx.clone())
+        };
+        self.pause();
+        let _ = broadcast.send(PlaybackToLogicMessage::PositionChanged(TrackAndPosition {
+            track_id,
+            position: Duration::ZERO,
+        }));
+    }
+
     /// Records a seek to be applied on the next audio-thread poll.
     pub fn seek(&self, position: Duration) {
         let mut state = self.state.lock().unwrap();
@@ -257,6 +323,18 @@ impl PlaybackController {
         state.volume = volume;
     }
 
+    /// Sets the level, in dB, below which leading and trailing silence is
+    /// skipped, or disables skipping with `None`. Takes effect from the next
+    /// track loaded, as the silence is found while decoding it.
+    pub fn set_skip_silence(&self, threshold_db: Option<f32>) {
+        let mut state = self.state.lock().unwrap();
+        state.silence_threshold = threshold_db.map(db_to_linear);
+    }
+
+    fn silence_threshold(&self) -> Option<f32> {
+        self.state.lock().unwrap().silence_threshold
+    }
+
     /// Enables or disables ReplayGain for both the currently playing
     /// source and any future ones.
     pub fn set_replaygain_enabled(&self, enabled: bool) {
@@ -269,6 +347,19 @@ impl PlaybackController {
         self.replaygain.set_preamp_db(preamp_db);
     }
 
+    /// Sets the user gain in dB for `track_id` if it is currently playing
+    /// or staged as the gapless next track.
+    pub fn set_track_gain(&self, track_id: &TrackId, gain_db: f32) {
+        let gain = db_to_linear(gain_db);
+        let mut state = self.state.lock().unwrap();
+        let State { current, next, .. } = &mut *state;
+        for track in [current, next].into_iter().flatten() {
+            if &track.track_id == track_id {
+                track.gain = gain;
+            }
+        }
+    }
+
     /// Snapshots the currently playing track and its position, if any.
     /// Returns `None` when nothing is loaded.
     pub fn current_position(&self) -> Option<TrackAndPosition> {
@@ -306,7 +397,7 @@ impl Iterator for PlaybackSource {
         if let Some(pos) = state.seek_request.take()
             && let Some(t) = state.current.as_mut()
         {
-            let _ = t.inner.try_seek(pos);
+            let _ = t.seek(pos);
         }
 
         if state.paused {
@@ -318,8 +409,8 @@ impl Iterator for PlaybackSource {
             let Some(track) = state.current.as_mut() else {
                 return Some(0.0);
             };
-            if let Some(sample) = track.inner.next() {
-                return Some(sample * volume);
+            if let Some(sample) = track.next_sample() {
+                return Some(sample * volume * track.gain);
             }
             // Current source exhausted; advance to the staged next slot,
             // or transition to stopped silence if nothing is queued.
@@ -335,15 +426,21 @@ impl Iterator for PlaybackSource {
             };
             let track_id = next.track_id.clone();
             let position = next.position();
+            let duration = next.total_duration();
             state.silence_channels = next.channels();
             state.silence_sample_rate = next.sample_rate();
             state.current = Some(next);
             let _ = state
                 .event_tx
                 .send(PlaybackToLogicMessage::TrackStarted(TrackAndPosition {
-                    track_id,
+                    track_id: track_id.clone(),
                     position,
                 }));
+            if let Some(duration) = duration {
+                let _ = state
+                    .event_tx
+                    .send(PlaybackToLogicMessage::DurationResolved(track_id, duration));
+            }
             // Loop to pull a sample from the new current.
         }
     }
@@ -390,7 +487,7 @@ impl Source for PlaybackSource {
     fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
         let mut state = self.state.lock().unwrap();
         match state.current.as_mut() {
-            Some(t) => t.inner.try_seek(pos),
+            Some(t) => t.seek(pos),
             None => Ok(()),
         }
     }
@@ -434,20 +531,30 @@ impl std::error::Error for DecodeError {
     }
 }
 
+/// Decodes `track` for playback. When `silence_threshold` is set, the ends
+/// of the track are decoded up front to find its audible part, so that the
+/// audio thread only has to stop early rather than read ahead.
 fn decode_track(
     track: TrackPlayback,
     control: &ReplayGainControl,
+    silence_threshold: Option<f32>,
 ) -> Result<LoadedTrack, DecodeError> {
     let TrackPlayback {
         track_id,
         data,
         replaygain,
+        gain_db,
     } = track;
-    let decoder = rodio::decoder::DecoderBuilder::new()
-        .with_byte_len(data.len() as u64)
-        .with_data(std::io::Cursor::new(data))
-        .build();
-    let decoder = match decoder {
+    let audible = match silence_threshold {
+        // A separate decoder, so that the one played from doesn't depend on
+        // seeking back to where the analysis started.
+        Some(threshold) => match build_decoder(data.clone()) {
+            Ok(decoder) => find_audible_span(decoder, threshold),
+            Err(error) => return Err(DecodeError { track_id, error }),
+        },
+        None => AudibleSpan::default(),
+    };
+    let decoder = match build_decoder(data) {
         Ok(d) => d,
         Err(error) => return Err(DecodeError { track_id, error }),
     };
@@ -461,8 +568,85 @@ fn decode_track(
         }),
         None => Box::new(decoder),
     };
-    let inner = boxed.track_position();
-    Ok(LoadedTrack { track_id, inner })
+    let mut inner = boxed.track_position();
+    if !audible.start.is_zero() {
+        // Seeking through the position tracker keeps the reported position
+        // relative to the start of the file.
+        let _ = inner.try_seek(audible.start);
+    }
+    Ok(LoadedTrack {
+        track_id,
+        inner,
+        gain: db_to_linear(gain_db),
+        audible,
+    })
+}
+
+fn build_decoder(
+    data: Vec<u8>,
+) -> Result<rodio::Decoder<std::io::Cursor<Vec<u8>>>, rodio::decoder::DecoderError> {
+    rodio::decoder::DecoderBuilder::new()
+        .with_byte_len(data.len() as u64)
+        .with_data(std::io::Cursor::new(data))
+        .build()
+}
+
+/// The part of a track between its leading and trailing silence.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct AudibleSpan {
+    /// Where the first audible frame starts.
+    start: Duration,
+    /// Where the last audible frame ends, or `None` to play to the end.
+    end: Option<Duration>,
+}
+
+/// Finds where `source` first becomes audible (any channel at or above
+/// `threshold`) and where it falls silent for good, looking at most
+/// [`MAX_SILENCE_SECS`] into either end. Silence that runs longer is played.
+fn find_audible_span(mut source: impl Source<Item = f32>, threshold: f32) -> AudibleSpan {
+    let channels = source.channels().get() as usize;
+    let rate = source.sample_rate().get();
+    let max_frames = rate as usize * MAX_SILENCE_SECS;
+    let frames_to_time = |frames: usize| Duration::from_secs_f64(frames as f64 / f64::from(rate));
+    let mut frame = Vec::with_capacity(channels);
+    let mut next_frame = |source: &mut dyn Iterator<Item = f32>| {
+        frame.clear();
+        frame.extend(source.take(channels));
+        (frame.len() == channels).then(|| frame.iter().any(|s| s.abs() >= threshold))
+    };
+
+    let mut start = Duration::ZERO;
+    for index in 0..max_frames {
+        match next_frame(&mut source) {
+            Some(true) => {
+                start = frames_to_time(index);
+                break;
+            }
+            Some(false) => {}
+            // Silent throughout; there is nothing to skip to.
+            None => return AudibleSpan::default(),
+        }
+    }
+
+    let Some(total) = source.total_duration() else {
+        return AudibleSpan { start, end: None };
+    };
+    let tail = total.saturating_sub(frames_to_time(max_frames)).max(start);
+    if source.try_seek(tail).is_err() {
+        return AudibleSpan { start, end: None };
+    }
+    let mut frames = 0;
+    let mut audible_frames = 0;
+    while let Some(audible) = next_frame(&mut source) {
+        frames += 1;
+        if audible {
+            audible_frames = frames;
+        }
+    }
+    AudibleSpan {
+        start,
+        end: Some(tail + frames_to_time(audible_frames)),
+    }
 }
 
 /// Shared, lock-free settings read per sample by every queued
@@ -520,8 +704,7 @@ where
         let sample = self.input.next()?;
         if self.control.enabled.load(Ordering::Relaxed) {
             let preamp = f32::from_bits(self.control.preamp_linear_bits.load(Ordering::Relaxed));
-            let multiplier = (self.info.factor * preamp).min(self.info.inv_peak);
-            Some(sample * multiplier)
+            Some(sample * self.info.multiplier(preamp))
         } else {
             Some(sample)
         }
@@ -581,9 +764,21 @@ mod tests {
         LoadedTrack {
             track_id: TrackId(track_id.to_string()),
             inner: boxed.track_position(),
+            gain: 1.0,
+            audible: AudibleSpan::default(),
         }
     }
 
+    /// Builds a mono track with its silence found as [`decode_track`] does.
+    fn loaded_skipping_silence(track_id: &str, samples: Vec<f32>, sr: u32) -> LoadedTrack {
+        let buf = SamplesBuffer::new(nz!(1), SampleRate::new(sr).unwrap(), samples.clone());
+        let audible = find_audible_span(buf, db_to_linear(-40.0));
+        let mut track = loaded(track_id, samples, sr);
+        track.audible = audible;
+        track.seek(Duration::ZERO).unwrap();
+        track
+    }
+
     #[test]
     fn silence_when_no_source() {
         let (_ctrl, mut src) =
@@ -630,6 +825,71 @@ mod tests {
         assert_eq!(src.next(), Some(3.0));
     }
 
+    #[test]
+    fn reset_to_start_pauses_and_rewinds_current_track() {
+        let (ctrl, mut src) =
+            PlaybackController::new(nz!(1), nz!(48000), 1.0, false, 0.0, ev_channel());
+        {
+            let mut state = ctrl.state.lock().unwrap();
+            state.current = Some(loaded("a", vec![1.0, 2.0, 3.0], 48000));
+        }
+        assert_eq!(src.next(), Some(1.0));
+        assert_eq!(src.next(), Some(2.0));
+        ctrl.reset_to_start();
+        assert_eq!(src.next(), Some(0.0));
+        assert_eq!(ctrl.current_state(), PlaybackState::Paused);
+        ctrl.play();
+        assert_eq!(src.next(), Some(1.0));
+    }
+
+    #[test]
+    fn skip_silence_skips_leading_frames_and_trailing_silence() {
+        let (ctrl, mut src) =
+            PlaybackController::new(nz!(1), nz!(4), 1.0, false, 0.0, ev_channel());
+        {
+            let mut state = ctrl.state.lock().unwrap();
+            state.current = Some(loaded_skipping_silence(
+                "a",
+                vec![0.0, 0.0, 0.5, 0.0, 0.5, 0.0, 0.0],
+                4,
+            ));
+            state.next = Some(loaded("b", vec![0.25], 4));
+        }
+        // Leading silence is skipped; silence between audible samples is kept.
+        assert_eq!(src.next(), Some(0.5));
+        // The position counts from the start of the file, not the first
+        // audible sample.
+        assert_eq!(
+            ctrl.current_position().unwrap().position,
+            Duration::from_millis(750)
+        );
+        assert_eq!(src.next(), Some(0.0));
+        assert_eq!(src.next(), Some(0.5));
+        // Trailing silence is dropped, moving straight on to the next track.
+        assert_eq!(src.next(), Some(0.25));
+    }
+
+    #[test]
+    fn audible_span_keeps_frames_audible_in_any_channel() {
+        let buf = SamplesBuffer::new(nz!(2), nz!(4), vec![0.0, 0.0, 0.0, 0.5, 0.5, 0.0, 0.0, 0.0]);
+        assert_eq!(
+            find_audible_span(buf, db_to_linear(-40.0)),
+            AudibleSpan {
+                start: Duration::from_millis(250),
+                end: Some(Duration::from_millis(750)),
+            }
+        );
+    }
+
+    #[test]
+    fn audible_span_of_a_silent_track_is_the_whole_track() {
+        let buf = SamplesBuffer::new(nz!(1), nz!(4), vec![0.0; 8]);
+        assert_eq!(
+            find_audible_span(buf, db_to_linear(-40.0)),
+            AudibleSpan::default()
+        );
+    }
+
     #[test]
     fn metadata_reflects_new_source_after_transition() {
         let (ctrl, mut src) =
@@ -674,4 +934,18 @@ mod tests {
         assert_eq!(src.next(), Some(0.5));
         assert_eq!(src.next(), Some(1.0));
     }
+
+    #[test]
+    fn track_gain_scales_matching_track_only() {
+        let (ctrl, mut src) =
+            PlaybackController::new(nz!(1), nz!(48000), 1.0, false, 0.0, ev_channel());
+        {
+            let mut state = ctrl.state.lock().unwrap();
+            state.current = Some(loaded("a", vec![1.0], 48000));
+            state.next = Some(loaded("b", vec![1.0], 48000));
+        }
+        ctrl.set_track_gain(&TrackId("a".into()), -20.0);
+        assert!((src.next().unwrap() - 0.1).abs() < 1e-6);
+        assert_eq!(src.next(), Some(1.0));
+    }
 }
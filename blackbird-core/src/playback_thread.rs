@@ -2,10 +2,13 @@ use std::time::Duration;
 
 use blackbird_state::TrackId;
 
-use crate::app_state::TrackAndPosition;
+use crate::app_state::{PlaybackMode, TrackAndPosition};
 
 #[cfg(feature = "audio")]
-use crate::playback_source::PlaybackController;
+use rodio::cpal::traits::DeviceTrait as _;
+
+#[cfg(feature = "audio")]
+use crate::{output_fanout::FanOut, playback_source::PlaybackController};
 
 /// How a track should be loaded into the playback thread.
 #[derive(Debug, Clone, Copy)]
@@ -31,6 +34,16 @@ pub struct ReplayGainTrackInfo {
     pub inv_peak: f32,
 }
 
+impl ReplayGainTrackInfo {
+    /// The linear multiplier to apply with the given linear preamp: the
+    /// ReplayGain factor boosted by the preamp, limited so that
+    /// `multiplier * peak <= 1.0`.
+    #[cfg(any(feature = "audio", test))]
+    pub fn multiplier(&self, preamp_linear: f32) -> f32 {
+        (self.factor * preamp_linear).min(self.inv_peak)
+    }
+}
+
 /// A track's decoded-audio payload as sent to the playback thread.
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -41,6 +54,9 @@ pub struct TrackPlayback {
     /// and will be played back untouched (no preamp or clipping clamp
     /// applied).
     pub replaygain: Option<ReplayGainTrackInfo>,
+    /// User gain for this track in dB, applied on top of ReplayGain and
+    /// volume. Zero if the user has not set one.
+    pub gain_db: f32,
 }
 
 pub struct PlaybackThread {
@@ -76,7 +92,10 @@ pub enum LogicToPlaybackMessage {
     TogglePlayback,
     Play,
     Pause,
+    /// Stops playback and unloads the current and next tracks.
     StopPlayback,
+    /// Pauses and rewinds the current track to its start, keeping it loaded.
+    ResetToStart,
     Seek(Duration),
     /// Seek without debouncing. Used on scrub bar release to ensure the
     /// final position is always applied.
@@ -88,6 +107,26 @@ pub enum LogicToPlaybackMessage {
     /// Adjusts the ReplayGain preamp (in dB) for the currently playing
     /// source and any future ones.
     SetReplayGainPreamp(f32),
+    /// Adjusts the user gain (in dB) for a track, if it is playing or
+    /// staged as the next track.
+    SetTrackGain {
+        track_id: TrackId,
+        gain_db: f32,
+    },
+    /// Sets the level in dB below which leading and trailing silence is
+    /// skipped in tracks loaded from now on, or disables skipping with `None`.
+    SetSkipSilence(Option<f32>),
+    /// Opens the named output device and plays the same audio on it, in
+    /// sync with the main output.
+    AddOutput(String),
+    /// Stops playing on the named extra output device.
+    RemoveOutput(String),
+    /// Sets the volume of an extra output device, from 0 to 1 like
+    /// [`LogicToPlaybackMessage::SetVolume`], relative to the main volume.
+    SetOutputVolume {
+        device: String,
+        volume: f32,
+    },
     /// Sent during shutdown to exit the playback loop immediately. Needed
     /// because cloned `PlaybackThreadSendHandle`s in tokio tasks keep the
     /// channel open, so disconnect alone is not reliable.
@@ -95,6 +134,9 @@ pub enum LogicToPlaybackMessage {
 }
 
 pub type PlaybackToLogicRx = tokio::sync::broadcast::Receiver<PlaybackToLogicMessage>;
+/// Why [`PlaybackToLogicRx::try_recv`] returned no event. `Lagged` means
+/// events were dropped because the receiver fell behind.
+pub type PlaybackToLogicTryRecvError = tokio::sync::broadcast::error::TryRecvError;
 #[derive(Debug, Clone)]
 pub enum PlaybackToLogicMessage {
     TrackStarted(TrackAndPosition),
@@ -102,6 +144,13 @@ pub enum PlaybackToLogicMessage {
     PositionChanged(TrackAndPosition),
     TrackEnded,
     FailedToPlayTrack(TrackId, String),
+    /// The decoder worked out how long a track is, for tracks whose tags
+    /// don't say. Sent after [`Self::TrackStarted`] when known.
+    DurationResolved(TrackId, Duration),
+    /// The playback mode changed, from any source. Sent by the logic rather
+    /// than the playback thread, so integrations can follow the mode without
+    /// polling it.
+    PlaybackModeChanged(PlaybackMode),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -127,13 +176,14 @@ impl Drop for PlaybackThread {
 }
 
 impl PlaybackThread {
-    /// Creates a new playback thread with the given volume, ReplayGain
-    /// settings, and broadcast sender. The broadcast sender is used to send
+    /// Creates a new playback thread with the given volume, ReplayGain and
+    /// silence-skipping settings, and broadcast sender. The broadcast sender is used to send
     /// playback events back to the logic layer.
     pub fn new(
         volume: f32,
         apply_replaygain: bool,
         replaygain_preamp_db: f32,
+        skip_silence_db: Option<f32>,
         playback_to_logic_tx: tokio::sync::broadcast::Sender<PlaybackToLogicMessage>,
     ) -> Self {
         let (logic_to_playback_tx, logic_to_playback_rx) =
@@ -146,6 +196,7 @@ impl PlaybackThread {
                 volume,
                 apply_replaygain,
                 replaygain_preamp_db,
+                skip_silence_db,
             );
         });
 
@@ -174,6 +225,7 @@ impl PlaybackThread {
         volume: f32,
         apply_replaygain: bool,
         replaygain_preamp_db: f32,
+        skip_silence_db: Option<f32>,
     ) {
         use LogicToPlaybackMessage as LTPM;
         use PlaybackToLogicMessage as PTLM;
@@ -186,6 +238,14 @@ impl PlaybackThread {
         // Use a fixed buffer size to avoid underruns on machines where the
         // default ALSA buffer is too small for real-time resampling.
         let buffer_size = rodio::cpal::BufferSize::Fixed(2048);
+        let open_device = |device: rodio::cpal::Device| {
+            rodio::DeviceSinkBuilder::from_device(device).and_then(|builder| {
+                builder
+                    .with_buffer_size(buffer_size)
+                    .with_error_callback(error_callback as fn(_))
+                    .open_stream()
+            })
+        };
 
         let mut stream_handle = rodio::DeviceSinkBuilder::from_default_device()
             .and_then(|builder| {
@@ -200,12 +260,7 @@ impl PlaybackThread {
                     .output_devices()
                     .map_err(|_| original_err)?;
                 for device in devices {
-                    if let Ok(builder) = rodio::DeviceSinkBuilder::from_device(device)
-                        && let Ok(handle) = builder
-                            .with_buffer_size(buffer_size)
-                            .with_error_callback(error_callback as fn(_))
-                            .open_stream()
-                    {
+                    if let Ok(handle) = open_device(device) {
                         return Ok(handle);
                     }
                 }
@@ -224,7 +279,12 @@ impl PlaybackThread {
             replaygain_preamp_db,
             logic_tx.clone(),
         );
-        stream_handle.mixer().add(source);
+        controller.set_skip_silence(skip_silence_db);
+        let taps = crate::output_fanout::OutputTaps::default();
+        stream_handle.mixer().add(FanOut::new(source, taps.clone()));
+
+        // Extra outputs by device name.
+        let mut extra_outputs = std::collections::HashMap::<String, ExtraOutput>::new();
 
         const SEEK_DEBOUNCE_DURATION: Duration = Duration::from_millis(250);
 
@@ -281,6 +341,7 @@ impl PlaybackThread {
                     LTPM::Play => controller.play(),
                     LTPM::Pause => controller.pause(),
                     LTPM::StopPlayback => controller.stop(),
+                    LTPM::ResetToStart => controller.reset_to_start(),
                     LTPM::Seek(position) => {
                         let now = std::time::Instant::now();
                         if now.duration_since(last_seek_time) >= SEEK_DEBOUNCE_DURATION {
@@ -313,6 +374,57 @@ impl PlaybackThread {
                     LTPM::SetReplayGainPreamp(preamp_db) => {
                         controller.set_replaygain_preamp_db(preamp_db);
                     }
+                    LTPM::SetTrackGain { track_id, gain_db } => {
+                        controller.set_track_gain(&track_id, gain_db);
+                    }
+                    LTPM::SetSkipSilence(threshold_db) => {
+                        controller.set_skip_silence(threshold_db);
+                    }
+                    LTPM::AddOutput(name) => {
+                        if extra_outputs.contains_key(&name) {
+                            continue;
+                        }
+                        let device = match find_output_device(&name) {
+                            Ok(device) => device,
+                            Err(names) => {
+                                tracing::warn!(
+                                    "Unknown output device {name:?}; available devices: {}",
+                                    names.join(", ")
+                                );
+                                continue;
+                            }
+                        };
+                        match open_device(device) {
+                            Ok(mut stream) => {
+                                stream.log_on_drop(false);
+                                let mirror = taps.add(target_channels, target_sample_rate);
+                                let tap = mirror.tap();
+                                stream.mixer().add(mirror);
+                                tracing::info!("Mirroring playback to {name:?}");
+                                extra_outputs.insert(
+                                    name,
+                                    ExtraOutput {
+                                        _stream: Box::new(stream),
+                                        tap,
+                                    },
+                                );
+                            }
+                            Err(e) => {
+                                tracing::warn!("Failed to open output device {name:?}: {e}");
+                            }
+                        }
+                    }
+                    LTPM::RemoveOutput(name) => {
+                        if let Some(output) = extra_outputs.remove(&name) {
+                            taps.remove(&output.tap);
+                            tracing::info!("Stopped mirroring playback to {name:?}");
+                        }
+                    }
+                    LTPM::SetOutputVolume { device, volume } => {
+                        if let Some(output) = extra_outputs.get(&device) {
+                            output.tap.set_volume(volume * volume);
+                        }
+                    }
                     LTPM::Shutdown => return,
                 }
             }
@@ -340,9 +452,39 @@ impl PlaybackThread {
         _volume: f32,
         _apply_replaygain: bool,
         _replaygain_preamp_db: f32,
+        _skip_silence_db: Option<f32>,
     ) {
         unimplemented!(
             "Audio playback is disabled - blackbird-core was built without the 'audio' feature"
         )
     }
 }
+
+/// An extra output device mirroring playback: the open stream, kept alive as
+/// `Any`, and the tap feeding it.
+#[cfg(feature = "audio")]
+struct ExtraOutput {
+    _stream: Box<dyn std::any::Any>,
+    tap: std::sync::Arc<crate::output_fanout::OutputTap>,
+}
+
+/// Finds the output device called `name`, or returns the names of the
+/// devices that are available.
+#[cfg(feature = "audio")]
+fn find_output_device(name: &str) -> Result<rodio::cpal::Device, Vec<String>> {
+    use rodio::cpal::traits::HostTrait as _;
+
+    let mut names = vec![];
+    let device = rodio::cpal::default_host()
+        .output_devices()
+        .into_iter()
+        .flatten()
+        .find(|device| {
+            let Ok(description) = device.description() else {
+                return false;
+            };
+            names.push(description.name().to_string());
+            description.name() == name
+        });
+    device.ok_or(names)
+}
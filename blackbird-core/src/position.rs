@@ -0,0 +1,143 @@
+//! Keeps frequent playback position reports off the state lock. The latest
+//! position is stored in a [`LivePosition`] for readers, while
+//! [`PositionThrottle`] coalesces the writes into [`AppState`](crate::AppState)
+//! to at most one per [`POSITION_WRITE_INTERVAL`].
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::TrackAndPosition;
+
+/// The minimum time between position writes to the locked state (~4Hz).
+pub(crate) const POSITION_WRITE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The most recently reported position, readable without taking the state
+/// lock.
+#[derive(Debug, Default)]
+pub(crate) struct LivePosition {
+    /// The position in milliseconds, plus one; zero means unset.
+    millis: AtomicU64,
+}
+
+impl LivePosition {
+    pub fn set(&self, position: Duration) {
+        let millis = u64::try_from(position.as_millis()).unwrap_or(u64::MAX - 1);
+        self.millis.store(millis + 1, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> Option<Duration> {
+        match self.millis.load(Ordering::Relaxed) {
+            0 => None,
+            millis => Some(Duration::from_millis(millis - 1)),
+        }
+    }
+}
+
+/// Holds back position reports so that bursts are written to the state as a
+/// single update.
+#[derive(Debug, Default)]
+pub(crate) struct PositionThrottle {
+    pending: Option<TrackAndPosition>,
+    last_write: Option<Instant>,
+}
+
+impl PositionThrottle {
+    /// Records a report, replacing any that hasn't been written yet.
+    pub fn push(&mut self, track_and_position: TrackAndPosition) {
+        self.pending = Some(track_and_position);
+    }
+
+    /// Drops the pending report, e.g. because a new track started.
+    pub fn discard(&mut self) {
+        self.pending = None;
+    }
+
+    /// Returns the pending report if it is due to be written at `now`.
+    pub fn take_due(&mut self, now: Instant) -> Option<TrackAndPosition> {
+        if self
+            .last_write
+            .is_some_and(|last| now.duration_since(last) < POSITION_WRITE_INTERVAL)
+        {
+            return None;
+        }
+        let pending = self.pending.take()?;
+        self.last_write = Some(now);
+        Some(pending)
+    }
+
+    /// Makes the next report due straight away, as if the interval had
+    /// elapsed.
+    #[cfg(test)]
+    pub fn expire(&mut self) {
+        self.last_write = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blackbird_state::TrackId;
+
+    use super::*;
+
+    fn report(millis: u64) -> TrackAndPosition {
+        TrackAndPosition {
+            track_id: TrackId("t".into()),
+            position: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn live_position_round_trips() {
+        let live = LivePosition::default();
+        assert_eq!(live.get(), None);
+        live.set(Duration::ZERO);
+        assert_eq!(live.get(), Some(Duration::ZERO));
+        live.set(Duration::from_millis(1500));
+        assert_eq!(live.get(), Some(Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn bursts_are_coalesced_into_one_write_per_interval() {
+        let mut throttle = PositionThrottle::default();
+        let start = Instant::now();
+
+        let mut writes = vec![];
+        for step in 0..20u64 {
+            let now = start + Duration::from_millis(step * 25);
+            throttle.push(report(step * 25));
+            writes.extend(throttle.take_due(now));
+        }
+        // 500ms of reports every 25ms: the first, then one per interval.
+        assert_eq!(
+            writes.iter().map(|w| w.position).collect::<Vec<_>>(),
+            [0, 250].map(Duration::from_millis)
+        );
+
+        // The latest report is written once the interval elapses.
+        let later = start + Duration::from_millis(500);
+        assert_eq!(throttle.take_due(later), Some(report(475)));
+        assert_eq!(throttle.take_due(later + POSITION_WRITE_INTERVAL), None);
+    }
+
+    #[test]
+    fn expiring_writes_the_next_report_straight_away() {
+        let mut throttle = PositionThrottle::default();
+        let now = Instant::now();
+        throttle.push(report(100));
+        assert_eq!(throttle.take_due(now), Some(report(100)));
+
+        throttle.push(report(200));
+        assert_eq!(throttle.take_due(now), None);
+        throttle.expire();
+        assert_eq!(throttle.take_due(now), Some(report(200)));
+    }
+
+    #[test]
+    fn discarded_reports_are_not_written() {
+        let mut throttle = PositionThrottle::default();
+        throttle.push(report(100));
+        throttle.discard();
+        assert_eq!(throttle.take_due(Instant::now()), None);
+    }
+}
@@ -1,16 +1,18 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Write as _,
     sync::{Arc, RwLock},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use blackbird_state::TrackId;
+use blackbird_state::{AlbumId, TrackId};
 use blackbird_subsonic::{ClientResult, ReplayGain};
 use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
 
 use crate::{
-    AppState, Logic, PlaybackMode, TrackLoadMode,
+    AppState, BoundaryBehavior, Logic, PlayScope, PlaybackMode, QueueEndBehavior, TrackLoadMode,
     app_state::AppStateError,
+    bs,
     library::Library,
     playback_thread::{
         LogicToPlaybackMessage, PlaybackThreadSendHandle, ReplayGainTrackInfo, TrackPlayback,
@@ -32,6 +34,11 @@ pub(crate) fn replaygain_for_track(
     compute_replaygain_info(track.replay_gain.as_ref())
 }
 
+/// The user gain for the track in dB, or zero if none has been set.
+pub(crate) fn track_gain_db(state: &AppState, track_id: &TrackId) -> f32 {
+    state.track_gains.get(track_id).copied().unwrap_or(0.0)
+}
+
 /// Computes the ReplayGain factor and peak-clipping ceiling described by
 /// `replay_gain`.
 ///
@@ -92,6 +99,63 @@ pub struct QueueState {
     pub ordered_tracks: Vec<TrackId>,
     /// The index of the currently playing track within `ordered_tracks`.
     pub current_index: usize,
+
+    /// Tracks fetched by an album radio, waiting to be queued on the next
+    /// update.
+    pub pending_radio: Option<Vec<TrackId>>,
+    /// A track loaded into the library by [`Logic::load_and_play_track`],
+    /// waiting to be played on the next update.
+    pub pending_play: Option<TrackId>,
+
+    /// The track after which playback stops rather than advancing, set by
+    /// [`Logic::play_from`].
+    pub stop_after: Option<TrackId>,
+
+    /// Where each recently played album was left off, for
+    /// [`Logic::play_group`].
+    pub album_resume: AlbumResumeMarkers,
+
+    /// Tracks explicitly queued by the user, played in order before the
+    /// computed ordering resumes.
+    pub manual_queue: VecDeque<TrackId>,
+    /// The tracks [`Logic::play_group`] put in the play queue that haven't
+    /// played yet. Picking another track takes them back out.
+    pub group_queued: Vec<TrackId>,
+    /// The tracks taken from the play queue since playback last left it, in
+    /// the order they played. [`QueueEndBehavior::Loop`] queues them again.
+    pub queue_played: Vec<TrackId>,
+}
+
+/// How many albums [`AlbumResumeMarkers`] remembers.
+const ALBUM_RESUME_CAPACITY: usize = 100;
+
+/// The last-played track of each recently played album, most recent first.
+/// Holds at most [`ALBUM_RESUME_CAPACITY`] albums; the least recently played
+/// is forgotten to make room.
+#[derive(Debug, Default)]
+pub struct AlbumResumeMarkers(VecDeque<(AlbumId, TrackId)>);
+impl AlbumResumeMarkers {
+    /// The track `album_id` was left off at, if any.
+    pub fn get(&self, album_id: &AlbumId) -> Option<&TrackId> {
+        self.0
+            .iter()
+            .find(|(album, _)| album == album_id)
+            .map(|(_, track)| track)
+    }
+
+    /// Records `track_id` as where `album_id` was left off.
+    pub(crate) fn set(&mut self, album_id: AlbumId, track_id: TrackId) {
+        self.remove(&album_id);
+        if self.0.len() >= ALBUM_RESUME_CAPACITY {
+            self.0.pop_back();
+        }
+        self.0.push_front((album_id, track_id));
+    }
+
+    /// Forgets where `album_id` was left off.
+    pub(crate) fn remove(&mut self, album_id: &AlbumId) {
+        self.0.retain(|(album, _)| album != album_id);
+    }
 }
 
 impl Default for QueueState {
@@ -118,6 +182,13 @@ impl QueueState {
             next_track_appended: None,
             ordered_tracks: vec![],
             current_index: 0,
+            pending_radio: None,
+            pending_play: None,
+            stop_after: None,
+            album_resume: AlbumResumeMarkers::default(),
+            manual_queue: VecDeque::new(),
+            group_queued: Vec::new(),
+            queue_played: vec![],
         }
     }
 
@@ -127,7 +198,7 @@ impl QueueState {
     /// rotated, so callers can decide whether a queue recompute is warranted.
     pub(crate) fn bump_shuffle_seed_for_mode(&mut self, mode: PlaybackMode) -> bool {
         match mode {
-            PlaybackMode::Shuffle | PlaybackMode::LikedShuffle => {
+            PlaybackMode::Shuffle | PlaybackMode::LikedShuffle | PlaybackMode::ArtistShuffle => {
                 self.shuffle_seed = next_seed(self.shuffle_seed);
                 true
             }
@@ -140,10 +211,29 @@ impl QueueState {
     }
 }
 
-impl Logic {
+impl<C: bs::SubsonicApi> Logic<C> {
     pub(super) fn handle_track_end_advance(&self) {
+        {
+            let mut st = self.write_state();
+            if !st.queue.manual_queue.is_empty() {
+                // Queued tracks play before stopping or repeating.
+                drop(st);
+                self.schedule_next_track();
+                return;
+            }
+            if at_stop_after(&st) {
+                tracing::debug!("Reached the end of the play-from scope; stopping");
+                st.queue.stop_after = None;
+                return;
+            }
+            if at_queue_end(&st) {
+                tracing::debug!("Reached the end of the play queue; stopping");
+                st.queue.queue_played.clear();
+                return;
+            }
+        }
         let mode = self.get_playback_mode();
-        tracing::debug!("End-of-track advance handling; mode={:?}", mode);
+        tracing::debug!("End-of-track advance: {}", explain_next(&self.read_state()));
         match mode {
             PlaybackMode::RepeatOne => {
                 if let Some(current) = self.get_playing_track_id() {
@@ -158,6 +248,20 @@ impl Logic {
     }
 
     pub(super) fn schedule_next_track(&self) {
+        // Tracks queued by the user come first. They are spliced into the
+        // ordering after the current track, so going back returns to it and
+        // the computed order resumes after them.
+        let queued = {
+            let mut st = self.write_state();
+            let on_empty = st.queue_end_behavior;
+            take_manual_next(&mut st.queue, on_empty)
+        };
+        if let Some(next) = queued {
+            tracing::debug!("Advancing to queued track {}", next.0);
+            self.schedule_play_track(&next);
+            return;
+        }
+
         // If advancing would wrap the queue back to the start, rotate the
         // shuffle seed and recompute so the next pass plays a fresh order
         // rather than replaying the previous permutation verbatim. The
@@ -166,7 +270,9 @@ impl Logic {
         let (mode, will_wrap, current_tid) = {
             let st = self.read_state();
             let len = st.queue.ordered_tracks.len();
-            let will_wrap = len > 0 && st.queue.current_index + 1 >= len;
+            let will_wrap = len > 0
+                && st.queue.current_index + 1 >= len
+                && st.end_behavior.for_mode(st.playback_mode) == BoundaryBehavior::Wrap;
             let cur = st.queue.ordered_tracks.get(st.queue.current_index).cloned();
             (st.playback_mode, will_wrap, cur)
         };
@@ -177,16 +283,16 @@ impl Logic {
             }
         }
 
-        if let Some(next) = self.compute_next_track_id() {
+        // Advance the index before scheduling.
+        let next = {
+            let mut st = self.write_state();
+            unskipped_neighbour_index(&st, 1).map(|idx| {
+                st.queue.current_index = idx;
+                st.queue.ordered_tracks[idx].clone()
+            })
+        };
+        if let Some(next) = next {
             tracing::debug!("Advancing to next track {}", next.0);
-            // Advance the index before scheduling.
-            {
-                let mut st = self.write_state();
-                let len = st.queue.ordered_tracks.len();
-                if len > 0 {
-                    st.queue.current_index = (st.queue.current_index + 1) % len;
-                }
-            }
             self.schedule_play_track(&next);
         } else {
             tracing::warn!("No next track available to advance to");
@@ -194,16 +300,16 @@ impl Logic {
     }
 
     pub(super) fn schedule_previous_track(&self) {
-        if let Some(prev) = self.compute_previous_track_id() {
+        // Decrement the index before scheduling.
+        let prev = {
+            let mut st = self.write_state();
+            unskipped_neighbour_index(&st, -1).map(|idx| {
+                st.queue.current_index = idx;
+                st.queue.ordered_tracks[idx].clone()
+            })
+        };
+        if let Some(prev) = prev {
             tracing::debug!("Advancing to previous track {}", prev.0);
-            // Decrement the index before scheduling.
-            {
-                let mut st = self.write_state();
-                let len = st.queue.ordered_tracks.len();
-                if len > 0 {
-                    st.queue.current_index = (st.queue.current_index + len - 1) % len;
-                }
-            }
             self.schedule_play_track(&prev);
         } else {
             tracing::warn!("No previous track available to advance to");
@@ -239,6 +345,7 @@ impl Logic {
                     track_id: track_id.clone(),
                     data,
                     replaygain,
+                    gain_db: track_gain_db(&st, track_id),
                 }
             })
         };
@@ -269,7 +376,7 @@ impl Logic {
         let client = self.client.clone();
         let state = self.state.clone();
         let playback_tx = pt.send_handle();
-        let transcode = self.transcode;
+        let format = self.stream_format(&track_id);
 
         state
             .write()
@@ -284,9 +391,7 @@ impl Logic {
                 track_id.0,
                 request_id
             );
-            let response = client
-                .stream(&track_id.0, transcode.then(|| "mp3".to_string()), None)
-                .await;
+            let response = client.stream(&track_id.0, format, None).await;
             handle_load_response(response, state, playback_tx, track_id, request_id, behavior);
         });
     }
@@ -315,32 +420,21 @@ impl Logic {
         }
     }
 
-    pub(super) fn compute_next_track_id(&self) -> Option<TrackId> {
-        let st = self.read_state();
-        let ordered = &st.queue.ordered_tracks;
-        if ordered.is_empty() {
-            return None;
-        }
-        let next_index = (st.queue.current_index + 1) % ordered.len();
-        Some(ordered[next_index].clone())
+    /// Describes how the track after the current one is chosen: the mode and
+    /// its shuffle seed, the position in the queue, and whether the next track
+    /// wraps around, steps over skipped tracks or starts another group. For
+    /// debugging unexpected next tracks.
+    pub fn explain_next(&self) -> String {
+        explain_next(&self.read_state())
     }
 
-    pub(super) fn compute_previous_track_id(&self) -> Option<TrackId> {
-        let st = self.read_state();
-        let ordered = &st.queue.ordered_tracks;
-        if ordered.is_empty() {
-            return None;
-        }
-        let prev_index = (st.queue.current_index + ordered.len() - 1) % ordered.len();
-        Some(ordered[prev_index].clone())
+    pub(super) fn compute_next_track_id(&self) -> Option<TrackId> {
+        next_track_id(&self.read_state())
     }
 
     /// Ensures that the audio cache contains tracks surrounding the current queue position.
     pub(super) fn ensure_cache_window(&self) {
-        let window = {
-            let st = self.read_state();
-            compute_window_from_queue(&st.queue, 2)
-        };
+        let window = compute_cache_window(&self.read_state(), 2);
 
         self.write_state()
             .queue
@@ -372,9 +466,117 @@ impl Logic {
         );
     }
 
+    /// Queues `tracks` to play after the current track, starting the first
+    /// one if nothing is playing.
+    pub(super) fn insert_next(&self, tracks: Vec<TrackId>) {
+        let start = {
+            let mut st = self.write_state();
+            let playing = st.current_track_and_position.is_some();
+            insert_after_current(&mut st.queue, tracks, playing);
+            (!playing)
+                .then(|| st.queue.ordered_tracks.get(st.queue.current_index).cloned())
+                .flatten()
+        };
+        // The staged gapless track is no longer the next one.
+        self.send_to_playback(LogicToPlaybackMessage::ClearQueuedNextTracks);
+
+        match start {
+            Some(track_id) => self.schedule_play_track(&track_id),
+            None => self.ensure_cache_window(),
+        }
+    }
+
+    /// Adds `track_id` to the end of the play queue.
+    pub fn enqueue_last(&self, track_id: &TrackId) {
+        self.write_state()
+            .queue
+            .manual_queue
+            .push_back(track_id.clone());
+        self.manual_queue_changed();
+    }
+
+    /// Adds `track_id` to the front of the play queue, to play after the
+    /// current track.
+    pub fn enqueue_next(&self, track_id: &TrackId) {
+        self.write_state()
+            .queue
+            .manual_queue
+            .push_front(track_id.clone());
+        self.manual_queue_changed();
+    }
+
+    /// Removes the entry at `index` from the play queue, returning it.
+    pub fn dequeue_track(&self, index: usize) -> Option<TrackId> {
+        let removed = {
+            let mut st = self.write_state();
+            let removed = st.queue.manual_queue.remove(index);
+            if let Some(track_id) = &removed {
+                forget_group_queued(&mut st.queue, track_id);
+            }
+            removed
+        };
+        if removed.is_some() {
+            self.manual_queue_changed();
+        }
+        removed
+    }
+
+    /// Moves the play queue entry at `from` to `to`. Returns false if either
+    /// index is out of range.
+    pub fn move_queued_track(&self, from: usize, to: usize) -> bool {
+        {
+            let mut st = self.write_state();
+            let manual_queue = &mut st.queue.manual_queue;
+            if from >= manual_queue.len() || to >= manual_queue.len() {
+                return false;
+            }
+            if from == to {
+                return true;
+            }
+            let track_id = manual_queue.remove(from).unwrap();
+            manual_queue.insert(to, track_id);
+        }
+        self.manual_queue_changed();
+        true
+    }
+
+    /// Empties the play queue. The playing track carries on, and the computed
+    /// order resumes after it.
+    pub fn clear_queue(&self) {
+        let was_empty = {
+            let mut st = self.write_state();
+            let was_empty = st.queue.manual_queue.is_empty();
+            st.queue.manual_queue.clear();
+            st.queue.group_queued.clear();
+            st.queue.queue_played.clear();
+            was_empty
+        };
+        if !was_empty {
+            self.manual_queue_changed();
+        }
+    }
+
+    /// The tracks in the play queue, in the order they will play.
+    pub fn get_queue(&self) -> Vec<TrackId> {
+        self.read_state()
+            .queue
+            .manual_queue
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Drops the staged gapless track, which may no longer be the next one,
+    /// and prefetches the start of the play queue.
+    pub(super) fn manual_queue_changed(&self) {
+        self.write_state().queue.next_track_appended = None;
+        self.send_to_playback(LogicToPlaybackMessage::ClearQueuedNextTracks);
+        self.ensure_cache_window();
+    }
+
     /// Recomputes the playback queue ordering for the current mode
     /// and sets `current_index` to the position of `current_track` (or 0 if not found).
-    pub fn recompute_queue(&self, current_track: Option<&TrackId>) {
+    pub(crate) fn recompute_queue(&self, current_track: Option<&TrackId>) {
         let mut st = self.write_state();
         recompute_queue_on_state(&mut st, current_track);
     }
@@ -426,12 +628,12 @@ pub(crate) fn handle_load_response(
 ) {
     match response {
         Ok(data) => {
-            let (is_current_target, replaygain) = {
+            let (is_current_target, replaygain, gain_db) = {
                 let mut st = state.write().unwrap();
                 st.queue.audio_cache.insert(track_id.clone(), data.clone());
                 let is_current = st.queue.current_target.as_ref() == Some(&track_id);
                 let replaygain = replaygain_for_track(&st, &track_id);
-                (is_current, replaygain)
+                (is_current, replaygain, track_gain_db(&st, &track_id))
             };
 
             match behavior {
@@ -446,6 +648,7 @@ pub(crate) fn handle_load_response(
                             track_id: track_id.clone(),
                             data,
                             replaygain,
+                            gain_db,
                         },
                         mode: TrackLoadMode::Play,
                     });
@@ -461,6 +664,7 @@ pub(crate) fn handle_load_response(
                             track_id: track_id.clone(),
                             data,
                             replaygain,
+                            gain_db,
                         },
                         mode: TrackLoadMode::Paused(position),
                     });
@@ -492,11 +696,14 @@ pub(crate) fn handle_load_response(
                     "Load error for current target {track_id} (req_id={request_id}): {}",
                     e.to_string()
                 );
-                st.error = Some(AppStateError::LoadTrackFailed {
+                st.started_loading_track = None;
+                // Skipping ahead would only fail again on the next track if
+                // the credentials were rejected.
+                let rejected = st.report_client_error(&e, |error| AppStateError::LoadTrackFailed {
                     track_id,
-                    error: e.to_string(),
+                    error,
                 });
-                st.queue.pending_skip_after_error = true;
+                st.queue.pending_skip_after_error = !rejected;
             } else {
                 tracing::debug!(
                     "Load error for stale/non-current {track_id} (req_id={request_id}): {e}"
@@ -559,10 +766,7 @@ fn compute_full_ordering(
             let Some(tid) = current_track else {
                 return vec![];
             };
-            let Some(&group_idx) = library.track_to_group_index.get(tid) else {
-                return vec![];
-            };
-            match library.groups.get(group_idx) {
+            match library.group_for_track(tid) {
                 Some(group) => group.tracks.clone(),
                 None => vec![],
             }
@@ -576,17 +780,18 @@ fn compute_full_ordering(
 
         PlaybackMode::LikedShuffle => {
             let mut tracks: Vec<TrackId> = library
-                .track_ids
-                .iter()
-                .filter(|tid| library.track_map.get(tid).is_some_and(|t| t.starred))
-                .cloned()
+                .iter_tracks_in_order()
+                .filter(|t| t.starred)
+                .map(|t| t.id.clone())
                 .collect();
             shuffle_with_seed(&mut tracks, queue.shuffle_seed);
             tracks
         }
 
         PlaybackMode::GroupShuffle => {
-            // Shuffle group indices, then flatten each group's tracks in their natural order.
+            // Shuffle group indices, then flatten each group's tracks in their
+            // natural (disc and track) order, so that each album plays whole
+            // and the track after an album's last is the next album's first.
             let mut group_indices: Vec<usize> = (0..library.groups.len()).collect();
             shuffle_with_seed(&mut group_indices, queue.group_shuffle_seed);
             group_indices
@@ -610,9 +815,357 @@ fn compute_full_ordering(
                 .flat_map(|idx| library.groups[idx].tracks.iter().cloned())
                 .collect()
         }
+
+        PlaybackMode::ArtistShuffle => {
+            // Every track in every group by the current track's album artist,
+            // or empty if nothing is playing.
+            let Some(tid) = current_track else {
+                return vec![];
+            };
+            let Some(artist) = library.group_for_track(tid).map(|group| &group.artist) else {
+                return vec![];
+            };
+            let mut tracks: Vec<TrackId> = library
+                .groups
+                .iter()
+                .filter(|g| &g.artist == artist)
+                .flat_map(|g| g.tracks.iter().cloned())
+                .collect();
+            shuffle_with_seed(&mut tracks, queue.shuffle_seed);
+            tracks
+        }
     }
 }
 
+/// Inserts `tracks` into the queue after the current track, or at the current
+/// position if nothing is `playing`. Other occurrences of the tracks are
+/// removed so that each plays once.
+fn insert_after_current(queue: &mut QueueState, tracks: Vec<TrackId>, playing: bool) {
+    let current = playing
+        .then(|| queue.ordered_tracks.get(queue.current_index).cloned())
+        .flatten();
+    let tracks: Vec<TrackId> = tracks
+        .into_iter()
+        .filter(|t| Some(t) != current.as_ref())
+        .collect();
+    let incoming: HashSet<&TrackId> = tracks.iter().collect();
+
+    let current_index = queue.current_index.min(queue.ordered_tracks.len());
+    let removed_before = queue.ordered_tracks[..current_index]
+        .iter()
+        .filter(|t| incoming.contains(t))
+        .count();
+    queue.ordered_tracks.retain(|t| !incoming.contains(t));
+    queue.current_index = current_index - removed_before;
+
+    let at = (queue.current_index + usize::from(current.is_some())).min(queue.ordered_tracks.len());
+    queue.ordered_tracks.splice(at..at, tracks);
+    queue.next_track_appended = None;
+}
+
+/// Takes the front of the play queue and splices it into the ordering after
+/// the current track, making it current. Taking the last queued track with
+/// `on_empty` set to [`QueueEndBehavior::Loop`] queues everything played from
+/// the queue again.
+pub(crate) fn take_manual_next(
+    queue: &mut QueueState,
+    on_empty: QueueEndBehavior,
+) -> Option<TrackId> {
+    let track_id = queue.manual_queue.pop_front()?;
+    forget_group_queued(queue, &track_id);
+    insert_after_current(queue, vec![track_id.clone()], true);
+    if queue.ordered_tracks.get(queue.current_index) != Some(&track_id) {
+        queue.current_index += 1;
+    }
+    queue.queue_played.push(track_id.clone());
+    if queue.manual_queue.is_empty() && on_empty == QueueEndBehavior::Loop {
+        queue.manual_queue.extend(queue.queue_played.drain(..));
+    }
+    Some(track_id)
+}
+
+/// Forgets the tracks played from the play queue once playback has moved on
+/// to `track_id`, unless it is the one just taken from the queue.
+pub(crate) fn note_track_started(queue: &mut QueueState, track_id: &TrackId) {
+    if queue.queue_played.last() != Some(track_id) {
+        queue.queue_played.clear();
+    }
+}
+
+/// The track that plays after the current one, if any.
+fn next_track_id(st: &AppState) -> Option<TrackId> {
+    if let Some(queued) = st.queue.manual_queue.front() {
+        return Some(queued.clone());
+    }
+    if at_stop_after(st) || at_queue_end(st) {
+        return None;
+    }
+    let next_index = unskipped_neighbour_index(st, 1)?;
+    Some(st.queue.ordered_tracks[next_index].clone())
+}
+
+/// Queues the tracks after `track_id` in `scope` to play next, and has
+/// playback stop after the last of them. `track_id` must be the current track
+/// in the queue.
+pub(crate) fn apply_play_scope(st: &mut AppState, track_id: &TrackId, scope: PlayScope) {
+    let library = &st.library;
+    let scope_tracks = match scope {
+        PlayScope::Library => &library.track_ids,
+        PlayScope::Album => {
+            let Some(group) = library.group_for_track(track_id) else {
+                return;
+            };
+            &group.tracks
+        }
+    };
+    let Some(position) = scope_tracks.iter().position(|t| t == track_id) else {
+        return;
+    };
+    let rest = scope_tracks[position + 1..].to_vec();
+    st.queue.stop_after = Some(rest.last().unwrap_or(track_id).clone());
+    insert_after_current(&mut st.queue, rest, true);
+}
+
+/// Records `track_id` as where its album was left off.
+pub(crate) fn mark_album_progress(st: &mut AppState, track_id: &TrackId) {
+    let Some(album_id) = st
+        .library
+        .group_for_track(track_id)
+        .map(|group| group.album_id.clone())
+    else {
+        return;
+    };
+    st.queue.album_resume.set(album_id, track_id.clone());
+}
+
+/// Clears the album's resume marker if `track_id`, which has just finished
+/// playing, is its last track.
+pub(crate) fn clear_finished_album(st: &mut AppState, track_id: &TrackId) {
+    let Some(group) = st.library.group_for_track(track_id) else {
+        return;
+    };
+    if group.tracks.last() == Some(track_id) {
+        let album_id = group.album_id.clone();
+        st.queue.album_resume.remove(&album_id);
+    }
+}
+
+/// The track to start `album_id` from: where it was left off if `resume` is
+/// set and it was, or its first track otherwise.
+pub(crate) fn album_start_track(
+    st: &AppState,
+    album_id: &AlbumId,
+    resume: bool,
+) -> Option<TrackId> {
+    let library = &st.library;
+    let group = library
+        .groups
+        .get(*library.album_to_group_index.get(album_id)?)?;
+    resume
+        .then(|| st.queue.album_resume.get(album_id))
+        .flatten()
+        .filter(|track_id| group.tracks.contains(track_id))
+        .or_else(|| group.tracks.first())
+        .cloned()
+}
+
+/// Puts the tracks of `album_id` after `track_id` at the front of the play
+/// queue, in album order, leaving out tracks that are always skipped.
+pub(crate) fn queue_rest_of_album(st: &mut AppState, album_id: &AlbumId, track_id: &TrackId) {
+    let library = &st.library;
+    let Some(group) = library
+        .album_to_group_index
+        .get(album_id)
+        .and_then(|&index| library.groups.get(index))
+    else {
+        return;
+    };
+    let Some(position) = group.tracks.iter().position(|t| t == track_id) else {
+        return;
+    };
+    let rest: Vec<TrackId> = group.tracks[position + 1..]
+        .iter()
+        .filter(|t| !st.skipped_tracks.contains(t))
+        .cloned()
+        .collect();
+    for track_id in rest.iter().rev() {
+        st.queue.manual_queue.push_front(track_id.clone());
+    }
+    st.queue.group_queued = rest;
+}
+
+/// Takes the tracks queued by [`Logic::play_group`] that haven't played yet
+/// back out of the play queue. Returns whether there were any.
+pub(crate) fn drop_group_queued(queue: &mut QueueState) -> bool {
+    let mut dropped = false;
+    for track_id in std::mem::take(&mut queue.group_queued) {
+        if let Some(index) = queue.manual_queue.iter().position(|t| *t == track_id) {
+            queue.manual_queue.remove(index);
+            dropped = true;
+        }
+    }
+    dropped
+}
+
+/// Stops treating `track_id` as queued by [`Logic::play_group`], as it has
+/// left the play queue.
+fn forget_group_queued(queue: &mut QueueState, track_id: &TrackId) {
+    if let Some(index) = queue.group_queued.iter().position(|t| t == track_id) {
+        queue.group_queued.remove(index);
+    }
+}
+
+/// Whether the current track is the one playback stops after.
+fn at_stop_after(st: &AppState) -> bool {
+    st.queue.stop_after.as_ref().is_some_and(|stop_after| {
+        st.queue.ordered_tracks.get(st.queue.current_index) == Some(stop_after)
+    })
+}
+
+/// Whether the current track is the last one taken from the play queue and
+/// playback stops once the queue runs out.
+fn at_queue_end(st: &AppState) -> bool {
+    st.queue_end_behavior == QueueEndBehavior::Stop
+        && st.queue.manual_queue.is_empty()
+        && st
+            .queue
+            .queue_played
+            .last()
+            .is_some_and(|last| st.queue.ordered_tracks.get(st.queue.current_index) == Some(last))
+}
+
+/// Builds the explanation for [`Logic::explain_next`].
+fn explain_next(st: &AppState) -> String {
+    let queue = &st.queue;
+    let mode = st.playback_mode;
+    let mut out = format!("mode {mode:?}");
+    match mode {
+        PlaybackMode::Shuffle | PlaybackMode::LikedShuffle | PlaybackMode::ArtistShuffle => {
+            let _ = write!(out, " (shuffle seed {})", queue.shuffle_seed);
+        }
+        PlaybackMode::GroupShuffle | PlaybackMode::LikedGroupShuffle => {
+            let _ = write!(out, " (group shuffle seed {})", queue.group_shuffle_seed);
+        }
+        PlaybackMode::Sequential | PlaybackMode::RepeatOne | PlaybackMode::GroupRepeat => {}
+    }
+
+    let len = queue.ordered_tracks.len();
+    if len == 0 {
+        out.push_str("; the queue is empty");
+        return out;
+    }
+    let current = queue.current_index;
+    let _ = write!(out, "; at {} of {len} in the queue", current + 1);
+
+    if let Some(queued) = queue.manual_queue.front() {
+        let _ = write!(
+            out,
+            "; next is {} from the play queue ({} queued)",
+            queued.0,
+            queue.manual_queue.len()
+        );
+        return out;
+    }
+
+    if mode == PlaybackMode::RepeatOne {
+        out.push_str("; the current track repeats");
+        return out;
+    }
+    if at_stop_after(st) {
+        out.push_str("; playback stops after this track, the end of the play-from scope");
+        return out;
+    }
+    if at_queue_end(st) {
+        out.push_str("; playback stops after this track, the end of the play queue");
+        return out;
+    }
+    let Some(plain) = neighbour_index(st, 1) else {
+        out.push_str("; the queue ends here, as the end behaviour is to stop");
+        return out;
+    };
+    let Some(next) = unskipped_neighbour_index(st, 1) else {
+        return out;
+    };
+
+    if plain <= current {
+        out.push_str("; wraps to the start of the queue");
+        if mode.is_shuffle() {
+            out.push_str(", reshuffled with a new seed first");
+        }
+    }
+    if next != plain {
+        let stepped = (next as isize - plain as isize).rem_euclid(len as isize);
+        let _ = write!(out, "; steps over {stepped} skipped track(s)");
+    }
+    let group_name = |idx: usize| {
+        group_at(st, idx).map(|group| {
+            let group = &st.library.groups[group];
+            format!("{} \u{2013} {}", group.artist, group.album)
+        })
+    };
+    let next_group = group_name(next);
+    if group_at(st, next) == group_at(st, current) {
+        out.push_str("; stays in the same group");
+    } else if let Some(next_group) = &next_group {
+        let _ = write!(out, "; moves on to the group {next_group}");
+    }
+
+    let next_id = &queue.ordered_tracks[next];
+    match st.library.track_map.get(next_id) {
+        Some(track) => {
+            let _ = write!(out, "; next is {} ({})", track.title, next_id.0);
+        }
+        None => {
+            let _ = write!(out, "; next is {}", next_id.0);
+        }
+    }
+    out
+}
+
+/// Returns the index of the track `direction` steps (+1 forward, -1 backward)
+/// from `current_index`. Stepping past either end of the queue wraps or
+/// returns `None`, depending on the mode's [`BoundaryBehavior`].
+fn neighbour_index(st: &AppState, direction: isize) -> Option<usize> {
+    neighbour_index_from(st, st.queue.current_index, direction)
+}
+
+/// Like [`neighbour_index`], but steps from `from` rather than
+/// `current_index`.
+fn neighbour_index_from(st: &AppState, from: usize, direction: isize) -> Option<usize> {
+    let len = st.queue.ordered_tracks.len();
+    if len == 0 {
+        return None;
+    }
+    let idx = from as isize + direction;
+    if (0..len as isize).contains(&idx) {
+        return Some(idx as usize);
+    }
+    match st.end_behavior.for_mode(st.playback_mode) {
+        BoundaryBehavior::Wrap => Some(idx.rem_euclid(len as isize) as usize),
+        BoundaryBehavior::Stop => None,
+    }
+}
+
+/// How many skipped tracks in a row are stepped over before giving up and
+/// landing on the first of them anyway, so that a queue made up of skipped
+/// tracks can't loop forever.
+const MAX_CONSECUTIVE_SKIPS: usize = 32;
+
+/// Like [`neighbour_index`], but steps over tracks marked to always be
+/// skipped. Falls back to the plain neighbour after
+/// [`MAX_CONSECUTIVE_SKIPS`] skipped tracks in a row.
+pub(crate) fn unskipped_neighbour_index(st: &AppState, direction: isize) -> Option<usize> {
+    let first = neighbour_index(st, direction)?;
+    let mut idx = first;
+    for _ in 0..MAX_CONSECUTIVE_SKIPS {
+        if !st.skipped_tracks.contains(&st.queue.ordered_tracks[idx]) {
+            return Some(idx);
+        }
+        idx = neighbour_index_from(st, idx, direction)?;
+    }
+    Some(first)
+}
+
 /// Returns the group index for the track at `idx` in `ordered_tracks`, if available.
 fn group_at(st: &AppState, idx: usize) -> Option<usize> {
     st.library
@@ -665,6 +1218,44 @@ fn find_previous_group_start(st: &AppState) -> Option<usize> {
     }
 }
 
+/// The cache window around `current_index`, plus the first track of the next
+/// group once the current track is near the end of its group.
+fn compute_cache_window(st: &AppState, radius: usize) -> Vec<TrackId> {
+    let mut window = compute_window_from_queue(&st.queue, radius);
+    // The next tracks to play may come from the play queue, which is
+    // prefetched as far ahead as the ordering is.
+    let queued = st.queue.manual_queue.iter().take(radius).cloned();
+    // The next track in the ordering may lie past skipped tracks, outside the
+    // window.
+    let next_unskipped =
+        unskipped_neighbour_index(st, 1).map(|idx| st.queue.ordered_tracks[idx].clone());
+    for track_id in queued.chain(
+        [next_unskipped, next_group_prefetch(st)]
+            .into_iter()
+            .flatten(),
+    ) {
+        if !window.contains(&track_id) {
+            window.push(track_id);
+        }
+    }
+    window
+}
+
+/// The first track of the next group, if the current track is within
+/// `next_group_prefetch_tracks` tracks of the end of its group. Sequential
+/// neighbours are already in the window; this covers skipping to the next
+/// group, and modes where the next group isn't the next track.
+fn next_group_prefetch(st: &AppState) -> Option<TrackId> {
+    let len = st.queue.ordered_tracks.len();
+    if st.next_group_prefetch_tracks == 0 || st.queue.current_index >= len {
+        return None;
+    }
+    let start = find_next_group_start(st)?;
+    let remaining_in_group = (start + len - st.queue.current_index) % len - 1;
+    (remaining_in_group <= st.next_group_prefetch_tracks)
+        .then(|| st.queue.ordered_tracks[start].clone())
+}
+
 /// Computes a cache window of track IDs around `current_index` in the precomputed queue.
 fn compute_window_from_queue(queue: &QueueState, radius: usize) -> Vec<TrackId> {
     let ordered = &queue.ordered_tracks;
@@ -722,85 +1313,118 @@ fn next_seed(seed: u64) -> u64 {
 mod tests {
     use std::sync::Arc;
 
-    use blackbird_state::{AlbumId, Group, Track, TrackId};
+    use blackbird_state::{AlbumId, TrackId};
     use smol_str::SmolStr;
 
     use super::*;
-    use crate::{Library, SortOrder};
-
-    fn make_track(idx: usize) -> Track {
-        Track {
-            id: TrackId(format!("t{idx}")),
-            title: SmolStr::new(format!("Track {idx}")),
-            artist: None,
-            track: None,
-            year: None,
-            _genre: None,
-            duration: Some(180),
-            disc_number: None,
-            starred: idx.is_multiple_of(3), // every 3rd track is starred
-            play_count: None,
-            album_id: None,
-            replay_gain: None,
-        }
+    use crate::{
+        Library, PlaybackErrorBehavior, PlaybackToLogicMessage, TrackAndPosition, bs,
+        test_util::make_library,
+    };
+
+    fn make_queue() -> QueueState {
+        let mut q = QueueState::new();
+        // Use fixed seeds for determinism.
+        q.shuffle_seed = 42;
+        q.group_shuffle_seed = 99;
+        q
     }
 
-    fn make_group(g: usize, track_ids: Vec<TrackId>) -> Arc<Group> {
-        Arc::new(Group {
-            album_id: AlbumId(SmolStr::new(format!("album{g}"))),
-            album: SmolStr::new(format!("Album {g}")),
-            artist: SmolStr::new(format!("Artist {g}")),
-            sort_artist: SmolStr::new(format!("Artist {g}")),
-            year: None,
-            duration: 0,
-            tracks: track_ids,
-            cover_art_id: None,
-            starred: g.is_multiple_of(2), // every other group is starred
-        })
+    #[test]
+    fn insert_after_current_moves_tracks_up_once() {
+        let ids = |names: &[&str]| -> Vec<TrackId> {
+            names.iter().map(|n| TrackId(n.to_string())).collect()
+        };
+        let mut queue = make_queue();
+        queue.ordered_tracks = ids(&["a", "b", "c", "d", "e"]);
+        queue.current_index = 2;
+
+        insert_after_current(&mut queue, ids(&["a", "e", "c"]), true);
+        assert_eq!(queue.ordered_tracks, ids(&["b", "c", "a", "e", "d"]));
+        assert_eq!(queue.current_index, 1);
+
+        // With nothing playing, the tracks go at the current position.
+        insert_after_current(&mut queue, ids(&["d"]), false);
+        assert_eq!(queue.ordered_tracks, ids(&["b", "d", "c", "a", "e"]));
+        assert_eq!(queue.current_index, 1);
     }
 
-    /// Creates a minimal library with `n` tracks spread across `group_count` groups.
-    fn make_library(n: usize, group_count: usize) -> Library {
-        let mut library = Library::default();
-        let mut track_map = HashMap::new();
-        let mut groups = Vec::new();
+    #[test]
+    fn queued_tracks_play_after_current_and_can_be_returned_to() {
+        let ids = |names: &[&str]| -> Vec<TrackId> {
+            names.iter().map(|n| TrackId(n.to_string())).collect()
+        };
+        let mut queue = make_queue();
+        queue.ordered_tracks = ids(&["a", "b", "c", "d"]);
+        queue.current_index = 1;
+        queue.manual_queue = ids(&["x", "d"]).into();
+
+        let on_empty = QueueEndBehavior::ContinueLibrary;
+        assert_eq!(
+            take_manual_next(&mut queue, on_empty),
+            Some(TrackId("x".into()))
+        );
+        assert_eq!(queue.ordered_tracks, ids(&["a", "b", "x", "c", "d"]));
+        assert_eq!(queue.current_index, 2);
+
+        // A queued track already in the ordering moves up rather than
+        // playing twice.
+        assert_eq!(
+            take_manual_next(&mut queue, on_empty),
+            Some(TrackId("d".into()))
+        );
+        assert_eq!(queue.ordered_tracks, ids(&["a", "b", "x", "d", "c"]));
+        assert_eq!(queue.current_index, 3);
 
-        let tracks_per_group = n / group_count.max(1);
-        let mut track_idx = 0;
+        assert_eq!(take_manual_next(&mut queue, on_empty), None);
+    }
 
-        for g in 0..group_count {
-            let mut group_tracks = Vec::new();
-            let count = if g == group_count - 1 {
-                n - track_idx
-            } else {
-                tracks_per_group
-            };
-            for _ in 0..count {
-                let track = make_track(track_idx);
-                let tid = track.id.clone();
-                track_map.insert(tid.clone(), track);
-                group_tracks.push(tid);
-                track_idx += 1;
-            }
-            groups.push(make_group(g, group_tracks));
+    /// Plays `t0`, then takes the last of the queued `t4` and `t2`, leaving
+    /// the queue's last track playing.
+    fn at_last_queued_track(on_empty: QueueEndBehavior) -> AppState {
+        let mut st = AppState {
+            library: make_library(6, 2),
+            queue_end_behavior: on_empty,
+            ..AppState::default()
+        };
+        recompute_queue_on_state(&mut st, Some(&TrackId("t0".into())));
+        st.queue.manual_queue = ["t4", "t2"].map(|t| TrackId(t.into())).into();
+        for _ in 0..2 {
+            let taken = take_manual_next(&mut st.queue, on_empty).unwrap();
+            note_track_started(&mut st.queue, &taken);
         }
+        st
+    }
 
-        library.populate(
-            vec![],
-            track_map,
-            groups,
-            HashMap::new(),
-            SortOrder::Alphabetical,
-        );
-        library
+    #[test]
+    fn stop_ends_playback_after_the_last_queued_track() {
+        let st = at_last_queued_track(QueueEndBehavior::Stop);
+        assert!(at_queue_end(&st));
+        assert_eq!(next_track_id(&st), None);
     }
 
-    fn make_queue() -> QueueState {
-        let mut q = QueueState::new();
-        // Use fixed seeds for determinism.
-        q.shuffle_seed = 42;
-        q.group_shuffle_seed = 99;
-        q
+    #[test]
+    fn continue_library_resumes_the_ordering_after_the_last_queued_track() {
+        let st = at_last_queued_track(QueueEndBehavior::ContinueLibrary);
+        assert!(!at_queue_end(&st));
+        assert_eq!(next_track_id(&st), Some(TrackId("t1".into())));
+    }
+
+    #[test]
+    fn loop_queues_the_played_tracks_again_after_the_last_queued_track() {
+        let st = at_last_queued_track(QueueEndBehavior::Loop);
+        assert!(!at_queue_end(&st));
+        assert_eq!(next_track_id(&st), Some(TrackId("t4".into())));
+        let queued: Vec<&str> = st.queue.manual_queue.iter().map(|t| t.0.as_str()).collect();
+        assert_eq!(queued, ["t4", "t2"]);
+    }
+
+    #[test]
+    fn playing_outside_the_queue_forgets_the_queue_end() {
+        let mut st = at_last_queued_track(QueueEndBehavior::Stop);
+        note_track_started(&mut st.queue, &TrackId("t5".into()));
+        st.queue.current_index = 5;
+        assert!(!at_queue_end(&st));
     }
 
     #[test]
@@ -931,6 +1555,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn artist_shuffle_spans_all_groups_by_the_current_artist() {
+        let mut library = make_library(12, 4);
+        Arc::make_mut(&mut library.groups[2]).artist = library.groups[0].artist.clone();
+        let queue = make_queue();
+        let current = library.groups[0].tracks[0].clone();
+
+        let ordering = compute_full_ordering(
+            &library,
+            PlaybackMode::ArtistShuffle,
+            &queue,
+            Some(&current),
+        );
+        let mut expected: Vec<TrackId> = library.groups[0]
+            .tracks
+            .iter()
+            .chain(&library.groups[2].tracks)
+            .cloned()
+            .collect();
+        let mut sorted = ordering.clone();
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(sorted, expected);
+
+        assert!(
+            compute_full_ordering(&library, PlaybackMode::ArtistShuffle, &queue, None).is_empty()
+        );
+    }
+
     #[test]
     fn single_track_library() {
         let library = make_library(1, 1);
@@ -1004,6 +1657,28 @@ mod tests {
         assert_eq!(st.queue.current_index, 0);
     }
 
+    #[test]
+    fn play_from_album_queues_the_rest_of_the_album_and_stops_after_it() {
+        let library = make_library(6, 2);
+        let mut st = AppState {
+            library,
+            playback_mode: PlaybackMode::Shuffle,
+            ..AppState::default()
+        };
+        st.queue.shuffle_seed = 42;
+        let album = st.library.groups[0].tracks.clone();
+        recompute_queue_on_state(&mut st, Some(&album[1]));
+
+        apply_play_scope(&mut st, &album[1], PlayScope::Album);
+        let current = st.queue.current_index;
+        assert_eq!(st.queue.ordered_tracks[current..current + 2], album[1..]);
+        assert_eq!(st.queue.stop_after.as_ref(), Some(&album[2]));
+        assert!(!at_stop_after(&st));
+
+        st.queue.current_index += 1;
+        assert!(at_stop_after(&st));
+    }
+
     #[test]
     fn compute_window_from_queue_basic() {
         let mut queue = make_queue();
@@ -1022,6 +1697,66 @@ mod tests {
         assert_eq!(window[0], TrackId("c".to_string())); // center
     }
 
+    #[test]
+    fn next_album_first_track_is_prefetched_near_album_end() {
+        // Two albums of four tracks: t0-t3 and t4-t7.
+        let mut st = AppState {
+            library: make_library(8, 2),
+            ..Default::default()
+        };
+        st.queue.ordered_tracks = st.library.track_ids.clone();
+        let next_album_start = TrackId("t4".to_string());
+
+        // Three tracks from the end: out of range, and not in the window.
+        st.queue.current_index = 0;
+        assert!(!compute_cache_window(&st, 2).contains(&next_album_start));
+
+        // Two tracks from the end: prefetched, although the window's
+        // sequential neighbours stop short of it.
+        st.queue.current_index = 1;
+        assert!(!compute_window_from_queue(&st.queue, 2).contains(&next_album_start));
+        assert!(compute_cache_window(&st, 2).contains(&next_album_start));
+
+        // Disabled.
+        st.next_group_prefetch_tracks = 0;
+        assert!(!compute_cache_window(&st, 2).contains(&next_album_start));
+    }
+
+    #[test]
+    fn playing_an_album_queues_the_rest_of_it() {
+        let mut st = AppState {
+            library: make_library(8, 2),
+            ..Default::default()
+        };
+        st.skipped_tracks.insert(TrackId("t2".to_string()));
+        st.queue.manual_queue.push_back(TrackId("t7".to_string()));
+
+        let album_id = st.library.groups[0].album_id.clone();
+        queue_rest_of_album(&mut st, &album_id, &TrackId("t0".to_string()));
+        let queued: Vec<&str> = st.queue.manual_queue.iter().map(|t| t.0.as_str()).collect();
+        assert_eq!(queued, ["t1", "t3", "t7"]);
+    }
+
+    #[test]
+    fn queued_tracks_are_prefetched() {
+        let mut st = AppState {
+            library: make_library(10, 1),
+            ..Default::default()
+        };
+        st.queue.ordered_tracks = st.library.track_ids.clone();
+        st.queue.current_index = 2;
+        st.queue.manual_queue = ["t7", "t8", "t6"]
+            .into_iter()
+            .map(|t| TrackId(t.to_string()))
+            .collect();
+
+        // Only as many queued tracks as the window's radius are prefetched.
+        let window = compute_cache_window(&st, 2);
+        assert!(window.contains(&TrackId("t7".to_string())));
+        assert!(window.contains(&TrackId("t8".to_string())));
+        assert!(!window.contains(&TrackId("t6".to_string())));
+    }
+
     #[test]
     fn compute_window_from_queue_empty() {
         let queue = make_queue();
@@ -1049,9 +1784,70 @@ mod tests {
         assert_ne!(ord1, ord2);
     }
 
+    #[test]
+    fn group_shuffle_keeps_track_order_within_groups() {
+        let library = make_library(20, 5);
+        let mut st = AppState {
+            library,
+            playback_mode: PlaybackMode::GroupShuffle,
+            ..AppState::default()
+        };
+        st.queue.group_shuffle_seed = 99;
+        recompute_queue_on_state(&mut st, None);
+
+        // Each run of four tracks is one whole group, in its own order.
+        let group_order: Vec<usize> = st
+            .queue
+            .ordered_tracks
+            .chunks(4)
+            .map(|chunk| {
+                let group_idx = st.library.track_to_group_index[&chunk[0]];
+                assert_eq!(chunk, st.library.groups[group_idx].tracks.as_slice());
+                group_idx
+            })
+            .collect();
+        // The groups themselves are shuffled.
+        assert_ne!(group_order, (0..5).collect::<Vec<_>>());
+
+        // Advancing past a group's last track lands on the next group's first.
+        for boundary in [4, 8, 12, 16] {
+            st.queue.current_index = boundary - 1;
+            assert_eq!(neighbour_index(&st, 1), Some(boundary));
+        }
+    }
+
+    #[test]
+    fn explain_next_describes_group_boundaries_and_wrapping() {
+        let library = make_library(8, 2);
+        let mut st = AppState {
+            library,
+            playback_mode: PlaybackMode::GroupShuffle,
+            ..AppState::default()
+        };
+        st.queue.group_shuffle_seed = 99;
+        recompute_queue_on_state(&mut st, None);
+
+        st.queue.current_index = 0;
+        let within = explain_next(&st);
+        assert!(within.contains("group shuffle seed 99"), "{within}");
+        assert!(within.contains("stays in the same group"), "{within}");
+
+        st.queue.current_index = 3;
+        let boundary = explain_next(&st);
+        assert!(boundary.contains("moves on to the group"), "{boundary}");
+
+        st.queue.current_index = 7;
+        let wrap = explain_next(&st);
+        assert!(wrap.contains("wraps to the start"), "{wrap}");
+    }
+
     #[test]
     fn bump_shuffle_seed_for_mode_rotates_track_seed_for_track_shuffles() {
-        for mode in [PlaybackMode::Shuffle, PlaybackMode::LikedShuffle] {
+        for mode in [
+            PlaybackMode::Shuffle,
+            PlaybackMode::LikedShuffle,
+            PlaybackMode::ArtistShuffle,
+        ] {
             let mut queue = make_queue();
             let track_before = queue.shuffle_seed;
             let group_before = queue.group_shuffle_seed;
@@ -1157,4 +1953,283 @@ mod tests {
         assert!(approx_eq(info.factor, 10f32.powf(0.3)));
         assert!(approx_eq(info.inv_peak, 1.0 / 0.9));
     }
+
+    #[test]
+    fn replaygain_multiplier_never_pushes_the_peak_past_full_scale() {
+        for (gain_db, peak, preamp_db) in [
+            (6.0, 0.9, 0.0),
+            (-3.0, 0.99, 6.0),
+            (-8.0, 0.5, 0.0),
+            (2.0, 1.2, 0.0),
+        ] {
+            let rg = ReplayGain {
+                track_gain: Some(gain_db),
+                track_peak: Some(peak),
+                ..Default::default()
+            };
+            let info = compute_replaygain_info(Some(&rg)).unwrap();
+            let multiplier = info.multiplier(10f32.powf(preamp_db / 20.0));
+            assert!(
+                multiplier * peak <= 1.0 + 1e-6,
+                "gain {gain_db} dB, peak {peak}, preamp {preamp_db} dB: {multiplier}"
+            );
+        }
+    }
+
+    #[test]
+    fn replaygain_multiplier_only_limits_gain_that_would_clip() {
+        let rg = ReplayGain {
+            album_gain: Some(-6.0),
+            album_peak: Some(0.9),
+            ..Default::default()
+        };
+        let info = compute_replaygain_info(Some(&rg)).unwrap();
+        // Attenuation leaves the peak well below full scale: no limiting.
+        assert!(approx_eq(info.multiplier(1.0), 0.501));
+        // A +12 dB preamp would clip, so the peak is brought to exactly 1.0.
+        assert!(approx_eq(info.multiplier(10f32.powf(0.6)), 1.0 / 0.9));
+
+        // Without peak data the gain is applied as-is.
+        let rg = ReplayGain {
+            album_gain: Some(6.0),
+            ..Default::default()
+        };
+        let info = compute_replaygain_info(Some(&rg)).unwrap();
+        assert!(approx_eq(info.multiplier(1.0), 10f32.powf(0.3)));
+    }
+
+    #[test]
+    fn end_behavior_wraps_or_stops_at_boundaries_in_every_mode() {
+        for mode in PlaybackMode::ALL {
+            for behavior in [BoundaryBehavior::Wrap, BoundaryBehavior::Stop] {
+                let library = make_library(6, 2);
+                let mut st = AppState {
+                    library,
+                    playback_mode: mode,
+                    ..AppState::default()
+                };
+                st.queue.shuffle_seed = 42;
+                st.queue.group_shuffle_seed = 99;
+                st.end_behavior.set_for_mode(mode, behavior);
+
+                // The first track is starred and in a starred group, so it
+                // appears in every mode's ordering.
+                let current = st.library.track_ids[0].clone();
+                recompute_queue_on_state(&mut st, Some(&current));
+                let len = st.queue.ordered_tracks.len();
+                assert!(len > 0, "mode {mode:?}");
+
+                st.queue.current_index = len - 1;
+                let expected_next = match behavior {
+                    BoundaryBehavior::Wrap => Some(0),
+                    BoundaryBehavior::Stop => None,
+                };
+                assert_eq!(
+                    neighbour_index(&st, 1),
+                    expected_next,
+                    "next at end, mode {mode:?}, {behavior:?}"
+                );
+
+                st.queue.current_index = 0;
+                let expected_prev = match behavior {
+                    BoundaryBehavior::Wrap => Some(len - 1),
+                    BoundaryBehavior::Stop => None,
+                };
+                assert_eq!(
+                    neighbour_index(&st, -1),
+                    expected_prev,
+                    "previous at start, mode {mode:?}, {behavior:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn end_behavior_does_not_affect_interior_steps() {
+        let library = make_library(5, 1);
+        let mut st = AppState {
+            library,
+            ..AppState::default()
+        };
+        st.end_behavior
+            .set_for_mode(PlaybackMode::Sequential, BoundaryBehavior::Stop);
+        recompute_queue_on_state(&mut st, None);
+
+        st.queue.current_index = 2;
+        assert_eq!(neighbour_index(&st, 1), Some(3));
+        assert_eq!(neighbour_index(&st, -1), Some(1));
+    }
+
+    #[test]
+    fn skipped_tracks_are_stepped_over() {
+        let mut st = AppState {
+            library: make_library(5, 1),
+            ..AppState::default()
+        };
+        recompute_queue_on_state(&mut st, None);
+        let ordered = st.queue.ordered_tracks.clone();
+        st.skipped_tracks.insert(ordered[1].clone());
+        st.skipped_tracks.insert(ordered[2].clone());
+
+        assert_eq!(unskipped_neighbour_index(&st, 1), Some(3));
+        st.queue.current_index = 3;
+        assert_eq!(unskipped_neighbour_index(&st, -1), Some(0));
+    }
+
+    #[test]
+    fn a_queue_of_skipped_tracks_falls_through_to_the_neighbour() {
+        let mut st = AppState {
+            library: make_library(5, 1),
+            ..AppState::default()
+        };
+        recompute_queue_on_state(&mut st, None);
+        st.skipped_tracks = st.queue.ordered_tracks.iter().cloned().collect();
+
+        assert_eq!(unskipped_neighbour_index(&st, 1), Some(1));
+    }
+
+    #[test]
+    fn album_resumes_from_its_last_played_track_until_it_finishes() {
+        let mut st = AppState {
+            library: make_library(6, 2),
+            ..AppState::default()
+        };
+        let group = st.library.groups[0].clone();
+        let album_id = &group.album_id;
+        let first = group.tracks[0].clone();
+        let last = group.tracks.last().unwrap().clone();
+
+        mark_album_progress(&mut st, &group.tracks[1]);
+        assert_eq!(album_start_track(&st, album_id, false), Some(first.clone()));
+        assert_eq!(
+            album_start_track(&st, album_id, true),
+            Some(group.tracks[1].clone())
+        );
+
+        // Finishing a track other than the last keeps the marker.
+        clear_finished_album(&mut st, &group.tracks[1]);
+        assert!(st.queue.album_resume.get(album_id).is_some());
+
+        mark_album_progress(&mut st, &last);
+        clear_finished_album(&mut st, &last);
+        assert_eq!(album_start_track(&st, album_id, true), Some(first));
+    }
+
+    #[test]
+    fn album_resume_markers_forget_the_least_recently_played_album() {
+        let mut markers = AlbumResumeMarkers::default();
+        let album = |i: usize| AlbumId(SmolStr::new(format!("album{i}")));
+        for i in 0..ALBUM_RESUME_CAPACITY {
+            markers.set(album(i), TrackId(format!("t{i}")));
+        }
+        // Replaying the first album makes it the most recent.
+        markers.set(album(0), TrackId("t0".into()));
+        markers.set(album(ALBUM_RESUME_CAPACITY), TrackId("new".into()));
+
+        assert!(markers.get(&album(0)).is_some());
+        assert!(markers.get(&album(1)).is_none());
+        assert!(markers.get(&album(ALBUM_RESUME_CAPACITY)).is_some());
+    }
+
+    fn track(id: &str) -> TrackId {
+        TrackId(id.into())
+    }
+
+    fn start(logic: &mut Logic<bs::MockClient>, id: &str) {
+        logic.simulate_playback(PlaybackToLogicMessage::TrackStarted(TrackAndPosition {
+            track_id: track(id),
+            position: Duration::ZERO,
+        }));
+    }
+
+    #[test]
+    fn queued_tracks_play_before_the_library_resumes() {
+        let mut logic = Logic::with_mock(bs::MockClient::fixture());
+        logic.request_play_track(&track("tr-debut-1"));
+        start(&mut logic, "tr-debut-1");
+        logic.enqueue_last(&track("tr-hits-2"));
+        logic.enqueue_last(&track("tr-second-1"));
+
+        logic.simulate_playback(PlaybackToLogicMessage::TrackEnded);
+        assert_eq!(logic.current_target(), Some(track("tr-hits-2")));
+        start(&mut logic, "tr-hits-2");
+        assert_eq!(logic.get_queue(), [track("tr-second-1")]);
+
+        logic.next();
+        assert_eq!(logic.current_target(), Some(track("tr-second-1")));
+        start(&mut logic, "tr-second-1");
+        assert!(logic.get_queue().is_empty());
+
+        logic.simulate_playback(PlaybackToLogicMessage::TrackEnded);
+        assert_eq!(logic.current_target(), Some(track("tr-debut-2")));
+    }
+
+    #[test]
+    fn picking_another_track_takes_an_album_back_out_of_the_queue() {
+        let mut logic = Logic::with_mock(bs::MockClient::fixture());
+        let debut = AlbumId("al-debut".into());
+        logic.enqueue_last(&track("tr-second-1"));
+
+        // Playing the album twice queues its rest once.
+        logic.play_group(&debut);
+        logic.play_group(&debut);
+        assert_eq!(logic.current_target(), Some(track("tr-debut-1")));
+        assert_eq!(
+            logic.get_queue(),
+            [track("tr-debut-2"), track("tr-second-1")]
+        );
+
+        // Another album replaces it.
+        logic.play_group(&AlbumId("al-hits".into()));
+        assert_eq!(
+            logic.get_queue(),
+            [track("tr-hits-2"), track("tr-second-1")]
+        );
+
+        // As does a single track, leaving what the user queued.
+        logic.request_play_track(&track("tr-debut-2"));
+        start(&mut logic, "tr-debut-2");
+        assert_eq!(logic.get_queue(), [track("tr-second-1")]);
+    }
+
+    #[test]
+    fn playback_errors_pause_or_skip_as_configured() {
+        let mut logic = Logic::with_mock(bs::MockClient::fixture());
+        logic.request_play_track(&track("tr-debut-1"));
+        start(&mut logic, "tr-debut-1");
+        let index = logic.read_state().queue.current_index;
+        let fail = |logic: &mut Logic<bs::MockClient>| {
+            logic.simulate_playback(PlaybackToLogicMessage::FailedToPlayTrack(
+                track("tr-debut-1"),
+                "undecodable".into(),
+            ));
+        };
+
+        logic.set_playback_error_behavior(PlaybackErrorBehavior::Pause);
+        fail(&mut logic);
+        assert_eq!(logic.current_target(), Some(track("tr-debut-1")));
+        assert_eq!(logic.read_state().queue.current_index, index);
+
+        logic.set_playback_error_behavior(PlaybackErrorBehavior::SkipNext);
+        fail(&mut logic);
+        assert_eq!(logic.current_target(), Some(track("tr-debut-2")));
+        start(&mut logic, "tr-debut-2");
+        assert_eq!(logic.read_state().queue.current_index, index + 1);
+    }
+
+    #[test]
+    fn playback_stops_after_the_queue_when_set_to() {
+        let mut logic = Logic::with_mock(bs::MockClient::fixture());
+        logic.set_queue_end_behavior(QueueEndBehavior::Stop);
+        logic.request_play_track(&track("tr-debut-1"));
+        start(&mut logic, "tr-debut-1");
+        logic.enqueue_last(&track("tr-hits-1"));
+
+        logic.simulate_playback(PlaybackToLogicMessage::TrackEnded);
+        assert_eq!(logic.current_target(), Some(track("tr-hits-1")));
+        start(&mut logic, "tr-hits-1");
+
+        logic.simulate_playback(PlaybackToLogicMessage::TrackEnded);
+        assert_eq!(logic.current_target(), Some(track("tr-hits-1")));
+    }
 }
@@ -0,0 +1,158 @@
+//! Album radio: a mix of tracks similar to an album, queued up after the
+//! playing track.
+use std::collections::HashSet;
+
+use blackbird_state::{AlbumId, ArtistId, TrackId};
+use smol_str::SmolStr;
+
+use crate::{Logic, app_state::AppStateError, bs};
+
+/// The maximum number of tracks in a radio mix.
+const RADIO_LENGTH: usize = 50;
+
+impl<C: bs::SubsonicApi> Logic<C> {
+    /// Starts a radio from `album_id`: looks up songs similar to its artist
+    /// and to the album itself, and queues a deduplicated mix of them after
+    /// the playing track, starting playback if nothing is playing. Servers
+    /// without similar-song support get random songs from the album's genre
+    /// instead.
+    ///
+    /// The mix lives in the current queue ordering, so it is replaced when the
+    /// queue is next recomputed (e.g. on a playback mode change).
+    pub fn start_album_radio(&self, album_id: &AlbumId) {
+        let (seeds, album_tracks, library_tracks, genre) = {
+            let st = self.read_state();
+            let library = &st.library;
+            let Some(group) = library
+                .album_to_group_index
+                .get(album_id)
+                .and_then(|&idx| library.groups.get(idx))
+            else {
+                tracing::warn!("Cannot start radio for unknown album {album_id}");
+                return;
+            };
+            let library_tracks: HashSet<TrackId> = library.track_map.keys().cloned().collect();
+            let album = library.albums.get(album_id);
+            let genre = album.and_then(|a| a.genre.clone());
+            (
+                seed_ids(album_id, album.and_then(|a| a.artist_id.as_ref())),
+                group.tracks.iter().cloned().collect::<HashSet<_>>(),
+                library_tracks,
+                genre,
+            )
+        };
+
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let track_updated_tx = self.track_updated_tx.clone();
+        let album_id = album_id.clone();
+        self.tokio_thread.spawn(async move {
+            // Only tracks in the library can be shown and played; the album's
+            // own tracks are left out so the radio moves on from it.
+            let usable = |songs: Vec<bs::Child>| -> Vec<TrackId> {
+                songs
+                    .into_iter()
+                    .map(|song| TrackId(song.id))
+                    .filter(|id| library_tracks.contains(id) && !album_tracks.contains(id))
+                    .collect()
+            };
+
+            let mut last_error = None;
+            let mut lists = vec![];
+            for seed in &seeds {
+                match client
+                    .get_similar_songs_2(seed.as_str(), Some(RADIO_LENGTH as u32))
+                    .await
+                {
+                    Ok(songs) => lists.push(usable(songs)),
+                    Err(e) => {
+                        tracing::warn!("Failed to fetch songs similar to {seed}: {e}");
+                        last_error = Some(e.to_string());
+                    }
+                }
+            }
+            let mut radio = mix(lists, RADIO_LENGTH);
+
+            if radio.is_empty()
+                && let Some(genre) = genre
+            {
+                tracing::info!("No similar songs for album {album_id}; falling back to {genre:?}");
+                match client
+                    .get_random_songs(Some(RADIO_LENGTH as u32), Some(genre))
+                    .await
+                {
+                    Ok(songs) => radio = mix(vec![usable(songs)], RADIO_LENGTH),
+                    Err(e) => last_error = Some(e.to_string()),
+                }
+            }
+
+            let mut st = state.write().unwrap();
+            if radio.is_empty() {
+                st.error = Some(AppStateError::AlbumRadioFailed {
+                    album_id,
+                    error: last_error.unwrap_or_else(|| "no similar tracks found".to_string()),
+                });
+            } else {
+                tracing::info!("Queueing {} radio tracks for album {album_id}", radio.len());
+                st.queue.pending_radio = Some(radio);
+            }
+            drop(st);
+            let _ = track_updated_tx.send(());
+        });
+    }
+}
+
+/// The IDs to look up similar songs for: the album's artist, which is what
+/// the spec asks for, then the album itself, which many servers also accept.
+fn seed_ids(album_id: &AlbumId, artist_id: Option<&ArtistId>) -> Vec<SmolStr> {
+    artist_id
+        .map(|id| id.0.clone())
+        .into_iter()
+        .chain([album_id.0.clone()])
+        .collect()
+}
+
+/// Interleaves `lists` round-robin, dropping repeats, up to `limit` tracks.
+fn mix(lists: Vec<Vec<TrackId>>, limit: usize) -> Vec<TrackId> {
+    let mut iters: Vec<_> = lists.into_iter().map(Vec::into_iter).collect();
+    let mut seen = HashSet::new();
+    let mut out = vec![];
+    while out.len() < limit && !iters.is_empty() {
+        iters.retain_mut(|iter| match iter.next() {
+            Some(track) => {
+                if out.len() < limit && seen.insert(track.clone()) {
+                    out.push(track);
+                }
+                true
+            }
+            None => false,
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(names: &[&str]) -> Vec<TrackId> {
+        names.iter().map(|n| TrackId(n.to_string())).collect()
+    }
+
+    #[test]
+    fn seeds_are_the_artist_then_the_album() {
+        let album_id = AlbumId("al".into());
+        assert_eq!(
+            seed_ids(&album_id, Some(&ArtistId("ar".into()))),
+            vec![SmolStr::new("ar"), SmolStr::new("al")]
+        );
+        assert_eq!(seed_ids(&album_id, None), vec![SmolStr::new("al")]);
+    }
+
+    #[test]
+    fn mix_interleaves_without_repeats() {
+        let lists = vec![ids(&["a", "b", "c"]), ids(&["b", "d"]), ids(&["e"])];
+        assert_eq!(mix(lists.clone(), 10), ids(&["a", "b", "e", "d", "c"]));
+        assert_eq!(mix(lists, 2), ids(&["a", "b"]));
+    }
+}
@@ -2,18 +2,20 @@ use std::sync::Arc;
 
 use blackbird_state::Group;
 
-use crate::Logic;
+use crate::{Logic, bs};
 
 pub struct VisibleGroupSet {
     pub groups: Vec<Arc<Group>>,
     pub start_row: usize,
 }
 
-impl Logic {
+impl<C: bs::SubsonicApi> Logic<C> {
+    /// Sums the rows of the groups shown in the library; see
+    /// [`Library::shown_groups`](crate::Library::shown_groups).
     pub fn calculate_total_rows(&self, group_line_count_getter: impl Fn(&Group) -> usize) -> usize {
         self.read_state()
             .library
-            .groups
+            .shown_groups()
             .iter()
             .map(|group| group_line_count_getter(group))
             .sum()
@@ -33,7 +35,7 @@ impl Logic {
 
         // First pass: find albums that intersect with visible range
         let mut intersecting_album_indices = vec![];
-        let groups = &state.library.groups;
+        let groups = state.library.shown_groups();
         for (album_index, group) in groups.iter().enumerate() {
             let group_lines = group_line_count_getter(group);
             let group_range = current_row..(current_row + group_lines);
@@ -0,0 +1,363 @@
+//! Scrobble targets. [`Logic`](crate::Logic) decides when a play counts as a
+//! scrobble; each [`Scrobbler`] submits it to a single service.
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use blackbird_state::TrackId;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+use crate::{AppState, bs};
+
+/// The services plays are scrobbled to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScrobbleTargets {
+    /// Whether to scrobble plays to the Subsonic server.
+    pub server: bool,
+    /// ListenBrainz user token. When set, plays are also submitted to
+    /// ListenBrainz.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listenbrainz_token: Option<String>,
+    /// Whether to record listens in the local listen history, which
+    /// listening stats are aggregated from.
+    pub history: bool,
+}
+impl Default for ScrobbleTargets {
+    fn default() -> Self {
+        Self {
+            server: true,
+            listenbrainz_token: None,
+            history: true,
+        }
+    }
+}
+
+/// How long a play must last to be scrobbled: 30 seconds, or half the track
+/// if that's sooner. Tracks of unknown length need the full 30 seconds.
+pub(crate) fn threshold(track_duration: Option<Duration>) -> Duration {
+    const SCROBBLE_TIME_THRESHOLD: Duration = Duration::from_secs(30);
+    track_duration.map_or(SCROBBLE_TIME_THRESHOLD, |duration| {
+        SCROBBLE_TIME_THRESHOLD.min(duration / 2)
+    })
+}
+
+/// A play that has met the scrobble threshold.
+#[derive(Debug, Clone)]
+pub struct Scrobble {
+    pub track_id: TrackId,
+    pub title: SmolStr,
+    pub artist: SmolStr,
+    pub release: Option<SmolStr>,
+    pub duration_secs: Option<u32>,
+    pub recording_mbid: Option<SmolStr>,
+    pub release_mbid: Option<SmolStr>,
+    /// When the play started, in milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+}
+impl Scrobble {
+    /// Builds a scrobble for `track_id` from the library. Returns `None` if the
+    /// track is unknown.
+    pub fn from_state(state: &AppState, track_id: &TrackId, timestamp_ms: u64) -> Option<Self> {
+        let track = state.library.track_map.get(track_id)?;
+        let album = track
+            .album_id
+            .as_ref()
+            .and_then(|id| state.library.albums.get(id));
+        let artist = track
+            .artist
+            .clone()
+            .or_else(|| album.map(|a| a.artist.clone()))
+            .unwrap_or_default();
+        Some(Scrobble {
+            track_id: track_id.clone(),
+            title: track.title.clone(),
+            artist,
+            release: album.map(|a| a.name.clone()),
+            duration_secs: state
+                .track_duration(track_id)
+                .map(|duration| duration.as_secs() as u32),
+            recording_mbid: track.music_brainz_id.clone(),
+            release_mbid: album.and_then(|a| a.music_brainz_id.clone()),
+            timestamp_ms,
+        })
+    }
+}
+
+/// Why a [`Scrobbler`] failed to submit a play.
+#[derive(Debug)]
+pub enum ScrobbleError {
+    /// The Subsonic server rejected or failed the request.
+    Server(bs::ClientError),
+    /// The scrobble could not be serialized.
+    Serialize(serde_json::Error),
+    /// The HTTP request could not be sent.
+    Request(reqwest::Error),
+    /// The service responded with a non-success status.
+    Rejected {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+}
+impl std::fmt::Display for ScrobbleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScrobbleError::Server(e) => write!(f, "submit scrobble to server: {e}"),
+            ScrobbleError::Serialize(e) => write!(f, "serialize scrobble: {e}"),
+            ScrobbleError::Request(e) => write!(f, "send scrobble request: {e}"),
+            ScrobbleError::Rejected { status, body } => {
+                write!(f, "submit scrobble: service returned {status}: {body}")
+            }
+        }
+    }
+}
+impl std::error::Error for ScrobbleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScrobbleError::Server(e) => Some(e),
+            ScrobbleError::Serialize(e) => Some(e),
+            ScrobbleError::Request(e) => Some(e),
+            ScrobbleError::Rejected { .. } => None,
+        }
+    }
+}
+impl From<bs::ClientError> for ScrobbleError {
+    fn from(e: bs::ClientError) -> Self {
+        ScrobbleError::Server(e)
+    }
+}
+impl From<serde_json::Error> for ScrobbleError {
+    fn from(e: serde_json::Error) -> Self {
+        ScrobbleError::Serialize(e)
+    }
+}
+impl From<reqwest::Error> for ScrobbleError {
+    fn from(e: reqwest::Error) -> Self {
+        ScrobbleError::Request(e)
+    }
+}
+
+/// The future returned by [`Scrobbler::scrobble`].
+pub type ScrobbleFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<(), ScrobbleError>> + Send + Sync + 'a>>;
+
+/// A service that plays can be submitted to.
+pub trait Scrobbler: Send + Sync {
+    /// A short name for the service, used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Submits `scrobble` to the service.
+    fn scrobble<'a>(&'a self, scrobble: &'a Scrobble) -> ScrobbleFuture<'a>;
+
+    /// Whether a scrobble changes the play counts reported by the Subsonic
+    /// server, so the track should be re-fetched afterwards.
+    fn updates_play_count(&self) -> bool {
+        false
+    }
+}
+
+/// Builds the scrobblers enabled by `targets`. Server scrobbles go through
+/// `client`.
+pub(crate) fn build_scrobblers<C: bs::SubsonicApi>(
+    targets: &ScrobbleTargets,
+    client: &Arc<C>,
+) -> Vec<Arc<dyn Scrobbler>> {
+    let mut scrobblers: Vec<Arc<dyn Scrobbler>> = vec![];
+    if targets.server {
+        scrobblers.push(Arc::new(SubsonicScrobbler {
+            client: client.clone(),
+        }));
+    }
+    if let Some(token) = targets
+        .listenbrainz_token
+        .as_ref()
+        .filter(|t| !t.is_empty())
+    {
+        scrobblers.push(Arc::new(ListenBrainzScrobbler::new(token.clone())));
+    }
+    scrobblers
+}
+
+/// Scrobbles to the Subsonic server, which also updates its play counts.
+pub struct SubsonicScrobbler<C = bs::Client> {
+    client: Arc<C>,
+}
+impl<C: bs::SubsonicApi> Scrobbler for SubsonicScrobbler<C> {
+    fn name(&self) -> &'static str {
+        "subsonic"
+    }
+
+    fn scrobble<'a>(&'a self, scrobble: &'a Scrobble) -> ScrobbleFuture<'a> {
+        Box::pin(async move {
+            self.client
+                .scrobble(
+                    &scrobble.track_id.0,
+                    Some(scrobble.timestamp_ms),
+                    Some(true),
+                )
+                .await
+                .map_err(ScrobbleError::from)
+        })
+    }
+
+    fn updates_play_count(&self) -> bool {
+        true
+    }
+}
+
+/// Submits listens to ListenBrainz with a user token.
+pub struct ListenBrainzScrobbler {
+    token: String,
+    client: reqwest::Client,
+}
+impl ListenBrainzScrobbler {
+    const SUBMIT_URL: &str = "https://api.listenbrainz.org/1/submit-listens";
+
+    pub fn new(token: String) -> Self {
+        Self {
+            token,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn payload(scrobble: &Scrobble) -> serde_json::Value {
+        let mut additional_info = serde_json::json!({
+            "submission_client": "blackbird",
+        });
+        if let Some(duration) = scrobble.duration_secs {
+            additional_info["duration_ms"] = (u64::from(duration) * 1000).into();
+        }
+        if let Some(mbid) = &scrobble.recording_mbid {
+            additional_info["recording_mbid"] = mbid.as_str().into();
+        }
+        if let Some(mbid) = &scrobble.release_mbid {
+            additional_info["release_mbid"] = mbid.as_str().into();
+        }
+
+        let mut track_metadata = serde_json::json!({
+            "artist_name": scrobble.artist.as_str(),
+            "track_name": scrobble.title.as_str(),
+            "additional_info": additional_info,
+        });
+        if let Some(release) = &scrobble.release {
+            track_metadata["release_name"] = release.as_str().into();
+        }
+
+        serde_json::json!({
+            "listen_type": "single",
+            "payload": [{
+                "listened_at": scrobble.timestamp_ms / 1000,
+                "track_metadata": track_metadata,
+            }],
+        })
+    }
+}
+impl Scrobbler for ListenBrainzScrobbler {
+    fn name(&self) -> &'static str {
+        "listenbrainz"
+    }
+
+    fn scrobble<'a>(&'a self, scrobble: &'a Scrobble) -> ScrobbleFuture<'a> {
+        Box::pin(async move {
+            let body = serde_json::to_vec(&Self::payload(scrobble))?;
+            let response = self
+                .client
+                .post(Self::SUBMIT_URL)
+                .header("Authorization", format!("Token {}", self.token))
+                .header("Content-Type", "application/json")
+                .body(body)
+                .send()
+                .await?;
+            let status = response.status();
+            if status.is_success() {
+                Ok(())
+            } else {
+                let body = response.text().await.unwrap_or_default();
+                Err(ScrobbleError::Rejected { status, body })
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Logic, PlaybackToLogicMessage, TrackAndPosition, test_util::wait_until};
+
+    #[test]
+    fn threshold_is_half_of_short_tracks_and_thirty_seconds_otherwise() {
+        assert_eq!(
+            threshold(Some(Duration::from_secs(40))),
+            Duration::from_secs(20)
+        );
+        assert_eq!(
+            threshold(Some(Duration::from_secs(200))),
+            Duration::from_secs(30)
+        );
+        assert_eq!(threshold(None), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn listenbrainz_payload_includes_optional_fields_when_present() {
+        let mut scrobble = Scrobble {
+            track_id: TrackId("t1".into()),
+            title: "Title".into(),
+            artist: "Artist".into(),
+            release: Some("Release".into()),
+            duration_secs: Some(200),
+            recording_mbid: Some("rec".into()),
+            release_mbid: None,
+            timestamp_ms: 1_700_000_000_123,
+        };
+        let payload = ListenBrainzScrobbler::payload(&scrobble);
+        let listen = &payload["payload"][0];
+        assert_eq!(listen["listened_at"], 1_700_000_000);
+        let metadata = &listen["track_metadata"];
+        assert_eq!(metadata["release_name"], "Release");
+        assert_eq!(metadata["additional_info"]["recording_mbid"], "rec");
+        assert_eq!(metadata["additional_info"]["duration_ms"], 200_000);
+        assert!(metadata["additional_info"].get("release_mbid").is_none());
+
+        scrobble.release = None;
+        let payload = ListenBrainzScrobbler::payload(&scrobble);
+        assert!(
+            payload["payload"][0]["track_metadata"]
+                .get("release_name")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn plays_are_reported_then_scrobbled_to_the_server() {
+        let client = bs::MockClient::fixture();
+        let mut logic = Logic::with_mock(client.clone());
+
+        let track_id = TrackId("tr-debut-1".into());
+        let at = |secs| TrackAndPosition {
+            track_id: track_id.clone(),
+            position: Duration::from_secs(secs),
+        };
+        logic.request_play_track(&track_id);
+        logic.simulate_playback(PlaybackToLogicMessage::TrackStarted(at(0)));
+        wait_until(|| !client.scrobbles().is_empty());
+        assert_eq!(
+            client.scrobbles(),
+            [bs::MockScrobble {
+                id: track_id.0.clone(),
+                time: None,
+                submission: Some(false),
+            }]
+        );
+
+        // Five seconds is too short a listen to scrobble.
+        logic.simulate_position(at(5));
+        assert!(!logic.read_state().scrobble_state.has_scrobbled);
+
+        logic.simulate_position(at(40));
+        assert!(logic.read_state().scrobble_state.has_scrobbled);
+        wait_until(|| client.scrobbles().len() == 2);
+        let scrobble = &client.scrobbles()[1];
+        assert_eq!(scrobble.id, track_id.0);
+        assert!(scrobble.time.is_some());
+        assert_eq!(scrobble.submission, Some(true));
+    }
+}
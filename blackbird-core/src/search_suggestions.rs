@@ -0,0 +1,148 @@
+//! Artist and album name completions from the server's `search3` endpoint,
+//! shown as the user types a search query, and the server's own track
+//! results for the query.
+use std::collections::{HashMap, VecDeque};
+
+use smol_str::SmolStr;
+
+use crate::bs;
+
+const SUGGESTION_CACHE_SIZE: usize = 50;
+
+/// The number of artists and of albums requested per query.
+pub(crate) const SUGGESTION_COUNT: u32 = 5;
+
+/// The number of tracks requested per server search.
+pub(crate) const SERVER_SEARCH_TRACK_COUNT: u32 = 50;
+
+/// An artist or album name matching a search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchSuggestion {
+    Artist(SmolStr),
+    Album { name: SmolStr, artist: SmolStr },
+}
+
+impl SearchSuggestion {
+    /// The text that replaces the search query when the suggestion is picked.
+    pub fn text(&self) -> &str {
+        match self {
+            SearchSuggestion::Artist(name) => name,
+            SearchSuggestion::Album { name, .. } => name,
+        }
+    }
+
+    /// Builds suggestions from a `search3` response, artists first.
+    pub(crate) fn from_response(response: bs::Search3Response) -> Vec<Self> {
+        let artists = response
+            .artist
+            .into_iter()
+            .map(|artist| SearchSuggestion::Artist(artist.name.into()));
+        let albums = response
+            .album
+            .into_iter()
+            .map(|album| SearchSuggestion::Album {
+                name: album.name.into(),
+                artist: album.artist.unwrap_or_default().into(),
+            });
+        artists.chain(albums).collect()
+    }
+}
+
+/// Results for the most recent [`SUGGESTION_CACHE_SIZE`] queries, keyed
+/// case-insensitively.
+#[derive(Debug)]
+pub(crate) struct SuggestionCache<T = Vec<SearchSuggestion>> {
+    /// `None` while the query is being fetched.
+    entries: HashMap<String, Option<T>>,
+    order: VecDeque<String>,
+}
+
+impl<T> Default for SuggestionCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+}
+
+impl<T> SuggestionCache<T> {
+    /// Marks `query` as being fetched. Returns `false` if it is already cached
+    /// or in flight, in which case there is nothing to fetch.
+    pub fn begin(&mut self, query: &str) -> bool {
+        let key = query.to_lowercase();
+        if self.entries.contains_key(&key) {
+            return false;
+        }
+        self.entries.insert(key.clone(), None);
+        self.order.push_back(key);
+        if self.order.len() > SUGGESTION_CACHE_SIZE
+            && let Some(oldest) = self.order.pop_front()
+        {
+            self.entries.remove(&oldest);
+        }
+        true
+    }
+
+    /// Stores the fetched results for `query`, unless it has since been
+    /// evicted.
+    pub fn finish(&mut self, query: &str, results: T) {
+        if let Some(entry) = self.entries.get_mut(&query.to_lowercase()) {
+            *entry = Some(results);
+        }
+    }
+
+    /// Forgets an in-flight `query` whose fetch failed, so it can be retried.
+    pub fn abandon(&mut self, query: &str) {
+        let key = query.to_lowercase();
+        if self.entries.get(&key).is_some_and(Option::is_none) {
+            self.entries.remove(&key);
+            self.order.retain(|k| *k != key);
+        }
+    }
+
+    /// The results for `query`, if they have been fetched.
+    pub fn get(&self, query: &str) -> Option<&T> {
+        self.entries.get(&query.to_lowercase())?.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artist(name: &str) -> Vec<SearchSuggestion> {
+        vec![SearchSuggestion::Artist(name.into())]
+    }
+
+    #[test]
+    fn queries_are_fetched_once_and_cached_case_insensitively() {
+        let mut cache = SuggestionCache::default();
+        assert!(cache.begin("Boa"));
+        assert!(!cache.begin("boa"), "in-flight queries aren't refetched");
+        assert_eq!(cache.get("boa"), None);
+
+        cache.finish("BOA", artist("Boards of Canada"));
+        assert_eq!(cache.get("boa"), Some(&artist("Boards of Canada")));
+        assert!(!cache.begin("Boa"));
+    }
+
+    #[test]
+    fn abandoned_queries_can_be_retried() {
+        let mut cache: SuggestionCache = SuggestionCache::default();
+        assert!(cache.begin("boa"));
+        cache.abandon("boa");
+        assert!(cache.begin("boa"));
+    }
+
+    #[test]
+    fn oldest_queries_are_evicted() {
+        let mut cache: SuggestionCache = SuggestionCache::default();
+        for idx in 0..=SUGGESTION_CACHE_SIZE {
+            assert!(cache.begin(&format!("query {idx}")));
+            cache.finish(&format!("query {idx}"), vec![]);
+        }
+        assert_eq!(cache.get("query 0"), None);
+        assert!(cache.get("query 1").is_some());
+    }
+}
@@ -0,0 +1,158 @@
+//! Library fixtures and helpers shared by the unit tests.
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use blackbird_state::{Album, AlbumId, Group, Track, TrackId};
+use smol_str::SmolStr;
+
+use crate::{Library, SortOrder};
+
+pub(crate) fn make_track(idx: usize) -> Track {
+    Track {
+        id: TrackId(format!("t{idx}")),
+        title: SmolStr::new(format!("Track {idx}")),
+        artist: None,
+        track: None,
+        year: None,
+        _genre: None,
+        duration: Some(180),
+        disc_number: None,
+        starred: idx.is_multiple_of(3), // every 3rd track is starred
+        starred_at: None,
+        created: None,
+        play_count: None,
+        album_id: None,
+        replay_gain: None,
+        music_brainz_id: None,
+        suffix: None,
+        transcoded_suffix: None,
+        path: None,
+        is_video: false,
+    }
+}
+
+pub(crate) fn make_group(g: usize, track_ids: Vec<TrackId>) -> Arc<Group> {
+    Arc::new(Group {
+        album_id: album_id(g),
+        edition_album_ids: vec![],
+        album: SmolStr::new(format!("Album {g}")),
+        artist: SmolStr::new(format!("Artist {g}")),
+        sort_artist: SmolStr::new(format!("Artist {g}")),
+        year: None,
+        duration: 0,
+        tracks: track_ids,
+        cover_art_id: None,
+        cover_art_fallbacks: vec![],
+        starred: g.is_multiple_of(2), // every other group is starred
+    })
+}
+
+pub(crate) fn album_id(g: usize) -> AlbumId {
+    AlbumId(SmolStr::new(format!("album{g}")))
+}
+
+/// The album behind the group made by [`make_group`].
+pub(crate) fn make_album(g: usize, track_count: usize) -> Album {
+    Album {
+        id: album_id(g),
+        name: SmolStr::new(format!("Album {g}")),
+        artist: SmolStr::new(format!("Artist {g}")),
+        artist_id: None,
+        cover_art_id: None,
+        cover_art_fallbacks: vec![],
+        track_count: track_count as u32,
+        duration: 0,
+        year: None,
+        genre: None,
+        starred: g.is_multiple_of(2),
+        starred_at: None,
+        created: SmolStr::default(),
+        music_brainz_id: None,
+    }
+}
+
+/// An unstarred track of unknown length with the given title and artist, on
+/// the album `album_id`.
+pub(crate) fn make_named_track(id: &str, title: &str, artist: &str, album_id: &str) -> Track {
+    Track {
+        id: TrackId(id.into()),
+        title: title.into(),
+        artist: Some(artist.into()),
+        duration: None,
+        starred: false,
+        album_id: Some(AlbumId(album_id.into())),
+        ..make_track(0)
+    }
+}
+
+/// An unstarred album with the given name and artist.
+pub(crate) fn make_named_album(id: &str, name: &str, artist: &str) -> Album {
+    Album {
+        id: AlbumId(id.into()),
+        name: name.into(),
+        artist: artist.into(),
+        starred: false,
+        ..make_album(0, 0)
+    }
+}
+
+/// The group for `album`, holding `tracks`.
+pub(crate) fn make_album_group(album: &Album, tracks: &[&str]) -> Arc<Group> {
+    Arc::new(Group {
+        album_id: album.id.clone(),
+        album: album.name.clone(),
+        artist: album.artist.clone(),
+        sort_artist: album.artist.clone(),
+        tracks: tracks.iter().map(|id| TrackId((*id).into())).collect(),
+        starred: false,
+        ..(*make_group(0, vec![])).clone()
+    })
+}
+
+/// Creates a minimal library with `n` tracks spread across `group_count` groups.
+pub(crate) fn make_library(n: usize, group_count: usize) -> Library {
+    let mut library = Library::default();
+    let mut track_map = HashMap::new();
+    let mut groups = Vec::new();
+    let mut albums = HashMap::new();
+
+    let tracks_per_group = n / group_count.max(1);
+    let mut track_idx = 0;
+
+    for g in 0..group_count {
+        let mut group_tracks = Vec::new();
+        let count = if g == group_count - 1 {
+            n - track_idx
+        } else {
+            tracks_per_group
+        };
+        for _ in 0..count {
+            let mut track = make_track(track_idx);
+            track.album_id = Some(album_id(g));
+            let tid = track.id.clone();
+            track_map.insert(tid.clone(), track);
+            group_tracks.push(tid);
+            track_idx += 1;
+        }
+        groups.push(make_group(g, group_tracks));
+        albums.insert(album_id(g), make_album(g, count));
+    }
+
+    library.populate(vec![], track_map, groups, albums, SortOrder::Alphabetical);
+    library
+}
+
+/// Polls `condition` until it holds, failing the test after a few seconds.
+pub(crate) fn wait_until(condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !condition() {
+        assert!(
+            Instant::now() < deadline,
+            "timed out waiting for a condition"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
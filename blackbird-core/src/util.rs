@@ -1,3 +1,6 @@
+/// Shown in place of a duration that isn't known.
+pub const UNKNOWN_DURATION: &str = "--:--";
+
 /// Convert a number of seconds to a string in the format "HH:MM:SS".
 /// If the number of hours is 0, it will be omitted.
 ///
@@ -25,6 +28,22 @@ pub fn seconds_to_hms_string(seconds: u32, pad_first: bool) -> String {
     }
 }
 
+/// Abbreviates a count to at most one decimal place, e.g. 1234 to "1.2k" and
+/// 1000000 to "1M". Counts are truncated rather than rounded, so an
+/// abbreviation never overstates the count.
+pub fn abbreviate_count(count: u64) -> String {
+    const SCALES: [(u64, &str); 3] = [(1_000_000_000, "B"), (1_000_000, "M"), (1_000, "k")];
+    let Some((scale, suffix)) = SCALES.into_iter().find(|(scale, _)| count >= *scale) else {
+        return count.to_string();
+    };
+    let tenths = count / (scale / 10);
+    if tenths.is_multiple_of(10) {
+        format!("{}{suffix}", tenths / 10)
+    } else {
+        format!("{}.{}{suffix}", tenths / 10, tenths % 10)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +81,17 @@ mod tests {
         assert_eq!(seconds_to_hms_string(0, false), "0:00");
         assert_eq!(seconds_to_hms_string(59, false), "0:59");
     }
+
+    #[test]
+    fn test_abbreviate_count() {
+        assert_eq!(abbreviate_count(0), "0");
+        assert_eq!(abbreviate_count(999), "999");
+        assert_eq!(abbreviate_count(1000), "1k");
+        assert_eq!(abbreviate_count(1234), "1.2k");
+        assert_eq!(abbreviate_count(1500), "1.5k");
+        assert_eq!(abbreviate_count(999_999), "999.9k");
+        assert_eq!(abbreviate_count(1_000_000), "1M");
+        assert_eq!(abbreviate_count(12_345_678), "12.3M");
+        assert_eq!(abbreviate_count(2_000_000_000), "2B");
+    }
 }
@@ -0,0 +1,209 @@
+//! `--fix-tags`: normalizes the tags of each file before it's organized, so
+//! that the server groups and matches them consistently. Only the items
+//! being normalized are touched; everything else in the tag is kept.
+use std::{collections::HashSet, fs, io::Write, path::Path};
+
+use anyhow::{Context, Result};
+use lofty::{
+    config::WriteOptions,
+    file::TaggedFileExt,
+    read_from_path,
+    tag::{ItemKey, Tag, TagExt},
+};
+use walkdir::WalkDir;
+
+/// The text items that have surrounding whitespace stripped.
+const TRIMMED_KEYS: &[ItemKey] = &[
+    ItemKey::TrackTitle,
+    ItemKey::TrackArtist,
+    ItemKey::AlbumTitle,
+    ItemKey::AlbumArtist,
+];
+
+/// A single item rewritten by the normalization.
+struct TagChange {
+    key: ItemKey,
+    old: Option<String>,
+    new: String,
+}
+
+/// Normalizes the tags of the music files in `input_dir`, skipping anything
+/// under `output_dir`. Files that fail to update are reported and skipped.
+/// Returns how many files were (or, in a dry run, would be) changed.
+pub fn fix_directory(
+    input_dir: &Path,
+    output_dir: &Path,
+    music_extensions: &HashSet<&str>,
+    dry_run: bool,
+    verbose: bool,
+    report_file: &mut Option<fs::File>,
+) -> Result<usize> {
+    let mut fixed_count = 0;
+    for entry in WalkDir::new(input_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && crate::is_music_file(e.path(), music_extensions))
+    {
+        let file_path = entry.path();
+        if file_path.starts_with(output_dir) {
+            continue;
+        }
+
+        let changes = match fix_file(file_path, dry_run) {
+            Ok(changes) => changes,
+            Err(e) => {
+                tracing::error!("Failed to fix tags of {}: {e:?}", file_path.display());
+                continue;
+            }
+        };
+        if changes.is_empty() {
+            continue;
+        }
+        fixed_count += 1;
+
+        let relative_path = file_path.strip_prefix(input_dir).unwrap_or(file_path);
+        for change in &changes {
+            let report_line = format!(
+                "{}: {:?} {:?} -> {:?}",
+                relative_path.display(),
+                change.key,
+                change.old.as_deref().unwrap_or_default(),
+                change.new
+            );
+            if verbose {
+                tracing::info!("{report_line}");
+            }
+            if let Some(file) = report_file {
+                writeln!(file, "{report_line}")
+                    .with_context(|| "Failed to write to report file")?;
+            }
+        }
+    }
+
+    Ok(fixed_count)
+}
+
+/// Normalizes the tag of a single file, writing it back unless `dry_run` is
+/// set. Returns the changes made.
+fn fix_file(file_path: &Path, dry_run: bool) -> Result<Vec<TagChange>> {
+    let mut tagged_file = read_from_path(file_path)
+        .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
+    let has_primary_tag = tagged_file.primary_tag().is_some();
+    let tag = if has_primary_tag {
+        tagged_file.primary_tag_mut()
+    } else {
+        tagged_file.first_tag_mut()
+    }
+    .with_context(|| format!("No tags found in file: {}", file_path.display()))?;
+
+    let changes = normalize(tag);
+    if !dry_run && !changes.is_empty() {
+        tag.save_to_path(file_path, WriteOptions::default())
+            .with_context(|| format!("Failed to write tags to {}", file_path.display()))?;
+    }
+    Ok(changes)
+}
+
+/// Applies the normalizations to `tag` in place, returning what changed.
+fn normalize(tag: &mut Tag) -> Vec<TagChange> {
+    let mut changes = vec![];
+    let mut set = |tag: &mut Tag, key: ItemKey, new: String| {
+        let old = tag.get_string(&key).map(str::to_string);
+        if old.as_deref() == Some(new.as_str()) {
+            return;
+        }
+        tag.insert_text(key.clone(), new.clone());
+        changes.push(TagChange { key, old, new });
+    };
+
+    for key in TRIMMED_KEYS {
+        if let Some(value) = tag.get_string(key) {
+            let trimmed = value.trim().to_string();
+            if !trimmed.is_empty() {
+                set(tag, key.clone(), trimmed);
+            }
+        }
+    }
+
+    // Fall back to the track artist, which is what the file would otherwise
+    // be organized under, so that the whole album groups under one artist.
+    let has_album_artist = tag
+        .get_string(&ItemKey::AlbumArtist)
+        .is_some_and(|value| !value.trim().is_empty());
+    if !has_album_artist && let Some(artist) = tag.get_string(&ItemKey::TrackArtist) {
+        let artist = artist.to_string();
+        set(tag, ItemKey::AlbumArtist, artist);
+    }
+
+    if let Some(track_number) = tag
+        .get_string(&ItemKey::TrackNumber)
+        .and_then(tidy_track_number)
+    {
+        set(tag, ItemKey::TrackNumber, track_number);
+    }
+
+    changes
+}
+
+/// Rewrites a track number as `n`, or `n/m` if it has a total, without
+/// whitespace or leading zeros. `None` if it isn't a track number.
+fn tidy_track_number(value: &str) -> Option<String> {
+    let parse = |part: &str| part.trim().parse::<u32>().ok();
+    Some(match value.split_once('/') {
+        Some((number, total)) => format!("{}/{}", parse(number)?, parse(total)?),
+        None => parse(value)?.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use lofty::tag::TagType;
+
+    use super::*;
+
+    fn tag(items: &[(ItemKey, &str)]) -> Tag {
+        let mut tag = Tag::new(TagType::Id3v2);
+        for (key, value) in items {
+            tag.insert_text(key.clone(), value.to_string());
+        }
+        tag
+    }
+
+    #[test]
+    fn tidy_track_number_keeps_the_total() {
+        assert_eq!(tidy_track_number("3").as_deref(), Some("3"));
+        assert_eq!(tidy_track_number(" 03 ").as_deref(), Some("3"));
+        assert_eq!(tidy_track_number("3 / 12").as_deref(), Some("3/12"));
+        assert_eq!(tidy_track_number("03/012").as_deref(), Some("3/12"));
+        assert_eq!(tidy_track_number("A1"), None);
+        assert_eq!(tidy_track_number("3/"), None);
+    }
+
+    #[test]
+    fn normalize_trims_and_fills_in_the_album_artist() {
+        let mut tag = tag(&[
+            (ItemKey::TrackTitle, " Song "),
+            (ItemKey::TrackArtist, "Artist"),
+            (ItemKey::TrackNumber, "4/10"),
+        ]);
+        let changes = normalize(&mut tag);
+
+        assert_eq!(tag.get_string(&ItemKey::TrackTitle), Some("Song"));
+        assert_eq!(tag.get_string(&ItemKey::AlbumArtist), Some("Artist"));
+        assert_eq!(tag.get_string(&ItemKey::TrackNumber), Some("4/10"));
+        let keys: Vec<_> = changes.iter().map(|change| &change.key).collect();
+        assert_eq!(keys, [&ItemKey::TrackTitle, &ItemKey::AlbumArtist]);
+    }
+
+    #[test]
+    fn normalize_leaves_tidy_tags_alone() {
+        let mut tag = tag(&[
+            (ItemKey::TrackTitle, "Song"),
+            (ItemKey::TrackArtist, "Artist"),
+            (ItemKey::AlbumArtist, "Band"),
+            (ItemKey::TrackNumber, "4"),
+        ]);
+        assert!(normalize(&mut tag).is_empty());
+        assert_eq!(tag.get_string(&ItemKey::AlbumArtist), Some("Band"));
+    }
+}
@@ -6,11 +6,14 @@ use std::{
 };
 
 use anyhow::{Context, Result};
+use blackbird_shared::track_path::TrackPath;
 use clap::Parser;
 use lofty::{file::TaggedFileExt, read_from_path};
-use sanitize_filename::sanitize;
 use walkdir::WalkDir;
 
+mod fix_tags;
+mod verify;
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
@@ -32,11 +35,25 @@ struct Args {
     /// Write file operation report to the specified file
     #[arg(long)]
     output_report: Option<PathBuf>,
+
+    /// Report which files aren't on the Subsonic server from the shared
+    /// config, instead of organizing them. No files are moved or copied.
+    #[arg(long)]
+    verify_against_server: bool,
+
+    /// Normalize each file's tags before organizing it: strip surrounding
+    /// whitespace, fill in a missing album artist from the artist, and tidy
+    /// track numbers into `n` or `n/m`. Respects --dry-run.
+    #[arg(long)]
+    fix_tags: bool,
 }
 
 fn main() {
     let args = Args::parse();
 
+    let subscriber = tracing_subscriber::FmtSubscriber::new();
+    tracing::subscriber::set_global_default(subscriber).unwrap();
+
     if !args.directory.exists() {
         eprintln!(
             "Error: Directory '{}' does not exist",
@@ -47,18 +64,6 @@ fn main() {
 
     let output_dir = args.directory.join("output");
 
-    let operation = if args.copy { "Copying" } else { "Moving" };
-    let operation_lower = if args.copy { "copying" } else { "moving" };
-
-    if args.dry_run {
-        println!("DRY RUN MODE - No files will be {operation_lower}");
-        println!("Output directory: {}", output_dir.display());
-        println!();
-    } else {
-        println!("{operation} files to: {}", output_dir.display());
-        println!();
-    }
-
     // Open report file if specified
     let mut report_file = if let Some(ref report_path) = args.output_report {
         match fs::OpenOptions::new()
@@ -89,6 +94,61 @@ fn main() {
         .cloned()
         .collect();
 
+    if args.verify_against_server {
+        match verify::verify_directory(
+            &args.directory,
+            &music_extensions,
+            args.verbose,
+            &mut report_file,
+        ) {
+            Ok((count, new_count)) => {
+                tracing::info!(
+                    "Verification complete. {new_count} of {count} files are not on the server."
+                );
+            }
+            Err(e) => {
+                tracing::error!("{e:?}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let operation = if args.copy { "Copying" } else { "Moving" };
+    let operation_lower = if args.copy { "copying" } else { "moving" };
+
+    if args.dry_run {
+        println!("DRY RUN MODE - No files will be {operation_lower}");
+        println!("Output directory: {}", output_dir.display());
+        println!();
+    } else {
+        println!("{operation} files to: {}", output_dir.display());
+        println!();
+    }
+
+    if args.fix_tags {
+        match fix_tags::fix_directory(
+            &args.directory,
+            &output_dir,
+            &music_extensions,
+            args.dry_run,
+            args.verbose,
+            &mut report_file,
+        ) {
+            Ok(count) => {
+                if args.dry_run {
+                    tracing::info!("Tags would be fixed in {count} files.");
+                } else {
+                    tracing::info!("Tags fixed in {count} files.");
+                }
+            }
+            Err(e) => {
+                tracing::error!("{e:?}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     match process_directory(
         &args.directory,
         &output_dir,
@@ -136,14 +196,7 @@ fn process_directory(
             continue;
         }
 
-        // Check if it's a music file
-        let Some(extension) = file_path.extension() else {
-            continue;
-        };
-        let Some(ext_str) = extension.to_str() else {
-            continue;
-        };
-        if !music_extensions.contains(ext_str.to_lowercase().as_str()) {
+        if !is_music_file(file_path, music_extensions) {
             continue;
         }
         match process_music_file(
@@ -166,6 +219,13 @@ fn process_directory(
     Ok(processed_count)
 }
 
+fn is_music_file(file_path: &Path, music_extensions: &HashSet<&str>) -> bool {
+    file_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|ext_str| music_extensions.contains(ext_str.to_lowercase().as_str()))
+}
+
 fn process_music_file(
     file_path: &Path,
     input_dir: &Path,
@@ -206,31 +266,25 @@ fn process_music_file(
         track_title
     });
 
-    // Get track number (pad to 2 digits)
-    let track_num = metadata
-        .track_number
-        .map(|t| format!("{t:02}"))
-        .unwrap_or_else(|| "00".to_string());
-
     // Get file extension
     let file_extension = file_path
         .extension()
         .and_then(|ext| ext.to_str())
         .with_context(|| format!("Missing file extension for {file_path_display}"))?;
 
-    // Build filename with optional disc number
-    let filename = if let Some(disc_num) = metadata.disc_number {
-        format!("{track_num} - {track_title} [{disc_num}].{file_extension}")
-    } else {
-        format!("{track_num} - {track_title}.{file_extension}")
-    };
-
     // Build target path with sanitized names
-    let target_dir = output_dir
-        .join(sanitize(album_artist))
-        .join(sanitize(album));
-
-    let target_path = target_dir.join(sanitize(&filename));
+    let target_path = output_dir.join(
+        TrackPath {
+            album_artist,
+            album,
+            title: track_title,
+            track_number: metadata.track_number,
+            disc_number: metadata.disc_number,
+            extension: file_extension,
+        }
+        .relative_path(),
+    );
+    let target_dir = target_path.parent().unwrap_or(output_dir);
     let target_path_display = target_path.display();
 
     // Format the movement report
@@ -260,7 +314,7 @@ fn process_music_file(
 
     if !dry_run {
         // Create target directory
-        fs::create_dir_all(&target_dir)
+        fs::create_dir_all(target_dir)
             .with_context(|| format!("Failed to create directory {target_dir:?}"))?;
 
         // Copy or move the file
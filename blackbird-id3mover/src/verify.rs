@@ -0,0 +1,239 @@
+//! `--verify-against-server`: checks which files are already on the Subsonic
+//! server by matching their tags against the server's library, and reports
+//! the ones that aren't, without touching any files.
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fs,
+    io::Write,
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use blackbird_shared::{
+    config::ConfigFile,
+    fuzzy::fuzzy_match,
+    normalize::{normalize_album_name, normalize_artist_name},
+};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+/// Partial view of the shared blackbird config — only the fields this tool
+/// needs. Unknown sections written by the clients are ignored on load.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Config {
+    server: blackbird_shared::config::Server,
+}
+
+impl ConfigFile for Config {}
+
+/// How similar names have to be to match.
+const FUZZY_THRESHOLD: f64 = 0.8;
+
+/// Normalized album -> lowercased titles of its tracks.
+type ArtistAlbums = HashMap<String, HashSet<String>>;
+
+/// The server's tracks, indexed for matching files against.
+struct ServerLibrary {
+    /// Normalized artist -> their albums, under both the album artist and
+    /// the track artist. Fuzzy matching narrows down by artist, then album,
+    /// before comparing titles.
+    artists: HashMap<String, ArtistAlbums>,
+    /// The server artists similar to each artist looked up so far, as most
+    /// files share their artist with others.
+    similar_artists: RefCell<HashMap<String, Vec<String>>>,
+}
+impl ServerLibrary {
+    fn new(fetched: blackbird_state::FetchAllOutput) -> Self {
+        Self::from_tracks(fetched.track_map.values().filter_map(|track| {
+            let album = fetched.albums.get(track.album_id.as_ref()?)?;
+            Some((
+                std::iter::once(album.artist.as_str())
+                    .chain(track.artist.as_deref())
+                    .collect::<Vec<_>>(),
+                album.name.as_str(),
+                track.title.as_str(),
+            ))
+        }))
+    }
+
+    /// Indexes tracks given as (artists, album, title).
+    fn from_tracks<'a>(tracks: impl IntoIterator<Item = (Vec<&'a str>, &'a str, &'a str)>) -> Self {
+        let mut artists: HashMap<String, ArtistAlbums> = HashMap::new();
+        for (track_artists, album, title) in tracks {
+            let album = normalize_album_name(album);
+            let title = title.to_lowercase();
+            for artist in track_artists {
+                artists
+                    .entry(normalize_artist_name(artist))
+                    .or_default()
+                    .entry(album.clone())
+                    .or_default()
+                    .insert(title.clone());
+            }
+        }
+        Self {
+            artists,
+            similar_artists: RefCell::default(),
+        }
+    }
+
+    /// Whether the library has a track matching these tags.
+    fn contains(&self, artist: &str, album: &str, title: &str) -> bool {
+        let artist = normalize_artist_name(artist);
+        let album = normalize_album_name(album);
+        let title = title.to_lowercase();
+
+        // Try an exact match first, as it's much cheaper.
+        if self
+            .artists
+            .get(&artist)
+            .and_then(|albums| albums.get(&album))
+            .is_some_and(|titles| titles.contains(&title))
+        {
+            return true;
+        }
+
+        let mut similar_artists = self.similar_artists.borrow_mut();
+        let similar_artists = similar_artists.entry(artist).or_insert_with_key(|artist| {
+            self.artists
+                .keys()
+                .filter(|server_artist| fuzzy_match(artist, server_artist) > FUZZY_THRESHOLD)
+                .cloned()
+                .collect()
+        });
+        similar_artists
+            .iter()
+            .flat_map(|server_artist| &self.artists[server_artist])
+            .filter(|(server_album, _)| fuzzy_match(&album, server_album) > FUZZY_THRESHOLD)
+            .any(|(_, titles)| {
+                titles.contains(&title)
+                    || titles
+                        .iter()
+                        .any(|server_title| fuzzy_match(&title, server_title) > FUZZY_THRESHOLD)
+            })
+    }
+}
+
+/// Reports the music files in `input_dir` that aren't on the server,
+/// returning how many files were checked and how many of them are new.
+pub fn verify_directory(
+    input_dir: &Path,
+    music_extensions: &HashSet<&str>,
+    verbose: bool,
+    report_file: &mut Option<fs::File>,
+) -> Result<(usize, usize)> {
+    let config = Config::load();
+    tracing::info!("Fetching library from {}...", config.server.base_url);
+    let library = tokio::runtime::Runtime::new()?.block_on(fetch_library(config.server))?;
+
+    let mut checked_count = 0;
+    let mut new_count = 0;
+    for entry in WalkDir::new(input_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && crate::is_music_file(e.path(), music_extensions))
+    {
+        let file_path = entry.path();
+        let file_path_display = file_path.display();
+        let metadata = match crate::read_metadata_with_lofty(file_path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                tracing::error!("Failed to read metadata from {file_path_display}: {e:?}");
+                continue;
+            }
+        };
+        let Some(title) = &metadata.title else {
+            tracing::error!("Missing title tag in {file_path_display}");
+            continue;
+        };
+        let album = metadata.album.as_ref().unwrap_or(title);
+        checked_count += 1;
+
+        let found = [&metadata.album_artist, &metadata.artist]
+            .into_iter()
+            .flatten()
+            .any(|artist| library.contains(artist, album, title));
+        let relative_path = file_path.strip_prefix(input_dir).unwrap_or(file_path);
+        if found {
+            if verbose {
+                tracing::info!("found: {}", relative_path.display());
+            }
+            continue;
+        }
+
+        new_count += 1;
+        tracing::info!("new: {}", relative_path.display());
+        if let Some(file) = report_file {
+            writeln!(file, "{}", relative_path.display())
+                .with_context(|| "Failed to write to report file")?;
+        }
+    }
+
+    Ok((checked_count, new_count))
+}
+
+async fn fetch_library(server: blackbird_shared::config::Server) -> Result<ServerLibrary> {
+    let client_id = server.client_id("blackbird-id3mover", env!("CARGO_PKG_VERSION"));
+    let client = blackbird_state::bs::Client::new(
+        server.base_url,
+        server.username,
+        server.password,
+        client_id,
+    );
+    let fetch_options = blackbird_state::FetchOptions {
+        album_page_size: server.album_page_size,
+        track_page_size: server.track_page_size,
+        ..Default::default()
+    };
+    let fetched = blackbird_state::fetch_all(&client, fetch_options, |progress| {
+        tracing::info!(
+            "Fetched {} albums, {}/{} tracks",
+            progress.albums_fetched,
+            progress.tracks_fetched,
+            progress.expected_tracks
+        );
+    })
+    .await?;
+    Ok(ServerLibrary::new(fetched))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> ServerLibrary {
+        ServerLibrary::from_tracks([
+            (vec!["The Band"], "Record", "Opening"),
+            (vec!["The Band"], "Record (Deluxe Edition)", "Bonus Track"),
+            (vec!["Various Artists", "Singer"], "Summer Hits", "Sunshine"),
+        ])
+    }
+
+    #[test]
+    fn contains_matches_exact_and_normalized_tags() {
+        let library = library();
+        assert!(library.contains("The Band", "Record", "Opening"));
+        assert!(library.contains("the band", "Record (Remastered)", "OPENING"));
+        // Tracks are indexed under their track artists too.
+        assert!(library.contains("Singer", "Summer Hits", "Sunshine"));
+    }
+
+    #[test]
+    fn contains_matches_similar_tags() {
+        let library = library();
+        assert!(library.contains("The Bnad", "Record", "Openign"));
+        assert!(library.contains("The Band", "Recrod", "Bonus Track"));
+    }
+
+    #[test]
+    fn contains_rejects_other_tracks() {
+        let library = library();
+        assert!(!library.contains("The Band", "Record", "Closing Time"));
+        assert!(!library.contains("The Band", "Another Record", "Opening"));
+        assert!(!library.contains("Someone Else", "Record", "Opening"));
+        // Lookups reuse the cached similar artists.
+        assert!(!library.contains("Someone Else", "Record", "Opening"));
+    }
+}
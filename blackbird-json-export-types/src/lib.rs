@@ -17,6 +17,8 @@ pub struct OutputTrack {
     pub play_count: Option<u64>,
     #[serde(skip_serializing_if = "is_false", default)]
     pub starred: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub music_brainz_id: Option<String>,
 }
 
 fn is_optional_zero(n: &Option<u64>) -> bool {
@@ -37,6 +39,67 @@ pub struct OutputGroup {
     pub tracks: Vec<OutputTrack>,
     #[serde(skip_serializing_if = "is_false", default)]
     pub starred: bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub music_brainz_id: Option<String>,
 }
 
 pub type Output = Vec<OutputGroup>;
+
+/// A track in the play count export.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayCountTrack {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    pub play_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub starred_at: Option<String>,
+}
+
+/// An album in the play count export; its play count is the sum of its
+/// tracks'.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayCountAlbum {
+    pub artist: String,
+    pub album: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<i32>,
+    pub play_count: u64,
+    pub created: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub starred_at: Option<String>,
+    pub tracks: Vec<PlayCountTrack>,
+}
+
+/// An entry in a top-N summary. `title` is only set for tracks.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayCountRanking {
+    pub artist: String,
+    pub album: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub play_count: u64,
+}
+
+/// Library-wide listening totals.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct PlayCountTotals {
+    pub plays: u64,
+    pub tracks: usize,
+    pub played_tracks: usize,
+    /// Sum of each track's duration times its play count.
+    pub listening_seconds: u64,
+}
+
+/// The play count export: totals, top-N summaries and every album.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayCountOutput {
+    pub totals: PlayCountTotals,
+    pub top_tracks: Vec<PlayCountRanking>,
+    pub top_albums: Vec<PlayCountRanking>,
+    pub albums: Vec<PlayCountAlbum>,
+}
@@ -1,7 +1,8 @@
+mod play_counts;
+
 use std::path::PathBuf;
 
 use anyhow::Context as _;
-
 use blackbird_json_export_types::{Output, OutputGroup, OutputTrack};
 use blackbird_shared::config::ConfigFile;
 use serde::{Deserialize, Serialize};
@@ -16,26 +17,75 @@ pub struct Config {
 
 impl ConfigFile for Config {}
 
+const USAGE: &str = "Usage: blackbird-json-export [--play-counts] <output path>
+
+Exports the library as JSON.
+
+Options:
+  --play-counts  Export listening analytics instead, as CSV if the output path
+                 ends in `.csv` and as JSON otherwise
+  -h, --help     Print this help";
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // Help is handled before anything else, so that it is never taken as the
+    // output path and doesn't need a config.
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
+    let mut play_counts = false;
+    let mut output_path = None;
+    for arg in args {
+        if arg == "--play-counts" {
+            play_counts = true;
+        } else if arg.starts_with('-') {
+            anyhow::bail!("Unknown option {arg:?}\n\n{USAGE}");
+        } else {
+            output_path = Some(PathBuf::from(arg));
+        }
+    }
+    let output_path = output_path.with_context(|| format!("Output path is required\n\n{USAGE}"))?;
+
     let config = Config::load();
-    let output_path = std::env::args()
-        .nth(1)
-        .map(PathBuf::from)
-        .context("Output path is required")?;
 
+    let client_id = config
+        .server
+        .client_id("blackbird-json-export", env!("CARGO_PKG_VERSION"));
     let client = blackbird_state::bs::Client::new(
         config.server.base_url,
         config.server.username,
         config.server.password,
-        "blackbird-json-export",
+        client_id,
     );
 
-    let fetched = blackbird_state::fetch_all(&client, |batch_count, total_count| {
-        println!("Fetched {batch_count} tracks, total {total_count} tracks");
+    let fetch_options = blackbird_state::FetchOptions {
+        album_page_size: config.server.album_page_size,
+        track_page_size: config.server.track_page_size,
+        ..Default::default()
+    };
+    let fetched = blackbird_state::fetch_all(&client, fetch_options, |progress| {
+        println!(
+            "Fetched {} albums, {}/{} tracks",
+            progress.albums_fetched, progress.tracks_fetched, progress.expected_tracks
+        );
     })
     .await?;
 
+    if play_counts {
+        let output = play_counts::build(&fetched);
+        let contents = if output_path.extension().is_some_and(|ext| ext == "csv") {
+            play_counts::to_csv(&output)
+        } else {
+            serde_json::to_string_pretty(&output)?
+        };
+        std::fs::write(&output_path, contents)
+            .with_context(|| format!("Failed to write to {output_path:?}"))?;
+        return Ok(());
+    }
+
     let mut output = Output::new();
     for group in fetched.groups {
         output.push(OutputGroup {
@@ -57,10 +107,16 @@ async fn main() -> anyhow::Result<()> {
                         disc_number: track.disc_number,
                         starred: track.starred,
                         play_count: track.play_count,
+                        music_brainz_id: track.music_brainz_id.as_ref().map(|id| id.to_string()),
                     }
                 })
                 .collect(),
             starred: group.starred,
+            music_brainz_id: fetched
+                .albums
+                .get(&group.album_id)
+                .and_then(|album| album.music_brainz_id.as_ref())
+                .map(|id| id.to_string()),
         });
     }
 
@@ -0,0 +1,167 @@
+//! The play count export: per-album and per-track play counts alongside
+//! their added and starred dates, for listening analytics rather than as a
+//! copy of the library.
+
+use std::borrow::Cow;
+
+use blackbird_json_export_types::{
+    PlayCountAlbum, PlayCountOutput, PlayCountRanking, PlayCountTotals, PlayCountTrack,
+};
+use blackbird_state::FetchAllOutput;
+
+/// How many entries the top track and album summaries hold.
+const TOP_N: usize = 25;
+
+/// Builds the play count dataset from a fetched library, in library order.
+pub fn build(fetched: &FetchAllOutput) -> PlayCountOutput {
+    let albums: Vec<PlayCountAlbum> = fetched
+        .groups
+        .iter()
+        .map(|group| {
+            let album = fetched.albums.get(&group.album_id);
+            let tracks: Vec<PlayCountTrack> = group
+                .tracks
+                .iter()
+                .filter_map(|id| fetched.track_map.get(id))
+                .map(|track| PlayCountTrack {
+                    title: track.title.to_string(),
+                    artist: track.artist.as_ref().map(|a| a.to_string()),
+                    play_count: track.play_count.unwrap_or(0),
+                    duration: track.duration,
+                    created: track.created.as_ref().map(|c| c.to_string()),
+                    starred_at: track.starred_at.as_ref().map(|s| s.to_string()),
+                })
+                .collect();
+            PlayCountAlbum {
+                artist: group.artist.to_string(),
+                album: group.album.to_string(),
+                year: group.year,
+                play_count: tracks.iter().map(|t| t.play_count).sum(),
+                created: album.map(|a| a.created.to_string()).unwrap_or_default(),
+                starred_at: album
+                    .and_then(|a| a.starred_at.as_ref())
+                    .map(|s| s.to_string()),
+                tracks,
+            }
+        })
+        .collect();
+
+    let mut totals = PlayCountTotals::default();
+    for track in albums.iter().flat_map(|album| &album.tracks) {
+        totals.plays += track.play_count;
+        totals.tracks += 1;
+        totals.played_tracks += usize::from(track.play_count > 0);
+        totals.listening_seconds += track.play_count * u64::from(track.duration.unwrap_or(0));
+    }
+
+    let top_tracks = top_n(albums.iter().flat_map(|album| {
+        album.tracks.iter().map(|track| PlayCountRanking {
+            artist: track.artist.clone().unwrap_or_else(|| album.artist.clone()),
+            album: album.album.clone(),
+            title: Some(track.title.clone()),
+            play_count: track.play_count,
+        })
+    }));
+    let top_albums = top_n(albums.iter().map(|album| PlayCountRanking {
+        artist: album.artist.clone(),
+        album: album.album.clone(),
+        title: None,
+        play_count: album.play_count,
+    }));
+
+    PlayCountOutput {
+        totals,
+        top_tracks,
+        top_albums,
+        albums,
+    }
+}
+
+/// The [`TOP_N`] most played entries, most played first. Unplayed entries
+/// are left out, and ties keep their library order.
+fn top_n(entries: impl Iterator<Item = PlayCountRanking>) -> Vec<PlayCountRanking> {
+    let mut entries: Vec<_> = entries.filter(|e| e.play_count > 0).collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.play_count));
+    entries.truncate(TOP_N);
+    entries
+}
+
+/// Renders the dataset as CSV, one row per track with its album's details
+/// alongside. The totals and summaries are only included in the JSON output.
+pub fn to_csv(output: &PlayCountOutput) -> String {
+    let mut csv = String::from(
+        "album_artist,album,year,album_created,album_starred_at,album_play_count,\
+         title,artist,play_count,duration,created,starred_at\n",
+    );
+    for album in &output.albums {
+        for track in &album.tracks {
+            let fields = [
+                album.artist.clone(),
+                album.album.clone(),
+                album.year.map(|y| y.to_string()).unwrap_or_default(),
+                album.created.clone(),
+                album.starred_at.clone().unwrap_or_default(),
+                album.play_count.to_string(),
+                track.title.clone(),
+                track.artist.clone().unwrap_or_default(),
+                track.play_count.to_string(),
+                track.duration.map(|d| d.to_string()).unwrap_or_default(),
+                track.created.clone().unwrap_or_default(),
+                track.starred_at.clone().unwrap_or_default(),
+            ];
+            let row: Vec<_> = fields.iter().map(|f| csv_field(f)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+    }
+    csv
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranking(album: &str, play_count: u64) -> PlayCountRanking {
+        PlayCountRanking {
+            artist: "Artist".into(),
+            album: album.into(),
+            title: None,
+            play_count,
+        }
+    }
+
+    #[test]
+    fn top_n_ranks_played_entries_most_played_first() {
+        let ranked = top_n(
+            [
+                ranking("a", 3),
+                ranking("b", 0),
+                ranking("c", 7),
+                ranking("d", 3),
+            ]
+            .into_iter(),
+        );
+        let albums: Vec<_> = ranked.iter().map(|r| r.album.as_str()).collect();
+        assert_eq!(albums, ["c", "a", "d"]);
+
+        let many = top_n((1..=TOP_N as u64 + 5).map(|n| ranking("x", n)));
+        assert_eq!(many.len(), TOP_N);
+        assert_eq!(many[0].play_count, TOP_N as u64 + 5);
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
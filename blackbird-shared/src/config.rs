@@ -61,6 +61,40 @@ pub struct Server {
     pub username: String,
     pub password: String,
     pub transcode: bool,
+    /// Format to transcode to when `transcode` is on, e.g. `mp3`. When unset,
+    /// the format the server reports for each track is used if it can be
+    /// played, falling back to MP3.
+    pub transcode_format: Option<String>,
+    /// Subsonic API version to send with every request. When unset, the
+    /// version is negotiated from the server's `ping` response.
+    pub api_version: Option<String>,
+    /// Number of albums requested per page when fetching the library.
+    pub album_page_size: u32,
+    /// Number of tracks requested per page when fetching the library.
+    pub track_page_size: u32,
+    /// Name reported to the server, e.g. to tell devices apart in its logs.
+    /// When unset, the name of the client or tool is used.
+    pub client_name: Option<String>,
+    /// Version reported to the server alongside the name. When unset, the
+    /// client or tool's version is used.
+    pub client_version: Option<String>,
+}
+impl Server {
+    /// The default number of albums requested per page.
+    pub const DEFAULT_ALBUM_PAGE_SIZE: u32 = 500;
+    /// The default number of tracks requested per page.
+    pub const DEFAULT_TRACK_PAGE_SIZE: u32 = 10000;
+
+    /// The identifier reported to the server as the Subsonic `c` parameter,
+    /// as `name/version`, falling back to the given defaults for whichever
+    /// of `client_name` and `client_version` are unset.
+    pub fn client_id(&self, default_name: &str, default_version: &str) -> String {
+        format!(
+            "{}/{}",
+            self.client_name.as_deref().unwrap_or(default_name),
+            self.client_version.as_deref().unwrap_or(default_version)
+        )
+    }
 }
 impl Default for Server {
     fn default() -> Self {
@@ -69,6 +103,12 @@ impl Default for Server {
             username: "YOUR_USERNAME".to_string(),
             password: "YOUR_PASSWORD".to_string(),
             transcode: false,
+            transcode_format: None,
+            api_version: None,
+            album_page_size: Self::DEFAULT_ALBUM_PAGE_SIZE,
+            track_page_size: Self::DEFAULT_TRACK_PAGE_SIZE,
+            client_name: None,
+            client_version: None,
         }
     }
 }
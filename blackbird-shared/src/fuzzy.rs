@@ -0,0 +1,135 @@
+//! Fuzzy string matching, for matching names that differ in spelling or
+//! wording.
+
+/// Scores how similar two names are, from 0 (nothing in common) to 1 (equal
+/// ignoring case), taking the best of Jaro-Winkler and word-level similarity.
+/// One name containing the other scores 0.8.
+pub fn fuzzy_match(a: &str, b: &str) -> f64 {
+    let a_lower = a.to_lowercase();
+    let b_lower = b.to_lowercase();
+
+    // Exact match gets highest score
+    if a_lower == b_lower {
+        return 1.0;
+    }
+
+    // Check if one string contains the other
+    if a_lower.contains(&b_lower) || b_lower.contains(&a_lower) {
+        return 0.8;
+    }
+
+    // Calculate Jaro-Winkler similarity
+    let jaro = jaro_similarity(&a_lower, &b_lower);
+    let winkler = winkler_similarity(&a_lower, &b_lower, jaro);
+
+    // Also check for word-level matches
+    let word_similarity = word_based_similarity(&a_lower, &b_lower);
+
+    // Return the maximum of the different similarity measures
+    winkler.max(word_similarity)
+}
+
+fn jaro_similarity(s1: &str, s2: &str) -> f64 {
+    if s1 == s2 {
+        return 1.0;
+    }
+
+    let len1 = s1.chars().count();
+    let len2 = s2.chars().count();
+
+    if len1 == 0 || len2 == 0 || (len1 + len2 <= 2) {
+        return 0.0;
+    }
+
+    let match_distance = (len1.max(len2) / 2) - 1;
+    let mut s1_matches = vec![false; len1];
+    let mut s2_matches = vec![false; len2];
+
+    let mut matches = 0;
+
+    for (i, c1) in s1.chars().enumerate() {
+        let start = i.saturating_sub(match_distance);
+        let end = (i + match_distance + 1).min(len2);
+
+        #[allow(clippy::needless_range_loop)]
+        for j in start..end {
+            if !s2_matches[j] && c1 == s2.chars().nth(j).unwrap() {
+                s1_matches[i] = true;
+                s2_matches[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let mut transpositions = 0;
+    let mut k = 0;
+
+    for (i, matched) in s1_matches.iter().enumerate() {
+        if *matched {
+            while !s2_matches[k] {
+                k += 1;
+            }
+            if s1.chars().nth(i).unwrap() != s2.chars().nth(k).unwrap() {
+                transpositions += 1;
+            }
+            k += 1;
+        }
+    }
+
+    let m = matches as f64;
+    let t = (transpositions / 2) as f64;
+
+    (m / len1 as f64 + m / len2 as f64 + (m - t) / m) / 3.0
+}
+
+fn winkler_similarity(s1: &str, s2: &str, jaro: f64) -> f64 {
+    if jaro < 0.7 {
+        return jaro;
+    }
+
+    let prefix_length = s1
+        .chars()
+        .zip(s2.chars())
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(4);
+
+    jaro + 0.1 * prefix_length as f64 * (1.0 - jaro)
+}
+
+fn word_based_similarity(s1: &str, s2: &str) -> f64 {
+    let words1: std::collections::HashSet<_> = s1.split_whitespace().collect();
+    let words2: std::collections::HashSet<_> = s2.split_whitespace().collect();
+
+    if words1.is_empty() && words2.is_empty() {
+        return 1.0;
+    }
+
+    if words1.is_empty() || words2.is_empty() {
+        return 0.0;
+    }
+
+    let intersection = words1.intersection(&words2).count();
+    let union = words1.union(&words2).count();
+
+    intersection as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("Visions", "visions"), 1.0);
+        assert_eq!(fuzzy_match("the beatles", "beatles"), 0.8);
+        assert!(fuzzy_match("radiohead", "radiohed") > 0.8);
+        assert!(fuzzy_match("ok computer", "kid a") < 0.8);
+        assert!(fuzzy_match("abbey road", "road abbey") >= 1.0);
+    }
+}
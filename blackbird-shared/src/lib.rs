@@ -1,4 +1,7 @@
 //! Types and helpers shared between blackbird clients and supporting tools.
 
 pub mod config;
+pub mod fuzzy;
+pub mod normalize;
 pub mod paths;
+pub mod track_path;
@@ -0,0 +1,209 @@
+//! Normalization of artist and album names, for telling whether two names
+//! refer to the same artist or album despite differences in edition, case,
+//! and punctuation.
+
+/// Lowercases an artist name and drops everything but letters and digits,
+/// so that punctuation and spacing differences don't matter.
+pub fn normalize_artist_name(artist: &str) -> String {
+    artist
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+/// Strips parenthesized content from the end of album names.
+/// For example: "Visions (2017 Remaster)" becomes "Visions"
+pub fn strip_album_parentheses(album_name: &str) -> String {
+    let trimmed = album_name.trim_end();
+    if let Some(idx) = trimmed.rfind('(') {
+        let before = &trimmed[..idx];
+        let after = &trimmed[idx..];
+        if after.ends_with(')') && before.chars().last().is_none_or(|c| c.is_whitespace()) {
+            return before.trim_end().to_string();
+        }
+    }
+    album_name.to_string()
+}
+
+/// Removes common superfluous words from album names.
+/// Only removes whole words to avoid partial matches.
+/// For example: "Album Name Deluxe Edition" becomes "Album Name"
+pub fn strip_superfluous_words(album_name: &str) -> String {
+    const SUPERFLUOUS_WORDS: &[&str] = &[
+        "edition",
+        "deluxe",
+        "remaster",
+        "remastered",
+        "ep",
+        "lp",
+        "single",
+        "live",
+        "acoustic",
+        "unplugged",
+        "studio",
+        "original",
+        "classic",
+        "anniversary",
+        "special",
+        "limited",
+        "expanded",
+        "complete",
+        "full",
+        "extended",
+        "bonus",
+        "extra",
+        "plus",
+        "reissue",
+        "import",
+        "international",
+        "uk",
+        "us",
+        "european",
+        "american",
+        "version",
+        "remix",
+        "explicit",
+        "clean",
+        "instrumental",
+        "vocal",
+        "demo",
+        "rough",
+        "alternate",
+        "alternative",
+        "take",
+        "outtake",
+        "part",
+        "chapter",
+        "volume",
+        "vol",
+        "disc",
+        "cd",
+        "vinyl",
+        "digital",
+        "streaming",
+        "download",
+        "online",
+        "internet",
+        "web",
+        "physical",
+        "hardcopy",
+    ];
+
+    album_name
+        .split_whitespace()
+        .filter(|word| !SUPERFLUOUS_WORDS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Normalizes album names by removing parentheses and superfluous words.
+/// This is the main function to use for album name processing.
+pub fn normalize_album_name(album_name: &str) -> String {
+    let lowercased = album_name.to_lowercase();
+    let stripped = strip_album_parentheses(&lowercased);
+    strip_superfluous_words(&stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_album_parentheses() {
+        // Basic cases
+        assert_eq!(
+            strip_album_parentheses("Visions (2017 Remaster)"),
+            "Visions"
+        );
+        assert_eq!(
+            strip_album_parentheses("Album Name (Deluxe Edition)"),
+            "Album Name"
+        );
+        assert_eq!(strip_album_parentheses("Test (2023)"), "Test");
+
+        // Cases that should now be stripped
+        assert_eq!(strip_album_parentheses("Album Name"), "Album Name");
+        assert_eq!(strip_album_parentheses("Album (Name)"), "Album");
+        assert_eq!(
+            strip_album_parentheses("Album Name (Remaster) (2023)"),
+            "Album Name (Remaster)"
+        );
+        assert_eq!(
+            strip_album_parentheses("Album Name (Remaster) - Bonus"),
+            "Album Name (Remaster) - Bonus"
+        );
+
+        // Edge cases
+        assert_eq!(strip_album_parentheses(""), "");
+        assert_eq!(strip_album_parentheses("(Remaster)"), "");
+        assert_eq!(strip_album_parentheses("Album Name ()"), "Album Name");
+        assert_eq!(strip_album_parentheses("Album Name ( )"), "Album Name");
+
+        // Multiple spaces
+        assert_eq!(
+            strip_album_parentheses("Album Name   (Remaster)   "),
+            "Album Name"
+        );
+
+        // Unbalanced parentheses
+        assert_eq!(
+            strip_album_parentheses("Album Name (Remaster"),
+            "Album Name (Remaster"
+        );
+        assert_eq!(
+            strip_album_parentheses("Album Name Remaster)"),
+            "Album Name Remaster)"
+        );
+    }
+
+    #[test]
+    fn test_strip_superfluous_words() {
+        // Single word removals
+        assert_eq!(strip_superfluous_words("album name edition"), "album name");
+        assert_eq!(strip_superfluous_words("album name ep"), "album name");
+        assert_eq!(strip_superfluous_words("album name deluxe"), "album name");
+        assert_eq!(strip_superfluous_words("album name remaster"), "album name");
+
+        // Multi-word phrase removals (these should no longer work since we simplified)
+        assert_eq!(
+            strip_superfluous_words("album name greatest hits"),
+            "album name greatest hits"
+        );
+        assert_eq!(
+            strip_superfluous_words("album name best of"),
+            "album name best of"
+        );
+        assert_eq!(
+            strip_superfluous_words("album name radio edit"),
+            "album name radio edit"
+        );
+
+        // Mixed cases
+        assert_eq!(
+            strip_superfluous_words("album name deluxe edition remaster"),
+            "album name"
+        );
+        assert_eq!(
+            strip_superfluous_words("album name greatest hits deluxe edition"),
+            "album name greatest hits"
+        );
+
+        // Cases that should NOT be changed
+        assert_eq!(strip_superfluous_words("album name"), "album name");
+        assert_eq!(strip_superfluous_words("replace"), "replace"); // Should not become "rlace"
+        assert_eq!(strip_superfluous_words("editionary"), "editionary"); // Should not become "ary"
+        assert_eq!(strip_superfluous_words("my ep collection"), "my collection");
+
+        // Edge cases
+        assert_eq!(strip_superfluous_words(""), "");
+        assert_eq!(strip_superfluous_words("edition"), "");
+        assert_eq!(strip_superfluous_words("   edition   "), "");
+        assert_eq!(strip_superfluous_words("edition album"), "album");
+
+        // Case sensitivity (now expects lowercase input)
+        assert_eq!(strip_superfluous_words("album name edition"), "album name"); // Lowercase input
+        assert_eq!(strip_superfluous_words("album name edition"), "album name"); // Lowercase input
+        assert_eq!(strip_superfluous_words("album name edition"), "album name"); // Lowercase input
+    }
+}
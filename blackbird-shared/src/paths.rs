@@ -41,3 +41,11 @@ pub fn cache_dir() -> PathBuf {
 pub fn data_dir() -> PathBuf {
     app_strategy().data_dir()
 }
+
+/// Returns the default directory for downloaded tracks: `~/Downloads`, or
+/// the working directory if the home directory can't be found.
+pub fn downloads_dir() -> PathBuf {
+    etcetera::home_dir()
+        .map(|home| home.join("Downloads"))
+        .unwrap_or_default()
+}
@@ -0,0 +1,70 @@
+//! Naming for audio files on disk, shared by the tools that write them so a
+//! downloaded track lands where `blackbird-id3mover` would have put it.
+use std::path::PathBuf;
+
+use sanitize_filename::sanitize;
+
+/// The metadata a track's path is built from.
+#[derive(Debug, Clone, Copy)]
+pub struct TrackPath<'a> {
+    pub album_artist: &'a str,
+    pub album: &'a str,
+    pub title: &'a str,
+    pub track_number: Option<u32>,
+    pub disc_number: Option<u32>,
+    /// The file extension, without the leading dot.
+    pub extension: &'a str,
+}
+impl TrackPath<'_> {
+    /// The path of the file relative to the library root, as
+    /// `Album Artist/Album/NN - Title [Disc].ext`, with every component
+    /// sanitized for use as a file name.
+    pub fn relative_path(&self) -> PathBuf {
+        let track_num = self
+            .track_number
+            .map(|t| format!("{t:02}"))
+            .unwrap_or_else(|| "00".to_string());
+        let (title, extension) = (self.title, self.extension);
+        let filename = if let Some(disc_num) = self.disc_number {
+            format!("{track_num} - {title} [{disc_num}].{extension}")
+        } else {
+            format!("{track_num} - {title}.{extension}")
+        };
+
+        PathBuf::from(sanitize(self.album_artist))
+            .join(sanitize(self.album))
+            .join(sanitize(&filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_path_pads_track_numbers_and_sanitizes_components() {
+        let path = TrackPath {
+            album_artist: "AC/DC",
+            album: "Back in Black",
+            title: "Hells Bells",
+            track_number: Some(1),
+            disc_number: None,
+            extension: "flac",
+        };
+        assert_eq!(
+            path.relative_path(),
+            PathBuf::from("ACDC/Back in Black/01 - Hells Bells.flac")
+        );
+
+        let path = TrackPath {
+            track_number: None,
+            disc_number: Some(2),
+            title: "What?",
+            ..path
+        };
+        assert_eq!(
+            path.relative_path(),
+            PathBuf::from("ACDC/Back in Black/00 - What [2].flac")
+        );
+    }
+}
@@ -122,3 +122,47 @@ impl Ndjson for Tracks {
         value.uri.clone()
     }
 }
+
+/// An album in the Subsonic library, as much of it as matching needs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubsonicAlbum {
+    pub id: String,
+    pub artist: String,
+    pub name: String,
+    /// The distinct artists of the album's tracks, where they differ from
+    /// the album artist (e.g. on compilations or with featured artists).
+    #[serde(default)]
+    pub track_artists: Vec<String>,
+}
+impl SubsonicAlbum {
+    /// Whether the album is credited to "Various Artists" rather than to a
+    /// single artist.
+    pub fn is_compilation(&self) -> bool {
+        matches!(
+            self.artist.to_lowercase().as_str(),
+            "various artists" | "various" | "va"
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SubsonicAlbums(pub HashMap<String, SubsonicAlbum>);
+impl From<HashMap<String, SubsonicAlbum>> for SubsonicAlbums {
+    fn from(value: HashMap<String, SubsonicAlbum>) -> Self {
+        SubsonicAlbums(value)
+    }
+}
+impl AsRef<HashMap<String, SubsonicAlbum>> for SubsonicAlbums {
+    fn as_ref(&self) -> &HashMap<String, SubsonicAlbum> {
+        &self.0
+    }
+}
+impl Ndjson for SubsonicAlbums {
+    type Id = String;
+    type Value = SubsonicAlbum;
+
+    fn get_id_for_value(value: &Self::Value) -> Self::Id {
+        value.id.clone()
+    }
+}
@@ -1,12 +1,20 @@
 use std::{
+    collections::{HashMap, HashSet},
     io::Write,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use blackbird_shared::config::ConfigFile;
+use anyhow::Context as _;
+use blackbird_shared::{
+    config::ConfigFile,
+    fuzzy::fuzzy_match,
+    normalize::{normalize_album_name, normalize_artist_name},
+};
+use futures::StreamExt as _;
 use serde::{Deserialize, Serialize};
 
-use crate::common::{Albums, Ndjson as _, Tracks};
+use crate::common::{Albums, Ndjson as _, SubsonicAlbum, SubsonicAlbums, Tracks};
 
 mod common;
 mod spotify;
@@ -17,22 +25,241 @@ mod spotify;
 #[serde(default)]
 pub struct Config {
     server: blackbird_shared::config::Server,
+    spotcheck: Spotcheck,
 }
 
 impl ConfigFile for Config {}
 
+/// Tuning for matching the Spotify albums against the library, read from the
+/// `[spotcheck]` section.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Spotcheck {
+    /// How many albums each matching task handles.
+    chunk_size: usize,
+    /// How many matching tasks run at once. Unset uses the available
+    /// parallelism.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    concurrency: Option<usize>,
+}
+impl Default for Spotcheck {
+    fn default() -> Self {
+        Self {
+            chunk_size: 100,
+            concurrency: None,
+        }
+    }
+}
+impl Spotcheck {
+    fn concurrency(&self) -> usize {
+        self.concurrency
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+            .max(1)
+    }
+}
+
+/// How strictly Spotify albums are matched against the library, set from the
+/// command line.
+#[derive(Debug, PartialEq)]
+struct MatchOptions {
+    /// Whether to also match against the artists of each album's tracks,
+    /// and not just its album artist. `--no-track-artists` turns it off.
+    match_track_artists: bool,
+    /// Whether to ignore the artist for "Various Artists" albums, matching
+    /// on the album name alone. `--no-compilations` turns it off.
+    ignore_compilation_artist: bool,
+    /// How similar album names must be to match when the artist is ignored,
+    /// from 0 to 1. Set with `--compilation-threshold <n>`.
+    compilation_album_threshold: f64,
+}
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            match_track_artists: true,
+            ignore_compilation_artist: true,
+            compilation_album_threshold: 0.9,
+        }
+    }
+}
+
+const USAGE: &str = "\
+Usage: blackbird-spotcheck [OPTIONS] [SPOTIFY_DATA_PATH]
+
+Options:
+  --refresh                      Refetch the library instead of using the cached copy
+  --no-track-artists             Only match against album artists, not track artists
+  --no-compilations              Don't match compilations on their album name alone
+  --compilation-threshold <N>    How similar compilation names must be, from 0 to 1 [default: 0.9]
+  -h, --help                     Print this help";
+
+/// The parsed command line.
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    /// Refetch the Subsonic library instead of using the copy cached by a
+    /// previous run.
+    refresh: bool,
+    /// The Spotify data to parse; unset reuses the previously parsed data.
+    spotify_data_path: Option<PathBuf>,
+    matching: MatchOptions,
+    help: bool,
+}
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => parsed.help = true,
+                "--refresh" => parsed.refresh = true,
+                "--no-track-artists" => parsed.matching.match_track_artists = false,
+                "--no-compilations" => parsed.matching.ignore_compilation_artist = false,
+                "--compilation-threshold" => {
+                    let value = args
+                        .next()
+                        .context("--compilation-threshold needs a value")?;
+                    let threshold: f64 = value
+                        .parse()
+                        .with_context(|| format!("invalid --compilation-threshold {value:?}"))?;
+                    anyhow::ensure!(
+                        (0.0..=1.0).contains(&threshold),
+                        "--compilation-threshold must be between 0 and 1"
+                    );
+                    parsed.matching.compilation_album_threshold = threshold;
+                }
+                other if other.starts_with('-') => {
+                    anyhow::bail!("unknown option {other}\n\n{USAGE}")
+                }
+                _ => parsed.spotify_data_path = Some(PathBuf::from(arg)),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// The library's albums, indexed for matching Spotify albums against.
+struct AlbumMatcher {
+    /// `artist - album` keys for exact matches.
+    exact_album_matches: HashSet<String>,
+    /// Normalized artist -> (artist, normalized album) for fuzzy matching.
+    normalized_artist_albums: HashMap<String, Vec<(String, String)>>,
+    /// The keys of `normalized_artist_albums`, pre-collected for scanning.
+    normalized_subsonic_artists: Vec<String>,
+    /// Normalized names of the compilations matched on name alone.
+    compilation_albums: Vec<String>,
+    /// How similar album names must be to match a compilation.
+    compilation_album_threshold: f64,
+}
+impl AlbumMatcher {
+    fn new<'a>(albums: impl Iterator<Item = &'a SubsonicAlbum>, options: &MatchOptions) -> Self {
+        let mut exact_album_matches = HashSet::new();
+        let mut normalized_artist_albums: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        let mut compilation_albums = vec![];
+
+        for album in albums {
+            let normalized_album = normalize_album_name(&album.name);
+            if options.ignore_compilation_artist && album.is_compilation() {
+                compilation_albums.push(normalized_album.clone());
+            }
+
+            // Index the album under each artist it could be credited to.
+            let track_artists = album
+                .track_artists
+                .iter()
+                .filter(|_| options.match_track_artists);
+            for artist in std::iter::once(&album.artist).chain(track_artists) {
+                // Store exact match for fast lookup (using stripped version)
+                let exact_key = format!("{} - {}", artist.to_lowercase(), normalized_album);
+                exact_album_matches.insert(exact_key);
+
+                // Store normalized version for fuzzy matching (using stripped version)
+                let normalized_artist = normalize_artist_name(artist);
+                normalized_artist_albums
+                    .entry(normalized_artist)
+                    .or_default()
+                    .push((artist.to_string(), normalized_album.clone()));
+            }
+        }
+
+        let normalized_subsonic_artists = normalized_artist_albums.keys().cloned().collect();
+        Self {
+            exact_album_matches,
+            normalized_artist_albums,
+            normalized_subsonic_artists,
+            compilation_albums,
+            compilation_album_threshold: options.compilation_album_threshold,
+        }
+    }
+
+    /// How `album` matched the library, or `None` if it's missing.
+    fn match_album(&self, album: &common::Album) -> Option<&'static str> {
+        let spotify_artist = &album.album_id.artist;
+        let spotify_album = &album.album_id.album;
+
+        // First try exact match (fastest)
+        let exact_key = format!(
+            "{} - {}",
+            spotify_artist.to_lowercase(),
+            normalize_album_name(spotify_album)
+        );
+        if self.exact_album_matches.contains(&exact_key) {
+            return Some("exact");
+        }
+
+        // If no exact match, try fuzzy matching (CPU-intensive work)
+        let normalized_spotify_artist = normalize_artist_name(spotify_artist);
+
+        // Look for similar artists
+        for subsonic_artist in &self.normalized_subsonic_artists {
+            if fuzzy_match(&normalized_spotify_artist, subsonic_artist) > 0.8 {
+                // Found a similar artist, now check their albums
+                if let Some(albums) = self.normalized_artist_albums.get(subsonic_artist) {
+                    for (_, subsonic_album_name) in albums {
+                        let album_similarity =
+                            fuzzy_match(&normalize_album_name(spotify_album), subsonic_album_name);
+                        if album_similarity > 0.8 {
+                            return Some("fuzzy");
+                        }
+                    }
+                }
+            }
+        }
+
+        // Spotify credits compilation tracks to their own artists, so fall
+        // back to matching compilations on their name alone, more strictly.
+        let normalized_spotify_album = normalize_album_name(spotify_album);
+        if self.compilation_albums.iter().any(|subsonic_album_name| {
+            fuzzy_match(&normalized_spotify_album, subsonic_album_name)
+                >= self.compilation_album_threshold
+        }) {
+            return Some("compilation");
+        }
+
+        None
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let Args {
+        refresh,
+        spotify_data_path,
+        matching,
+        help,
+    } = Args::parse(std::env::args().skip(1))?;
+    if help {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
     let subscriber = tracing_subscriber::FmtSubscriber::new();
     tracing::subscriber::set_global_default(subscriber).unwrap();
 
     tracing::info!("Loading configuration from {}...", Config::path().display());
     let config = Config::load();
-
-    let spotify_data_path = std::env::args().nth(1).map(PathBuf::from);
     let output_dir = Path::new("spotcheck-output");
     let albums_path = output_dir.join("albums.ndjson");
     let tracks_path = output_dir.join("tracks.ndjson");
+    let subsonic_albums_path = output_dir.join("subsonic-albums.ndjson");
 
     let (albums, _tracks) = if let Some(spotify_data_path) = spotify_data_path {
         tracing::info!("Parsing Spotify data from: {:?}", spotify_data_path);
@@ -65,50 +292,22 @@ async fn main() -> anyhow::Result<()> {
         )?;
     }
 
-    tracing::info!("Connecting to Subsonic server...");
-    let client = blackbird_state::bs::Client::new(
-        config.server.base_url,
-        config.server.username,
-        config.server.password,
-        "blackbird-spotcheck",
-    );
-
-    tracing::info!("Fetching all albums from Subsonic...");
-    let fetched = blackbird_state::fetch_all(&client, |batch_count, total_count| {
-        tracing::info!("Fetched {batch_count} tracks, total {total_count} tracks");
-    })
-    .await?;
-    tracing::info!("Found {} albums in Subsonic", fetched.albums.len());
-
-    // Create a more efficient lookup structure
-    // 1. Exact matches for fast lookup
-    let mut exact_album_matches: std::collections::HashSet<String> =
-        std::collections::HashSet::new();
-
-    // 2. Normalized artist -> albums mapping for fuzzy matching
-    let mut normalized_artist_albums: std::collections::HashMap<String, Vec<(String, String)>> =
-        std::collections::HashMap::new();
-
-    for album in fetched.albums.values() {
-        // Store exact match for fast lookup (using stripped version)
-        let exact_key = format!(
-            "{} - {}",
-            album.artist.to_lowercase(),
-            normalize_album_name(&album.name)
+    // The library is cached alongside the Spotify data, so repeated runs
+    // (e.g. while tuning the matching) don't refetch it.
+    let subsonic_albums = if refresh || !subsonic_albums_path.exists() {
+        let albums = fetch_subsonic_albums(config.server).await?;
+        tracing::info!("Saving Subsonic albums to output directory...");
+        albums.save(&subsonic_albums_path)?;
+        albums
+    } else {
+        tracing::info!(
+            "Loading cached Subsonic albums from output directory (pass --refresh to refetch)..."
         );
-        exact_album_matches.insert(exact_key);
-
-        // Store normalized version for fuzzy matching (using stripped version)
-        let normalized_artist = normalize_artist_name(&album.artist);
-        normalized_artist_albums
-            .entry(normalized_artist)
-            .or_default()
-            .push((album.artist.to_string(), normalize_album_name(&album.name)));
-    }
+        SubsonicAlbums::load(&subsonic_albums_path)?
+    };
+    tracing::info!("Found {} albums in Subsonic", subsonic_albums.0.len());
 
-    // Pre-compute normalized Subsonic artist names for faster lookup
-    let normalized_subsonic_artists: Vec<String> =
-        normalized_artist_albums.keys().cloned().collect();
+    let matcher = Arc::new(AlbumMatcher::new(subsonic_albums.0.values(), &matching));
 
     tracing::info!("Generating missing albums report...");
     let mut output = std::fs::File::create(output_dir.join("top-missing-albums.md"))?;
@@ -118,81 +317,54 @@ async fn main() -> anyhow::Result<()> {
     let mut found_output = std::fs::File::create(output_dir.join("top-found-albums.md"))?;
     writeln!(found_output, "# Top Found Albums")?;
 
-    // Process albums in parallel chunks
-    let chunk_size = 100;
-    let mut all_results = Vec::new();
-
-    for (chunk_idx, chunk) in albums_vec.chunks(chunk_size).enumerate() {
-        tracing::info!(
-            "Processing chunk {} of {}",
-            chunk_idx + 1,
-            albums_vec.len().div_ceil(chunk_size)
-        );
-
-        let tasks: Vec<_> = chunk
-            .iter()
-            .enumerate()
-            .map(|(i, album)| {
-                let exact_album_matches = exact_album_matches.clone();
-                let normalized_artist_albums = normalized_artist_albums.clone();
-                let normalized_subsonic_artists = normalized_subsonic_artists.clone();
-                let album = album.clone();
-                let global_idx = chunk_idx * chunk_size + i;
-
-                tokio::task::spawn_blocking(move || {
-                    let spotify_artist = &album.album_id.artist;
-                    let spotify_album = &album.album_id.album;
-
-                    // First try exact match (fastest)
-                    let exact_key = format!(
-                        "{} - {}",
-                        spotify_artist.to_lowercase(),
-                        normalize_album_name(spotify_album)
-                    );
-                    if exact_album_matches.contains(&exact_key) {
-                        return (global_idx, album, Some("exact"));
-                    }
+    // Match albums in chunks on the blocking pool, with a bounded number of
+    // chunks in flight. Every chunk shares the same matcher, and `buffered`
+    // yields the chunks in order, so the results keep their play count order.
+    let chunk_size = config.spotcheck.chunk_size.max(1);
+    let concurrency = config.spotcheck.concurrency();
+    let chunk_count = albums_vec.len().div_ceil(chunk_size);
+    tracing::info!(
+        "Matching {} albums in {chunk_count} chunks, {concurrency} at a time...",
+        albums_vec.len()
+    );
 
-                    // If no exact match, try fuzzy matching (CPU-intensive work)
-                    let normalized_spotify_artist = normalize_artist_name(spotify_artist);
-
-                    // Look for similar artists
-                    for subsonic_artist in &normalized_subsonic_artists {
-                        if fuzzy_match(&normalized_spotify_artist, subsonic_artist) > 0.8 {
-                            // Found a similar artist, now check their albums
-                            if let Some(albums) = normalized_artist_albums.get(subsonic_artist) {
-                                for (_, subsonic_album_name) in albums {
-                                    let album_similarity = fuzzy_match(
-                                        &normalize_album_name(spotify_album),
-                                        subsonic_album_name,
-                                    );
-                                    if album_similarity > 0.8 {
-                                        return (global_idx, album, Some("fuzzy"));
-                                    }
-                                }
-                            }
-                        }
-                    }
+    let mut chunks = vec![];
+    let mut albums_iter = albums_vec.into_iter();
+    loop {
+        let chunk: Vec<_> = albums_iter.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push(chunk);
+    }
 
-                    (global_idx, album, None)
+    let mut chunk_results = futures::stream::iter(chunks.into_iter().map(|chunk| {
+        let matcher = matcher.clone();
+        tokio::task::spawn_blocking(move || {
+            chunk
+                .into_iter()
+                .map(|album| {
+                    let match_type = matcher.match_album(&album);
+                    (album, match_type)
                 })
-            })
-            .collect();
+                .collect::<Vec<_>>()
+        })
+    }))
+    .buffered(concurrency);
 
-        let chunk_results = futures::future::join_all(tasks).await;
-        for result in chunk_results {
-            all_results.push(result?);
-        }
+    let mut all_results = Vec::new();
+    let mut chunks_done = 0;
+    while let Some(result) = chunk_results.next().await {
+        all_results.extend(result?);
+        chunks_done += 1;
+        tracing::info!("Processed chunk {chunks_done} of {chunk_count}");
     }
 
-    // Sort results by original index to maintain play count order
-    all_results.sort_by_key(|(idx, _, _)| *idx);
-
     // Write results to files
     let mut found_counter = 0;
     let mut missing_counter = 0;
 
-    for (_, album, match_type) in all_results {
+    for (album, match_type) in all_results {
         match match_type {
             Some(match_kind) => {
                 writeln!(
@@ -230,322 +402,142 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn fuzzy_match(a: &str, b: &str) -> f64 {
-    let a_lower = a.to_lowercase();
-    let b_lower = b.to_lowercase();
+/// Fetches the Subsonic library, keeping only what matching needs.
+async fn fetch_subsonic_albums(
+    server: blackbird_shared::config::Server,
+) -> anyhow::Result<SubsonicAlbums> {
+    tracing::info!("Connecting to Subsonic server...");
+    let client_id = server.client_id("blackbird-spotcheck", env!("CARGO_PKG_VERSION"));
+    let client = blackbird_state::bs::Client::new(
+        server.base_url,
+        server.username,
+        server.password,
+        client_id,
+    );
 
-    // Exact match gets highest score
-    if a_lower == b_lower {
-        return 1.0;
-    }
+    tracing::info!("Fetching all albums from Subsonic...");
+    let fetch_options = blackbird_state::FetchOptions {
+        album_page_size: server.album_page_size,
+        track_page_size: server.track_page_size,
+        ..Default::default()
+    };
+    let fetched = blackbird_state::fetch_all(&client, fetch_options, |progress| {
+        tracing::info!(
+            "Fetched {} albums, {}/{} tracks",
+            progress.albums_fetched,
+            progress.tracks_fetched,
+            progress.expected_tracks
+        );
+    })
+    .await?;
 
-    // Check if one string contains the other
-    if a_lower.contains(&b_lower) || b_lower.contains(&a_lower) {
-        return 0.8;
+    let mut track_artists: HashMap<_, Vec<String>> = HashMap::new();
+    for track in fetched.track_map.values() {
+        let (Some(album_id), Some(artist)) = (&track.album_id, &track.artist) else {
+            continue;
+        };
+        let artists = track_artists.entry(album_id.clone()).or_default();
+        if !artists.iter().any(|a| a == artist) {
+            artists.push(artist.to_string());
+        }
     }
 
-    // Calculate Jaro-Winkler similarity
-    let jaro = jaro_similarity(&a_lower, &b_lower);
-    let winkler = winkler_similarity(&a_lower, &b_lower, jaro);
-
-    // Also check for word-level matches
-    let word_similarity = word_based_similarity(&a_lower, &b_lower);
-
-    // Return the maximum of the different similarity measures
-    winkler.max(word_similarity)
+    Ok(SubsonicAlbums(
+        fetched
+            .albums
+            .into_values()
+            .map(|album| {
+                let id = album.id.0.to_string();
+                let mut track_artists = track_artists.remove(&album.id).unwrap_or_default();
+                track_artists.retain(|artist| *artist != album.artist);
+                let album = SubsonicAlbum {
+                    id: id.clone(),
+                    artist: album.artist.to_string(),
+                    name: album.name.to_string(),
+                    track_artists,
+                };
+                (id, album)
+            })
+            .collect(),
+    ))
 }
 
-fn jaro_similarity(s1: &str, s2: &str) -> f64 {
-    if s1 == s2 {
-        return 1.0;
-    }
-
-    let len1 = s1.chars().count();
-    let len2 = s2.chars().count();
-
-    if len1 == 0 || len2 == 0 || (len1 + len2 <= 2) {
-        return 0.0;
-    }
-
-    let match_distance = (len1.max(len2) / 2) - 1;
-    let mut s1_matches = vec![false; len1];
-    let mut s2_matches = vec![false; len2];
-
-    let mut matches = 0;
-
-    for (i, c1) in s1.chars().enumerate() {
-        let start = i.saturating_sub(match_distance);
-        let end = (i + match_distance + 1).min(len2);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        #[allow(clippy::needless_range_loop)]
-        for j in start..end {
-            if !s2_matches[j] && c1 == s2.chars().nth(j).unwrap() {
-                s1_matches[i] = true;
-                s2_matches[j] = true;
-                matches += 1;
-                break;
-            }
+    fn subsonic_album(artist: &str, name: &str, track_artists: &[&str]) -> SubsonicAlbum {
+        SubsonicAlbum {
+            id: format!("{artist} - {name}"),
+            artist: artist.to_string(),
+            name: name.to_string(),
+            track_artists: track_artists.iter().map(|a| a.to_string()).collect(),
         }
     }
 
-    if matches == 0 {
-        return 0.0;
-    }
-
-    let mut transpositions = 0;
-    let mut k = 0;
-
-    for (i, matched) in s1_matches.iter().enumerate() {
-        if *matched {
-            while !s2_matches[k] {
-                k += 1;
-            }
-            if s1.chars().nth(i).unwrap() != s2.chars().nth(k).unwrap() {
-                transpositions += 1;
-            }
-            k += 1;
+    fn spotify_album(artist: &str, album: &str) -> common::Album {
+        common::Album {
+            album_id: common::AlbumId {
+                artist: artist.to_string(),
+                album: album.to_string(),
+            },
+            uri: None,
+            play_count: 1,
         }
     }
 
-    let m = matches as f64;
-    let t = (transpositions / 2) as f64;
-
-    (m / len1 as f64 + m / len2 as f64 + (m - t) / m) / 3.0
-}
-
-fn winkler_similarity(s1: &str, s2: &str, jaro: f64) -> f64 {
-    if jaro < 0.7 {
-        return jaro;
-    }
-
-    let prefix_length = s1
-        .chars()
-        .zip(s2.chars())
-        .take_while(|(a, b)| a == b)
-        .count()
-        .min(4);
-
-    jaro + 0.1 * prefix_length as f64 * (1.0 - jaro)
-}
-
-fn word_based_similarity(s1: &str, s2: &str) -> f64 {
-    let words1: std::collections::HashSet<_> = s1.split_whitespace().collect();
-    let words2: std::collections::HashSet<_> = s2.split_whitespace().collect();
-
-    if words1.is_empty() && words2.is_empty() {
-        return 1.0;
-    }
-
-    if words1.is_empty() || words2.is_empty() {
-        return 0.0;
-    }
-
-    let intersection = words1.intersection(&words2).count();
-    let union = words1.union(&words2).count();
-
-    intersection as f64 / union as f64
-}
-
-fn normalize_artist_name(artist: &str) -> String {
-    artist
-        .to_lowercase()
-        .chars()
-        .filter(|c| c.is_alphanumeric())
-        .collect()
-}
-
-/// Strips parenthesized content from the end of album names.
-/// For example: "Visions (2017 Remaster)" becomes "Visions"
-fn strip_album_parentheses(album_name: &str) -> String {
-    let trimmed = album_name.trim_end();
-    if let Some(idx) = trimmed.rfind('(') {
-        let before = &trimmed[..idx];
-        let after = &trimmed[idx..];
-        if after.ends_with(')') && before.chars().last().is_none_or(|c| c.is_whitespace()) {
-            return before.trim_end().to_string();
-        }
+    #[test]
+    fn test_args_parse() {
+        let parse = |args: &[&str]| Args::parse(args.iter().map(|a| a.to_string()));
+
+        assert_eq!(parse(&[]).unwrap(), Args::default());
+        let args = parse(&["--refresh", "--compilation-threshold", "0.75", "data"]).unwrap();
+        assert!(args.refresh);
+        assert_eq!(args.spotify_data_path, Some(PathBuf::from("data")));
+        assert_eq!(args.matching.compilation_album_threshold, 0.75);
+        assert!(args.matching.match_track_artists);
+
+        assert!(parse(&["--compilation-threshold"]).is_err());
+        assert!(parse(&["--compilation-threshold", "2"]).is_err());
+        assert!(parse(&["--strict"]).is_err());
     }
-    album_name.to_string()
-}
-
-/// Removes common superfluous words from album names.
-/// Only removes whole words to avoid partial matches.
-/// For example: "Album Name Deluxe Edition" becomes "Album Name"
-fn strip_superfluous_words(album_name: &str) -> String {
-    const SUPERFLUOUS_WORDS: &[&str] = &[
-        "edition",
-        "deluxe",
-        "remaster",
-        "remastered",
-        "ep",
-        "lp",
-        "single",
-        "live",
-        "acoustic",
-        "unplugged",
-        "studio",
-        "original",
-        "classic",
-        "anniversary",
-        "special",
-        "limited",
-        "expanded",
-        "complete",
-        "full",
-        "extended",
-        "bonus",
-        "extra",
-        "plus",
-        "reissue",
-        "import",
-        "international",
-        "uk",
-        "us",
-        "european",
-        "american",
-        "version",
-        "remix",
-        "explicit",
-        "clean",
-        "instrumental",
-        "vocal",
-        "demo",
-        "rough",
-        "alternate",
-        "alternative",
-        "take",
-        "outtake",
-        "part",
-        "chapter",
-        "volume",
-        "vol",
-        "disc",
-        "cd",
-        "vinyl",
-        "digital",
-        "streaming",
-        "download",
-        "online",
-        "internet",
-        "web",
-        "physical",
-        "hardcopy",
-    ];
-
-    album_name
-        .split_whitespace()
-        .filter(|word| !SUPERFLUOUS_WORDS.contains(word))
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
-/// Normalizes album names by removing parentheses and superfluous words.
-/// This is the main function to use for album name processing.
-fn normalize_album_name(album_name: &str) -> String {
-    let lowercased = album_name.to_lowercase();
-    let stripped = strip_album_parentheses(&lowercased);
-    strip_superfluous_words(&stripped)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test_strip_album_parentheses() {
-        // Basic cases
-        assert_eq!(
-            strip_album_parentheses("Visions (2017 Remaster)"),
-            "Visions"
-        );
-        assert_eq!(
-            strip_album_parentheses("Album Name (Deluxe Edition)"),
-            "Album Name"
-        );
-        assert_eq!(strip_album_parentheses("Test (2023)"), "Test");
-
-        // Cases that should now be stripped
-        assert_eq!(strip_album_parentheses("Album Name"), "Album Name");
-        assert_eq!(strip_album_parentheses("Album (Name)"), "Album");
-        assert_eq!(
-            strip_album_parentheses("Album Name (Remaster) (2023)"),
-            "Album Name (Remaster)"
-        );
-        assert_eq!(
-            strip_album_parentheses("Album Name (Remaster) - Bonus"),
-            "Album Name (Remaster) - Bonus"
-        );
+    fn test_album_matcher_artists() {
+        let albums = [
+            subsonic_album("Band", "Record", &["Band feat. Singer"]),
+            subsonic_album("Various Artists", "Summer Hits 2004", &["Someone"]),
+        ];
 
-        // Edge cases
-        assert_eq!(strip_album_parentheses(""), "");
-        assert_eq!(strip_album_parentheses("(Remaster)"), "");
-        assert_eq!(strip_album_parentheses("Album Name ()"), "Album Name");
-        assert_eq!(strip_album_parentheses("Album Name ( )"), "Album Name");
-
-        // Multiple spaces
-        assert_eq!(
-            strip_album_parentheses("Album Name   (Remaster)   "),
-            "Album Name"
-        );
-
-        // Unbalanced parentheses
-        assert_eq!(
-            strip_album_parentheses("Album Name (Remaster"),
-            "Album Name (Remaster"
-        );
+        let matcher = AlbumMatcher::new(albums.iter(), &MatchOptions::default());
         assert_eq!(
-            strip_album_parentheses("Album Name Remaster)"),
-            "Album Name Remaster)"
+            matcher.match_album(&spotify_album("band", "Record (Deluxe)")),
+            Some("exact")
         );
-    }
-
-    #[test]
-    fn test_strip_superfluous_words() {
-        // Single word removals
-        assert_eq!(strip_superfluous_words("album name edition"), "album name");
-        assert_eq!(strip_superfluous_words("album name ep"), "album name");
-        assert_eq!(strip_superfluous_words("album name deluxe"), "album name");
-        assert_eq!(strip_superfluous_words("album name remaster"), "album name");
-
-        // Multi-word phrase removals (these should no longer work since we simplified)
         assert_eq!(
-            strip_superfluous_words("album name greatest hits"),
-            "album name greatest hits"
+            matcher.match_album(&spotify_album("Band feat. Singer", "Record")),
+            Some("exact")
         );
         assert_eq!(
-            strip_superfluous_words("album name best of"),
-            "album name best of"
+            matcher.match_album(&spotify_album("Someone Else", "Summer Hits 2004")),
+            Some("compilation")
         );
         assert_eq!(
-            strip_superfluous_words("album name radio edit"),
-            "album name radio edit"
+            matcher.match_album(&spotify_album("Someone Else", "Winter Hits 1998")),
+            None
         );
 
-        // Mixed cases
+        let strict = Args::parse(["--no-track-artists", "--no-compilations"].map(String::from))
+            .unwrap()
+            .matching;
+        let matcher = AlbumMatcher::new(albums.iter(), &strict);
         assert_eq!(
-            strip_superfluous_words("album name deluxe edition remaster"),
-            "album name"
+            matcher.match_album(&spotify_album("Band feat. Singer", "Record")),
+            None
         );
         assert_eq!(
-            strip_superfluous_words("album name greatest hits deluxe edition"),
-            "album name greatest hits"
+            matcher.match_album(&spotify_album("Someone Else", "Summer Hits 2004")),
+            None
         );
-
-        // Cases that should NOT be changed
-        assert_eq!(strip_superfluous_words("album name"), "album name");
-        assert_eq!(strip_superfluous_words("replace"), "replace"); // Should not become "rlace"
-        assert_eq!(strip_superfluous_words("editionary"), "editionary"); // Should not become "ary"
-        assert_eq!(strip_superfluous_words("my ep collection"), "my collection");
-
-        // Edge cases
-        assert_eq!(strip_superfluous_words(""), "");
-        assert_eq!(strip_superfluous_words("edition"), "");
-        assert_eq!(strip_superfluous_words("   edition   "), "");
-        assert_eq!(strip_superfluous_words("edition album"), "album");
-
-        // Case sensitivity (now expects lowercase input)
-        assert_eq!(strip_superfluous_words("album name edition"), "album name"); // Lowercase input
-        assert_eq!(strip_superfluous_words("album name edition"), "album name"); // Lowercase input
-        assert_eq!(strip_superfluous_words("album name edition"), "album name"); // Lowercase input
     }
 }
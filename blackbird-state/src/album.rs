@@ -26,6 +26,10 @@ pub struct Album {
     pub artist_id: Option<ArtistId>,
     /// The album cover art ID
     pub cover_art_id: Option<CoverArtId>,
+    /// IDs to request the cover art by if `cover_art_id` is missing or fails
+    /// to load. `getCoverArt` also accepts album and track IDs, which servers
+    /// resolve to art embedded in the album's files.
+    pub cover_art_fallbacks: Vec<CoverArtId>,
     /// The number of tracks in the album
     pub track_count: u32,
     /// The total duration of the album in seconds
@@ -33,32 +37,55 @@ pub struct Album {
     /// The release year of the album
     pub year: Option<i32>,
     /// The genre of the album
-    pub _genre: Option<String>,
+    pub genre: Option<String>,
     /// Whether the album is starred.
     pub starred: bool,
+    /// When the album was starred (ISO 8601 format), if it is.
+    pub starred_at: Option<SmolStr>,
     /// The date the album was added to the library (ISO 8601 format).
     pub created: SmolStr,
+    /// The MusicBrainz release ID, if provided by the server.
+    pub music_brainz_id: Option<SmolStr>,
 }
 impl From<bs::AlbumID3> for Album {
     fn from(album: bs::AlbumID3) -> Self {
+        let id = AlbumId(album.id.into());
+        let cover_art_id = album.cover_art.map(|id| CoverArtId(id.into()));
+        let cover_art_fallbacks = if cover_art_id.is_none() {
+            vec![CoverArtId(id.0.clone())]
+        } else {
+            vec![]
+        };
         Album {
-            id: AlbumId(album.id.into()),
+            id,
             name: album.name.into(),
             artist: album
                 .artist
                 .unwrap_or_else(|| "Unknown Artist".to_string())
                 .into(),
             artist_id: album.artist_id.map(|id| ArtistId(id.into())),
-            cover_art_id: album.cover_art.map(|id| CoverArtId(id.into())),
+            cover_art_id,
+            cover_art_fallbacks,
             track_count: album.song_count,
             duration: album.duration,
             year: album.year,
-            _genre: album.genre,
+            genre: album.genre,
             starred: album.starred.is_some(),
+            starred_at: album.starred.map(|s| s.into()),
             created: album.created.into(),
+            music_brainz_id: album
+                .music_brainz_id
+                .filter(|id| !id.is_empty())
+                .map(|id| id.into()),
         }
     }
 }
+impl Album {
+    /// The IDs to request this album's cover art by, in order of preference.
+    pub fn cover_art_ids(&self) -> impl Iterator<Item = &CoverArtId> {
+        self.cover_art_id.iter().chain(&self.cover_art_fallbacks)
+    }
+}
 impl PartialEq for Album {
     fn eq(&self, other: &Self) -> bool {
         (self.artist.as_str(), self.year, self.name.as_str())
@@ -86,26 +113,34 @@ impl Ord for Album {
     }
 }
 impl Album {
-    /// Returns all albums; does not include tracks.
-    pub async fn fetch_all(client: &bs::Client) -> bs::ClientResult<Vec<Album>> {
-        let mut all_albums = vec![];
+    /// Fetches all albums page by page; does not include tracks.
+    ///
+    /// Albums are fetched with `getAlbumList2` in pages of `page_size`, and
+    /// each page is passed to `on_page` as it arrives. `page_size` is clamped
+    /// to what the server returns per page, as a short page ends the fetch.
+    pub async fn fetch_pages(
+        client: &impl bs::SubsonicApi,
+        page_size: u32,
+        mut on_page: impl FnMut(Vec<Album>),
+    ) -> bs::ClientResult<()> {
+        let page_size = page_size.clamp(1, crate::FetchOptions::MAX_ALBUM_PAGE_SIZE);
         let mut offset = 0;
         loop {
             let albums = client
                 .get_album_list_2(
                     bs::AlbumListType::AlphabeticalByArtist,
-                    Some(500),
+                    Some(page_size as usize),
                     Some(offset),
                 )
                 .await?;
             let album_count = albums.len();
 
             offset += album_count;
-            all_albums.extend(albums.into_iter().map(|a| a.into()));
-            if album_count < 500 {
+            on_page(albums.into_iter().map(|a| a.into()).collect());
+            if album_count < page_size as usize {
                 break;
             }
         }
-        Ok(all_albums)
+        Ok(())
     }
 }
@@ -17,10 +17,30 @@ pub struct Group {
     pub duration: u32,
     /// The tracks in the group.
     pub tracks: Vec<TrackId>,
-    /// The album cover art ID
+    /// The album cover art ID, or the first fallback ID if the server
+    /// reported none. Cover art is cached under this ID, whichever ID it was
+    /// loaded by.
     pub cover_art_id: Option<CoverArtId>,
+    /// IDs to request the cover art by, in order, if `cover_art_id` fails to
+    /// load.
+    pub cover_art_fallbacks: Vec<CoverArtId>,
     /// The associated album's ID
     pub album_id: AlbumId,
+    /// The IDs of the other editions of the album merged into this group.
+    /// Empty unless editions are merged.
+    pub edition_album_ids: Vec<AlbumId>,
     /// Whether the group is starred.
     pub starred: bool,
 }
+impl Group {
+    /// Whether the server reported cover art for the group's album. Without
+    /// it, the art is requested by fallback IDs, which may have none.
+    pub fn has_server_art(&self) -> bool {
+        self.cover_art_fallbacks.is_empty()
+    }
+
+    /// The IDs of the group's album and of the editions merged into it.
+    pub fn album_ids(&self) -> impl Iterator<Item = &AlbumId> {
+        std::iter::once(&self.album_id).chain(&self.edition_album_ids)
+    }
+}
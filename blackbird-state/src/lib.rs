@@ -3,8 +3,12 @@
 //! Separated out to allow for use in other utilities.
 #![deny(missing_docs)]
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
+use blackbird_shared::normalize::normalize_album_name;
 pub use blackbird_subsonic as bs;
 use blackbird_subsonic::ArtistID3;
 use smol_str::{SmolStr, format_smolstr};
@@ -56,31 +60,139 @@ pub struct FetchAllOutput {
     pub groups: Vec<Arc<Group>>,
 }
 
+/// Page sizes used by [`fetch_all`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchOptions {
+    /// The number of albums requested per `getAlbumList2` page, clamped to
+    /// [`Self::MAX_ALBUM_PAGE_SIZE`].
+    pub album_page_size: u32,
+    /// The number of tracks requested per `search3` page.
+    pub track_page_size: u32,
+    /// Whether to merge the editions of an album (e.g. the standard and
+    /// deluxe editions) into one group. Albums by the same artist are
+    /// editions of each other if their names are the same once
+    /// [normalized](blackbird_shared::normalize::normalize_album_name).
+    pub merge_editions: bool,
+}
+impl FetchOptions {
+    /// The default number of albums requested per page.
+    pub const DEFAULT_ALBUM_PAGE_SIZE: u32 =
+        blackbird_shared::config::Server::DEFAULT_ALBUM_PAGE_SIZE;
+    /// The default number of tracks requested per page.
+    pub const DEFAULT_TRACK_PAGE_SIZE: u32 =
+        blackbird_shared::config::Server::DEFAULT_TRACK_PAGE_SIZE;
+    /// The most albums the Subsonic API returns per `getAlbumList2` page.
+    pub const MAX_ALBUM_PAGE_SIZE: u32 = 500;
+}
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            album_page_size: Self::DEFAULT_ALBUM_PAGE_SIZE,
+            track_page_size: Self::DEFAULT_TRACK_PAGE_SIZE,
+            merge_editions: false,
+        }
+    }
+}
+
+/// Progress reported by [`fetch_all`] and [`fetch_streaming`] as each page
+/// arrives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchProgress {
+    /// The number of albums fetched so far.
+    pub albums_fetched: u32,
+    /// The number of tracks the fetched albums report containing. Once all
+    /// albums have been fetched, this is the number of tracks the library is
+    /// expected to contain.
+    pub expected_tracks: u32,
+    /// The number of tracks fetched in the most recent page.
+    pub batch_tracks: u32,
+    /// The number of tracks fetched so far.
+    pub tracks_fetched: u32,
+}
+impl FetchProgress {
+    /// Whether fewer tracks were fetched than the albums report containing,
+    /// which suggests that the library may be incomplete.
+    pub fn is_incomplete(&self) -> bool {
+        self.tracks_fetched < self.expected_tracks
+    }
+}
+
+/// An update reported by [`fetch_streaming`] as each page arrives.
+#[derive(Debug, Clone)]
+pub enum FetchEvent {
+    /// A page of albums. All albums arrive before any tracks.
+    Albums(Vec<Album>),
+    /// A page of tracks, in no particular order. Videos are left out.
+    Tracks(Vec<Track>),
+    /// A page of artists, used to sort albums by their artists' sort names.
+    /// These arrive last.
+    Artists(Vec<ArtistID3>),
+    /// The progress made so far, sent after every album and track page.
+    Progress(FetchProgress),
+}
+
 /// Fetches all albums and tracks from the server, and constructs groups.
 ///
-/// `on_tracks_fetched` is called with the number of tracks that were just fetched,
-/// as well as the total number of tracks fetched so far.
+/// `on_progress` is called after every album and track page with the progress
+/// made so far. This is a convenience wrapper around [`fetch_streaming`] that
+/// waits for the whole library before sorting it.
 pub async fn fetch_all(
-    client: &bs::Client,
-    on_tracks_fetched: impl Fn(u32, u32),
+    client: &impl bs::SubsonicApi,
+    options: FetchOptions,
+    on_progress: impl Fn(FetchProgress),
 ) -> bs::ClientResult<FetchAllOutput> {
+    let mut albums = HashMap::new();
+    let mut tracks = HashMap::new();
+    let mut artists = HashMap::new();
+    fetch_streaming(client, options, |event| match event {
+        FetchEvent::Albums(page) => albums.extend(page.into_iter().map(|a| (a.id.clone(), a))),
+        FetchEvent::Tracks(page) => tracks.extend(page.into_iter().map(|t| (t.id.clone(), t))),
+        FetchEvent::Artists(page) => {
+            artists.extend(page.into_iter().map(|a| (ArtistId(a.id.clone().into()), a)))
+        }
+        FetchEvent::Progress(progress) => on_progress(progress),
+    })
+    .await?;
+
+    Ok(collate(albums, tracks, &artists, options.merge_editions))
+}
+
+/// Fetches all albums, tracks and artists from the server, passing each page
+/// to `on_event` as it arrives, for consumers that want to process the library
+/// while it loads. Pages can be forwarded to a channel to consume them
+/// elsewhere.
+///
+/// Groups aren't reported, as they can only be built once the library has
+/// been sorted; see [`fetch_all`] for that.
+pub async fn fetch_streaming(
+    client: &impl bs::SubsonicApi,
+    options: FetchOptions,
+    mut on_event: impl FnMut(FetchEvent),
+) -> bs::ClientResult<()> {
     // Fetch all albums.
-    let albums: HashMap<AlbumId, Album> = Album::fetch_all(client)
-        .await?
-        .into_iter()
-        .map(|a| (a.id.clone(), a))
-        .collect();
+    let mut albums_fetched = 0;
+    let mut expected_tracks = 0;
+    Album::fetch_pages(client, options.album_page_size, |page| {
+        albums_fetched += page.len() as u32;
+        expected_tracks += page.iter().map(|a| a.track_count).sum::<u32>();
+        on_event(FetchEvent::Albums(page));
+        on_event(FetchEvent::Progress(FetchProgress {
+            albums_fetched,
+            expected_tracks,
+            ..Default::default()
+        }));
+    })
+    .await?;
 
     // Fetch all tracks.
     let mut offset = 0;
-    let mut tracks = HashMap::new();
     loop {
         let response = client
             .search3(&bs::Search3Request {
                 query: "".to_string(),
                 artist_count: Some(0),
                 album_count: Some(0),
-                song_count: Some(10000),
+                song_count: Some(options.track_page_size),
                 song_offset: Some(offset),
                 ..Default::default()
             })
@@ -91,19 +203,18 @@ pub async fn fetch_all(
         }
 
         let track_count = response.song.len();
-        tracks.extend(
-            response
-                .song
-                .into_iter()
-                .map(|s| (TrackId(s.id.clone()), Track::from(s))),
-        );
+        on_event(FetchEvent::Tracks(audio_tracks(response.song).collect()));
         offset += track_count as u32;
-        on_tracks_fetched(track_count as u32, offset);
+        on_event(FetchEvent::Progress(FetchProgress {
+            albums_fetched,
+            expected_tracks,
+            batch_tracks: track_count as u32,
+            tracks_fetched: offset,
+        }));
     }
 
     // Fetch all artists.
     let mut offset = 0;
-    let mut artists = HashMap::new();
     loop {
         let response = client
             .search3(&bs::Search3Request {
@@ -119,16 +230,45 @@ pub async fn fetch_all(
         }
 
         let artist_count = response.artist.len();
-        artists.extend(
-            response
-                .artist
-                .into_iter()
-                .map(|a| (ArtistId(a.id.clone().into()), a)),
-        );
+        on_event(FetchEvent::Artists(response.artist));
 
         offset += artist_count as u32;
     }
 
+    Ok(())
+}
+
+/// Sorts the fetched tracks and builds groups from them. Used by
+/// [`fetch_all`] once the library has been fetched, and to rebuild the groups
+/// of tracks added to a library afterwards.
+///
+/// Albums without sort names in `artists` sort by their lowercased artist.
+pub fn collate(
+    albums: HashMap<AlbumId, Album>,
+    mut tracks: HashMap<TrackId, Track>,
+    artists: &HashMap<ArtistId, ArtistID3>,
+    merge_editions: bool,
+) -> FetchAllOutput {
+    // When merging editions, each album is sorted and grouped as the edition
+    // standing in for all of them.
+    let editions = if merge_editions {
+        edition_representatives(&albums, artists)
+    } else {
+        HashMap::new()
+    };
+    let mut merged_editions: HashMap<&AlbumId, Vec<AlbumId>> = HashMap::new();
+    for (album_id, representative) in &editions {
+        if album_id != representative {
+            merged_editions
+                .entry(representative)
+                .or_default()
+                .push(album_id.clone());
+        }
+    }
+    for edition_ids in merged_editions.values_mut() {
+        edition_ids.sort();
+    }
+
     // This is all mad ineffcient but cbf doing it better.
     // Sort tracks.
     let mut track_ids: Vec<TrackId> = tracks.keys().cloned().collect();
@@ -142,15 +282,20 @@ pub async fn fetch_all(
                 let album_id = track.album_id.as_ref().unwrap_or_else(|| {
                     panic!("Album ID not found in track: {track:?}");
                 });
+                let representative = editions.get(album_id).unwrap_or(album_id);
+                // Of the copies of a track shared by editions, the
+                // representative's sorts first, and so is the one kept.
+                let other_edition = representative != album_id;
+                let album_id = representative;
                 let album = albums.get(album_id).unwrap_or_else(|| {
                     panic!("Album not found in state: {album_id:?}");
                 });
-                let album_artist = normalized_artist_sort_name(album, &artists);
+                let album_artist = normalized_artist_sort_name(album, artists);
                 let is_various_artists = album_artist == "various artists";
                 (
                     id.clone(),
                     format!(
-                        "{} - {} - {} - {} - {} - {}",
+                        "{} - {} - {} - {} - {} - {} - {} - {}",
                         album_artist,
                         album
                             .year
@@ -165,6 +310,8 @@ pub async fn fetch_all(
                         track.disc_number.unwrap_or_default(),
                         track.track.unwrap_or_default(),
                         track.title,
+                        u8::from(other_edition),
+                        id,
                     ),
                 )
             })
@@ -181,8 +328,10 @@ pub async fn fetch_all(
 
     // Build groups.
     let mut groups = vec![];
+    let mut duplicates = HashSet::new();
     {
         let mut current_group: Option<Group> = None;
+        let mut seen_tracks = HashSet::new();
         for track_id in &track_ids {
             let track = tracks.get(track_id).unwrap_or_else(|| {
                 panic!("Track not found in track map: {track_id}");
@@ -190,49 +339,119 @@ pub async fn fetch_all(
             let album_id = track.album_id.as_ref().unwrap_or_else(|| {
                 panic!("Album ID not found in track: {track:?}");
             });
+            let album_id = editions.get(album_id).unwrap_or(album_id);
             let album = albums.get(album_id).unwrap_or_else(|| {
                 panic!("Album not found in album map: {album_id:?}");
             });
 
             if !current_group.as_ref().is_some_and(|group| {
-                group.sort_artist == normalized_artist_sort_name(album, &artists)
+                group.sort_artist == normalized_artist_sort_name(album, artists)
                     && group.album == album.name
                     && group.year == album.year
             }) {
                 if let Some(group) = current_group.take() {
                     groups.push(Arc::new(group));
                 }
+                seen_tracks.clear();
+
+                // Without cover art of its own, the album's first track may
+                // still have art embedded.
+                let mut cover_art_ids = album.cover_art_ids().cloned().collect::<Vec<_>>();
+                if album.cover_art_id.is_none() {
+                    cover_art_ids.push(CoverArtId(track_id.0.as_str().into()));
+                }
+                let mut cover_art_ids = cover_art_ids.into_iter();
 
                 current_group = Some(Group {
                     artist: album.artist.clone(),
-                    sort_artist: normalized_artist_sort_name(album, &artists),
+                    sort_artist: normalized_artist_sort_name(album, artists),
                     album: album.name.clone(),
                     year: album.year,
-                    duration: album.duration,
+                    // Merged editions are timed by the tracks they keep.
+                    duration: if merge_editions { 0 } else { album.duration },
                     tracks: vec![],
-                    cover_art_id: album.cover_art_id.clone(),
+                    cover_art_id: cover_art_ids.next(),
+                    cover_art_fallbacks: cover_art_ids.collect(),
                     album_id: album.id.clone(),
+                    edition_album_ids: merged_editions.get(&album.id).cloned().unwrap_or_default(),
                     starred: album.starred,
                 });
             }
 
-            current_group
-                .as_mut()
-                .unwrap()
-                .tracks
-                .push(track_id.clone());
+            // Editions share most of their tracks, so only the first of
+            // each is kept.
+            if merge_editions
+                && !seen_tracks.insert((track.disc_number, track.track, track.title.to_lowercase()))
+            {
+                duplicates.insert(track_id.clone());
+                continue;
+            }
+
+            let group = current_group.as_mut().unwrap();
+            group.tracks.push(track_id.clone());
+            if merge_editions {
+                group.duration += track.duration.unwrap_or_default();
+            }
         }
         if let Some(group) = current_group.take() {
             groups.push(Arc::new(group));
         }
     }
 
-    Ok(FetchAllOutput {
+    // Duplicates belong to no group, so they are dropped entirely.
+    track_ids.retain(|id| !duplicates.contains(id));
+    tracks.retain(|id, _| !duplicates.contains(id));
+
+    FetchAllOutput {
         albums,
         track_map: tracks,
         track_ids,
         groups,
-    })
+    }
+}
+
+/// Maps each album to the edition standing in for all editions of it: the one
+/// with the shortest name, which is usually the standard edition.
+fn edition_representatives(
+    albums: &HashMap<AlbumId, Album>,
+    artists: &HashMap<ArtistId, ArtistID3>,
+) -> HashMap<AlbumId, AlbumId> {
+    let mut editions: HashMap<(SmolStr, String), Vec<&Album>> = HashMap::new();
+    for album in albums.values() {
+        // Names made only of edition words (e.g. "Live") are kept as they
+        // are, so they don't all merge into one.
+        let mut name = normalize_album_name(&album.name);
+        if name.is_empty() {
+            name = album.name.to_lowercase();
+        }
+        editions
+            .entry((normalized_artist_sort_name(album, artists), name))
+            .or_default()
+            .push(album);
+    }
+
+    let mut representatives = HashMap::new();
+    for editions in editions.into_values() {
+        let Some(representative) = editions
+            .iter()
+            .min_by_key(|album| (album.name.len(), album.year, &album.id))
+        else {
+            continue;
+        };
+        for album in &editions {
+            representatives.insert(album.id.clone(), representative.id.clone());
+        }
+    }
+    representatives
+}
+
+/// Converts a page of `search3` songs into tracks, leaving out any entries the
+/// server reports as videos, as they can't be played as audio.
+fn audio_tracks(songs: Vec<bs::Child>) -> impl Iterator<Item = Track> {
+    songs
+        .into_iter()
+        .map(Track::from)
+        .filter(|track| !track.is_video)
 }
 
 fn normalized_artist_sort_name(album: &Album, artists: &HashMap<ArtistId, ArtistID3>) -> SmolStr {
@@ -262,3 +481,253 @@ fn normalized_artist_sort_name(album: &Album, artists: &HashMap<ArtistId, Artist
         })
         .unwrap_or_else(|| album_artist.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_tracks_excludes_videos() {
+        let response: bs::Search3Response = serde_json::from_str(
+            r#"{
+                "song": [
+                    {"id": "song", "isDir": false, "title": "A Song", "albumId": "album"},
+                    {"id": "video", "isDir": false, "title": "A Video", "isVideo": true},
+                    {"id": "audio", "isDir": false, "title": "Not A Video", "albumId": "album", "isVideo": false}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let mut ids: Vec<_> = audio_tracks(response.song).map(|t| t.id.0).collect();
+        ids.sort();
+        assert_eq!(ids, ["audio", "song"]);
+    }
+
+    fn album(id: &str, name: &str) -> (AlbumId, Album) {
+        let album = Album {
+            id: AlbumId(id.into()),
+            name: name.into(),
+            artist: "Band".into(),
+            artist_id: None,
+            cover_art_id: None,
+            cover_art_fallbacks: vec![],
+            track_count: 0,
+            duration: 0,
+            year: None,
+            genre: None,
+            starred: false,
+            starred_at: None,
+            created: SmolStr::default(),
+            music_brainz_id: None,
+        };
+        (album.id.clone(), album)
+    }
+
+    fn track(id: &str, album_id: &str, number: u32, title: &str) -> (TrackId, Track) {
+        let track = Track {
+            id: TrackId(id.into()),
+            title: title.into(),
+            artist: None,
+            track: Some(number),
+            year: None,
+            _genre: None,
+            duration: Some(100),
+            disc_number: None,
+            album_id: Some(AlbumId(album_id.into())),
+            starred: false,
+            starred_at: None,
+            created: None,
+            play_count: None,
+            replay_gain: None,
+            music_brainz_id: None,
+            suffix: None,
+            transcoded_suffix: None,
+            path: None,
+            is_video: false,
+        };
+        (track.id.clone(), track)
+    }
+
+    #[test]
+    fn collate_merges_editions() {
+        let albums = HashMap::from([
+            album("standard", "Record"),
+            album("deluxe", "Record (Deluxe Edition)"),
+            album("other", "Another Record"),
+        ]);
+        let tracks = HashMap::from([
+            track("s1", "standard", 1, "One"),
+            track("s2", "standard", 2, "Two"),
+            track("d1", "deluxe", 1, "One"),
+            track("d2", "deluxe", 2, "Two"),
+            track("d3", "deluxe", 3, "Bonus"),
+            track("o1", "other", 1, "One"),
+        ]);
+
+        let separate = collate(albums.clone(), tracks.clone(), &HashMap::new(), false);
+        assert_eq!(separate.groups.len(), 3);
+        assert_eq!(separate.track_ids.len(), 6);
+
+        let merged = collate(albums, tracks, &HashMap::new(), true);
+        let groups: Vec<_> = merged
+            .groups
+            .iter()
+            .map(|g| (g.album.as_str(), g.tracks.len(), g.duration))
+            .collect();
+        assert_eq!(groups, [("Another Record", 1, 100), ("Record", 3, 300)]);
+        assert_eq!(merged.track_ids.len(), 4);
+        assert!(merged.track_ids.iter().any(|id| id.0 == "d3"));
+        // The deluxe edition's copies of the standard tracks are dropped.
+        assert_eq!(merged.track_map.len(), 4);
+        assert!(!merged.track_map.contains_key(&TrackId("d1".into())));
+    }
+
+    #[test]
+    fn collate_records_the_merged_editions_on_the_group() {
+        let albums = HashMap::from([
+            album("standard", "Record"),
+            album("deluxe", "Record (Deluxe Edition)"),
+            album("remaster", "Record (Remastered)"),
+        ]);
+        let tracks = HashMap::from([
+            track("s1", "standard", 1, "One"),
+            track("d1", "deluxe", 1, "One"),
+            track("r1", "remaster", 1, "One"),
+        ]);
+
+        let merged = collate(albums, tracks, &HashMap::new(), true);
+        let [group] = merged.groups.as_slice() else {
+            panic!("expected one group, got {:?}", merged.groups);
+        };
+        assert_eq!(group.album_id, AlbumId("standard".into()));
+        assert_eq!(
+            group.edition_album_ids,
+            [AlbumId("deluxe".into()), AlbumId("remaster".into())]
+        );
+        assert_eq!(group.tracks, [TrackId("s1".into())]);
+    }
+
+    /// Small pages, so that fetching has to page through the fixture.
+    const FIXTURE_OPTIONS: FetchOptions = FetchOptions {
+        album_page_size: 2,
+        track_page_size: 2,
+        merge_editions: false,
+    };
+
+    #[tokio::test]
+    async fn fetch_all_groups_the_fixture_library() {
+        let client = bs::MockClient::fixture();
+        let progress = std::sync::Mutex::new(vec![]);
+        let output = fetch_all(&client, FIXTURE_OPTIONS, |p| {
+            progress.lock().unwrap().push(p)
+        })
+        .await
+        .unwrap();
+
+        let groups: Vec<_> = output
+            .groups
+            .iter()
+            .map(|g| (g.album.as_str(), g.tracks.len(), g.duration))
+            .collect();
+        assert_eq!(
+            groups,
+            [("Debut", 2, 420), ("Second", 2, 480), ("Hits", 2, 360)]
+        );
+        let track_ids: Vec<_> = output.track_ids.iter().map(|id| id.0.as_str()).collect();
+        assert_eq!(
+            track_ids,
+            [
+                "tr-debut-1",
+                "tr-debut-2",
+                "tr-second-1",
+                "tr-second-2",
+                "tr-hits-1",
+                "tr-hits-2"
+            ]
+        );
+        assert_eq!(output.albums.len(), 3);
+
+        let last = *progress.lock().unwrap().last().unwrap();
+        assert_eq!(last.albums_fetched, 3);
+        assert_eq!(last.expected_tracks, 6);
+        assert!(!last.is_incomplete());
+    }
+
+    #[tokio::test]
+    async fn fetch_all_groups_a_built_library() {
+        let client = bs::MockClient::default()
+            .with_album("Solo", "First", &[("One", 100), ("Two", 120)])
+            .with_album("Duo", "Together", &[("Both", 90)])
+            .with_album("Solo", "Later", &[("Three", 150)]);
+        let output = fetch_all(&client, FIXTURE_OPTIONS, |_| {}).await.unwrap();
+
+        let groups: Vec<_> = output
+            .groups
+            .iter()
+            .map(|g| (g.artist.as_str(), g.album.as_str(), g.duration))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("Duo", "Together", 90),
+                ("Solo", "First", 220),
+                ("Solo", "Later", 150)
+            ]
+        );
+        let track_ids: Vec<_> = output.track_ids.iter().map(|id| id.0.as_str()).collect();
+        assert_eq!(track_ids, ["tr-2-1", "tr-1-1", "tr-1-2", "tr-3-1"]);
+        assert_eq!(client.artists.len(), 2);
+        assert_eq!(client.artists[0].album_count, 2);
+    }
+
+    #[tokio::test]
+    async fn fetch_all_pages_past_the_album_page_cap() {
+        let client = (0..FetchOptions::MAX_ALBUM_PAGE_SIZE + 10)
+            .fold(bs::MockClient::default(), |client, i| {
+                client.with_album("Artist", &format!("Album {i}"), &[])
+            });
+        let options = FetchOptions {
+            album_page_size: 1000,
+            ..FIXTURE_OPTIONS
+        };
+        let output = fetch_all(&client, options, |_| {}).await.unwrap();
+        assert_eq!(output.albums.len(), 510);
+    }
+
+    #[tokio::test]
+    async fn fetch_streaming_reports_pages_in_order() {
+        let client = bs::MockClient::fixture();
+        let mut events = vec![];
+        fetch_streaming(&client, FIXTURE_OPTIONS, |event| {
+            events.push(match event {
+                FetchEvent::Albums(page) => format!("albums {}", page.len()),
+                FetchEvent::Tracks(page) => format!("tracks {}", page.len()),
+                FetchEvent::Artists(page) => format!("artists {}", page.len()),
+                FetchEvent::Progress(_) => "progress".to_string(),
+            })
+        })
+        .await
+        .unwrap();
+
+        // The video is left out of the third page of tracks.
+        assert_eq!(
+            events,
+            [
+                "albums 2",
+                "progress",
+                "albums 1",
+                "progress",
+                "tracks 2",
+                "progress",
+                "tracks 2",
+                "progress",
+                "tracks 1",
+                "progress",
+                "tracks 1",
+                "progress",
+                "artists 2",
+            ]
+        );
+    }
+}
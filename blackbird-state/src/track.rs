@@ -35,10 +35,28 @@ pub struct Track {
     pub album_id: Option<AlbumId>,
     /// Whether the track is starred
     pub starred: bool,
+    /// When the track was starred (ISO 8601 format), if it is.
+    pub starred_at: Option<SmolStr>,
+    /// The date the track was added to the library (ISO 8601 format), if
+    /// provided by the server.
+    pub created: Option<SmolStr>,
     /// The number of times this track has been played
     pub play_count: Option<u64>,
     /// ReplayGain metadata, if provided by the server.
     pub replay_gain: Option<bs::ReplayGain>,
+    /// The MusicBrainz recording ID, if provided by the server.
+    pub music_brainz_id: Option<SmolStr>,
+    /// The extension of the original file (e.g. `flac`), if provided by the
+    /// server.
+    pub suffix: Option<SmolStr>,
+    /// The extension the server transcodes this track to (e.g. `mp3`), if it
+    /// reports one.
+    pub transcoded_suffix: Option<SmolStr>,
+    /// The path of the file as reported by the server, if provided. Servers
+    /// usually report it relative to their music folder.
+    pub path: Option<SmolStr>,
+    /// Whether the server reports this entry as a video rather than audio.
+    pub is_video: bool,
 }
 impl From<bs::Child> for Track {
     fn from(child: bs::Child) -> Self {
@@ -56,8 +74,18 @@ impl From<bs::Child> for Track {
             disc_number: child.disc_number,
             album_id: child.album_id.map(|id| AlbumId(id.into())),
             starred: child.starred.is_some(),
+            starred_at: child.starred.map(|s| s.into()),
+            created: child.created.map(|c| c.into()),
             play_count: child.play_count,
             replay_gain: child.replay_gain,
+            music_brainz_id: child
+                .music_brainz_id
+                .filter(|id| !id.is_empty())
+                .map(|id| id.into()),
+            suffix: child.suffix.map(|s| s.into()),
+            transcoded_suffix: child.transcoded_suffix.map(|s| s.into()),
+            path: child.path.map(|p| p.into()),
+            is_video: child.is_video.unwrap_or(false),
         }
     }
 }
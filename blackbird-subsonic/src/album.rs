@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::{Client, ClientResult, song::Child};
 
 /// Represents an album with ID3 metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AlbumID3 {
     /// The album ID
@@ -37,6 +37,9 @@ pub struct AlbumID3 {
     /// The genre of the album
     #[serde(skip_serializing_if = "Option::is_none")]
     pub genre: Option<String>,
+    /// The MusicBrainz release ID (OpenSubsonic extension).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub music_brainz_id: Option<String>,
 }
 
 /// Represents an album with ID3 metadata and songs
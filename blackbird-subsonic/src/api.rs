@@ -0,0 +1,334 @@
+use std::future::Future;
+
+use crate::{
+    AlbumID3, AlbumListType, AlbumWithSongsID3, Child, Client, ClientResult, NowPlayingEntry,
+    Playlist, PlaylistWithSongs, ScanStatus, ServerInfo,
+};
+#[cfg(feature = "opensubsonic")]
+use crate::{LyricsList, Search3Request, Search3Response};
+
+/// The requests made of a Subsonic server, so that the library and playback
+/// logic can run against something other than a live server, such as a
+/// `MockClient` in tests. See the [`Client`] methods of the same names.
+pub trait SubsonicApi: Send + Sync + 'static {
+    /// See [`Client::username`].
+    fn username(&self) -> &str;
+
+    /// See [`Client::api_version`].
+    fn api_version(&self) -> String;
+
+    /// See [`Client::set_api_version`].
+    fn set_api_version(&self, version: String);
+
+    /// See [`Client::ping`].
+    fn ping(&self) -> impl Future<Output = ClientResult<String>> + Send + Sync;
+
+    /// See [`Client::ping_with_version`].
+    fn ping_with_version(
+        &self,
+        api_version: &str,
+    ) -> impl Future<Output = ClientResult<String>> + Send + Sync;
+
+    /// See [`Client::negotiate_api_version`].
+    fn negotiate_api_version(&self) -> impl Future<Output = ClientResult<String>> + Send + Sync;
+
+    /// See [`Client::get_server_info`].
+    fn get_server_info(&self) -> impl Future<Output = ClientResult<ServerInfo>> + Send + Sync;
+
+    /// See [`Client::get_album_list_2`].
+    fn get_album_list_2(
+        &self,
+        ty: AlbumListType,
+        size: Option<usize>,
+        offset: Option<usize>,
+    ) -> impl Future<Output = ClientResult<Vec<AlbumID3>>> + Send + Sync;
+
+    /// See [`Client::get_album_with_songs`].
+    fn get_album_with_songs(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = ClientResult<AlbumWithSongsID3>> + Send + Sync;
+
+    /// See [`Client::search3`].
+    #[cfg(feature = "opensubsonic")]
+    fn search3(
+        &self,
+        request: &Search3Request,
+    ) -> impl Future<Output = ClientResult<Search3Response>> + Send + Sync;
+
+    /// See [`Client::get_song`].
+    fn get_song(&self, id: &str) -> impl Future<Output = ClientResult<Child>> + Send + Sync;
+
+    /// See [`Client::get_similar_songs_2`].
+    fn get_similar_songs_2(
+        &self,
+        id: &str,
+        count: Option<u32>,
+    ) -> impl Future<Output = ClientResult<Vec<Child>>> + Send + Sync;
+
+    /// See [`Client::get_random_songs`].
+    fn get_random_songs(
+        &self,
+        size: Option<u32>,
+        genre: Option<String>,
+    ) -> impl Future<Output = ClientResult<Vec<Child>>> + Send + Sync;
+
+    /// See [`Client::stream`].
+    fn stream(
+        &self,
+        id: &str,
+        format: Option<String>,
+        max_bitrate_kbps: Option<u32>,
+    ) -> impl Future<Output = ClientResult<Vec<u8>>> + Send + Sync;
+
+    /// See [`Client::download`].
+    fn download(&self, id: &str) -> impl Future<Output = ClientResult<Vec<u8>>> + Send + Sync;
+
+    /// See [`Client::get_cover_art`].
+    fn get_cover_art(
+        &self,
+        id: &str,
+        size: Option<usize>,
+    ) -> impl Future<Output = ClientResult<Vec<u8>>> + Send + Sync;
+
+    /// See [`Client::get_lyrics_by_song_id`].
+    #[cfg(feature = "opensubsonic")]
+    fn get_lyrics_by_song_id(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = ClientResult<LyricsList>> + Send + Sync;
+
+    /// See [`Client::star`].
+    fn star(
+        &self,
+        track_ids: Vec<String>,
+        album_ids: Vec<String>,
+        artist_ids: Vec<String>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync;
+
+    /// See [`Client::unstar`].
+    fn unstar(
+        &self,
+        track_ids: Vec<String>,
+        album_ids: Vec<String>,
+        artist_ids: Vec<String>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync;
+
+    /// See [`Client::scrobble`].
+    fn scrobble(
+        &self,
+        id: &str,
+        time: Option<u64>,
+        submission: Option<bool>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync;
+
+    /// See [`Client::get_now_playing`].
+    fn get_now_playing(
+        &self,
+    ) -> impl Future<Output = ClientResult<Vec<NowPlayingEntry>>> + Send + Sync;
+
+    /// See [`Client::get_scan_status`].
+    fn get_scan_status(&self) -> impl Future<Output = ClientResult<ScanStatus>> + Send + Sync;
+
+    /// See [`Client::start_scan`].
+    fn start_scan(&self) -> impl Future<Output = ClientResult<ScanStatus>> + Send + Sync;
+
+    /// See [`Client::get_playlists`].
+    fn get_playlists(&self) -> impl Future<Output = ClientResult<Vec<Playlist>>> + Send + Sync;
+
+    /// See [`Client::get_playlist`].
+    fn get_playlist(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = ClientResult<PlaylistWithSongs>> + Send + Sync;
+
+    /// See [`Client::create_playlist`].
+    fn create_playlist(
+        &self,
+        name: &str,
+        song_ids: Vec<String>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync;
+
+    /// See [`Client::update_playlist`].
+    fn update_playlist(
+        &self,
+        id: &str,
+        song_ids_to_add: Vec<String>,
+        song_indexes_to_remove: Vec<usize>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync;
+}
+
+impl SubsonicApi for Client {
+    fn username(&self) -> &str {
+        Client::username(self)
+    }
+
+    fn api_version(&self) -> String {
+        Client::api_version(self)
+    }
+
+    fn set_api_version(&self, version: String) {
+        Client::set_api_version(self, version)
+    }
+
+    fn ping(&self) -> impl Future<Output = ClientResult<String>> + Send + Sync {
+        Client::ping(self)
+    }
+
+    fn ping_with_version(
+        &self,
+        api_version: &str,
+    ) -> impl Future<Output = ClientResult<String>> + Send + Sync {
+        Client::ping_with_version(self, api_version)
+    }
+
+    fn negotiate_api_version(&self) -> impl Future<Output = ClientResult<String>> + Send + Sync {
+        Client::negotiate_api_version(self)
+    }
+
+    fn get_server_info(&self) -> impl Future<Output = ClientResult<ServerInfo>> + Send + Sync {
+        Client::get_server_info(self)
+    }
+
+    fn get_album_list_2(
+        &self,
+        ty: AlbumListType,
+        size: Option<usize>,
+        offset: Option<usize>,
+    ) -> impl Future<Output = ClientResult<Vec<AlbumID3>>> + Send + Sync {
+        Client::get_album_list_2(self, ty, size, offset)
+    }
+
+    fn get_album_with_songs(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = ClientResult<AlbumWithSongsID3>> + Send + Sync {
+        Client::get_album_with_songs(self, id)
+    }
+
+    #[cfg(feature = "opensubsonic")]
+    fn search3(
+        &self,
+        request: &Search3Request,
+    ) -> impl Future<Output = ClientResult<Search3Response>> + Send + Sync {
+        Client::search3(self, request)
+    }
+
+    fn get_song(&self, id: &str) -> impl Future<Output = ClientResult<Child>> + Send + Sync {
+        Client::get_song(self, id)
+    }
+
+    fn get_similar_songs_2(
+        &self,
+        id: &str,
+        count: Option<u32>,
+    ) -> impl Future<Output = ClientResult<Vec<Child>>> + Send + Sync {
+        Client::get_similar_songs_2(self, id, count)
+    }
+
+    fn get_random_songs(
+        &self,
+        size: Option<u32>,
+        genre: Option<String>,
+    ) -> impl Future<Output = ClientResult<Vec<Child>>> + Send + Sync {
+        Client::get_random_songs(self, size, genre)
+    }
+
+    fn stream(
+        &self,
+        id: &str,
+        format: Option<String>,
+        max_bitrate_kbps: Option<u32>,
+    ) -> impl Future<Output = ClientResult<Vec<u8>>> + Send + Sync {
+        Client::stream(self, id, format, max_bitrate_kbps)
+    }
+
+    fn download(&self, id: &str) -> impl Future<Output = ClientResult<Vec<u8>>> + Send + Sync {
+        Client::download(self, id)
+    }
+
+    fn get_cover_art(
+        &self,
+        id: &str,
+        size: Option<usize>,
+    ) -> impl Future<Output = ClientResult<Vec<u8>>> + Send + Sync {
+        Client::get_cover_art(self, id, size)
+    }
+
+    #[cfg(feature = "opensubsonic")]
+    fn get_lyrics_by_song_id(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = ClientResult<LyricsList>> + Send + Sync {
+        Client::get_lyrics_by_song_id(self, id)
+    }
+
+    fn star(
+        &self,
+        track_ids: Vec<String>,
+        album_ids: Vec<String>,
+        artist_ids: Vec<String>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        Client::star(self, track_ids, album_ids, artist_ids)
+    }
+
+    fn unstar(
+        &self,
+        track_ids: Vec<String>,
+        album_ids: Vec<String>,
+        artist_ids: Vec<String>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        Client::unstar(self, track_ids, album_ids, artist_ids)
+    }
+
+    fn scrobble(
+        &self,
+        id: &str,
+        time: Option<u64>,
+        submission: Option<bool>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        Client::scrobble(self, id, time, submission)
+    }
+
+    fn get_now_playing(
+        &self,
+    ) -> impl Future<Output = ClientResult<Vec<NowPlayingEntry>>> + Send + Sync {
+        Client::get_now_playing(self)
+    }
+
+    fn get_scan_status(&self) -> impl Future<Output = ClientResult<ScanStatus>> + Send + Sync {
+        Client::get_scan_status(self)
+    }
+
+    fn start_scan(&self) -> impl Future<Output = ClientResult<ScanStatus>> + Send + Sync {
+        Client::start_scan(self)
+    }
+
+    fn get_playlists(&self) -> impl Future<Output = ClientResult<Vec<Playlist>>> + Send + Sync {
+        Client::get_playlists(self)
+    }
+
+    fn get_playlist(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = ClientResult<PlaylistWithSongs>> + Send + Sync {
+        Client::get_playlist(self, id)
+    }
+
+    fn create_playlist(
+        &self,
+        name: &str,
+        song_ids: Vec<String>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        Client::create_playlist(self, name, song_ids)
+    }
+
+    fn update_playlist(
+        &self,
+        id: &str,
+        song_ids_to_add: Vec<String>,
+        song_indexes_to_remove: Vec<usize>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        Client::update_playlist(self, id, song_ids_to_add, song_indexes_to_remove)
+    }
+}
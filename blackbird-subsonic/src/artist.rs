@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// An artist with ID3 metadata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ArtistID3 {
     /// The id of the artist.
@@ -1,3 +1,7 @@
+use std::sync::RwLock;
+
+use crate::SubsonicApi;
+
 #[derive(Debug)]
 /// An error that can occur when interacting with the client.
 pub enum ClientError {
@@ -29,6 +33,30 @@ impl std::fmt::Display for ClientError {
     }
 }
 impl std::error::Error for ClientError {}
+impl ClientError {
+    /// Whether the server rejected the client's credentials, rather than
+    /// failing the request for another reason.
+    pub fn is_auth_failure(&self) -> bool {
+        match self {
+            // 40: wrong username or password; 41: token authentication not
+            // supported for the user; 44: invalid API key.
+            ClientError::SubsonicError { code, .. } => matches!(code, 40 | 41 | 44),
+            ClientError::ReqwestError(e) => e.status() == Some(reqwest::StatusCode::UNAUTHORIZED),
+            ClientError::DeserializationError(_) => false,
+        }
+    }
+
+    /// Whether the server reported that the requested data doesn't exist,
+    /// rather than failing to answer.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            // 70: the requested data was not found.
+            ClientError::SubsonicError { code, .. } => *code == 70,
+            ClientError::ReqwestError(e) => e.status() == Some(reqwest::StatusCode::NOT_FOUND),
+            ClientError::DeserializationError(_) => false,
+        }
+    }
+}
 impl From<reqwest::Error> for ClientError {
     fn from(e: reqwest::Error) -> Self {
         ClientError::ReqwestError(e)
@@ -49,11 +77,17 @@ pub struct Client {
     pub(crate) password: String,
     pub(crate) client_id: String,
     pub(crate) client: reqwest::Client,
+    pub(crate) api_version: RwLock<String>,
 }
 impl Client {
-    /// The API version of the client.
+    /// The highest API version supported by the client, and the version used
+    /// for requests until another version is negotiated or set.
     pub const API_VERSION: &str = "1.16.1";
 
+    /// The lowest API version supported by the client. `search3` and
+    /// `getAlbumList2` were introduced in 1.8.0.
+    pub const MIN_API_VERSION: &str = "1.8.0";
+
     /// Create a new client.
     pub fn new(
         base_url: impl Into<String>,
@@ -67,6 +101,108 @@ impl Client {
             password: password.into(),
             client_id: client_id.into(),
             client: reqwest::Client::new(),
+            api_version: RwLock::new(Self::API_VERSION.to_string()),
+        }
+    }
+
+    /// The user the client authenticates as.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// The API version currently sent with every request.
+    pub fn api_version(&self) -> String {
+        self.api_version.read().unwrap().clone()
+    }
+
+    /// Set the API version sent with every request. The version is used as-is,
+    /// without clamping to the supported range.
+    pub fn set_api_version(&self, version: impl Into<String>) {
+        *self.api_version.write().unwrap() = version.into();
+    }
+
+    /// Ping the server and use the version it reports, clamped to the range
+    /// supported by the client, for all subsequent requests.
+    ///
+    /// Returns the negotiated version.
+    pub async fn negotiate_api_version(&self) -> ClientResult<String> {
+        let version = negotiate_api_version(self).await?;
+        self.set_api_version(version.clone());
+        Ok(version)
+    }
+
+    /// Clamp a version reported by a server to the range supported by the
+    /// client. Versions that cannot be parsed fall back to [`Self::API_VERSION`].
+    pub fn clamp_api_version(version: &str) -> String {
+        let (Some(parsed), Some(min), Some(max)) = (
+            parse_api_version(version),
+            parse_api_version(Self::MIN_API_VERSION),
+            parse_api_version(Self::API_VERSION),
+        ) else {
+            return Self::API_VERSION.to_string();
+        };
+
+        if parsed < min {
+            Self::MIN_API_VERSION.to_string()
+        } else if parsed > max {
+            Self::API_VERSION.to_string()
+        } else {
+            version.to_string()
         }
     }
 }
+
+/// Ping `api` with [`Client::MIN_API_VERSION`], which every supported server
+/// accepts, and return the version the server reports, clamped to the range
+/// supported by the client. Pinging with a newer version would be rejected by
+/// older servers before they report their version.
+pub async fn negotiate_api_version(api: &impl SubsonicApi) -> ClientResult<String> {
+    let server_version = api.ping_with_version(Client::MIN_API_VERSION).await?;
+    Ok(Client::clamp_api_version(&server_version))
+}
+
+/// Parse a `major.minor.patch` version string into a comparable tuple. A
+/// missing patch component is treated as zero.
+pub(crate) fn parse_api_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+#[cfg(all(test, feature = "opensubsonic"))]
+mod tests {
+    use super::*;
+    use crate::MockClient;
+
+    async fn negotiate(server_version: &str) -> ClientResult<String> {
+        let client = MockClient::default().with_server_api_version(server_version);
+        negotiate_api_version(&client).await
+    }
+
+    #[tokio::test]
+    async fn negotiates_down_to_an_older_server() {
+        assert_eq!(negotiate("1.13.0").await.unwrap(), "1.13.0");
+        assert_eq!(negotiate("1.8.0").await.unwrap(), "1.8.0");
+    }
+
+    #[tokio::test]
+    async fn clamps_a_newer_server_to_the_supported_version() {
+        assert_eq!(negotiate("1.99.0").await.unwrap(), Client::API_VERSION);
+    }
+
+    #[tokio::test]
+    async fn fails_against_a_server_older_than_the_minimum() {
+        assert!(matches!(
+            negotiate("1.4.0").await,
+            Err(ClientError::SubsonicError { code: 30, .. })
+        ));
+    }
+}
@@ -18,8 +18,23 @@ mod search;
 pub use search::*;
 
 mod misc;
+pub use misc::*;
 
 mod lyrics;
 pub use lyrics::*;
 
+mod playlist;
+pub use playlist::*;
+
+mod server_info;
+pub use server_info::*;
+
+mod api;
+pub use api::*;
+
+#[cfg(all(any(test, feature = "test-util"), feature = "opensubsonic"))]
+mod mock;
+#[cfg(all(any(test, feature = "test-util"), feature = "opensubsonic"))]
+pub use mock::*;
+
 mod request;
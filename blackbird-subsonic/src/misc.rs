@@ -1,11 +1,72 @@
-use crate::{Client, ClientResult};
+use serde::{Deserialize, Serialize};
+
+use crate::{Child, Client, ClientResult};
+
+/// The status of a server-side media library scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanStatus {
+    /// Whether a scan is in progress.
+    pub scanning: bool,
+    /// The number of files scanned so far, if reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScanStatusResponse {
+    scan_status: ScanStatus,
+}
+
+/// A track being played by a user of the server, as reported by
+/// `getNowPlaying`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NowPlayingEntry {
+    /// The track being played.
+    #[serde(flatten)]
+    pub child: Child,
+    /// The user playing the track.
+    pub username: String,
+    /// How many minutes ago the track started playing.
+    #[serde(default)]
+    pub minutes_ago: u32,
+    /// The name of the player the track is being played on, if reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NowPlayingResponse {
+    now_playing: NowPlaying,
+}
+
+#[derive(Deserialize)]
+struct NowPlaying {
+    #[serde(default)]
+    entry: Vec<NowPlayingEntry>,
+}
 
 /// Miscellaneous endpoints.
 impl Client {
     /// Ping the server and verify the connection.
-    pub async fn ping(&self) -> ClientResult<()> {
-        self.request::<()>("ping", &[]).await?;
-        Ok(())
+    ///
+    /// Returns the API version reported by the server.
+    pub async fn ping(&self) -> ClientResult<String> {
+        self.ping_with_version(self.api_version()).await
+    }
+
+    /// Ping the server, sending `api_version` rather than the client's current
+    /// version.
+    ///
+    /// Returns the API version reported by the server.
+    pub async fn ping_with_version(&self, api_version: impl Into<String>) -> ClientResult<String> {
+        let (version, ()) = self
+            .request_with_version::<()>("ping", &[], api_version.into())
+            .await?;
+        Ok(version)
     }
 
     /// Get cover art for a given ID.
@@ -94,4 +155,31 @@ impl Client {
 
         self.request::<()>("scrobble", &parameters).await
     }
+
+    /// Get the tracks currently being played by all users of the server.
+    pub async fn get_now_playing(&self) -> ClientResult<Vec<NowPlayingEntry>> {
+        Ok(self
+            .request::<NowPlayingResponse>("getNowPlaying", &[])
+            .await?
+            .now_playing
+            .entry)
+    }
+
+    /// Get the status of the server's media library scan.
+    pub async fn get_scan_status(&self) -> ClientResult<ScanStatus> {
+        Ok(self
+            .request::<ScanStatusResponse>("getScanStatus", &[])
+            .await?
+            .scan_status)
+    }
+
+    /// Ask the server to start scanning its media library for changes.
+    ///
+    /// Returns the status of the scan that was started.
+    pub async fn start_scan(&self) -> ClientResult<ScanStatus> {
+        Ok(self
+            .request::<ScanStatusResponse>("startScan", &[])
+            .await?
+            .scan_status)
+    }
 }
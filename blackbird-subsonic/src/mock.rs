@@ -0,0 +1,456 @@
+use std::{
+    future::{Future, pending, ready},
+    sync::{Arc, Mutex, RwLock},
+};
+
+use crate::{
+    AlbumID3, AlbumListType, AlbumWithSongsID3, ArtistID3, Child, Client, ClientError,
+    ClientResult, LyricsList, NowPlayingEntry, Playlist, PlaylistWithSongs, ScanStatus,
+    Search3Request, Search3Response, ServerInfo, SubsonicApi, client::parse_api_version,
+};
+
+/// A [`SubsonicApi`] that serves a fixed library from memory, for testing
+/// without a live server. Lists are paged through in the order given.
+///
+/// Only the library is served: requests for audio, cover art, lyrics,
+/// playlists and the like fail. Scrobbles and cover art requests are recorded
+/// so that tests can check them with [`MockClient::scrobbles`] and
+/// [`MockClient::cover_art_requests`].
+#[derive(Debug, Clone)]
+pub struct MockClient {
+    /// The albums returned by `getAlbumList2`, whatever the list type.
+    pub albums: Vec<AlbumID3>,
+    /// The songs returned by `search3`.
+    pub songs: Vec<Child>,
+    /// The artists returned by `search3`.
+    pub artists: Vec<ArtistID3>,
+    /// The API version the server reports, which it rejects newer requests
+    /// than. Unset, the server supports [`Client::API_VERSION`].
+    pub server_api_version: Option<String>,
+    /// Whether cover art requests are left pending rather than failing, so
+    /// that tests can make requests while others are still in flight.
+    pub hold_cover_art: bool,
+    /// The API version requests are made with. Shared between clones, as are
+    /// the scrobbles and cover art requests, so that a clone handed to the
+    /// code under test can be inspected through the original.
+    api_version: Arc<RwLock<String>>,
+    scrobbles: Arc<Mutex<Vec<MockScrobble>>>,
+    cover_art_requests: Arc<Mutex<Vec<(String, Option<usize>)>>>,
+}
+impl Default for MockClient {
+    fn default() -> Self {
+        Self {
+            albums: vec![],
+            songs: vec![],
+            artists: vec![],
+            server_api_version: None,
+            hold_cover_art: false,
+            api_version: Arc::new(RwLock::new(Client::API_VERSION.to_string())),
+            scrobbles: Default::default(),
+            cover_art_requests: Default::default(),
+        }
+    }
+}
+
+/// A scrobble received by a [`MockClient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockScrobble {
+    /// The ID of the scrobbled song.
+    pub id: String,
+    /// When the song was played, in milliseconds since the Unix epoch.
+    pub time: Option<u64>,
+    /// Whether this was a scrobble, rather than a now-playing report.
+    pub submission: Option<bool>,
+}
+
+impl MockClient {
+    /// Loads a library from JSON shaped like a `search3` response, with
+    /// `album`, `song` and `artist` lists.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let response: Search3Response = serde_json::from_str(json)?;
+        Ok(Self {
+            albums: response.album,
+            songs: response.song,
+            artists: response.artist,
+            ..Self::default()
+        })
+    }
+
+    /// Adds an album by `artist` with `tracks` of `(title, duration in
+    /// seconds)`, adding the artist too if it's new. IDs are derived from the
+    /// order things are added in: the first album is `al-1`, its tracks are
+    /// `tr-1-1`, `tr-1-2` and so on, and the first artist is `ar-1`.
+    pub fn with_album(mut self, artist: &str, name: &str, tracks: &[(&str, u32)]) -> Self {
+        let artist_id = match self.artists.iter_mut().find(|a| a.name == artist) {
+            Some(existing) => {
+                existing.album_count += 1;
+                existing.id.clone()
+            }
+            None => {
+                let id = format!("ar-{}", self.artists.len() + 1);
+                self.artists.push(ArtistID3 {
+                    id: id.clone(),
+                    name: artist.to_string(),
+                    album_count: 1,
+                    ..ArtistID3::default()
+                });
+                id
+            }
+        };
+
+        let album_id = format!("al-{}", self.albums.len() + 1);
+        self.albums.push(AlbumID3 {
+            id: album_id.clone(),
+            name: name.to_string(),
+            artist: Some(artist.to_string()),
+            artist_id: Some(artist_id.clone()),
+            song_count: tracks.len() as u32,
+            duration: tracks.iter().map(|(_, duration)| duration).sum(),
+            created: "2024-01-01T00:00:00Z".to_string(),
+            ..AlbumID3::default()
+        });
+        for (number, (title, duration)) in (1..).zip(tracks) {
+            self.songs.push(Child {
+                id: format!("tr-{}-{number}", self.albums.len()),
+                title: title.to_string(),
+                album: Some(name.to_string()),
+                artist: Some(artist.to_string()),
+                track: Some(number),
+                duration: Some(*duration),
+                album_id: Some(album_id.clone()),
+                artist_id: Some(artist_id.clone()),
+                ..Child::default()
+            });
+        }
+        self
+    }
+
+    /// Reports `version` as the server's API version, rejecting requests
+    /// made with newer versions.
+    pub fn with_server_api_version(mut self, version: &str) -> Self {
+        self.server_api_version = Some(version.to_string());
+        self
+    }
+
+    /// A small library of two artists and three albums, one of them by
+    /// various artists, from `fixtures/library.json`.
+    pub fn fixture() -> Self {
+        Self::from_json(include_str!("../fixtures/library.json"))
+            .expect("the library fixture should be valid")
+    }
+
+    /// The scrobbles and now-playing reports received so far, oldest first.
+    pub fn scrobbles(&self) -> Vec<MockScrobble> {
+        self.scrobbles.lock().unwrap().clone()
+    }
+
+    /// The `(id, size)` of each cover art request received so far, oldest
+    /// first.
+    pub fn cover_art_requests(&self) -> Vec<(String, Option<usize>)> {
+        self.cover_art_requests.lock().unwrap().clone()
+    }
+
+    /// The API version the server reports.
+    fn server_api_version(&self) -> String {
+        self.server_api_version
+            .clone()
+            .unwrap_or_else(|| Client::API_VERSION.to_string())
+    }
+
+    /// The response to a ping made with `api_version`: the server's version,
+    /// unless the request is newer than the server.
+    fn pong(&self, api_version: &str) -> ClientResult<String> {
+        let server_version = self.server_api_version();
+        if parse_api_version(api_version) > parse_api_version(&server_version) {
+            Err(ClientError::SubsonicError {
+                // 30: the client's protocol version is newer than the
+                // server's.
+                code: 30,
+                message: Some("Incompatible Subsonic REST protocol version".to_string()),
+            })
+        } else {
+            Ok(server_version)
+        }
+    }
+}
+
+impl SubsonicApi for MockClient {
+    fn username(&self) -> &str {
+        "mock"
+    }
+
+    fn api_version(&self) -> String {
+        self.api_version.read().unwrap().clone()
+    }
+
+    fn set_api_version(&self, version: String) {
+        *self.api_version.write().unwrap() = version;
+    }
+
+    fn ping(&self) -> impl Future<Output = ClientResult<String>> + Send + Sync {
+        ready(self.pong(&self.api_version()))
+    }
+
+    fn ping_with_version(
+        &self,
+        api_version: &str,
+    ) -> impl Future<Output = ClientResult<String>> + Send + Sync {
+        ready(self.pong(api_version))
+    }
+
+    async fn negotiate_api_version(&self) -> ClientResult<String> {
+        let version = crate::negotiate_api_version(self).await?;
+        self.set_api_version(version.clone());
+        Ok(version)
+    }
+
+    fn get_server_info(&self) -> impl Future<Output = ClientResult<ServerInfo>> + Send + Sync {
+        ready(Ok(ServerInfo {
+            api_version: self.server_api_version(),
+            ..ServerInfo::default()
+        }))
+    }
+
+    fn get_album_list_2(
+        &self,
+        _ty: AlbumListType,
+        size: Option<usize>,
+        offset: Option<usize>,
+    ) -> impl Future<Output = ClientResult<Vec<AlbumID3>>> + Send + Sync {
+        // Servers return at most 500 albums per page.
+        ready(Ok(page(
+            &self.albums,
+            offset,
+            size.map(|size| size.min(500)),
+        )))
+    }
+
+    fn get_album_with_songs(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = ClientResult<AlbumWithSongsID3>> + Send + Sync {
+        let album = self.albums.iter().find(|a| a.id == id).cloned();
+        ready(album.ok_or_else(|| not_found("Album", id)).map(|album| {
+            let song = self
+                .songs
+                .iter()
+                .filter(|s| s.album_id.as_deref() == Some(id))
+                .cloned()
+                .collect();
+            AlbumWithSongsID3 { album, song }
+        }))
+    }
+
+    fn search3(
+        &self,
+        request: &Search3Request,
+    ) -> impl Future<Output = ClientResult<Search3Response>> + Send + Sync {
+        let query = request.query.to_lowercase();
+        let matches = |name: &str| name.to_lowercase().contains(&query);
+        let count = |count: Option<u32>| count.map(|c| c as usize);
+        let offset = |offset: Option<u32>| offset.map(|o| o as usize);
+
+        let artists: Vec<_> = self
+            .artists
+            .iter()
+            .filter(|a| matches(&a.name))
+            .cloned()
+            .collect();
+        let albums: Vec<_> = self
+            .albums
+            .iter()
+            .filter(|a| matches(&a.name))
+            .cloned()
+            .collect();
+        let songs: Vec<_> = self
+            .songs
+            .iter()
+            .filter(|s| matches(&s.title))
+            .cloned()
+            .collect();
+        ready(Ok(Search3Response {
+            artist: page(
+                &artists,
+                offset(request.artist_offset),
+                count(request.artist_count),
+            ),
+            album: page(
+                &albums,
+                offset(request.album_offset),
+                count(request.album_count),
+            ),
+            song: page(
+                &songs,
+                offset(request.song_offset),
+                count(request.song_count),
+            ),
+        }))
+    }
+
+    fn get_song(&self, id: &str) -> impl Future<Output = ClientResult<Child>> + Send + Sync {
+        let song = self.songs.iter().find(|s| s.id == id).cloned();
+        ready(song.ok_or_else(|| not_found("Song", id)))
+    }
+
+    fn get_similar_songs_2(
+        &self,
+        _id: &str,
+        _count: Option<u32>,
+    ) -> impl Future<Output = ClientResult<Vec<Child>>> + Send + Sync {
+        ready(Err(unsupported("getSimilarSongs2")))
+    }
+
+    fn get_random_songs(
+        &self,
+        _size: Option<u32>,
+        _genre: Option<String>,
+    ) -> impl Future<Output = ClientResult<Vec<Child>>> + Send + Sync {
+        ready(Err(unsupported("getRandomSongs")))
+    }
+
+    fn stream(
+        &self,
+        _id: &str,
+        _format: Option<String>,
+        _max_bitrate_kbps: Option<u32>,
+    ) -> impl Future<Output = ClientResult<Vec<u8>>> + Send + Sync {
+        ready(Err(unsupported("stream")))
+    }
+
+    fn download(&self, _id: &str) -> impl Future<Output = ClientResult<Vec<u8>>> + Send + Sync {
+        ready(Err(unsupported("download")))
+    }
+
+    fn get_cover_art(
+        &self,
+        id: &str,
+        size: Option<usize>,
+    ) -> impl Future<Output = ClientResult<Vec<u8>>> + Send + Sync {
+        self.cover_art_requests
+            .lock()
+            .unwrap()
+            .push((id.to_string(), size));
+        let hold = self.hold_cover_art;
+        async move {
+            if hold {
+                pending::<()>().await;
+            }
+            Err(unsupported("getCoverArt"))
+        }
+    }
+
+    fn get_lyrics_by_song_id(
+        &self,
+        _id: &str,
+    ) -> impl Future<Output = ClientResult<LyricsList>> + Send + Sync {
+        ready(Err(unsupported("getLyricsBySongId")))
+    }
+
+    fn star(
+        &self,
+        _track_ids: Vec<String>,
+        _album_ids: Vec<String>,
+        _artist_ids: Vec<String>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        ready(Err(unsupported("star")))
+    }
+
+    fn unstar(
+        &self,
+        _track_ids: Vec<String>,
+        _album_ids: Vec<String>,
+        _artist_ids: Vec<String>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        ready(Err(unsupported("unstar")))
+    }
+
+    fn scrobble(
+        &self,
+        id: &str,
+        time: Option<u64>,
+        submission: Option<bool>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        let result = if self.songs.iter().any(|s| s.id == id) {
+            self.scrobbles.lock().unwrap().push(MockScrobble {
+                id: id.to_string(),
+                time,
+                submission,
+            });
+            Ok(())
+        } else {
+            Err(not_found("Song", id))
+        };
+        ready(result)
+    }
+
+    fn get_now_playing(
+        &self,
+    ) -> impl Future<Output = ClientResult<Vec<NowPlayingEntry>>> + Send + Sync {
+        ready(Err(unsupported("getNowPlaying")))
+    }
+
+    fn get_scan_status(&self) -> impl Future<Output = ClientResult<ScanStatus>> + Send + Sync {
+        ready(Err(unsupported("getScanStatus")))
+    }
+
+    fn start_scan(&self) -> impl Future<Output = ClientResult<ScanStatus>> + Send + Sync {
+        ready(Err(unsupported("startScan")))
+    }
+
+    fn get_playlists(&self) -> impl Future<Output = ClientResult<Vec<Playlist>>> + Send + Sync {
+        ready(Err(unsupported("getPlaylists")))
+    }
+
+    fn get_playlist(
+        &self,
+        _id: &str,
+    ) -> impl Future<Output = ClientResult<PlaylistWithSongs>> + Send + Sync {
+        ready(Err(unsupported("getPlaylist")))
+    }
+
+    fn create_playlist(
+        &self,
+        _name: &str,
+        _song_ids: Vec<String>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        ready(Err(unsupported("createPlaylist")))
+    }
+
+    fn update_playlist(
+        &self,
+        _id: &str,
+        _song_ids_to_add: Vec<String>,
+        _song_indexes_to_remove: Vec<usize>,
+    ) -> impl Future<Output = ClientResult<()>> + Send + Sync {
+        ready(Err(unsupported("updatePlaylist")))
+    }
+}
+
+/// The error for a request for something the mock doesn't have.
+fn not_found(kind: &str, id: &str) -> ClientError {
+    ClientError::SubsonicError {
+        // 70: the requested data was not found.
+        code: 70,
+        message: Some(format!("{kind} not found: {id}")),
+    }
+}
+
+/// The error for an endpoint the mock doesn't serve.
+fn unsupported(endpoint: &str) -> ClientError {
+    ClientError::SubsonicError {
+        // 0: a generic error.
+        code: 0,
+        message: Some(format!("MockClient does not serve {endpoint}")),
+    }
+}
+
+/// The `size` items of `items` from `offset`. Unset sizes default to 20, as
+/// they do on Subsonic servers.
+fn page<T: Clone>(items: &[T], offset: Option<usize>, size: Option<usize>) -> Vec<T> {
+    items
+        .iter()
+        .skip(offset.unwrap_or(0))
+        .take(size.unwrap_or(20))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Child, Client, ClientResult};
+
+/// A playlist owned by or shared with the user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Playlist {
+    /// The playlist ID
+    pub id: String,
+    /// The playlist name
+    pub name: String,
+    /// The user that owns the playlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// The number of songs in the playlist
+    #[serde(default)]
+    pub song_count: u32,
+}
+
+/// A playlist and its songs, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistWithSongs {
+    /// The playlist metadata
+    #[serde(flatten)]
+    pub playlist: Playlist,
+    /// The songs in the playlist
+    #[serde(default)]
+    pub entry: Vec<Child>,
+}
+
+/// Playlist endpoints.
+impl Client {
+    /// Get the playlists the user can access.
+    pub async fn get_playlists(&self) -> ClientResult<Vec<Playlist>> {
+        #[derive(Deserialize)]
+        struct GetPlaylistsResponse {
+            playlists: Playlists,
+        }
+        #[derive(Deserialize)]
+        struct Playlists {
+            #[serde(default)]
+            playlist: Vec<Playlist>,
+        }
+        Ok(self
+            .request::<GetPlaylistsResponse>("getPlaylists", &[])
+            .await?
+            .playlists
+            .playlist)
+    }
+
+    /// Get a playlist and its songs.
+    pub async fn get_playlist(&self, id: impl Into<String>) -> ClientResult<PlaylistWithSongs> {
+        #[derive(Deserialize)]
+        struct GetPlaylistResponse {
+            playlist: PlaylistWithSongs,
+        }
+        Ok(self
+            .request::<GetPlaylistResponse>("getPlaylist", &[("id", id.into())])
+            .await?
+            .playlist)
+    }
+
+    /// Create a playlist with the given songs.
+    ///
+    /// Servers older than API version 1.14.0 don't return the new playlist,
+    /// so look it up with [`Client::get_playlists`] if its ID is needed.
+    pub async fn create_playlist(
+        &self,
+        name: impl Into<String>,
+        song_ids: impl IntoIterator<Item = String>,
+    ) -> ClientResult<()> {
+        let mut parameters = vec![("name", name.into())];
+        for song_id in song_ids {
+            parameters.push(("songId", song_id));
+        }
+        self.request::<()>("createPlaylist", &parameters).await
+    }
+
+    /// Update a playlist, appending `song_ids_to_add` and removing the songs
+    /// at `song_indexes_to_remove`, which are positions in the playlist
+    /// before the update.
+    pub async fn update_playlist(
+        &self,
+        id: impl Into<String>,
+        song_ids_to_add: impl IntoIterator<Item = String>,
+        song_indexes_to_remove: impl IntoIterator<Item = usize>,
+    ) -> ClientResult<()> {
+        let mut parameters = vec![("playlistId", id.into())];
+        for song_id in song_ids_to_add {
+            parameters.push(("songIdToAdd", song_id));
+        }
+        for index in song_indexes_to_remove {
+            parameters.push(("songIndexToRemove", index.to_string()));
+        }
+        self.request::<()>("updatePlaylist", &parameters).await
+    }
+}
@@ -25,6 +25,18 @@ impl Client {
         &self,
         endpoint: &str,
         parameters: &[(&str, String)],
+    ) -> ClientResult<Vec<u8>> {
+        self.request_raw_at_version(endpoint, parameters, self.api_version())
+            .await
+    }
+
+    /// Like [`Self::request_raw`], but sends `api_version` rather than the
+    /// client's current version.
+    pub(crate) async fn request_raw_at_version(
+        &self,
+        endpoint: &str,
+        parameters: &[(&str, String)],
+        api_version: String,
     ) -> ClientResult<Vec<u8>> {
         let (salt, token) = self.generate_salt_and_token();
         let request = self
@@ -32,7 +44,7 @@ impl Client {
             .get(format!("{}/rest/{endpoint}", self.base_url))
             .query(&[
                 ("u", self.username.clone()),
-                ("v", Self::API_VERSION.to_string()),
+                ("v", api_version),
                 ("c", self.client_id.clone()),
                 ("f", "json".to_string()),
                 ("t", token),
@@ -56,7 +68,25 @@ impl Client {
         }
     }
 
+    /// Make a request to the Subsonic API at `api_version`, returning the API
+    /// version reported by the server alongside the body.
+    pub(crate) async fn request_with_version<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        parameters: &[(&str, String)],
+        api_version: String,
+    ) -> ClientResult<(String, T)> {
+        let bytes = self
+            .request_raw_at_version(endpoint, parameters, api_version)
+            .await?;
+        Self::parse_response_with_version::<T>(&bytes)
+    }
+
     fn parse_response<T: DeserializeOwned>(bytes: &[u8]) -> ClientResult<T> {
+        Self::parse_response_with_version(bytes).map(|(_, body)| body)
+    }
+
+    fn parse_response_with_version<T: DeserializeOwned>(bytes: &[u8]) -> ClientResult<(String, T)> {
         let response: Response<T> = serde_json::from_slice(bytes)?;
 
         if response.subsonic_response.status == ResponseStatus::Failed {
@@ -72,7 +102,10 @@ impl Client {
             });
         }
 
-        Ok(response.subsonic_response.body)
+        Ok((
+            response.subsonic_response.version,
+            response.subsonic_response.body,
+        ))
     }
 
     fn generate_salt_and_token(&self) -> (String, String) {
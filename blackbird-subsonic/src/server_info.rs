@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Client, ClientResult, client::parse_api_version};
+
+/// An OpenSubsonic extension supported by the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenSubsonicExtension {
+    /// The name of the extension.
+    pub name: String,
+    /// The versions of the extension the server supports.
+    #[serde(default)]
+    pub versions: Vec<u32>,
+}
+
+/// Details about the server, as collected by [`Client::get_server_info`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerInfo {
+    /// The API version reported by the server.
+    pub api_version: String,
+    /// The server software (e.g. `navidrome`), if reported.
+    pub server_type: Option<String>,
+    /// The version of the server software, if reported.
+    pub server_version: Option<String>,
+    /// Whether the server supports the OpenSubsonic extensions to the API.
+    pub open_subsonic: bool,
+    /// The OpenSubsonic extensions the server supports.
+    pub extensions: Vec<OpenSubsonicExtension>,
+    /// Whether the server's license is valid, if it reports one.
+    pub license_valid: Option<bool>,
+}
+impl ServerInfo {
+    /// Whether the server supports the named OpenSubsonic extension.
+    pub fn supports_extension(&self, name: &str) -> bool {
+        self.extensions.iter().any(|e| e.name == name)
+    }
+
+    /// Whether lyrics can be fetched with `getLyricsBySongId` (the
+    /// `songLyrics` extension).
+    pub fn supports_lyrics(&self) -> bool {
+        self.supports_extension("songLyrics")
+    }
+
+    /// Whether playback positions can be saved as bookmarks, which was
+    /// introduced in API version 1.9.0.
+    pub fn supports_bookmarks(&self) -> bool {
+        parse_api_version(&self.api_version).is_some_and(|version| version >= (1, 9, 0))
+    }
+
+    /// Whether transcoded streams can start partway through a track (the
+    /// `transcodeOffset` extension).
+    pub fn supports_transcode_offset(&self) -> bool {
+        self.supports_extension("transcodeOffset")
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PingResponse {
+    #[serde(rename = "type")]
+    server_type: Option<String>,
+    server_version: Option<String>,
+    #[serde(default)]
+    open_subsonic: bool,
+}
+
+#[derive(Deserialize)]
+struct LicenseResponse {
+    license: License,
+}
+
+#[derive(Deserialize)]
+struct License {
+    valid: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(feature = "opensubsonic")]
+struct OpenSubsonicExtensionsResponse {
+    #[serde(default)]
+    open_subsonic_extensions: Vec<OpenSubsonicExtension>,
+}
+
+/// Server information.
+impl Client {
+    /// Collect details about the server: its type and version, the
+    /// OpenSubsonic extensions it supports, and whether its license is valid.
+    ///
+    /// Only the `ping` has to succeed; details the server can't provide are
+    /// left empty.
+    pub async fn get_server_info(&self) -> ClientResult<ServerInfo> {
+        let (api_version, ping) = self
+            .request_with_version::<PingResponse>("ping", &[], self.api_version())
+            .await?;
+
+        #[cfg(feature = "opensubsonic")]
+        let extensions = if ping.open_subsonic {
+            self.get_open_subsonic_extensions()
+                .await
+                .unwrap_or_default()
+        } else {
+            vec![]
+        };
+        #[cfg(not(feature = "opensubsonic"))]
+        let extensions = vec![];
+
+        let license_valid = self
+            .request::<LicenseResponse>("getLicense", &[])
+            .await
+            .ok()
+            .map(|response| response.license.valid);
+
+        Ok(ServerInfo {
+            api_version,
+            server_type: ping.server_type,
+            server_version: ping.server_version,
+            open_subsonic: ping.open_subsonic,
+            extensions,
+            license_valid,
+        })
+    }
+
+    /// Get the OpenSubsonic extensions the server supports.
+    #[cfg(feature = "opensubsonic")]
+    pub async fn get_open_subsonic_extensions(&self) -> ClientResult<Vec<OpenSubsonicExtension>> {
+        Ok(self
+            .request::<OpenSubsonicExtensionsResponse>("getOpenSubsonicExtensions", &[])
+            .await?
+            .open_subsonic_extensions)
+    }
+}
@@ -29,7 +29,7 @@ pub struct ReplayGain {
 }
 
 /// Represents a child item (file or directory) in the Subsonic API
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Child {
     /// The unique identifier
@@ -125,6 +125,9 @@ pub struct Child {
     /// ReplayGain metadata (OpenSubsonic extension).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub replay_gain: Option<ReplayGain>,
+    /// The MusicBrainz recording ID (OpenSubsonic extension).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub music_brainz_id: Option<String>,
 }
 
 impl Client {
@@ -164,4 +167,63 @@ impl Client {
             .await?
             .song)
     }
+
+    /// Get songs similar to an item, for building a radio-style mix.
+    ///
+    /// The spec takes an artist ID; many servers also accept a song or album
+    /// ID. `count` is the maximum number of songs to return.
+    pub async fn get_similar_songs_2(
+        &self,
+        id: impl Into<String>,
+        count: Option<u32>,
+    ) -> ClientResult<Vec<Child>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct GetSimilarSongs2Response {
+            similar_songs_2: SongList,
+        }
+        let mut parameters = vec![("id", id.into())];
+        if let Some(count) = count {
+            parameters.push(("count", count.to_string()));
+        }
+        Ok(self
+            .request::<GetSimilarSongs2Response>("getSimilarSongs2", &parameters)
+            .await?
+            .similar_songs_2
+            .song)
+    }
+
+    /// Get random songs, optionally restricted to a genre. `size` is the
+    /// maximum number of songs to return.
+    pub async fn get_random_songs(
+        &self,
+        size: Option<u32>,
+        genre: Option<String>,
+    ) -> ClientResult<Vec<Child>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct GetRandomSongsResponse {
+            random_songs: SongList,
+        }
+        let mut parameters = vec![];
+        if let Some(size) = size {
+            parameters.push(("size", size.to_string()));
+        }
+        if let Some(genre) = genre {
+            parameters.push(("genre", genre));
+        }
+        Ok(self
+            .request::<GetRandomSongsResponse>("getRandomSongs", &parameters)
+            .await?
+            .random_songs
+            .song)
+    }
+}
+
+/// A list of songs, as returned by the list endpoints. Servers omit `song`
+/// when the list is empty.
+#[derive(Deserialize)]
+struct SongList {
+    #[serde(default)]
+    song: Vec<Child>,
 }
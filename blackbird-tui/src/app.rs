@@ -1,5 +1,8 @@
 use std::time::{Duration, Instant};
 
+use blackbird_client_shared::{
+    config::StartupPanel, config_sync::ConfigSync, double_press::NextPress, listeners::RefreshTimer,
+};
 use blackbird_core::{self as bc, PlaybackToLogicMessage};
 use blackbird_shared::config::ConfigFile as _;
 
@@ -9,8 +12,10 @@ use crate::{
     keys,
     log_buffer::LogBuffer,
     ui::{
-        album_art_overlay::AlbumArtOverlay, library::LibraryState, logs::LogsState,
-        lyrics::LyricsViewState, queue::QueueState, search::SearchState, settings::SettingsState,
+        album_art_overlay::AlbumArtOverlay, command_palette::CommandPaletteState,
+        library::LibraryState, logs::LogsState, lyrics::LyricsViewState, queue::QueueState,
+        recent::RecentState, search::SearchState, settings::SettingsState,
+        track_number::TrackNumberState,
     },
 };
 
@@ -22,6 +27,10 @@ pub enum FocusedPanel {
     Lyrics,
     Logs,
     Queue,
+    Recent,
+    Listeners,
+    ServerInfo,
+    ListeningStats,
     Settings,
 }
 
@@ -41,10 +50,16 @@ pub struct App {
     pub quit_confirming: bool,
     pub should_quit: bool,
     pub needs_redraw: bool,
+    /// Whether the terminal has focus, for terminals that report it.
+    pub terminal_focused: bool,
     pub mouse_position: Option<(u16, u16)>,
     pub album_art_overlay: Option<AlbumArtOverlay>,
     /// Whether the playback mode dropdown is open.
     pub playback_mode_dropdown: bool,
+    /// The command palette, while open.
+    pub command_palette: Option<CommandPaletteState>,
+    /// The entry of a track number to jump to, while open.
+    pub track_number: Option<TrackNumberState>,
     /// Clickable regions in the help bar: (x_start, x_end, action).
     pub help_bar_items: Vec<(u16, u16, keys::Action)>,
     /// Monotonically increasing tick counter for animations.
@@ -63,7 +78,14 @@ pub struct App {
     pub lyrics: LyricsViewState,
     pub logs: LogsState,
     pub queue: QueueState,
+    pub recent: RecentState,
+    /// Paces re-fetching the listeners panel while it is open.
+    pub listeners_refresh: RefreshTimer,
     pub settings: SettingsState,
+    /// Upgrades a quick second press of Next to going to the next album.
+    next_press: NextPress,
+    /// Applies playback setting changes from the config to the logic.
+    config_sync: ConfigSync,
 }
 
 impl App {
@@ -78,7 +100,8 @@ impl App {
         track_updated_rx: std::sync::mpsc::Receiver<()>,
         log_buffer: LogBuffer,
     ) -> Self {
-        Self {
+        let search_history = config.search.initial_history();
+        let mut app = Self {
             logic,
             config,
             cover_art_cache,
@@ -94,32 +117,73 @@ impl App {
             quit_confirming: false,
             should_quit: false,
             needs_redraw: true,
+            terminal_focused: true,
             mouse_position: None,
             album_art_overlay: None,
             playback_mode_dropdown: false,
+            command_palette: None,
+            track_number: None,
             help_bar_items: Vec::new(),
             tick_count: 0,
             scrub_dragging: false,
             scrub_preview_ratio: None,
 
             library: LibraryState::new(),
-            search: SearchState::new(),
+            search: SearchState::new(search_history),
             lyrics: LyricsViewState::new(),
             logs: LogsState::new(log_buffer),
             queue: QueueState::new(),
+            recent: RecentState::new(),
+            listeners_refresh: RefreshTimer::default(),
             settings: SettingsState::new(),
+            next_press: NextPress::default(),
+            config_sync: ConfigSync::default(),
+        };
+        app.open_startup_panel();
+        app
+    }
+
+    /// Opens the panel the config asks to start in.
+    fn open_startup_panel(&mut self) {
+        match self.config.layout.base.startup_panel {
+            StartupPanel::Library => {}
+            StartupPanel::Search => self.toggle_search(),
+            StartupPanel::Lyrics => self.toggle_lyrics(),
+            StartupPanel::Queue => self.toggle_queue(),
+            StartupPanel::Recent => self.toggle_recent(),
         }
     }
 
     pub fn tick(&mut self) {
         self.tick_count = self.tick_count.wrapping_add(1);
 
-        // Keep ReplayGain settings in sync with the config. Cheap: the
-        // setters are no-ops when the value is unchanged.
-        self.logic
-            .set_apply_replaygain(self.config.playback.apply_replaygain);
-        self.logic
-            .set_replaygain_preamp_db(self.config.playback.replaygain_preamp_db);
+        // Keep the playback settings in sync with the config.
+        self.config_sync.apply(
+            &mut self.logic,
+            &self.config.playback,
+            &self.config.queue,
+            &self.config.server,
+            &self.config.layout.base,
+        );
+        // Input can't be seen while the terminal is in the background, so
+        // don't let that count as idling unless configured to.
+        if !self.terminal_focused && !self.config.playback.idle_pause_in_background {
+            self.logic.note_user_activity();
+        }
+
+        self.cover_art_cache
+            .set_full_res_size(self.config.layout.base.full_art_size);
+
+        if self.focused_panel == FocusedPanel::Search {
+            if let Some(query) = self.search.suggestions.poll(&self.search.query) {
+                self.logic.request_search_suggestions(query);
+                self.logic.search_server(query);
+            }
+            self.search.merge_server_results(&self.logic);
+        }
+        if self.focused_panel == FocusedPanel::Listeners && self.listeners_refresh.due() {
+            self.logic.refresh_server_now_playing();
+        }
 
         let mut changed = false;
 
@@ -130,7 +194,15 @@ impl App {
         while let Ok(event) = self.playback_to_logic_rx.try_recv() {
             changed = true;
             if let PlaybackToLogicMessage::TrackStarted(tap) = event {
-                // Scroll to the new track unless it is already visible.
+                // Scroll to the new track unless it is already visible, or
+                // following it is suspended.
+                let follow = self
+                    .config
+                    .layout
+                    .base
+                    .follow_playing
+                    .should_follow(self.library.user_scrolled);
+                self.library.user_scrolled = false;
                 let visible = {
                     let state = self.logic.get_state();
                     let state = state.read().unwrap();
@@ -138,7 +210,7 @@ impl App {
                         .find_flat_index_for_track(&state, &tap.track_id)
                         .is_some_and(|idx| self.library.is_index_visible(idx))
                 };
-                if !visible {
+                if follow && !visible {
                     self.library.scroll_to_track = Some(tap.track_id.clone());
                 }
                 self.library.needs_scroll_to_playing = false;
@@ -164,7 +236,7 @@ impl App {
         // Process library population.
         while let Ok(()) = self.library_populated_rx.try_recv() {
             changed = true;
-            self.library.mark_dirty();
+            self.library.reanchor_after_refresh(&self.logic);
             if self.library.needs_scroll_to_playing
                 && let Some(track_id) = self.logic.get_playing_track_id()
             {
@@ -228,6 +300,11 @@ impl App {
             changed |= self.search.tick_inertia();
         }
 
+        // Redraw to show the art preview once the mouse has rested long enough.
+        if self.library.art_hover_pending() {
+            changed = true;
+        }
+
         if self.logic.should_shutdown() {
             self.should_quit = true;
         }
@@ -251,6 +328,24 @@ impl App {
         self.search.reset();
     }
 
+    /// Returns to the library from any panel and scrolls to the playing
+    /// track.
+    pub fn focus_now_playing(&mut self) {
+        let was_hiding_unstarred = self.logic.get_hide_unstarred();
+        let Some(track_id) = self.logic.goto_playing() else {
+            return;
+        };
+        if was_hiding_unstarred != self.logic.get_hide_unstarred() {
+            self.library.mark_dirty();
+        }
+        if self.focused_panel == FocusedPanel::Search {
+            self.search.reset();
+        }
+        self.focused_panel = FocusedPanel::Library;
+        self.library.scroll_to_track = Some(track_id);
+        self.library.user_scrolled = false;
+    }
+
     pub fn toggle_lyrics(&mut self) {
         if self.focused_panel == FocusedPanel::Lyrics {
             self.focused_panel = FocusedPanel::Library;
@@ -285,6 +380,154 @@ impl App {
         }
     }
 
+    pub fn toggle_recent(&mut self) {
+        if self.focused_panel == FocusedPanel::Recent {
+            self.focused_panel = FocusedPanel::Library;
+        } else {
+            self.focused_panel = FocusedPanel::Recent;
+            self.recent.reset();
+        }
+    }
+
+    pub fn toggle_listeners(&mut self) {
+        if self.focused_panel == FocusedPanel::Listeners {
+            self.focused_panel = FocusedPanel::Library;
+        } else {
+            self.focused_panel = FocusedPanel::Listeners;
+            self.listeners_refresh.reset();
+        }
+    }
+
+    pub fn toggle_server_info(&mut self) {
+        if self.focused_panel == FocusedPanel::ServerInfo {
+            self.focused_panel = FocusedPanel::Library;
+        } else {
+            self.focused_panel = FocusedPanel::ServerInfo;
+        }
+    }
+
+    pub fn toggle_listening_stats(&mut self) {
+        if self.focused_panel == FocusedPanel::ListeningStats {
+            self.focused_panel = FocusedPanel::Library;
+        } else {
+            self.focused_panel = FocusedPanel::ListeningStats;
+            self.logic.refresh_listening_stats();
+        }
+    }
+
+    /// Starts a radio from the playing track's album.
+    pub fn start_album_radio(&self) {
+        if let Some(album_id) = self.logic.get_playing_album_id() {
+            self.logic.start_album_radio(&album_id);
+        }
+    }
+
+    /// Adds the selected track to the play queue.
+    pub fn enqueue_selected(&self) {
+        if let Some(track_id) = self.library.selected_track_id() {
+            self.logic.enqueue_last(track_id);
+        }
+    }
+
+    /// Plays from the selected track to the end of `scope`.
+    pub fn play_from_selected(&self, scope: bc::PlayScope) {
+        if let Some(track_id) = self.library.selected_track_id() {
+            self.logic.play_from(track_id, scope);
+        }
+    }
+
+    /// Copies the playing track to the clipboard, formatted with the
+    /// configured template.
+    pub fn copy_now_playing(&self) {
+        let Some(details) = self.logic.get_track_display_details() else {
+            return;
+        };
+        let text = blackbird_client_shared::now_playing_text::format(
+            &self.config.clipboard.now_playing_template,
+            &details,
+        );
+        match crate::clipboard::copy(&text) {
+            Ok(()) => tracing::info!("Copied {text:?} to the clipboard"),
+            Err(e) => tracing::warn!("Failed to copy to the clipboard: {e}"),
+        }
+    }
+
+    /// Downloads the original file of a track into the configured downloads
+    /// directory.
+    pub fn download_track(&self, track_id: &bc::blackbird_state::TrackId) {
+        self.logic
+            .download_track_to(track_id, &self.config.downloads.directory());
+    }
+
+    /// Shows a track's file in the file manager, if the server's path for it
+    /// is accessible from here.
+    pub fn reveal_track(&self, track_id: &bc::blackbird_state::TrackId) {
+        let Some(path) = self.logic.track_path(track_id) else {
+            tracing::warn!("The server didn't report a local path for {track_id}");
+            return;
+        };
+        if let Err(e) = blackbird_client_shared::reveal::reveal_in_file_manager(&path) {
+            tracing::warn!(
+                "Failed to open the file manager for {}: {e}",
+                path.display()
+            );
+        }
+    }
+
+    /// Flips whether tracks are streamed transcoded, applied on the next tick.
+    pub fn toggle_transcode(&mut self) {
+        self.config.server.transcode = !self.config.server.transcode;
+        self.config.save();
+    }
+
+    /// Flips whether the inline lyrics overlay is shown.
+    pub fn toggle_inline_lyrics(&mut self) {
+        self.config.layout.base.show_inline_lyrics = !self.config.layout.base.show_inline_lyrics;
+        self.config.save();
+    }
+
+    /// Flips whether the library shows only starred tracks, keeping the
+    /// selected track selected if it's still shown.
+    pub fn toggle_hide_unstarred(&mut self) {
+        self.logic
+            .set_hide_unstarred(!self.logic.get_hide_unstarred());
+        self.library.reanchor_after_refresh(&self.logic);
+    }
+
+    /// Flips whether the playing track is always skipped, moving on to the
+    /// next track when it's marked.
+    pub fn toggle_track_skipped(&mut self) {
+        let Some(track_id) = self.logic.get_playing_track_id() else {
+            return;
+        };
+        let skipped = !self.logic.is_track_skipped(&track_id);
+        self.logic.set_track_skipped(&track_id, skipped);
+        if skipped {
+            self.logic.next();
+        }
+    }
+
+    pub fn open_command_palette(&mut self) {
+        self.command_palette = Some(CommandPaletteState::new(&self.logic, &self.config));
+    }
+
+    /// Opens the entry of a track number to play, within the selected album.
+    pub fn open_track_number(&mut self) {
+        if let Some(album_id) = self.library.selected_album_id() {
+            self.track_number = Some(TrackNumberState::new(album_id.clone()));
+        }
+    }
+
+    /// Whether to prompt for new credentials: the server rejected the current
+    /// ones and the settings panel isn't already open to fix them.
+    pub fn reauth_prompt_visible(&self) -> bool {
+        self.focused_panel != FocusedPanel::Settings
+            && matches!(
+                self.logic.get_error(),
+                Some(bc::AppStateError::AuthenticationFailed { .. })
+            )
+    }
+
     pub fn toggle_settings(&mut self) {
         if self.focused_panel == FocusedPanel::Settings {
             self.focused_panel = FocusedPanel::Library;
@@ -311,7 +554,11 @@ impl App {
             config.last_playback.track_position_secs = tap.position.as_secs_f64();
         }
         config.last_playback.playback_mode = self.logic.get_playback_mode();
+        config.last_playback.preferred_shuffle = self.logic.get_preferred_shuffle();
         config.last_playback.sort_order = self.logic.get_sort_order();
+        config.playback.track_gains_db = self.logic.get_track_gains().into_iter().collect();
+        config.playback.skipped_tracks = self.logic.get_skipped_tracks().into_iter().collect();
+        config.search.store_history(&self.search.history);
         config.save();
     }
 
@@ -320,6 +567,24 @@ impl App {
         self.logic.set_volume(vol);
     }
 
+    /// Nudges the playing track's user gain by `delta_db`, within
+    /// [`TRACK_GAIN_RANGE_DB`](blackbird_client_shared::TRACK_GAIN_RANGE_DB).
+    pub fn adjust_track_gain(&mut self, delta_db: f32) {
+        let Some(track_id) = self.logic.get_playing_track_id() else {
+            return;
+        };
+        let range = blackbird_client_shared::TRACK_GAIN_RANGE_DB;
+        let gain =
+            (self.logic.get_track_gain(&track_id) + delta_db).clamp(*range.start(), *range.end());
+        self.logic.set_track_gain(&track_id, gain);
+    }
+
+    /// Goes to the next track, or the next album if Next was double-pressed.
+    pub fn next(&mut self) {
+        let window = self.config.playback.double_press_next_window();
+        self.next_press.press(&self.logic, window);
+    }
+
     pub fn seek_relative(&mut self, seconds: i64) {
         if let Some(details) = self.logic.get_track_display_details() {
             let current = details.track_position;
@@ -12,8 +12,9 @@ use serde::{Deserialize, Serialize};
 pub struct Config {
     #[serde(default)]
     pub general: General,
+    /// Style settings, preserving fields used only by other clients.
     #[serde(default)]
-    pub style: blackbird_client_shared::style::Style,
+    pub style: Style,
     /// Layout settings, extending the shared layout with TUI-specific fields.
     #[serde(default)]
     pub layout: Layout,
@@ -26,6 +27,18 @@ pub struct Config {
     /// Playback-related settings shared across clients.
     #[serde(default)]
     pub playback: blackbird_client_shared::config::Playback,
+    /// Play queue settings shared across clients.
+    #[serde(default)]
+    pub queue: blackbird_client_shared::config::Queue,
+    /// Clipboard settings shared across clients.
+    #[serde(default)]
+    pub clipboard: blackbird_client_shared::config::Clipboard,
+    /// Where downloaded tracks are saved.
+    #[serde(default)]
+    pub downloads: blackbird_client_shared::config::Downloads,
+    /// Search settings shared across clients.
+    #[serde(default)]
+    pub search: blackbird_client_shared::config::Search,
     /// Catch-all for unknown top-level sections (e.g. keybindings from GUI).
     #[serde(flatten)]
     pub extra: toml::Table,
@@ -47,6 +60,19 @@ pub enum AlbumArtProtocol {
     Halfblock,
 }
 
+/// Controls what the library area shows while the library is loading.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadingStyle {
+    /// An animated flock of birds above a status line.
+    #[default]
+    Animation,
+    /// The current fetch phase and counts, without animation.
+    Text,
+    /// A single status line.
+    Minimal,
+}
+
 /// TUI layout configuration, extending the shared [`blackbird_client_shared::config::Layout`]
 /// with TUI-specific fields. Unknown fields from other clients are preserved via the catch-all.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -58,6 +84,17 @@ pub struct Layout {
     /// Controls how album art is rendered (graphics protocol vs. half-blocks).
     #[serde(default)]
     pub album_art_protocol: AlbumArtProtocol,
+    /// Controls what is shown while the library is loading.
+    #[serde(default)]
+    pub loading_style: LoadingStyle,
+    /// Show a status line with volume, playback mode and connection state
+    /// above the help bar.
+    #[serde(default)]
+    pub show_status_line: bool,
+    /// Preview album art, enlarged in a corner of the library, while the
+    /// mouse rests on it. Clicking still opens the full overlay.
+    #[serde(default)]
+    pub art_hover_preview: bool,
     /// Shared layout settings.
     #[serde(flatten)]
     pub base: blackbird_client_shared::config::Layout,
@@ -70,12 +107,28 @@ impl Default for Layout {
         Self {
             use_terminal_background: false,
             album_art_protocol: AlbumArtProtocol::default(),
+            loading_style: LoadingStyle::default(),
+            show_status_line: false,
+            art_hover_preview: false,
             base: blackbird_client_shared::config::Layout::default(),
             extra: toml::Table::new(),
         }
     }
 }
 
+/// TUI style configuration, wrapping the shared [`blackbird_client_shared::style::Style`]
+/// so that fields only other clients use (e.g. the egui album art shape) are preserved.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct Style {
+    /// Shared style settings.
+    #[serde(flatten)]
+    pub base: blackbird_client_shared::style::Style,
+    /// Catch-all for unknown fields from other clients.
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
 impl blackbird_shared::config::ConfigFile for Config {}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -116,6 +169,16 @@ mod tests {
         assert_eq!(config, parsed);
     }
 
+    #[test]
+    fn startup_panel_must_be_a_known_panel() {
+        let parsed: Config = toml::from_str("[layout]\nstartup_panel = \"queue\"\n").unwrap();
+        assert_eq!(
+            parsed.layout.base.startup_panel,
+            blackbird_client_shared::config::StartupPanel::Queue
+        );
+        assert!(toml::from_str::<Config>("[layout]\nstartup_panel = \"nowhere\"\n").is_err());
+    }
+
     #[test]
     fn config_roundtrip_with_tui_field() {
         let mut config = Config::default();
@@ -147,6 +210,21 @@ some_gui_only_field = 42
         assert!(re_serialized.contains("some_gui_only_field = 42"));
     }
 
+    #[test]
+    fn config_preserves_unknown_style_fields() {
+        let toml_str = r#"
+[style]
+album_art_border = true
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.style.base.extra.get("album_art_border"),
+            Some(&toml::Value::Boolean(true))
+        );
+        let re_serialized = toml::to_string(&config).unwrap();
+        assert!(re_serialized.contains("album_art_border = true"));
+    }
+
     #[test]
     fn config_roundtrip_with_album_art_protocol() {
         for protocol in [
@@ -192,6 +192,40 @@ impl CoverArtCache {
         self.visible_sliced_protocols.clear();
     }
 
+    /// Sets the size requested from the server for full-res art (`None` for
+    /// the original image), forgetting derived data for any full-res art
+    /// that will be re-fetched.
+    pub fn set_full_res_size(&mut self, size: Option<usize>) {
+        for id in self.inner.set_full_res_size(size) {
+            self.forget_derived(&id);
+        }
+    }
+
+    /// Drops the colors, grids and image protocols derived from an id's art.
+    fn forget_derived(&mut self, id: &CoverArtId) {
+        let is_tmux = self.protocol_picker.as_ref().is_some_and(Picker::is_tmux);
+        self.colors.evict_matching(|color_id| color_id == id);
+        self.grids.evict_matching(|(grid_id, _, _)| grid_id == id);
+        let evicted = self
+            .protocols
+            .evict_matching(|(proto_id, _, _)| proto_id == id);
+        forget_protocol_images(
+            &mut self.pending_deletes,
+            &mut self.protocol_ids,
+            is_tmux,
+            &evicted,
+        );
+        let evicted = self
+            .sliced_protocols
+            .evict_matching(|(sliced_id, _, _)| sliced_id == id);
+        forget_protocol_images(
+            &mut self.pending_deletes,
+            &mut self.sliced_protocol_ids,
+            is_tmux,
+            &evicted,
+        );
+    }
+
     /// Reconciles the underlying cache against the current demand (fetches,
     /// eviction, prefetch) and drains completed color/grid computations.
     /// Returns `true` if any visual state changed.
@@ -204,26 +238,7 @@ impl CoverArtCache {
         }
         let is_tmux = self.protocol_picker.as_ref().is_some_and(Picker::is_tmux);
         for id in &result.evicted {
-            self.colors.evict_matching(|color_id| color_id == id);
-            self.grids.evict_matching(|(grid_id, _, _)| grid_id == id);
-            let evicted = self
-                .protocols
-                .evict_matching(|(proto_id, _, _)| proto_id == id);
-            forget_protocol_images(
-                &mut self.pending_deletes,
-                &mut self.protocol_ids,
-                is_tmux,
-                &evicted,
-            );
-            let evicted = self
-                .sliced_protocols
-                .evict_matching(|(sliced_id, _, _)| sliced_id == id);
-            forget_protocol_images(
-                &mut self.pending_deletes,
-                &mut self.sliced_protocol_ids,
-                is_tmux,
-                &evicted,
-            );
+            self.forget_derived(id);
         }
 
         // Evict image protocols whose art was not drawn in the most recent
@@ -25,18 +25,33 @@ pub enum Action {
     NextGroup,
     PreviousGroup,
     CyclePlaybackMode(Direction),
+    /// Switches between the preferred shuffle mode and sequential playback.
+    ToggleShuffle,
+    /// Switches to the next shuffle mode.
+    CycleShuffleScope,
     ToggleSortOrder(Direction),
     Search,
     Lyrics,
     Logs,
     Queue,
+    RecentAlbums,
+    Listeners,
+    ServerInfo,
+    ListeningStats,
     VolumeMode,
     VolumeUp,
     VolumeDown,
     Star,
     SeekForward,
     SeekBackward,
+    /// Seeks to the start of the next synced lyrics line.
+    NextLyricLine,
+    /// Seeks to the start of the previous synced lyrics line.
+    PreviousLyricLine,
+    /// Seeks to the given number of tenths of the way through the track.
+    SeekTenths(u8),
     GotoPlaying,
+    FocusNowPlaying,
     MoveUp,
     MoveDown,
     PageUp,
@@ -45,15 +60,44 @@ pub enum Action {
     GotoBottom,
     Select,
     GotoSelected,
+    CompleteSuggestion,
     Back,
     ClearLine,
     Char(char),
     DeleteChar,
     Settings,
+    Rescan,
+    AlbumRadio,
+    /// Adds the selected track to the play queue.
+    Enqueue,
+    /// Moves the selected play queue entry up.
+    MoveQueuedUp,
+    /// Moves the selected play queue entry down.
+    MoveQueuedDown,
+    /// Removes the selected entry from the play queue.
+    Dequeue,
+    /// Empties the play queue.
+    ClearQueue,
+    /// Plays from the selected track to the end of the scope.
+    PlayFrom(bc::PlayScope),
+    CopyNowPlaying,
+    Download,
+    RevealInFileManager,
+    ToggleTranscode,
+    /// Toggles the inline lyrics overlay.
+    ToggleInlineLyrics,
+    HideUnstarred,
+    /// Toggles whether the playing track is always skipped.
+    SkipTrack,
     MoveLeft,
     MoveRight,
     ResetField,
     ResetSection,
+    CommandPalette,
+    /// Plays a track of the selected album by its number.
+    TrackNumber,
+    TrackGainUp,
+    TrackGainDown,
 }
 
 // ── Key code constants ───────────────────────────────────────────
@@ -67,18 +111,26 @@ pub const KEY_NEXT_GROUP: KeyCode = KeyCode::Char('N');
 pub const KEY_PREVIOUS_GROUP: KeyCode = KeyCode::Char('P');
 pub const KEY_CYCLE_MODE_FWD: KeyCode = KeyCode::Char('m');
 pub const KEY_CYCLE_MODE_BWD: KeyCode = KeyCode::Char('M');
+pub const KEY_TOGGLE_SHUFFLE: KeyCode = KeyCode::Char('z');
+pub const KEY_CYCLE_SHUFFLE_SCOPE: KeyCode = KeyCode::Char('Z');
 pub const KEY_TOGGLE_SORT_FWD: KeyCode = KeyCode::Char('o');
 pub const KEY_TOGGLE_SORT_BWD: KeyCode = KeyCode::Char('O');
 pub const KEY_SEARCH: KeyCode = KeyCode::Char('/');
 pub const KEY_LYRICS: KeyCode = KeyCode::Char('l');
 pub const KEY_LOGS: KeyCode = KeyCode::Char('L');
 pub const KEY_QUEUE: KeyCode = KeyCode::Char('u');
+pub const KEY_RECENT_ALBUMS: KeyCode = KeyCode::Char('h');
+pub const KEY_LISTENERS: KeyCode = KeyCode::Char('w');
+pub const KEY_SERVER_INFO: KeyCode = KeyCode::Char('?');
+pub const KEY_LISTENING_STATS: KeyCode = KeyCode::Char('t');
 pub const KEY_VOLUME: KeyCode = KeyCode::Char('v');
 pub const KEY_GOTO_PLAYING: KeyCode = KeyCode::Char('g');
 pub const KEY_SEEK_BACK: KeyCode = KeyCode::Char('<');
 pub const KEY_SEEK_BACK_ALT: KeyCode = KeyCode::Char(',');
 pub const KEY_SEEK_FWD: KeyCode = KeyCode::Char('>');
 pub const KEY_SEEK_FWD_ALT: KeyCode = KeyCode::Char('.');
+pub const KEY_NEXT_LYRIC_LINE: KeyCode = KeyCode::Char(')');
+pub const KEY_PREVIOUS_LYRIC_LINE: KeyCode = KeyCode::Char('(');
 pub const KEY_STAR: KeyCode = KeyCode::Char('*');
 pub const KEY_SELECT: KeyCode = KeyCode::Enter;
 pub const KEY_BACK: KeyCode = KeyCode::Esc;
@@ -91,7 +143,25 @@ pub const KEY_PAGE_DOWN: KeyCode = KeyCode::PageDown;
 pub const KEY_GOTO_TOP: KeyCode = KeyCode::Home;
 pub const KEY_GOTO_BOTTOM: KeyCode = KeyCode::End;
 pub const KEY_DELETE_CHAR: KeyCode = KeyCode::Backspace;
+pub const KEY_COMPLETE: KeyCode = KeyCode::Tab;
 pub const KEY_SETTINGS: KeyCode = KeyCode::Char('i');
+pub const KEY_RESCAN: KeyCode = KeyCode::Char('r');
+pub const KEY_ALBUM_RADIO: KeyCode = KeyCode::Char('a');
+pub const KEY_ENQUEUE: KeyCode = KeyCode::Char('+');
+pub const KEY_CLEAR_QUEUE: KeyCode = KeyCode::Char('C');
+pub const KEY_PLAY_FROM: KeyCode = KeyCode::Char('f');
+pub const KEY_PLAY_ALBUM_FROM: KeyCode = KeyCode::Char('F');
+pub const KEY_COPY_NOW_PLAYING: KeyCode = KeyCode::Char('c');
+pub const KEY_DOWNLOAD: KeyCode = KeyCode::Char('D');
+pub const KEY_REVEAL: KeyCode = KeyCode::Char('e');
+pub const KEY_TOGGLE_TRANSCODE: KeyCode = KeyCode::Char('x');
+pub const KEY_TOGGLE_INLINE_LYRICS: KeyCode = KeyCode::Char('I');
+pub const KEY_HIDE_UNSTARRED: KeyCode = KeyCode::Char('H');
+pub const KEY_SKIP_TRACK: KeyCode = KeyCode::Char('K');
+pub const KEY_TRACK_GAIN_UP: KeyCode = KeyCode::Char(']');
+pub const KEY_TRACK_GAIN_DOWN: KeyCode = KeyCode::Char('[');
+pub const KEY_COMMAND_PALETTE: KeyCode = KeyCode::Char(':');
+pub const KEY_TRACK_NUMBER: KeyCode = KeyCode::Char('#');
 pub const KEY_CONFIRM_YES: KeyCode = KeyCode::Char('y');
 pub const KEY_CONFIRM_NO: KeyCode = KeyCode::Char('n');
 
@@ -122,13 +192,22 @@ impl Action {
             Action::Lyrics => (key_label(KEY_LYRICS), "lyrics".into()),
             Action::Logs => (key_label(KEY_LOGS), "logs".into()),
             Action::Queue => (key_label(KEY_QUEUE), "queue".into()),
+            Action::RecentAlbums => (key_label(KEY_RECENT_ALBUMS), "recent".into()),
+            Action::Listeners => (key_label(KEY_LISTENERS), "listeners".into()),
+            Action::ServerInfo => (key_label(KEY_SERVER_INFO), "server".into()),
+            Action::ListeningStats => (key_label(KEY_LISTENING_STATS), "stats".into()),
             Action::VolumeMode => (key_label(KEY_VOLUME), "vol".into()),
             Action::Star => (key_label(KEY_STAR), "star".into()),
             Action::SeekForward => (key_label(KEY_SEEK_FWD), "seek+".into()),
             Action::SeekBackward => (key_label(KEY_SEEK_BACK), "seek-".into()),
+            Action::NextLyricLine => (key_label(KEY_NEXT_LYRIC_LINE), "next line".into()),
+            Action::PreviousLyricLine => (key_label(KEY_PREVIOUS_LYRIC_LINE), "prev line".into()),
+            Action::SeekTenths(_) => ("0-9".into(), "seek %".into()),
             Action::GotoPlaying => (key_label(KEY_GOTO_PLAYING), "goto".into()),
+            Action::FocusNowPlaying => ("ctrl+g".into(), "now playing".into()),
             Action::Select => (key_label(KEY_SELECT), "play".into()),
             Action::GotoSelected => ("shift+enter".into(), "goto".into()),
+            Action::CompleteSuggestion => (key_label(KEY_COMPLETE), "complete".into()),
             Action::Back => (key_label(KEY_BACK), "close".into()),
             Action::CyclePlaybackMode(Direction::Forward) => {
                 let mode = logic.get_playback_mode().as_str();
@@ -137,6 +216,17 @@ impl Action {
                     format!("mode ({mode})").into(),
                 )
             }
+            Action::ToggleShuffle => {
+                let description = if logic.get_playback_mode().is_shuffle() {
+                    "unshuffle".into()
+                } else {
+                    format!("shuffle ({})", logic.get_preferred_shuffle()).into()
+                };
+                (key_label(KEY_TOGGLE_SHUFFLE), description)
+            }
+            Action::CycleShuffleScope => {
+                (key_label(KEY_CYCLE_SHUFFLE_SCOPE), "shuffle scope".into())
+            }
             Action::ToggleSortOrder(Direction::Forward) => {
                 let order = logic.get_sort_order().as_str();
                 (
@@ -145,6 +235,58 @@ impl Action {
                 )
             }
             Action::Settings => (key_label(KEY_SETTINGS), "settings".into()),
+            Action::Rescan => {
+                let desc = match logic.get_server_scan_count() {
+                    Some(count) => format!("scanning ({count})").into(),
+                    None => "rescan".into(),
+                };
+                (key_label(KEY_RESCAN), desc)
+            }
+            Action::AlbumRadio => (key_label(KEY_ALBUM_RADIO), "radio".into()),
+            Action::Enqueue => (key_label(KEY_ENQUEUE), "enqueue".into()),
+            Action::MoveQueuedUp => ("shift+up".into(), "move up".into()),
+            Action::MoveQueuedDown => ("shift+down".into(), "move down".into()),
+            Action::Dequeue => (key_label(KEY_DELETE_CHAR), "remove".into()),
+            Action::ClearQueue => (key_label(KEY_CLEAR_QUEUE), "clear queue".into()),
+            Action::PlayFrom(bc::PlayScope::Library) => {
+                (key_label(KEY_PLAY_FROM), "play from here".into())
+            }
+            Action::PlayFrom(bc::PlayScope::Album) => (
+                key_label(KEY_PLAY_ALBUM_FROM),
+                "play album from here".into(),
+            ),
+            Action::CopyNowPlaying => (key_label(KEY_COPY_NOW_PLAYING), "copy".into()),
+            Action::Download => (key_label(KEY_DOWNLOAD), "download".into()),
+            Action::RevealInFileManager => (key_label(KEY_REVEAL), "reveal".into()),
+            Action::ToggleTranscode => {
+                let state = if logic.get_transcode() { "on" } else { "off" };
+                (
+                    key_label(KEY_TOGGLE_TRANSCODE),
+                    format!("transcode ({state})").into(),
+                )
+            }
+            Action::ToggleInlineLyrics => {
+                (key_label(KEY_TOGGLE_INLINE_LYRICS), "inline lyrics".into())
+            }
+            Action::HideUnstarred => {
+                let description = if logic.get_hide_unstarred() {
+                    "show all"
+                } else {
+                    "starred only"
+                };
+                (key_label(KEY_HIDE_UNSTARRED), description.into())
+            }
+            Action::SkipTrack => {
+                let skipped = logic
+                    .get_playing_track_id()
+                    .is_some_and(|track_id| logic.is_track_skipped(&track_id));
+                let description = if skipped { "unskip" } else { "always skip" };
+                (key_label(KEY_SKIP_TRACK), description.into())
+            }
+            Action::CommandPalette => (key_label(KEY_COMMAND_PALETTE), "commands".into()),
+            Action::TrackNumber => (key_label(KEY_TRACK_NUMBER), "track #".into()),
+            Action::TrackGainUp => (key_label(KEY_TRACK_GAIN_UP), "gain+".into()),
+            Action::TrackGainDown => (key_label(KEY_TRACK_GAIN_DOWN), "gain-".into()),
             Action::MoveLeft => (key_label(KEY_LEFT), "left".into()),
             Action::MoveRight => (key_label(KEY_RIGHT), "right".into()),
             Action::ResetField => (key_label(KeyCode::Char('d')), "reset field".into()),
@@ -181,17 +323,26 @@ pub fn library_action(key: &KeyEvent) -> Option<Action> {
         KEY_STOP => Some(Action::Stop),
         KEY_CYCLE_MODE_FWD => Some(Action::CyclePlaybackMode(Direction::Forward)),
         KEY_CYCLE_MODE_BWD => Some(Action::CyclePlaybackMode(Direction::Backward)),
+        KEY_TOGGLE_SHUFFLE => Some(Action::ToggleShuffle),
+        KEY_CYCLE_SHUFFLE_SCOPE => Some(Action::CycleShuffleScope),
         KEY_TOGGLE_SORT_FWD => Some(Action::ToggleSortOrder(Direction::Forward)),
         KEY_TOGGLE_SORT_BWD => Some(Action::ToggleSortOrder(Direction::Backward)),
         KEY_SEARCH => Some(Action::Search),
         KEY_LYRICS => Some(Action::Lyrics),
         KEY_LOGS => Some(Action::Logs),
         KEY_QUEUE => Some(Action::Queue),
+        KEY_RECENT_ALBUMS => Some(Action::RecentAlbums),
+        KEY_LISTENERS => Some(Action::Listeners),
+        KEY_SERVER_INFO => Some(Action::ServerInfo),
+        KEY_LISTENING_STATS => Some(Action::ListeningStats),
         KEY_VOLUME => Some(Action::VolumeMode),
         KEY_GOTO_PLAYING => Some(Action::GotoPlaying),
         KEY_SEEK_BACK | KEY_SEEK_BACK_ALT => Some(Action::SeekBackward),
         KEY_SEEK_FWD | KEY_SEEK_FWD_ALT => Some(Action::SeekForward),
+        KEY_NEXT_LYRIC_LINE => Some(Action::NextLyricLine),
+        KEY_PREVIOUS_LYRIC_LINE => Some(Action::PreviousLyricLine),
         KEY_STAR => Some(Action::Star),
+        KeyCode::Char(c @ '0'..='9') => Some(Action::SeekTenths(c as u8 - b'0')),
         KEY_UP => Some(Action::MoveUp),
         KEY_DOWN => Some(Action::MoveDown),
         KEY_PAGE_UP => Some(Action::PageUp),
@@ -200,6 +351,22 @@ pub fn library_action(key: &KeyEvent) -> Option<Action> {
         KEY_GOTO_BOTTOM => Some(Action::GotoBottom),
         KEY_SELECT => Some(Action::Select),
         KEY_SETTINGS => Some(Action::Settings),
+        KEY_RESCAN => Some(Action::Rescan),
+        KEY_ALBUM_RADIO => Some(Action::AlbumRadio),
+        KEY_ENQUEUE => Some(Action::Enqueue),
+        KEY_PLAY_FROM => Some(Action::PlayFrom(bc::PlayScope::Library)),
+        KEY_PLAY_ALBUM_FROM => Some(Action::PlayFrom(bc::PlayScope::Album)),
+        KEY_COPY_NOW_PLAYING => Some(Action::CopyNowPlaying),
+        KEY_DOWNLOAD => Some(Action::Download),
+        KEY_REVEAL => Some(Action::RevealInFileManager),
+        KEY_TOGGLE_TRANSCODE => Some(Action::ToggleTranscode),
+        KEY_TOGGLE_INLINE_LYRICS => Some(Action::ToggleInlineLyrics),
+        KEY_HIDE_UNSTARRED => Some(Action::HideUnstarred),
+        KEY_SKIP_TRACK => Some(Action::SkipTrack),
+        KEY_COMMAND_PALETTE => Some(Action::CommandPalette),
+        KEY_TRACK_NUMBER => Some(Action::TrackNumber),
+        KEY_TRACK_GAIN_UP => Some(Action::TrackGainUp),
+        KEY_TRACK_GAIN_DOWN => Some(Action::TrackGainDown),
         _ => None,
     }
 }
@@ -235,11 +402,13 @@ pub fn search_action(key: &KeyEvent) -> Option<Action> {
         KEY_UP => Some(Action::MoveUp),
         KEY_DOWN => Some(Action::MoveDown),
         KEY_DELETE_CHAR => Some(Action::DeleteChar),
+        KEY_COMPLETE => Some(Action::CompleteSuggestion),
         KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => match c {
             // Terminals that don't disambiguate shift+enter send a raw `\n`
             // (0x0A), which crossterm parses as Ctrl+J in raw mode. Treat it
             // as GotoSelected so shift+enter works there too.
             'j' => Some(Action::GotoSelected),
+            'g' => Some(Action::FocusNowPlaying),
             'u' => Some(Action::ClearLine),
             _ => Some(Action::Char(c)),
         },
@@ -257,8 +426,11 @@ pub fn lyrics_action(key: &KeyEvent) -> Option<Action> {
         KEY_PAGE_UP => Some(Action::PageUp),
         KEY_PAGE_DOWN => Some(Action::PageDown),
         KEY_SELECT => Some(Action::Select),
+        KEY_GOTO_PLAYING => Some(Action::GotoPlaying),
         KEY_SEEK_BACK | KEY_SEEK_BACK_ALT => Some(Action::SeekBackward),
         KEY_SEEK_FWD | KEY_SEEK_FWD_ALT => Some(Action::SeekForward),
+        KEY_NEXT_LYRIC_LINE => Some(Action::NextLyricLine),
+        KEY_PREVIOUS_LYRIC_LINE => Some(Action::PreviousLyricLine),
         KEY_PLAY_PAUSE => Some(Action::PlayPause),
         KEY_NEXT => Some(Action::Next),
         KEY_PREVIOUS => Some(Action::Previous),
@@ -297,6 +469,33 @@ pub fn playback_mode_dropdown_action(key: &KeyEvent) -> Option<Action> {
     }
 }
 
+/// Resolve a key event into an action in command palette context.
+pub fn command_palette_action(key: &KeyEvent) -> Option<Action> {
+    match key.code {
+        KEY_BACK => Some(Action::Back),
+        KEY_SELECT => Some(Action::Select),
+        KEY_UP => Some(Action::MoveUp),
+        KEY_DOWN => Some(Action::MoveDown),
+        KEY_DELETE_CHAR => Some(Action::DeleteChar),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::ClearLine)
+        }
+        KeyCode::Char(c) => Some(Action::Char(c)),
+        _ => None,
+    }
+}
+
+/// Resolve a key event into an action in track number entry context.
+pub fn track_number_action(key: &KeyEvent) -> Option<Action> {
+    match key.code {
+        KEY_BACK => Some(Action::Back),
+        KEY_SELECT => Some(Action::Select),
+        KEY_DELETE_CHAR => Some(Action::DeleteChar),
+        KeyCode::Char(c) => Some(Action::Char(c)),
+        _ => None,
+    }
+}
+
 /// Resolve a key event into an action in quit-confirmation context.
 /// `y` / Enter confirms; any other key cancels.
 pub fn quit_confirm_action(key: &KeyEvent) -> Action {
@@ -306,12 +505,25 @@ pub fn quit_confirm_action(key: &KeyEvent) -> Action {
     }
 }
 
+/// Resolve a key event into an action in the re-authentication prompt.
+/// `i` / Enter opens settings; any other key dismisses the prompt.
+pub fn reauth_prompt_action(key: &KeyEvent) -> Action {
+    match key.code {
+        KEY_SETTINGS | KEY_SELECT => Action::Settings,
+        _ => Action::Back,
+    }
+}
+
 /// Resolve a key event into an action in queue context.
 pub fn queue_action(key: &KeyEvent) -> Option<Action> {
     match key.code {
         KEY_BACK | KEY_QUEUE | KEY_QUIT => Some(Action::Back),
+        KEY_UP if key.modifiers.contains(KeyModifiers::SHIFT) => Some(Action::MoveQueuedUp),
+        KEY_DOWN if key.modifiers.contains(KeyModifiers::SHIFT) => Some(Action::MoveQueuedDown),
         KEY_UP => Some(Action::MoveUp),
         KEY_DOWN => Some(Action::MoveDown),
+        KEY_DELETE_CHAR => Some(Action::Dequeue),
+        KEY_CLEAR_QUEUE => Some(Action::ClearQueue),
         KEY_PAGE_UP => Some(Action::PageUp),
         KEY_PAGE_DOWN => Some(Action::PageDown),
         KEY_SELECT => Some(Action::Select),
@@ -326,6 +538,44 @@ pub fn queue_action(key: &KeyEvent) -> Option<Action> {
     }
 }
 
+/// Resolve a key event into an action in recently played context.
+pub fn recent_action(key: &KeyEvent) -> Option<Action> {
+    match key.code {
+        KEY_BACK | KEY_RECENT_ALBUMS | KEY_QUIT => Some(Action::Back),
+        KEY_UP => Some(Action::MoveUp),
+        KEY_DOWN => Some(Action::MoveDown),
+        KEY_PAGE_UP => Some(Action::PageUp),
+        KEY_PAGE_DOWN => Some(Action::PageDown),
+        KEY_SELECT => Some(Action::Select),
+        KEY_PLAY_PAUSE => Some(Action::PlayPause),
+        _ => None,
+    }
+}
+
+/// Resolve a key event into an action in listeners context.
+pub fn listeners_action(key: &KeyEvent) -> Option<Action> {
+    match key.code {
+        KEY_BACK | KEY_LISTENERS | KEY_QUIT => Some(Action::Back),
+        _ => None,
+    }
+}
+
+/// Resolve a key event into an action in server info context.
+pub fn server_info_action(key: &KeyEvent) -> Option<Action> {
+    match key.code {
+        KEY_BACK | KEY_SERVER_INFO | KEY_QUIT => Some(Action::Back),
+        _ => None,
+    }
+}
+
+/// Resolve a key event into an action in listening stats context.
+pub fn listening_stats_action(key: &KeyEvent) -> Option<Action> {
+    match key.code {
+        KEY_BACK | KEY_LISTENING_STATS | KEY_QUIT => Some(Action::Back),
+        _ => None,
+    }
+}
+
 /// Resolve a key event into an action in logs context.
 pub fn logs_action(key: &KeyEvent) -> Option<Action> {
     match key.code {
@@ -348,16 +598,46 @@ pub const LIBRARY_HELP: &[HelpEntry] = &[
     HelpEntry::Pair(Action::NextGroup, Action::PreviousGroup, "next/prev group"),
     HelpEntry::Single(Action::Stop),
     HelpEntry::Pair(Action::SeekBackward, Action::SeekForward, "seek-/+"),
+    HelpEntry::Single(Action::SeekTenths(0)),
+    HelpEntry::Pair(
+        Action::PreviousLyricLine,
+        Action::NextLyricLine,
+        "prev/next line",
+    ),
     HelpEntry::Single(Action::Star),
+    HelpEntry::Single(Action::SkipTrack),
+    HelpEntry::Single(Action::HideUnstarred),
     HelpEntry::Single(Action::GotoPlaying),
+    HelpEntry::Single(Action::TrackNumber),
     HelpEntry::Single(Action::Search),
     HelpEntry::Single(Action::Lyrics),
+    HelpEntry::Single(Action::ToggleInlineLyrics),
     HelpEntry::Single(Action::Queue),
+    HelpEntry::Single(Action::RecentAlbums),
+    HelpEntry::Single(Action::Listeners),
+    HelpEntry::Single(Action::AlbumRadio),
+    HelpEntry::Single(Action::Enqueue),
+    HelpEntry::Single(Action::CopyNowPlaying),
+    HelpEntry::Single(Action::Download),
+    HelpEntry::Single(Action::RevealInFileManager),
     HelpEntry::Single(Action::VolumeMode),
+    HelpEntry::Single(Action::ToggleTranscode),
+    HelpEntry::Pair(Action::TrackGainDown, Action::TrackGainUp, "gain-/+"),
     HelpEntry::Single(Action::Select),
+    HelpEntry::Pair(
+        Action::PlayFrom(bc::PlayScope::Library),
+        Action::PlayFrom(bc::PlayScope::Album),
+        "play from here/album",
+    ),
     HelpEntry::Single(Action::CyclePlaybackMode(Direction::Forward)),
+    HelpEntry::Single(Action::ToggleShuffle),
+    HelpEntry::Single(Action::CycleShuffleScope),
     HelpEntry::Single(Action::ToggleSortOrder(Direction::Forward)),
+    HelpEntry::Single(Action::Rescan),
+    HelpEntry::Single(Action::ServerInfo),
+    HelpEntry::Single(Action::ListeningStats),
     HelpEntry::Single(Action::Settings),
+    HelpEntry::Single(Action::CommandPalette),
 ];
 
 /// Ordered list of entries to show in the settings help bar.
@@ -375,6 +655,8 @@ pub const SEARCH_HELP: &[HelpEntry] = &[
     HelpEntry::Single(Action::Back),
     HelpEntry::Single(Action::Select),
     HelpEntry::Single(Action::GotoSelected),
+    HelpEntry::Single(Action::CompleteSuggestion),
+    HelpEntry::Single(Action::FocusNowPlaying),
     HelpEntry::Pair(Action::MoveUp, Action::MoveDown, "up/down"),
 ];
 
@@ -383,7 +665,13 @@ pub const LYRICS_HELP: &[HelpEntry] = &[
     HelpEntry::Single(Action::Back),
     HelpEntry::Pair(Action::MoveUp, Action::MoveDown, "up/down"),
     HelpEntry::Single(Action::Select),
+    HelpEntry::Single(Action::GotoPlaying),
     HelpEntry::Pair(Action::SeekBackward, Action::SeekForward, "seek-/+"),
+    HelpEntry::Pair(
+        Action::PreviousLyricLine,
+        Action::NextLyricLine,
+        "prev/next line",
+    ),
     HelpEntry::Single(Action::PlayPause),
     HelpEntry::Pair(Action::Next, Action::Previous, "next/prev"),
     HelpEntry::Pair(Action::NextGroup, Action::PreviousGroup, "next/prev group"),
@@ -394,12 +682,32 @@ pub const QUEUE_HELP: &[HelpEntry] = &[
     HelpEntry::Single(Action::Back),
     HelpEntry::Pair(Action::MoveUp, Action::MoveDown, "up/down"),
     HelpEntry::Single(Action::Select),
+    HelpEntry::Pair(Action::MoveQueuedUp, Action::MoveQueuedDown, "reorder"),
+    HelpEntry::Single(Action::Dequeue),
+    HelpEntry::Single(Action::ClearQueue),
     HelpEntry::Single(Action::PlayPause),
     HelpEntry::Pair(Action::Next, Action::Previous, "next/prev"),
     HelpEntry::Pair(Action::NextGroup, Action::PreviousGroup, "next/prev group"),
     HelpEntry::Single(Action::CyclePlaybackMode(Direction::Forward)),
 ];
 
+/// Ordered list of entries to show in the recently played help bar.
+pub const RECENT_HELP: &[HelpEntry] = &[
+    HelpEntry::Single(Action::Back),
+    HelpEntry::Pair(Action::MoveUp, Action::MoveDown, "up/down"),
+    HelpEntry::Single(Action::Select),
+    HelpEntry::Single(Action::PlayPause),
+];
+
+/// Ordered list of entries to show in the listeners help bar.
+pub const LISTENERS_HELP: &[HelpEntry] = &[HelpEntry::Single(Action::Back)];
+
+/// Ordered list of entries to show in the server info help bar.
+pub const SERVER_INFO_HELP: &[HelpEntry] = &[HelpEntry::Single(Action::Back)];
+
+/// Ordered list of entries to show in the listening stats help bar.
+pub const LISTENING_STATS_HELP: &[HelpEntry] = &[HelpEntry::Single(Action::Back)];
+
 /// Ordered list of entries to show in the logs help bar.
 pub const LOGS_HELP: &[HelpEntry] = &[
     HelpEntry::Single(Action::Back),
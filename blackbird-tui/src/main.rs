@@ -1,4 +1,5 @@
 mod app;
+mod clipboard;
 mod config;
 mod cover_art;
 mod keys;
@@ -15,11 +16,12 @@ use config::Config;
 use cover_art::CoverArtCache;
 use keys::Action;
 use log_buffer::{LogBuffer, LogBufferLayer};
+use ui::command_palette::{Command, CommandPaletteAction};
 
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, MouseButton, MouseEvent,
-        MouseEventKind,
+        self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+        Event, MouseButton, MouseEvent, MouseEventKind,
     },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
@@ -61,12 +63,36 @@ fn main() -> anyhow::Result<()> {
         base_url: config.server.base_url.clone(),
         username: config.server.username.clone(),
         password: config.server.password.clone(),
+        client_id: config
+            .server
+            .client_id("blackbird-tui", env!("CARGO_PKG_VERSION")),
         transcode: config.server.transcode,
+        transcode_format: config.server.transcode_format.clone(),
+        api_version: config.server.api_version.clone(),
+        fetch_options: bc::blackbird_state::FetchOptions {
+            album_page_size: config.server.album_page_size,
+            track_page_size: config.server.track_page_size,
+            merge_editions: config.layout.base.merge_editions,
+        },
+        scrobble_targets: config.playback.scrobbling.clone(),
         volume: config.general.volume,
         apply_replaygain: config.playback.apply_replaygain,
         replaygain_preamp_db: config.playback.replaygain_preamp_db,
+        track_gains: config
+            .playback
+            .track_gains_db
+            .iter()
+            .map(|(id, gain)| (id.clone(), *gain))
+            .collect(),
+        skipped_tracks: config.playback.skipped_tracks.iter().cloned().collect(),
+        skip_silence_db: config.playback.skip_silence_db(),
         sort_order: config.last_playback.sort_order,
         playback_mode: config.last_playback.playback_mode,
+        preferred_shuffle: config.last_playback.preferred_shuffle,
+        end_behavior: config.playback.end_behavior,
+        stop_behavior: config.playback.stop_behavior,
+        previous_behavior: config.playback.previous_behavior,
+        previous_restart_threshold: config.playback.previous_restart_threshold(),
         last_playback: config.last_playback.as_track_and_position(),
         cover_art_loaded_tx,
         lyrics_loaded_tx,
@@ -74,6 +100,9 @@ fn main() -> anyhow::Result<()> {
         track_updated_tx,
     });
 
+    // Check the platform's reduced-motion preference off the UI thread.
+    blackbird_client_shared::accessibility::detect_platform_reduced_motion();
+
     // Initialize platform-specific tray icon requirements (GTK on Linux).
     #[cfg(feature = "tray-icon")]
     blackbird_client_shared::tray::init_platform();
@@ -91,7 +120,7 @@ fn main() -> anyhow::Result<()> {
                 None
             }
         },
-        logic.subscribe_to_playback_events(),
+        logic.subscribe_events(),
         logic.request_handle(),
         logic.get_state(),
     )
@@ -102,10 +131,10 @@ fn main() -> anyhow::Result<()> {
     #[cfg(feature = "tray-icon")]
     let (tray_icon, mut tray_menu) = {
         let icon = blackbird_client_shared::load_icon();
-        blackbird_client_shared::tray::TrayMenu::new(icon, logic.get_playback_mode())
+        blackbird_client_shared::tray::TrayMenu::new(icon, &logic)
     };
 
-    let playback_rx = logic.subscribe_to_playback_events();
+    let playback_rx = logic.subscribe_events();
     let cover_art_cache = CoverArtCache::new(cover_art_loaded_rx);
 
     let mut app = App::new(
@@ -122,7 +151,12 @@ fn main() -> anyhow::Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -205,7 +239,8 @@ fn main() -> anyhow::Result<()> {
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableFocusChange
     )?;
     terminal.show_cursor()?;
 
@@ -226,7 +261,12 @@ fn main() -> anyhow::Result<()> {
         std::mem::forget(tray_menu);
     }
     #[cfg(feature = "media-controls")]
-    std::mem::forget(media_controls);
+    {
+        if let Some(mc) = media_controls.as_mut() {
+            mc.remove_cover_art();
+        }
+        std::mem::forget(media_controls);
+    }
 
     result
 }
@@ -326,26 +366,32 @@ fn run_app(
             // Process the first event, then drain all remaining queued events.
             let mut process_event = |evt: Event, app: &mut App| match evt {
                 Event::Key(key) if key.kind == event::KeyEventKind::Press => {
+                    app.logic.note_user_activity();
                     handle_key_event(app, &key);
                     app.needs_redraw = true;
                 }
-                Event::Mouse(mouse) => match mouse.kind {
-                    MouseEventKind::ScrollUp => {
-                        scroll_delta -= 1;
-                        app.needs_redraw = true;
-                    }
-                    MouseEventKind::ScrollDown => {
-                        scroll_delta += 1;
-                        app.needs_redraw = true;
-                    }
-                    _ => {
-                        handle_mouse_event(app, &mouse, size);
-                        app.needs_redraw = true;
+                Event::Mouse(mouse) => {
+                    app.logic.note_user_activity();
+                    match mouse.kind {
+                        MouseEventKind::ScrollUp => {
+                            scroll_delta -= 1;
+                            app.needs_redraw = true;
+                        }
+                        MouseEventKind::ScrollDown => {
+                            scroll_delta += 1;
+                            app.needs_redraw = true;
+                        }
+                        _ => {
+                            handle_mouse_event(app, &mouse, size);
+                            app.needs_redraw = true;
+                        }
                     }
-                },
+                }
                 Event::Resize(_, _) => {
                     app.needs_redraw = true;
                 }
+                Event::FocusGained => app.terminal_focused = true,
+                Event::FocusLost => app.terminal_focused = false,
                 _ => {}
             };
 
@@ -374,7 +420,7 @@ fn run_app(
             }
             #[cfg(feature = "media-controls")]
             if let Some(mc) = media_controls.as_mut() {
-                mc.update();
+                mc.update(&app.logic);
             }
             #[cfg(feature = "tray-icon")]
             {
@@ -419,6 +465,46 @@ fn handle_key_event(app: &mut App, key: &event::KeyEvent) {
         return;
     }
 
+    // Handle the prompt to re-enter rejected credentials.
+    if app.reauth_prompt_visible() {
+        if keys::reauth_prompt_action(key) == Action::Settings {
+            app.toggle_settings();
+        }
+        app.logic.clear_error();
+        return;
+    }
+
+    // Handle the command palette.
+    if let Some(palette) = app.command_palette.as_mut() {
+        if let Some(action) = keys::command_palette_action(key)
+            && let Some(pa) = palette.handle_key(action)
+        {
+            app.command_palette = None;
+            match pa {
+                CommandPaletteAction::Close => {}
+                CommandPaletteAction::Run(Command::Action(action)) => {
+                    run_action(app, action);
+                }
+                CommandPaletteAction::Run(command) => {
+                    command.toggle(&mut app.config);
+                    app.config.save();
+                    app.library.mark_dirty();
+                }
+            }
+        }
+        return;
+    }
+
+    // Handle the track number entry.
+    if let Some(entry) = app.track_number.as_mut() {
+        if let Some(action) = keys::track_number_action(key)
+            && entry.handle_key(&app.logic, action)
+        {
+            app.track_number = None;
+        }
+        return;
+    }
+
     // Handle playback mode dropdown.
     if app.playback_mode_dropdown {
         if let Some(action) = keys::playback_mode_dropdown_action(key) {
@@ -475,6 +561,7 @@ fn handle_key_event(app: &mut App, key: &event::KeyEvent) {
                         app.library.scroll_to_track = Some(track_id);
                         app.toggle_search();
                     }
+                    ui::search::SearchAction::FocusNowPlaying => app.focus_now_playing(),
                 }
             }
         }
@@ -486,6 +573,8 @@ fn handle_key_event(app: &mut App, key: &event::KeyEvent) {
                     ui::lyrics::LyricsAction::ToggleLyrics => app.toggle_lyrics(),
                     ui::lyrics::LyricsAction::Quit => app.should_quit = true,
                     ui::lyrics::LyricsAction::SeekRelative(secs) => app.seek_relative(secs),
+                    ui::lyrics::LyricsAction::FocusNowPlaying => app.focus_now_playing(),
+                    ui::lyrics::LyricsAction::Next => app.next(),
                 }
             }
         }
@@ -506,6 +595,49 @@ fn handle_key_event(app: &mut App, key: &event::KeyEvent) {
                 match qa {
                     ui::queue::QueueAction::ToggleQueue => app.toggle_queue(),
                     ui::queue::QueueAction::Quit => app.should_quit = true,
+                    ui::queue::QueueAction::Next => app.next(),
+                }
+            }
+        }
+        FocusedPanel::Recent => {
+            if let Some(action) = keys::recent_action(key)
+                && let Some(ra) = ui::recent::handle_key(&mut app.recent, &app.logic, action)
+            {
+                match ra {
+                    ui::recent::RecentAction::ToggleRecent => app.toggle_recent(),
+                    ui::recent::RecentAction::Quit => app.should_quit = true,
+                }
+            }
+        }
+        FocusedPanel::Listeners => {
+            if let Some(action) = keys::listeners_action(key)
+                && let Some(la) = ui::listeners::handle_key(action)
+            {
+                match la {
+                    ui::listeners::ListenersAction::ToggleListeners => app.toggle_listeners(),
+                    ui::listeners::ListenersAction::Quit => app.should_quit = true,
+                }
+            }
+        }
+        FocusedPanel::ServerInfo => {
+            if let Some(action) = keys::server_info_action(key)
+                && let Some(sa) = ui::server_info::handle_key(action)
+            {
+                match sa {
+                    ui::server_info::ServerInfoAction::ToggleServerInfo => app.toggle_server_info(),
+                    ui::server_info::ServerInfoAction::Quit => app.should_quit = true,
+                }
+            }
+        }
+        FocusedPanel::ListeningStats => {
+            if let Some(action) = keys::listening_stats_action(key)
+                && let Some(la) = ui::listening_stats::handle_key(action)
+            {
+                match la {
+                    ui::listening_stats::ListeningStatsAction::ToggleListeningStats => {
+                        app.toggle_listening_stats()
+                    }
+                    ui::listening_stats::ListeningStatsAction::Quit => app.should_quit = true,
                 }
             }
         }
@@ -515,12 +647,7 @@ fn handle_key_event(app: &mut App, key: &event::KeyEvent) {
                     ui::settings::handle_key(&mut app.settings, &mut app.config, action);
                 if server_changed {
                     app.config.save();
-                    app.logic.reload_library(
-                        app.config.server.base_url.clone(),
-                        app.config.server.username.clone(),
-                        app.config.server.password.clone(),
-                        app.config.server.transcode,
-                    );
+                    app.logic.reconfigure_server(&app.config.server);
                 }
                 // Config changes are applied in-memory for live preview;
                 // disk save is deferred to settings exit or app exit.
@@ -539,7 +666,7 @@ fn handle_key_event(app: &mut App, key: &event::KeyEvent) {
 
 fn handle_mouse_event(app: &mut App, mouse: &MouseEvent, size: Rect) {
     // Compute layout areas matching ui::draw
-    let main = ui::layout::split_main(size);
+    let main = ui::layout::split_main(size, app.config.layout.show_status_line);
 
     let now_playing_area = main.now_playing;
     let scrub_area = main.scrub_bar;
@@ -569,6 +696,18 @@ fn handle_mouse_event(app: &mut App, mouse: &MouseEvent, size: Rect) {
         MouseEventKind::Down(MouseButton::Left) => {
             app.mouse_position = Some((x, y));
 
+            // --- Re-authentication prompt: any click dismisses it ---
+            if app.reauth_prompt_visible() {
+                app.logic.clear_error();
+                return;
+            }
+
+            // --- Command palette: any click closes it ---
+            if app.command_palette.is_some() {
+                app.command_palette = None;
+                return;
+            }
+
             // --- Playback mode dropdown (handled before other areas) ---
             if app.playback_mode_dropdown {
                 let dropdown_rect = ui::now_playing::playback_mode_dropdown_rect(size);
@@ -647,6 +786,18 @@ fn handle_mouse_event(app: &mut App, mouse: &MouseEvent, size: Rect) {
                     ui::lyrics::handle_mouse_click(&mut app.lyrics, &app.logic, library_area, x, y);
                 } else if app.focused_panel == FocusedPanel::Queue {
                     ui::queue::handle_mouse_click(&mut app.queue, &app.logic, library_area, x, y);
+                } else if app.focused_panel == FocusedPanel::Recent {
+                    if ui::recent::handle_mouse_click(
+                        &mut app.recent,
+                        &app.logic,
+                        library_area,
+                        x,
+                        y,
+                    )
+                    .is_some()
+                    {
+                        app.toggle_recent();
+                    }
                 } else if app.focused_panel == FocusedPanel::Settings {
                     let server_changed = ui::settings::handle_mouse_click(
                         &mut app.settings,
@@ -657,12 +808,7 @@ fn handle_mouse_event(app: &mut App, mouse: &MouseEvent, size: Rect) {
                     );
                     if server_changed {
                         app.config.save();
-                        app.logic.reload_library(
-                            app.config.server.base_url.clone(),
-                            app.config.server.username.clone(),
-                            app.config.server.password.clone(),
-                            app.config.server.transcode,
-                        );
+                        app.logic.reconfigure_server(&app.config.server);
                     }
                 }
                 return;
@@ -688,6 +834,7 @@ fn handle_mouse_event(app: &mut App, mouse: &MouseEvent, size: Rect) {
             app.scrub_dragging = false;
             app.scrub_preview_ratio = None;
             ui::library::handle_mouse_up(app);
+            ui::queue::handle_mouse_up(&mut app.queue);
             if app.focused_panel == FocusedPanel::Search
                 && let Some(sa) = app.search.handle_mouse_up(&app.logic)
             {
@@ -698,6 +845,7 @@ fn handle_mouse_event(app: &mut App, mouse: &MouseEvent, size: Rect) {
                         app.library.scroll_to_track = Some(track_id);
                         app.toggle_search();
                     }
+                    ui::search::SearchAction::FocusNowPlaying => app.focus_now_playing(),
                 }
             }
         }
@@ -715,6 +863,8 @@ fn handle_mouse_event(app: &mut App, mouse: &MouseEvent, size: Rect) {
                 ui::library::handle_mouse_drag(app, library_area, x, y);
             } else if app.focused_panel == FocusedPanel::Search {
                 app.search.handle_mouse_drag(library_area, x, y);
+            } else if app.focused_panel == FocusedPanel::Queue {
+                ui::queue::handle_mouse_drag(&mut app.queue, &app.logic, library_area, y);
             }
         }
         MouseEventKind::ScrollUp => {
@@ -732,6 +882,12 @@ fn handle_mouse_event(app: &mut App, mouse: &MouseEvent, size: Rect) {
                     &app.logic,
                     -(ui::layout::SCROLL_WHEEL_STEPS as i32),
                 );
+            } else if app.focused_panel == FocusedPanel::Recent {
+                ui::recent::scroll_selection(
+                    &mut app.recent,
+                    &app.logic,
+                    -(ui::layout::SCROLL_WHEEL_STEPS as i32),
+                );
             } else if app.focused_panel == FocusedPanel::Logs {
                 app.logs.scroll_offset = app
                     .logs
@@ -759,6 +915,12 @@ fn handle_mouse_event(app: &mut App, mouse: &MouseEvent, size: Rect) {
                     &app.logic,
                     ui::layout::SCROLL_WHEEL_STEPS as i32,
                 );
+            } else if app.focused_panel == FocusedPanel::Recent {
+                ui::recent::scroll_selection(
+                    &mut app.recent,
+                    &app.logic,
+                    ui::layout::SCROLL_WHEEL_STEPS as i32,
+                );
             } else if app.focused_panel == FocusedPanel::Logs {
                 let log_len = app.logs.log_buffer.len();
                 if log_len > 0 {
@@ -784,7 +946,12 @@ fn handle_help_bar_click(app: &mut App, x: u16) {
     else {
         return;
     };
+    run_action(app, action);
+}
 
+/// Runs a library-level action, as clicked in the help bar or chosen from the
+/// command palette.
+fn run_action(app: &mut App, action: Action) {
     match action {
         Action::Quit => {
             // In settings, "q" closes the panel rather than triggering quit.
@@ -796,7 +963,7 @@ fn handle_help_bar_click(app: &mut App, x: u16) {
             }
         }
         Action::PlayPause => app.logic.toggle_current(),
-        Action::Next => app.logic.next(),
+        Action::Next => app.next(),
         Action::Previous => app.logic.previous(),
         Action::NextGroup => app.logic.next_group(),
         Action::PreviousGroup => app.logic.previous_group(),
@@ -804,6 +971,10 @@ fn handle_help_bar_click(app: &mut App, x: u16) {
         Action::Search => app.toggle_search(),
         Action::Lyrics => app.toggle_lyrics(),
         Action::Queue => app.toggle_queue(),
+        Action::RecentAlbums => app.toggle_recent(),
+        Action::Listeners => app.toggle_listeners(),
+        Action::ServerInfo => app.toggle_server_info(),
+        Action::ListeningStats => app.toggle_listening_stats(),
         Action::Logs => app.toggle_logs(),
         Action::VolumeMode => app.volume_editing = !app.volume_editing,
         Action::Star => {
@@ -822,13 +993,14 @@ fn handle_help_bar_click(app: &mut App, x: u16) {
         }
         Action::SeekForward => app.seek_relative(ui::layout::SEEK_STEP_SECS),
         Action::SeekBackward => app.seek_relative(-ui::layout::SEEK_STEP_SECS),
-        Action::GotoPlaying => {
-            if let Some(track_id) = app.logic.get_playing_track_id() {
-                app.logic.set_scroll_target(&track_id);
-                app.library.scroll_to_track = Some(track_id);
-            }
+        Action::NextLyricLine => ui::lyrics::seek_adjacent_line(&mut app.lyrics, &app.logic, 1),
+        Action::PreviousLyricLine => {
+            ui::lyrics::seek_adjacent_line(&mut app.lyrics, &app.logic, -1)
         }
+        Action::GotoPlaying | Action::FocusNowPlaying => app.focus_now_playing(),
         Action::CyclePlaybackMode(dir) => app.cycle_playback_mode(dir),
+        Action::ToggleShuffle => app.logic.toggle_shuffle(),
+        Action::CycleShuffleScope => app.logic.cycle_shuffle_scope(),
         Action::ToggleSortOrder(dir) => {
             let scroll_target = app.library.selected_track_id().cloned();
             let next = blackbird_client_shared::cycle(
@@ -841,9 +1013,40 @@ fn handle_help_bar_click(app: &mut App, x: u16) {
             app.library.scroll_to_track = scroll_target;
         }
         Action::Settings => app.toggle_settings(),
+        Action::Rescan => app.logic.trigger_server_scan(),
+        Action::AlbumRadio => app.start_album_radio(),
+        Action::Enqueue => app.enqueue_selected(),
+        Action::PlayFrom(scope) => app.play_from_selected(scope),
+        Action::CopyNowPlaying => app.copy_now_playing(),
+        Action::Download => {
+            if let Some(track_id) = app.logic.get_playing_track_id() {
+                app.download_track(&track_id);
+            }
+        }
+        Action::RevealInFileManager => {
+            if let Some(track_id) = app.logic.get_playing_track_id() {
+                app.reveal_track(&track_id);
+            }
+        }
+        Action::ToggleTranscode => app.toggle_transcode(),
+        Action::ToggleInlineLyrics => app.toggle_inline_lyrics(),
+        Action::HideUnstarred => app.toggle_hide_unstarred(),
+        Action::SkipTrack => app.toggle_track_skipped(),
+        Action::CommandPalette => app.open_command_palette(),
+        Action::TrackNumber => app.open_track_number(),
+        Action::TrackGainUp => app.adjust_track_gain(blackbird_client_shared::TRACK_GAIN_STEP_DB),
+        Action::TrackGainDown => {
+            app.adjust_track_gain(-blackbird_client_shared::TRACK_GAIN_STEP_DB)
+        }
         Action::Select if app.focused_panel == FocusedPanel::Library => {
             ui::library::handle_key(app, Action::Select);
         }
+        Action::ClearQueue => app.logic.clear_queue(),
+        Action::MoveQueuedUp | Action::MoveQueuedDown | Action::Dequeue
+            if app.focused_panel == FocusedPanel::Queue =>
+        {
+            ui::queue::handle_key(&mut app.queue, &app.logic, action);
+        }
         Action::Back if app.focused_panel != FocusedPanel::Library => {
             app.focused_panel = FocusedPanel::Library;
         }
@@ -870,6 +1073,9 @@ fn apply_scroll(app: &mut App, scroll_delta: i32) {
         FocusedPanel::Queue => {
             ui::queue::scroll_selection(&mut app.queue, &app.logic, direction * steps as i32);
         }
+        FocusedPanel::Recent => {
+            ui::recent::scroll_selection(&mut app.recent, &app.logic, direction * steps as i32);
+        }
         FocusedPanel::Logs => {
             if direction < 0 {
                 app.logs.scroll_offset = app.logs.scroll_offset.saturating_sub(steps);
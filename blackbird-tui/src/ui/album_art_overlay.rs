@@ -31,7 +31,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, size: Rect) {
     let title_text = format!(" {} ", overlay.title);
 
     let background_color = super::effective_bg(&app.config);
-    let text_color = app.config.style.text_color();
+    let text_color = app.config.style.base.text_color();
 
     let aspect_ratio = app.cover_art_cache.get_aspect_ratio(Some(&cover_art_id));
     let overlay_rect = super::layout::overlay_rect(size, aspect_ratio);
@@ -41,7 +41,6 @@ pub fn draw(frame: &mut Frame, app: &mut App, size: Rect) {
     // Recompute actual art rows based on available height.
     let actual_art_term_rows =
         (overlay_rect.height - super::layout::OVERLAY_BORDER_OVERHEAD) as usize;
-    let actual_art_pixel_rows = actual_art_term_rows * 2;
 
     // Clear the area behind the overlay.
     frame.render_widget(Clear, overlay_rect);
@@ -82,28 +81,79 @@ pub fn draw(frame: &mut Frame, app: &mut App, size: Rect) {
     )]);
     frame.render_widget(Paragraph::new(x_button), x_button_area);
 
-    // Compute art color grid using full-resolution data when available.
-    let (grid, loading) = app.cover_art_cache.get_full_res_art_grid(
-        Some(&cover_art_id),
-        art_cols,
-        actual_art_pixel_rows,
+    // Render art inside the border.
+    let art_x = overlay_rect.x + 1; // inside left border
+    let art_y = overlay_rect.y + 1; // below top border
+    let art_area = Rect::new(art_x, art_y, art_cols as u16, actual_art_term_rows as u16);
+    let loading = draw_art(frame, app, &cover_art_id, art_area);
+
+    // Show loading indicator centered over the art while high-res is computing.
+    if loading {
+        draw_loading_label(frame, overlay_rect, text_color, background_color);
+    }
+}
+
+/// Draws the art hover preview in the bottom-right corner of the library,
+/// once the mouse has rested on some album art. Smaller than the overlay and
+/// without its close button, as it goes away when the mouse moves on.
+pub fn draw_hover_preview(frame: &mut Frame, app: &mut App, library_area: Rect) {
+    let Some(hovered) = app.library.art_hover_preview() else {
+        return;
+    };
+    let Some(preview_rect) = super::layout::art_hover_preview_rect(library_area) else {
+        return;
+    };
+    let cover_art_id = hovered.cover_art_id.clone();
+    let title_text = format!(" {} ", hovered.title);
+
+    let background_color = super::effective_bg(&app.config);
+    let text_color = app.config.style.base.text_color();
+
+    frame.render_widget(Clear, preview_rect);
+    let border_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(text_color))
+        .title(Span::styled(
+            title_text,
+            Style::default().fg(text_color).add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::default().bg(background_color));
+    frame.render_widget(border_block, preview_rect);
+
+    let art_area = Rect::new(
+        preview_rect.x + 1,
+        preview_rect.y + 1,
+        preview_rect.width - 2,
+        preview_rect.height - super::layout::OVERLAY_BORDER_OVERHEAD,
     );
+    if draw_art(frame, app, &cover_art_id, art_area) {
+        draw_loading_label(frame, preview_rect, text_color, background_color);
+    }
+}
+
+/// Draws the full-resolution art into `art_area`, through the graphics
+/// protocol when one is available and as half-block characters otherwise.
+/// Returns true while the full-resolution art is still loading.
+fn draw_art(frame: &mut Frame, app: &mut App, cover_art_id: &CoverArtId, art_area: Rect) -> bool {
+    let background_color = super::effective_bg(&app.config);
+    let art_cols = art_area.width as usize;
+    let art_term_rows = art_area.height as usize;
+
+    // Compute art color grid using full-resolution data when available.
+    let (grid, loading) =
+        app.cover_art_cache
+            .get_full_res_art_grid(Some(cover_art_id), art_cols, art_term_rows * 2);
 
     // Request a graphics-protocol image (Kitty/iTerm2/Sixel/halfblocks).
     // Returns None while computing or when no picker is configured.
     let protocol = app.cover_art_cache.get_protocol(
-        Some(&cover_art_id),
+        Some(cover_art_id),
         Resolution::Full,
-        art_cols as u16,
-        actual_art_term_rows as u16,
+        art_area.width,
+        art_area.height,
     );
 
-    // Render art using half-block characters inside the border.
-    let art_x = overlay_rect.x + 1; // inside left border
-    let art_y = overlay_rect.y + 1; // below top border
-
-    let art_area = Rect::new(art_x, art_y, art_cols as u16, actual_art_term_rows as u16);
-
     if let Some(ref protocol) = protocol {
         // Graphics protocol available — render the actual image.
         // Clear first to erase any previous half-block content.
@@ -111,7 +161,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, size: Rect) {
         frame.render_widget(Image::new(protocol).allow_clipping(true), art_area);
     } else {
         // Fall back to half-block character rendering.
-        for term_row in 0..actual_art_term_rows {
+        for term_row in 0..art_term_rows {
             let color_row_top = term_row * 2;
             let color_row_bot = color_row_top + 1;
 
@@ -131,28 +181,30 @@ pub fn draw(frame: &mut Frame, app: &mut App, size: Rect) {
                 spans.push(Span::styled("\u{2580}", Style::default().fg(fg).bg(bg)));
             }
 
-            let row_rect = Rect::new(art_x, art_y + term_row as u16, art_cols as u16, 1);
+            let row_rect = Rect::new(art_area.x, art_area.y + term_row as u16, art_area.width, 1);
             frame.render_widget(Paragraph::new(Line::from(spans)), row_rect);
         }
     }
 
-    // Show loading indicator centered over the art while high-res is computing.
-    if loading {
-        let label = " Loading\u{2026} ";
-        let label_len = label.len() as u16;
-        if label_len < overlay_rect.width - 2 {
-            let label_x = overlay_rect.x + (overlay_rect.width - label_len) / 2;
-            let label_y = overlay_rect.y + overlay_rect.height / 2;
-            let label_rect = Rect::new(label_x, label_y, label_len, 1);
-            let label_widget = Paragraph::new(Line::from(Span::styled(
-                label,
-                Style::default()
-                    .fg(text_color)
-                    .bg(background_color)
-                    .add_modifier(Modifier::BOLD),
-            )));
-            frame.render_widget(label_widget, label_rect);
-        }
+    loading
+}
+
+/// Draws a loading label centered over the bordered `rect`.
+fn draw_loading_label(frame: &mut Frame, rect: Rect, text_color: Color, background_color: Color) {
+    let label = " Loading\u{2026} ";
+    let label_len = label.len() as u16;
+    if label_len < rect.width - 2 {
+        let label_x = rect.x + (rect.width - label_len) / 2;
+        let label_y = rect.y + rect.height / 2;
+        let label_rect = Rect::new(label_x, label_y, label_len, 1);
+        let label_widget = Paragraph::new(Line::from(Span::styled(
+            label,
+            Style::default()
+                .fg(text_color)
+                .bg(background_color)
+                .add_modifier(Modifier::BOLD),
+        )));
+        frame.render_widget(label_widget, label_rect);
     }
 }
 
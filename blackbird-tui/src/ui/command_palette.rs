@@ -0,0 +1,293 @@
+//! The command palette: a fuzzy-matched list of library actions and setting
+//! toggles, opened with `:`, for running commands without knowing their keys.
+
+use blackbird_client_shared::Direction;
+use blackbird_core as bc;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+use smol_str::SmolStr;
+
+use crate::{app::App, config::Config, keys::Action};
+
+use super::{StyleExt, settings::SettingToggle};
+
+/// Maximum width of the palette, including its border.
+const PALETTE_WIDTH: u16 = 60;
+/// Maximum number of matches shown at once.
+const MAX_VISIBLE_MATCHES: usize = 12;
+
+/// The actions offered by the palette, in the order shown for an empty query.
+const PALETTE_ACTIONS: &[Action] = &[
+    Action::PlayPause,
+    Action::Next,
+    Action::Previous,
+    Action::NextGroup,
+    Action::PreviousGroup,
+    Action::Stop,
+    Action::SeekBackward,
+    Action::SeekForward,
+    Action::PreviousLyricLine,
+    Action::NextLyricLine,
+    Action::Star,
+    Action::SkipTrack,
+    Action::GotoPlaying,
+    Action::TrackNumber,
+    Action::Search,
+    Action::Lyrics,
+    Action::Queue,
+    Action::RecentAlbums,
+    Action::Listeners,
+    Action::Logs,
+    Action::AlbumRadio,
+    Action::Enqueue,
+    Action::PlayFrom(bc::PlayScope::Library),
+    Action::PlayFrom(bc::PlayScope::Album),
+    Action::CopyNowPlaying,
+    Action::Download,
+    Action::RevealInFileManager,
+    Action::HideUnstarred,
+    Action::VolumeMode,
+    Action::TrackGainDown,
+    Action::TrackGainUp,
+    Action::CyclePlaybackMode(Direction::Forward),
+    Action::ToggleShuffle,
+    Action::CycleShuffleScope,
+    Action::ToggleSortOrder(Direction::Forward),
+    Action::Rescan,
+    Action::ServerInfo,
+    Action::ListeningStats,
+    Action::Settings,
+    Action::Quit,
+];
+
+/// What an entry does when run.
+#[derive(Debug, Clone, Copy)]
+pub enum Command {
+    Action(Action),
+    Toggle(SettingToggle),
+}
+
+impl Command {
+    /// Flips the setting of a [`Command::Toggle`]; actions are dispatched by
+    /// the caller.
+    pub fn toggle(&self, config: &mut Config) {
+        if let Command::Toggle(toggle) = self {
+            (toggle.set)(config, !(toggle.get)(config));
+        }
+    }
+}
+
+struct Entry {
+    label: SmolStr,
+    /// The key that runs the entry directly, or a hint for settings.
+    key: SmolStr,
+    command: Command,
+}
+
+pub enum CommandPaletteAction {
+    Close,
+    Run(Command),
+}
+
+pub struct CommandPaletteState {
+    pub query: String,
+    selected: usize,
+    entries: Vec<Entry>,
+    /// Indices into `entries` that match `query`, best match first.
+    matches: Vec<usize>,
+}
+
+impl CommandPaletteState {
+    /// Builds the palette from the current help labels and settings, so
+    /// entries read the same as the help bar.
+    pub fn new(logic: &bc::Logic, config: &Config) -> Self {
+        let actions = PALETTE_ACTIONS.iter().filter_map(|&action| {
+            let (key, label) = action.help_label(logic)?;
+            Some(Entry {
+                label,
+                key,
+                command: Command::Action(action),
+            })
+        });
+        let toggles = super::settings::setting_toggles()
+            .into_iter()
+            .map(|toggle| {
+                let state = if (toggle.get)(config) { "on" } else { "off" };
+                Entry {
+                    label: format!("{}: {state}", toggle.label).into(),
+                    key: "setting".into(),
+                    command: Command::Toggle(toggle),
+                }
+            });
+        let mut palette = Self {
+            query: String::new(),
+            selected: 0,
+            entries: actions.chain(toggles).collect(),
+            matches: vec![],
+        };
+        palette.update_matches();
+        palette
+    }
+
+    pub fn handle_key(&mut self, action: Action) -> Option<CommandPaletteAction> {
+        match action {
+            Action::Back => return Some(CommandPaletteAction::Close),
+            Action::Select => {
+                let &index = self.matches.get(self.selected)?;
+                return Some(CommandPaletteAction::Run(self.entries[index].command));
+            }
+            Action::MoveUp => self.selected = self.selected.saturating_sub(1),
+            Action::MoveDown => {
+                self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1));
+            }
+            Action::DeleteChar => {
+                self.query.pop();
+                self.update_matches();
+            }
+            Action::ClearLine => {
+                self.query.clear();
+                self.update_matches();
+            }
+            Action::Char(c) => {
+                self.query.push(c);
+                self.update_matches();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn update_matches(&mut self) {
+        let mut scored: Vec<(usize, i32)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| Some((index, fuzzy_score(&self.query, &entry.label)?)))
+            .collect();
+        scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        self.matches = scored.into_iter().map(|(index, _)| index).collect();
+        self.selected = 0;
+    }
+}
+
+/// Scores `candidate` as a case-insensitive subsequence match of `query`, or
+/// `None` if it doesn't contain every query character in order. Runs of
+/// consecutive characters and matches at the start of a word score higher.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut start = 0;
+    let mut previous: Option<usize> = None;
+    for q in query.to_lowercase().chars() {
+        let index = start + candidate[start..].iter().position(|&c| c == q)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == index) {
+            score += 3;
+        }
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous = Some(index);
+        start = index + 1;
+    }
+    Some(score)
+}
+
+pub fn draw(frame: &mut Frame, app: &App, size: Rect) {
+    let Some(palette) = &app.command_palette else {
+        return;
+    };
+    let style = &app.config.style.base;
+    let bg = super::effective_bg(&app.config);
+
+    let visible = palette.matches.len().clamp(1, MAX_VISIBLE_MATCHES);
+    // Border (2) + query line (1).
+    let height = (visible as u16 + 3).min(size.height);
+    let width = PALETTE_WIDTH.min(size.width.saturating_sub(4));
+    let x = size.x + size.width.saturating_sub(width) / 2;
+    let y = size.y + size.height.saturating_sub(height) / 2;
+    let rect = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, rect);
+    let block = Block::bordered()
+        .title(" commands ")
+        .border_style(Style::default().fg(style.track_name_playing_color()))
+        .style(Style::default().fg(style.text_color()).bg(bg));
+    let inner = block.inner(rect);
+    frame.render_widget(block, rect);
+    if inner.height == 0 {
+        return;
+    }
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(": ", Style::default().fg(style.track_name_playing_color())),
+        Span::styled(&palette.query, Style::default().fg(style.text_color())),
+        Span::styled(
+            "\u{2588}",
+            Style::default().fg(style.track_name_playing_color()),
+        ),
+    ]));
+    frame.render_widget(input, Rect::new(inner.x, inner.y, inner.width, 1));
+
+    let rows = inner.height.saturating_sub(1) as usize;
+    if palette.matches.is_empty() {
+        if rows > 0 {
+            frame.render_widget(
+                Paragraph::new("no matches")
+                    .style(Style::default().fg(style.track_duration_color())),
+                Rect::new(inner.x, inner.y + 1, inner.width, 1),
+            );
+        }
+        return;
+    }
+
+    // Keep the selected entry in view.
+    let first = (palette.selected + 1).saturating_sub(rows);
+    for (row, &index) in palette.matches.iter().skip(first).take(rows).enumerate() {
+        let entry = &palette.entries[index];
+        let is_selected = first + row == palette.selected;
+        let (marker, label_color) = if is_selected {
+            ("> ", style.track_name_playing_color())
+        } else {
+            ("  ", style.track_name_color())
+        };
+        let label = format!("{marker}{}", entry.label);
+        let padding = (inner.width as usize)
+            .saturating_sub(label.chars().count() + entry.key.chars().count());
+        let line = Line::from(vec![
+            Span::styled(label, Style::default().fg(label_color)),
+            Span::raw(" ".repeat(padding)),
+            Span::styled(
+                entry.key.as_str(),
+                Style::default().fg(style.track_duration_color()),
+            ),
+        ]);
+        frame.render_widget(
+            Paragraph::new(line),
+            Rect::new(inner.x, inner.y + 1 + row as u16, inner.width, 1),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score_matches_subsequences_and_prefers_word_starts() {
+        assert!(fuzzy_score("", "anything").is_some());
+        assert!(fuzzy_score("nxt", "next group").is_some());
+        assert!(fuzzy_score("xn", "next").is_none());
+        assert!(fuzzy_score("NEXT", "next").is_some());
+
+        // "ng" hits both word starts of "next group", but only one of "tuning".
+        assert!(fuzzy_score("ng", "next group") > fuzzy_score("ng", "tuning"));
+        // Consecutive characters beat scattered ones.
+        assert!(fuzzy_score("sta", "star") > fuzzy_score("sta", "settings: a"));
+    }
+}
@@ -7,21 +7,49 @@ pub const SCRUB_BAR_HEIGHT: u16 = 1;
 pub const INLINE_LYRICS_HEIGHT: u16 = 3;
 pub const CONTENT_MIN_HEIGHT: u16 = 3;
 pub const HELP_BAR_HEIGHT: u16 = 1;
+pub const STATUS_LINE_HEIGHT: u16 = 1;
+
+/// The smallest terminal height the main layout fits in: every fixed-height
+/// region, including the status line if shown, plus the minimum content
+/// height. Smaller terminals get a warning instead.
+pub fn min_terminal_height(show_status_line: bool) -> u16 {
+    NOW_PLAYING_HEIGHT
+        + SCRUB_BAR_HEIGHT
+        + CONTENT_MIN_HEIGHT
+        + status_line_height(show_status_line)
+        + HELP_BAR_HEIGHT
+}
+
+/// The smallest terminal width the main layout fits in: the now-playing bar's
+/// album art at its widest, track info, and transport.
+pub const MIN_TERMINAL_WIDTH: u16 =
+    NOW_PLAYING_ART_MAX_WIDTH + TRACK_INFO_MIN_WIDTH + TRANSPORT_WIDTH;
 
 pub struct MainLayout {
     pub now_playing: Rect,
     pub scrub_bar: Rect,
     pub content: Rect,
     pub help_bar: Rect,
+    /// The status line above the help bar, if enabled.
+    pub status_line: Option<Rect>,
+}
+
+fn status_line_height(show_status_line: bool) -> u16 {
+    if show_status_line {
+        STATUS_LINE_HEIGHT
+    } else {
+        0
+    }
 }
 
-pub fn split_main(area: Rect) -> MainLayout {
+pub fn split_main(area: Rect, show_status_line: bool) -> MainLayout {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(NOW_PLAYING_HEIGHT),
             Constraint::Length(SCRUB_BAR_HEIGHT),
             Constraint::Min(CONTENT_MIN_HEIGHT),
+            Constraint::Length(status_line_height(show_status_line)),
             Constraint::Length(HELP_BAR_HEIGHT),
         ])
         .split(area);
@@ -29,7 +57,8 @@ pub fn split_main(area: Rect) -> MainLayout {
         now_playing: chunks[0],
         scrub_bar: chunks[1],
         content: chunks[2],
-        help_bar: chunks[3],
+        status_line: show_status_line.then_some(chunks[3]),
+        help_bar: chunks[4],
     }
 }
 
@@ -52,6 +81,8 @@ pub fn inline_lyrics_overlay(content: Rect) -> Option<Rect> {
 
 pub const TRACK_INFO_MIN_WIDTH: u16 = 20;
 pub const TRANSPORT_WIDTH: u16 = 24;
+/// The widest the album art column gets: the art plus a column either side.
+pub const NOW_PLAYING_ART_MAX_WIDTH: u16 = ART_MAX_COLS + 2;
 
 pub struct NowPlayingLayout {
     pub album_art: Rect,
@@ -104,6 +135,8 @@ pub fn split_scrub_volume(area: Rect) -> ScrubVolumeLayout {
 // ── Album art (now-playing & library) ───────────────────────────────────────
 
 const ART_PIXEL_ROWS: u16 = 4;
+/// The most display columns the art grid is stretched to.
+const ART_MAX_COLS: u16 = 8;
 pub const ART_TERM_ROWS: u16 = 2;
 pub const ART_LEFT_MARGIN: u16 = 1;
 
@@ -112,7 +145,7 @@ pub const ART_LEFT_MARGIN: u16 = 1;
 pub(crate) fn art_cols() -> u16 {
     (ART_PIXEL_ROWS as f64 / half_block_correction())
         .round()
-        .clamp(ART_PIXEL_ROWS as f64, 8.0) as u16
+        .clamp(ART_PIXEL_ROWS as f64, ART_MAX_COLS as f64) as u16
 }
 
 /// Returns the first column past the album art (margin + display columns).
@@ -216,6 +249,23 @@ pub fn overlay_rect(size: Rect, aspect_ratio: f64) -> Rect {
     Rect::new(overlay_x, overlay_y, overlay_width, overlay_height)
 }
 
+/// Computes the rect of the art hover preview, in the bottom-right corner of
+/// `area`: twice the size of the large art, plus a border. Returns `None` if
+/// `area` is too small to hold it beside some of the library.
+pub fn art_hover_preview_rect(area: Rect) -> Option<Rect> {
+    let width = large_art_cols() * 2 + OVERLAY_BORDER_OVERHEAD;
+    let height = (LARGE_ART_TERM_ROWS * 2) as u16 + OVERLAY_BORDER_OVERHEAD;
+    if width * 2 > area.width || height > area.height {
+        return None;
+    }
+    Some(Rect::new(
+        area.x + area.width - width,
+        area.y + area.height - height,
+        width,
+        height,
+    ))
+}
+
 // ── Large album art (BelowAlbum mode) ────────────────────────────────────────
 
 /// Number of terminal rows for the large art displayed beside tracks in
@@ -1,9 +1,16 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
-use blackbird_client_shared::{config::AlbumArtStyle, cover_art_cache::Resolution, library_scroll};
+use blackbird_client_shared::{
+    config::{AlbumArtStyle, MissingArt},
+    cover_art_cache::Resolution,
+    library_scroll,
+};
 use blackbird_core::{
     self as bc, SortOrder,
-    blackbird_state::{CoverArtId, TrackId},
+    blackbird_state::{AlbumId, CoverArtId, TrackId},
     util::seconds_to_hms_string,
 };
 use blackbird_shared::config::ConfigFile as _;
@@ -28,7 +35,7 @@ use crate::{
     ui::album_art_overlay::AlbumArtOverlay,
 };
 
-use super::{StyleExt, string_to_color};
+use super::{StyleExt, missing_art_color, string_to_color};
 
 /// Context for rendering a single `LibraryEntry` into a `ListItem`.
 ///
@@ -50,6 +57,8 @@ pub(crate) struct EntryRenderContext<'a> {
     pub track_name_hovered_color: Color,
     pub track_length_color: Color,
     pub track_duration_color: Color,
+    /// Whether to show play counts as e.g. "1.2k".
+    pub abbreviate_play_counts: bool,
     pub playing_track_id: Option<&'a TrackId>,
     pub selected_index: usize,
     pub underline_index: Option<usize>,
@@ -82,6 +91,8 @@ pub(crate) fn render_library_entry<'a>(
             duration,
             starred,
             cover_art_id,
+            missing_art_color,
+            gain,
             ..
         } => {
             let is_heart_hovered =
@@ -102,19 +113,30 @@ pub(crate) fn render_library_entry<'a>(
                 .map(|d| format!(" +{d}"))
                 .unwrap_or_default();
             let dur_str = seconds_to_hms_string(*duration, false);
+            let right_content = match gain {
+                Some(gain) => format!(" {gain}  {dur_str} "),
+                None => format!(" {dur_str} "),
+            };
 
             match ctx.album_art_style {
                 AlbumArtStyle::LeftOfAlbum => {
-                    let colors = cover_art_id
-                        .as_ref()
-                        .and_then(|id| ctx.art_colors.get(id))
-                        .copied()
-                        .unwrap_or_default();
+                    let colors = match missing_art_color {
+                        Some(color) => QuadrantColors {
+                            colors: [[*color; 4]; 4],
+                        },
+                        None => cover_art_id
+                            .as_ref()
+                            .and_then(|id| ctx.art_colors.get(id))
+                            .copied()
+                            .unwrap_or_default(),
+                    };
+                    // Images are only drawn over groups with art.
+                    let reserve_for_image = ctx.has_image_protocol && missing_art_color.is_none();
 
                     let thumbnail = super::layout::ArtColumn::thumbnail();
                     let mut line1_spans =
                         vec![Span::raw(" ".repeat(thumbnail.left_margin as usize))];
-                    if ctx.has_image_protocol {
+                    if reserve_for_image {
                         // Reserve space for the Image widget overlay.
                         line1_spans.push(Span::raw(" ".repeat(thumbnail.cols as usize)));
                     } else {
@@ -131,7 +153,6 @@ pub(crate) fn render_library_entry<'a>(
                         + album.width()
                         + year_str.width()
                         + added_str.width();
-                    let right_content = format!(" {dur_str} ");
                     let right_width = right_content.width() + 1;
                     let padding_needed = ctx
                         .list_width
@@ -140,7 +161,7 @@ pub(crate) fn render_library_entry<'a>(
 
                     let mut line2_spans =
                         vec![Span::raw(" ".repeat(thumbnail.left_margin as usize))];
-                    if ctx.has_image_protocol {
+                    if reserve_for_image {
                         line2_spans.push(Span::raw(" ".repeat(thumbnail.cols as usize)));
                     } else {
                         line2_spans.extend(super::art_row_spans(&colors, 2, 3));
@@ -179,7 +200,6 @@ pub(crate) fn render_library_entry<'a>(
 
                     let left_content_width =
                         1 + album.width() + year_str.width() + added_str.width();
-                    let right_content = format!(" {dur_str} ");
                     let right_width = right_content.width() + 1;
                     let padding_needed = ctx
                         .list_width
@@ -225,6 +245,7 @@ pub(crate) fn render_library_entry<'a>(
             starred,
             play_count,
             cover_art_id,
+            missing_art_color,
             track_index_in_group,
         } => {
             let is_playing = ctx.playing_track_id == Some(id);
@@ -276,6 +297,7 @@ pub(crate) fn render_library_entry<'a>(
                             &mut left_spans,
                             ctx,
                             cover_art_id.as_ref(),
+                            *missing_art_color,
                             *track_index_in_group,
                         );
                     } else {
@@ -307,7 +329,10 @@ pub(crate) fn render_library_entry<'a>(
             left_width += title.width();
 
             if let Some(pc) = play_count {
-                let pc_str = format!(" {pc}");
+                let pc_str = format!(
+                    " {}",
+                    blackbird_client_shared::format_play_count(*pc, ctx.abbreviate_play_counts)
+                );
                 left_width += pc_str.width();
                 left_spans.push(Span::styled(
                     pc_str,
@@ -356,13 +381,19 @@ pub(crate) fn render_library_entry<'a>(
         }
         LibraryEntry::GroupSpacer {
             cover_art_id,
+            missing_art_color,
             art_row_index,
-            ..
         } => {
             let mut spans: Vec<Span<'_>> = Vec::new();
 
             if *art_row_index < ctx.large_art.rows as usize {
-                large_art_row_spans(&mut spans, ctx, cover_art_id.as_ref(), *art_row_index);
+                large_art_row_spans(
+                    &mut spans,
+                    ctx,
+                    cover_art_id.as_ref(),
+                    *missing_art_color,
+                    *art_row_index,
+                );
             } else {
                 spans.push(Span::raw(" ".repeat(ctx.large_art.total_width() as usize)));
             }
@@ -395,17 +426,24 @@ struct OverlayWindow<'a> {
 /// When an image protocol is available, the art cells are blank — reserving
 /// the exact cells the `SlicedImage` widget is drawn over afterwards (both
 /// derive their geometry from [`EntryRenderContext::large_art`]). Otherwise
-/// the row is rendered as half-block characters from the color grid.
+/// the row is rendered as half-block characters from the color grid. Groups
+/// without art are filled with `missing_art_color` if given.
 fn large_art_row_spans<'a>(
     spans: &mut Vec<Span<'a>>,
     ctx: &EntryRenderContext<'a>,
     cover_art_id: Option<&CoverArtId>,
+    missing_art_color: Option<Color>,
     term_row: usize,
 ) {
     let art = &ctx.large_art;
     spans.push(Span::raw(" ".repeat(art.left_margin as usize)));
 
-    if ctx.has_image_protocol {
+    if let Some(color) = missing_art_color {
+        spans.push(Span::styled(
+            " ".repeat(art.cols as usize),
+            Style::default().bg(color),
+        ));
+    } else if ctx.has_image_protocol {
         // Reserve blank cells for the SlicedImage overlay.
         spans.push(Span::raw(" ".repeat(art.cols as usize)));
     } else {
@@ -475,6 +513,10 @@ pub enum LibraryEntry {
         starred: bool,
         album_id: blackbird_core::blackbird_state::AlbumId,
         cover_art_id: Option<blackbird_core::blackbird_state::CoverArtId>,
+        /// Shown in place of the art when the group has none, if configured.
+        missing_art_color: Option<Color>,
+        /// The group's ReplayGain, if configured to be shown and known.
+        gain: Option<String>,
     },
     Track {
         id: TrackId,
@@ -488,6 +530,8 @@ pub enum LibraryEntry {
         play_count: Option<u64>,
         /// The group's cover art ID (used in `BelowAlbum` mode).
         cover_art_id: Option<CoverArtId>,
+        /// The group's stand-in for missing art (used in `BelowAlbum` mode).
+        missing_art_color: Option<Color>,
         /// 0-based index of this track within its group (used in `BelowAlbum` mode).
         track_index_in_group: usize,
     },
@@ -496,6 +540,8 @@ pub enum LibraryEntry {
     GroupSpacer {
         /// The group's cover art ID (used to render art continuation rows).
         cover_art_id: Option<CoverArtId>,
+        /// The group's stand-in for missing art.
+        missing_art_color: Option<Color>,
         /// The track index within the group this spacer row corresponds to
         /// (i.e., `track_count + spacer_index`), used for art row calculation.
         art_row_index: usize,
@@ -540,9 +586,13 @@ pub(crate) fn assemble_flat_library(
     let mut result = Vec::new();
 
     for (group_index, (header, tracks)) in groups.into_iter().enumerate() {
-        let cover_art_id = match &header {
-            LibraryEntry::GroupHeader { cover_art_id, .. } => cover_art_id.clone(),
-            _ => None,
+        let (cover_art_id, missing_art_color) = match &header {
+            LibraryEntry::GroupHeader {
+                cover_art_id,
+                missing_art_color,
+                ..
+            } => (cover_art_id.clone(), *missing_art_color),
+            _ => (None, None),
         };
         let track_count = tracks.len();
 
@@ -556,6 +606,7 @@ pub(crate) fn assemble_flat_library(
             for si in 0..(super::layout::LARGE_ART_TERM_ROWS - track_count) {
                 result.push(LibraryEntry::GroupSpacer {
                     cover_art_id: cover_art_id.clone(),
+                    missing_art_color,
                     art_row_index: track_count + si,
                 });
             }
@@ -576,6 +627,38 @@ pub fn total_entry_lines(entries: &[LibraryEntry]) -> usize {
     entries.iter().map(LibraryEntry::height).sum()
 }
 
+/// Returns the first line of the entry at `index`.
+fn entry_start_line(entries: &[LibraryEntry], index: usize) -> usize {
+    total_entry_lines(&entries[..index.min(entries.len())])
+}
+
+/// Finds where the track selected at `old_index` in `old` ended up in `new`.
+/// If it was removed, the nearest track in `old` that survived is used
+/// instead, looking below the selection before above it at each distance.
+fn reanchor_index(old: &[LibraryEntry], old_index: usize, new: &[LibraryEntry]) -> Option<usize> {
+    let new_indices: HashMap<&TrackId, usize> = new
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| match entry {
+            LibraryEntry::Track { id, .. } => Some((id, i)),
+            _ => None,
+        })
+        .collect();
+    (0..old.len())
+        .flat_map(|distance| {
+            [
+                old_index.checked_add(distance),
+                old_index.checked_sub(distance),
+            ]
+        })
+        .flatten()
+        .filter(|&i| i < old.len())
+        .find_map(|i| match &old[i] {
+            LibraryEntry::Track { id, .. } => new_indices.get(id).copied(),
+            _ => None,
+        })
+}
+
 /// Returns the entry index whose line span contains `target_line`, if any.
 fn entry_at_line(entries: &[LibraryEntry], target_line: usize) -> Option<usize> {
     let mut current_line = 0usize;
@@ -594,6 +677,9 @@ pub struct LibraryState {
     pub selected_index: usize,
     pub needs_scroll_to_playing: bool,
     pub scroll_to_track: Option<TrackId>,
+    /// Whether the user has moved around the library since the last track
+    /// change, which suspends following the playing track.
+    pub user_scrolled: bool,
 
     /// Shared scroll/drag/inertia mechanism.
     pub viewport: super::scroll::Scroller,
@@ -607,8 +693,17 @@ pub struct LibraryState {
     flat_library_dirty: bool,
     album_art_style: AlbumArtStyle,
     album_spacing: usize,
+    missing_art: MissingArt,
+    show_album_gain: bool,
+    /// The art under the mouse and when it started resting there, for the
+    /// hover preview.
+    art_hover: Option<(AlbumArtOverlay, Instant)>,
 }
 
+/// How long the mouse must rest on album art before its preview appears, so
+/// that sweeping across the library doesn't flicker previews.
+const ART_HOVER_PREVIEW_DELAY: Duration = Duration::from_millis(400);
+
 impl LibraryState {
     pub fn new() -> Self {
         Self {
@@ -616,6 +711,7 @@ impl LibraryState {
             selected_index: 0,
             needs_scroll_to_playing: true,
             scroll_to_track: None,
+            user_scrolled: false,
 
             viewport: super::scroll::Scroller::new(),
 
@@ -626,9 +722,41 @@ impl LibraryState {
             flat_library_dirty: true,
             album_art_style: AlbumArtStyle::default(),
             album_spacing: 1,
+            missing_art: MissingArt::default(),
+            show_album_gain: false,
+            art_hover: None,
+        }
+    }
+
+    /// Updates the art under the mouse, restarting the preview delay when it
+    /// changes.
+    fn set_art_hover(&mut self, hovered: Option<AlbumArtOverlay>) {
+        let unchanged = match (&self.art_hover, &hovered) {
+            (Some((current, _)), Some(hovered)) => current.cover_art_id == hovered.cover_art_id,
+            (None, None) => true,
+            _ => false,
+        };
+        if !unchanged {
+            self.art_hover = hovered.map(|hovered| (hovered, Instant::now()));
         }
     }
 
+    /// The art to preview, once the mouse has rested on it long enough.
+    pub fn art_hover_preview(&self) -> Option<&AlbumArtOverlay> {
+        self.art_hover
+            .as_ref()
+            .filter(|(_, since)| since.elapsed() >= ART_HOVER_PREVIEW_DELAY)
+            .map(|(hovered, _)| hovered)
+    }
+
+    /// Whether the mouse is resting on art whose preview hasn't appeared yet,
+    /// so a redraw is needed to show it.
+    pub fn art_hover_pending(&self) -> bool {
+        self.art_hover
+            .as_ref()
+            .is_some_and(|(_, since)| since.elapsed() < ART_HOVER_PREVIEW_DELAY)
+    }
+
     /// Update the album art style used for spacer entry generation.
     pub fn set_album_art_style(&mut self, style: AlbumArtStyle) {
         if self.album_art_style != style {
@@ -645,6 +773,22 @@ impl LibraryState {
         }
     }
 
+    /// Update what is shown in place of missing album art.
+    pub fn set_missing_art(&mut self, missing_art: MissingArt) {
+        if self.missing_art != missing_art {
+            self.missing_art = missing_art;
+            self.flat_library_dirty = true;
+        }
+    }
+
+    /// Update whether group headers show the group's ReplayGain.
+    pub fn set_show_album_gain(&mut self, show_album_gain: bool) {
+        if self.show_album_gain != show_album_gain {
+            self.show_album_gain = show_album_gain;
+            self.flat_library_dirty = true;
+        }
+    }
+
     /// Marks the flat library cache as dirty, forcing a rebuild on next access.
     pub fn mark_dirty(&mut self) {
         self.flat_library_dirty = true;
@@ -660,6 +804,19 @@ impl LibraryState {
         }
     }
 
+    /// Returns the album of the selected entry: that of the nearest group
+    /// header at or above it.
+    pub fn selected_album_id(&self) -> Option<&AlbumId> {
+        let end = (self.selected_index + 1).min(self.cached_flat_library.len());
+        self.cached_flat_library[..end]
+            .iter()
+            .rev()
+            .find_map(|entry| match entry {
+                LibraryEntry::GroupHeader { album_id, .. } => Some(album_id),
+                _ => None,
+            })
+    }
+
     /// Returns the cached flat library, rebuilding if needed.
     pub fn get_flat_library(&mut self, logic: &bc::Logic) -> &[LibraryEntry] {
         self.ensure_flat_library(logic);
@@ -698,13 +855,23 @@ impl LibraryState {
         let state = logic.get_state();
         let state = state.read().unwrap();
 
-        let groups = state.library.groups.iter().map(|group| {
+        let missing_art = self.missing_art;
+        let show_album_gain = self.show_album_gain;
+        let groups = state.library.shown_groups().iter().map(|group| {
             let created = state
                 .library
                 .albums
                 .get(&group.album_id)
                 .map(|a| a.created.to_string());
 
+            let missing_art_color = missing_art
+                .shows_color(group)
+                .then(|| missing_art_color(&group.artist, &group.album));
+            let gain = show_album_gain
+                .then(|| state.library.group_gain(group))
+                .flatten()
+                .map(|gain| gain.to_string());
+
             let header = LibraryEntry::GroupHeader {
                 artist: group.artist.to_string(),
                 album: group.album.to_string(),
@@ -714,6 +881,8 @@ impl LibraryState {
                 starred: group.starred,
                 album_id: group.album_id.clone(),
                 cover_art_id: group.cover_art_id.clone(),
+                missing_art_color,
+                gain,
             };
 
             let tracks: Vec<_> = group
@@ -733,6 +902,7 @@ impl LibraryState {
                         starred: track.starred,
                         play_count: track.play_count,
                         cover_art_id: group.cover_art_id.clone(),
+                        missing_art_color,
                         track_index_in_group: track_index,
                     })
                 })
@@ -745,6 +915,27 @@ impl LibraryState {
             assemble_flat_library(groups, self.album_art_style, self.album_spacing);
     }
 
+    /// Rebuilds the flat library after the library was replaced or updated
+    /// underneath it, so that the selected track stays selected and on the
+    /// same screen row instead of the selection keeping its now-stale index.
+    pub fn reanchor_after_refresh(&mut self, logic: &bc::Logic) {
+        let old = std::mem::take(&mut self.cached_flat_library);
+        self.rebuild_flat_library(logic);
+        self.flat_library_dirty = false;
+        let Some(index) = reanchor_index(&old, self.selected_index, &self.cached_flat_library)
+        else {
+            return;
+        };
+
+        let screen_line =
+            entry_start_line(&old, self.selected_index).saturating_sub(self.viewport.line);
+        self.selected_index = index;
+        let total_lines = total_entry_lines(&self.cached_flat_library);
+        self.viewport.line = entry_start_line(&self.cached_flat_library, index)
+            .saturating_sub(screen_line)
+            .min(self.viewport.max_line(total_lines));
+    }
+
     /// Finds the flat index for a given track in the library.
     pub fn find_flat_index_for_track(
         &self,
@@ -947,9 +1138,14 @@ fn draw_connection_error(
                 Style::default().fg(accent).add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                " to open settings and check your server configuration.",
+                " to open settings and check your server configuration, or ",
                 Style::default().fg(dim),
             ),
+            Span::styled(
+                "Enter",
+                Style::default().fg(accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" to retry.", Style::default().fg(dim)),
         ]),
         Line::from(Span::styled(
             format!("Config file: {config_path_str}"),
@@ -964,19 +1160,51 @@ fn draw_connection_error(
     frame.render_widget(paragraph, area);
 }
 
+/// Draws a centered message when the server has no tracks, offering to
+/// retry the fetch.
+fn draw_empty_library(
+    frame: &mut Frame,
+    style: &blackbird_client_shared::style::Style,
+    area: Rect,
+) {
+    let accent = style.track_name_playing_color();
+    let dim = style.track_duration_color();
+
+    let lines = vec![
+        Line::from(Span::styled(
+            blackbird_client_shared::EMPTY_LIBRARY_MESSAGE,
+            Style::default().fg(style.text_color()),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Press ", Style::default().fg(dim)),
+            Span::styled(
+                "Enter",
+                Style::default().fg(accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" to retry.", Style::default().fg(dim)),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .centered();
+    frame.render_widget(paragraph, area);
+}
+
 pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     // Extract style colors upfront to avoid borrow conflicts later.
     let background_color = super::effective_bg(&app.config);
-    let text_color = app.config.style.text_color();
-    let album_color = app.config.style.album_color();
-    let album_year_color = app.config.style.album_year_color();
-    let album_length_color = app.config.style.album_length_color();
-    let track_number_color = app.config.style.track_number_color();
-    let track_name_color = app.config.style.track_name_color();
-    let track_name_playing_color = app.config.style.track_name_playing_color();
-    let track_length_color = app.config.style.track_length_color();
-    let track_duration_color = app.config.style.track_duration_color();
-    let track_name_hovered_color = app.config.style.track_name_hovered_color();
+    let text_color = app.config.style.base.text_color();
+    let album_color = app.config.style.base.album_color();
+    let album_year_color = app.config.style.base.album_year_color();
+    let album_length_color = app.config.style.base.album_length_color();
+    let track_number_color = app.config.style.base.track_number_color();
+    let track_name_color = app.config.style.base.track_name_color();
+    let track_name_playing_color = app.config.style.base.track_name_playing_color();
+    let track_length_color = app.config.style.base.track_length_color();
+    let track_duration_color = app.config.style.base.track_duration_color();
+    let track_name_hovered_color = app.config.style.base.track_name_hovered_color();
 
     let has_loaded = app.logic.has_loaded_all_tracks();
 
@@ -986,7 +1214,18 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     if !has_loaded {
         // Check if the initial fetch failed (server unreachable, auth error, etc.).
         if let Some(bc::AppStateError::InitialFetchFailed { ref error }) = app.logic.get_error() {
-            draw_connection_error(frame, &app.config.style, error, inner);
+            draw_connection_error(frame, &app.config.style.base, error, inner);
+            return;
+        }
+        // The reauth prompt may have been dismissed, so the rejection is
+        // shown here too.
+        if app.logic.has_rejected_credentials() {
+            draw_connection_error(
+                frame,
+                &app.config.style.base,
+                "The server rejected the credentials.",
+                inner,
+            );
             return;
         }
 
@@ -998,7 +1237,21 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             .library
             .track_ids
             .len();
-        super::loading::draw(frame, app.tick_count, &app.config.style, track_count, inner);
+        super::loading::draw(
+            frame,
+            app.tick_count,
+            &app.config.style.base,
+            app.config.layout.loading_style,
+            app.config.layout.base.wants_reduced_motion(),
+            track_count,
+            app.logic.get_fetch_progress(),
+            inner,
+        );
+        return;
+    }
+
+    if app.logic.is_library_empty() {
+        draw_empty_library(frame, &app.config.style.base, inner);
         return;
     }
 
@@ -1013,6 +1266,10 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     app.library.set_album_art_style(album_art_style);
     app.library
         .set_album_spacing(app.config.layout.base.album_spacing);
+    app.library
+        .set_missing_art(app.config.layout.base.missing_art);
+    app.library
+        .set_show_album_gain(app.config.layout.base.show_album_gain);
 
     if app.library.flat_library().is_empty() {
         let empty =
@@ -1050,6 +1307,13 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     // During a content drag, keep the underline on the drag-selected row;
     // otherwise fall back to the normal hover detection.
     let underline_index = app.library.drag_selected_index.or(hovered_entry_index);
+    // Track the art under the mouse for the hover preview.
+    let hovered_art = if app.config.layout.art_hover_preview && app.album_art_overlay.is_none() {
+        compute_hovered_art(app, area)
+    } else {
+        None
+    };
+    app.library.set_art_hover(hovered_art);
 
     // Now take the immutable borrow on entries for the rest of rendering.
     let entries = app.library.flat_library();
@@ -1189,6 +1453,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
         track_name_hovered_color,
         track_length_color,
         track_duration_color,
+        abbreviate_play_counts: app.config.layout.base.abbreviate_play_counts,
         playing_track_id: playing_track_id.as_ref(),
         selected_index,
         underline_index,
@@ -1528,6 +1793,61 @@ fn compute_hovered_entry_index(app: &App, area: Rect) -> Option<usize> {
     None
 }
 
+/// Computes which album art is under the mouse, if any, as the overlay that
+/// clicking it would open.
+/// The caller must ensure the flat library cache is fresh before calling this.
+fn compute_hovered_art(app: &App, area: Rect) -> Option<AlbumArtOverlay> {
+    // Suppress hover when the playback mode dropdown is covering the library.
+    if app.playback_mode_dropdown {
+        return None;
+    }
+
+    let (mx, my) = app.mouse_position?;
+
+    // Must be within library area.
+    if my < area.y || my >= area.y + area.height || mx < area.x || mx >= area.x + area.width {
+        return None;
+    }
+
+    let scroll_offset = app.library.scroll_offset;
+    let album_art_style = app.config.layout.base.album_art_style;
+    let entries = app.library.flat_library();
+
+    let inner_y = my.saturating_sub(area.y) as usize;
+
+    let mut line = 0usize;
+    for (i, entry) in entries.iter().enumerate().skip(scroll_offset) {
+        let h = entry.height();
+
+        if inner_y >= line && inner_y < line + h {
+            if is_over_below_album_art(album_art_style, mx, area, entry) {
+                return Some(AlbumArtOverlay {
+                    cover_art_id: entry.cover_art_id()?.clone(),
+                    title: find_group_title_for_entry(entries, i),
+                });
+            }
+            return match entry {
+                LibraryEntry::GroupHeader {
+                    artist,
+                    album,
+                    cover_art_id: Some(cover_art_id),
+                    ..
+                } if album_art_style == AlbumArtStyle::LeftOfAlbum
+                    && mx < area.x + super::layout::art_end_col() =>
+                {
+                    Some(AlbumArtOverlay {
+                        cover_art_id: cover_art_id.clone(),
+                        title: format!("{artist} \u{2013} {album}"),
+                    })
+                }
+                _ => None,
+            };
+        }
+        line += h;
+    }
+    None
+}
+
 /// Renders a combined scrollbar + library scroll indicator on the rightmost column.
 ///
 /// The indicator shows different labels based on sort order:
@@ -1674,16 +1994,29 @@ fn render_scrollbar_with_library_indicator(
 pub fn handle_key(app: &mut App, action: Action) {
     app.library.cancel_inertia(&app.logic);
     let entries_len = app.library.flat_library_len();
+    if matches!(
+        action,
+        Action::MoveUp
+            | Action::MoveDown
+            | Action::PageUp
+            | Action::PageDown
+            | Action::GotoTop
+            | Action::GotoBottom
+    ) {
+        app.library.user_scrolled = true;
+    }
 
     match action {
         Action::Quit => app.quit_confirming = true,
         Action::PlayPause => app.logic.toggle_current(),
-        Action::Next => app.logic.next(),
+        Action::Next => app.next(),
         Action::Previous => app.logic.previous(),
         Action::NextGroup => app.logic.next_group(),
         Action::PreviousGroup => app.logic.previous_group(),
         Action::Stop => app.logic.stop_current(),
         Action::CyclePlaybackMode(dir) => app.cycle_playback_mode(dir),
+        Action::ToggleShuffle => app.logic.toggle_shuffle(),
+        Action::CycleShuffleScope => app.logic.cycle_shuffle_scope(),
         Action::ToggleSortOrder(dir) => {
             let scroll_target = app.library.selected_track_id().cloned();
             let next = blackbird_client_shared::cycle(
@@ -1700,15 +2033,59 @@ pub fn handle_key(app: &mut App, action: Action) {
         Action::Lyrics => app.toggle_lyrics(),
         Action::Logs => app.toggle_logs(),
         Action::Queue => app.toggle_queue(),
+        Action::RecentAlbums => app.toggle_recent(),
+        Action::Listeners => app.toggle_listeners(),
+        Action::ServerInfo => app.toggle_server_info(),
+        Action::ListeningStats => app.toggle_listening_stats(),
         Action::Settings => app.toggle_settings(),
-        Action::VolumeMode => app.volume_editing = true,
-        Action::GotoPlaying => {
-            if let Some(track_id) = app.logic.get_playing_track_id() {
-                app.library.scroll_to_track = Some(track_id);
+        Action::CommandPalette => app.open_command_palette(),
+        Action::TrackNumber => app.open_track_number(),
+        Action::TrackGainUp => app.adjust_track_gain(blackbird_client_shared::TRACK_GAIN_STEP_DB),
+        Action::TrackGainDown => {
+            app.adjust_track_gain(-blackbird_client_shared::TRACK_GAIN_STEP_DB)
+        }
+        Action::Rescan => app.logic.trigger_server_scan(),
+        Action::AlbumRadio => app.start_album_radio(),
+        Action::Enqueue => app.enqueue_selected(),
+        Action::PlayFrom(scope) => app.play_from_selected(scope),
+        Action::CopyNowPlaying => app.copy_now_playing(),
+        Action::Download => {
+            // Download the selected track, or the playing one if a header is
+            // selected.
+            let track_id = app
+                .library
+                .selected_track_id()
+                .cloned()
+                .or_else(|| app.logic.get_playing_track_id());
+            if let Some(track_id) = track_id {
+                app.download_track(&track_id);
             }
         }
+        Action::RevealInFileManager => {
+            let track_id = app
+                .library
+                .selected_track_id()
+                .cloned()
+                .or_else(|| app.logic.get_playing_track_id());
+            if let Some(track_id) = track_id {
+                app.reveal_track(&track_id);
+            }
+        }
+        Action::ToggleTranscode => app.toggle_transcode(),
+        Action::ToggleInlineLyrics => app.toggle_inline_lyrics(),
+        Action::HideUnstarred => app.toggle_hide_unstarred(),
+        Action::SkipTrack => app.toggle_track_skipped(),
+        Action::VolumeMode => app.volume_editing = true,
+        Action::GotoPlaying => app.focus_now_playing(),
         Action::SeekBackward => app.seek_relative(-super::layout::SEEK_STEP_SECS),
         Action::SeekForward => app.seek_relative(super::layout::SEEK_STEP_SECS),
+        Action::NextLyricLine => super::lyrics::seek_adjacent_line(&mut app.lyrics, &app.logic, 1),
+        Action::PreviousLyricLine => {
+            super::lyrics::seek_adjacent_line(&mut app.lyrics, &app.logic, -1)
+        }
+        Action::SeekTenths(tenths) if app.config.playback.seek_with_digits => {
+            app.logic.seek_fraction(f32::from(tenths) / 10.0);
+        }
         Action::Star => {
             if let Some(track_id) = app.logic.get_playing_track_id() {
                 let state = app.logic.get_state();
@@ -1820,12 +2197,22 @@ pub fn handle_key(app: &mut App, action: Action) {
                 }
             }
         }
+        Action::Select
+            if matches!(
+                app.logic.get_error(),
+                Some(bc::AppStateError::InitialFetchFailed { .. })
+            ) || (!app.logic.has_loaded_all_tracks()
+                && app.logic.has_rejected_credentials()) =>
+        {
+            app.logic.reconfigure_server(&app.config.server);
+        }
+        Action::Select if app.logic.is_library_empty() => app.logic.refresh_library(),
         Action::Select => {
             let selected = app.library.selected_index;
-            if let Some(LibraryEntry::Track { id, .. }) =
-                app.library.get_library_entry(&app.logic, selected)
-            {
-                app.logic.request_play_track(&id);
+            match app.library.get_library_entry(&app.logic, selected) {
+                Some(LibraryEntry::Track { id, .. }) => app.logic.request_play_track(&id),
+                Some(LibraryEntry::GroupHeader { album_id, .. }) => app.logic.play_group(&album_id),
+                _ => {}
             }
         }
         _ => {}
@@ -1946,7 +2333,7 @@ pub fn handle_mouse_click(app: &mut App, library_area: Rect, x: u16, y: u16) {
         LibraryEntry::GroupSpacer { .. } | LibraryEntry::AlbumGap => {
             // Spacers and gaps can't be clicked to play, but should allow drag-scrolling.
             // Setting click_pending with the index is safe because
-            // handle_mouse_up only plays Track entries.
+            // handle_mouse_up only plays Track and GroupHeader entries.
             app.library.click_pending = Some((x, y, index));
             app.library.viewport.dragging = false;
             app.library.viewport.drag_last_y = Some(y);
@@ -1982,6 +2369,7 @@ pub fn handle_mouse_drag(app: &mut App, library_area: Rect, x: u16, y: u16) -> b
         );
         app.library.snap_cursor_to_viewport_center();
         app.library.click_pending = None;
+        app.library.user_scrolled = true;
         return true;
     }
 
@@ -1999,6 +2387,7 @@ pub fn handle_mouse_drag(app: &mut App, library_area: Rect, x: u16, y: u16) -> b
         );
         app.library.snap_cursor_to_viewport_center();
         app.library.click_pending = None;
+        app.library.user_scrolled = true;
         return true;
     }
 
@@ -2032,6 +2421,7 @@ pub fn handle_mouse_drag(app: &mut App, library_area: Rect, x: u16, y: u16) -> b
                 app.library.drag_selected_index = Some(idx);
             }
         }
+        app.library.user_scrolled = true;
         return true;
     }
 
@@ -2042,11 +2432,16 @@ pub fn handle_mouse_drag(app: &mut App, library_area: Rect, x: u16, y: u16) -> b
 pub fn handle_mouse_up(app: &mut App) {
     if let Some((_cx, _cy, index)) = app.library.click_pending.take()
         && !app.library.viewport.dragging
-        && let Some(LibraryEntry::Track { id, .. }) =
-            app.library.get_library_entry(&app.logic, index)
     {
-        app.library.selected_index = index;
-        app.logic.request_play_track(&id);
+        match app.library.get_library_entry(&app.logic, index) {
+            Some(LibraryEntry::Track { id, .. }) => {
+                app.library.selected_index = index;
+                app.logic.request_play_track(&id);
+            }
+            // Clicking an album's header plays the album.
+            Some(LibraryEntry::GroupHeader { album_id, .. }) => app.logic.play_group(&album_id),
+            _ => {}
+        }
     }
 
     match app.library.viewport.end_drag() {
@@ -2060,6 +2455,7 @@ pub fn handle_mouse_up(app: &mut App) {
 
 /// Handle scroll wheel in the library. `direction` is -1 for up, 1 for down.
 pub fn handle_scroll(app: &mut App, direction: i32, steps: usize) {
+    app.library.user_scrolled = true;
     app.library.cancel_inertia(&app.logic);
     let entries = app.library.get_flat_library(&app.logic);
     let total_lines = total_entry_lines(entries);
@@ -2071,6 +2467,7 @@ pub fn handle_scroll(app: &mut App, direction: i32, steps: usize) {
 
 /// Scroll library to a position based on Y coordinate (for scrollbar dragging).
 pub fn scroll_to_y(app: &mut App, total_lines: usize, library_area: Rect, y: u16) {
+    app.library.user_scrolled = true;
     app.library
         .viewport
         .apply_scrollbar_drag(y, total_lines, library_area.y, library_area.height);
@@ -2094,6 +2491,8 @@ mod tests {
             starred: false,
             album_id: blackbird_core::blackbird_state::AlbumId(id.into()),
             cover_art_id: Some(CoverArtId(id.into())),
+            missing_art_color: None,
+            gain: None,
         }
     }
 
@@ -2109,6 +2508,7 @@ mod tests {
             starred: false,
             play_count: None,
             cover_art_id: Some(CoverArtId(id.into())),
+            missing_art_color: None,
             track_index_in_group: index,
         }
     }
@@ -2219,4 +2619,26 @@ mod tests {
 
         assert_eq!(art_rows_after_render(&entries, item_offset, 4), vec![0, 1]);
     }
+
+    #[test]
+    fn test_reanchor_follows_selected_track_or_its_nearest_survivor() {
+        let old: Vec<_> = [test_entries("a"), test_entries("b")].concat();
+        // "a-2" is at index 3 in the old library.
+        let selected = 3;
+
+        // A new album sorted in front shifts everything down.
+        let new: Vec<_> = [test_entries("c"), old.clone()].concat();
+        assert_eq!(reanchor_index(&old, selected, &new), Some(10));
+
+        // With the track removed, the one below it takes its place.
+        let mut removed = old.clone();
+        removed.remove(selected);
+        assert_eq!(reanchor_index(&old, selected, &removed), Some(3));
+
+        // With the rest of its album gone, the nearest survivor is above it.
+        let only_first = vec![test_header("a"), test_track("a", 0)];
+        assert_eq!(reanchor_index(&old, selected, &only_first), Some(1));
+
+        assert_eq!(reanchor_index(&old, selected, &[]), None);
+    }
 }
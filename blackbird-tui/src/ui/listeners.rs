@@ -0,0 +1,78 @@
+use blackbird_client_shared::{listeners, style as shared_style};
+use blackbird_core as bc;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::keys::Action;
+
+use super::StyleExt;
+
+pub enum ListenersAction {
+    ToggleListeners,
+    Quit,
+}
+
+pub fn draw(frame: &mut Frame, style: &shared_style::Style, logic: &bc::Logic, area: Rect) {
+    let block = Block::default()
+        .title(" Listening on the server ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(style.album_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = listeners::rows(
+        &logic.get_server_now_playing(),
+        &logic.get_server_username(),
+    );
+
+    let mut lines: Vec<Line> = rows
+        .iter()
+        .map(|row| {
+            let username = if row.is_you {
+                format!("{} (you)", row.username)
+            } else {
+                row.username.clone()
+            };
+            Line::from(vec![
+                Span::styled(
+                    username,
+                    Style::default()
+                        .fg(style.album_color())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" "),
+                Span::styled(row.track.clone(), Style::default().fg(style.text_color())),
+                Span::styled(
+                    format!(" [{}]", row.detail),
+                    Style::default().fg(style.track_duration_color()),
+                ),
+            ])
+        })
+        .collect();
+
+    if let Some(message) = listeners::status_message(&rows) {
+        if !lines.is_empty() {
+            lines.push(Line::default());
+        }
+        lines.push(Line::styled(
+            message,
+            Style::default().fg(style.track_duration_color()),
+        ));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+pub fn handle_key(action: Action) -> Option<ListenersAction> {
+    match action {
+        Action::Back => Some(ListenersAction::ToggleListeners),
+        Action::Quit => Some(ListenersAction::Quit),
+        _ => None,
+    }
+}
@@ -0,0 +1,85 @@
+use blackbird_client_shared::{listening_stats, style as shared_style};
+use blackbird_core as bc;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::keys::Action;
+
+use super::StyleExt;
+
+pub enum ListeningStatsAction {
+    ToggleListeningStats,
+    Quit,
+}
+
+pub fn draw(
+    frame: &mut Frame,
+    style: &shared_style::Style,
+    stats: Option<&bc::ListeningStats>,
+    area: Rect,
+) {
+    let block = Block::default()
+        .title(" Listening stats ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(style.album_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let stats = match stats {
+        Some(stats) if stats.total_plays > 0 => stats,
+        stats => {
+            let message = if stats.is_none() {
+                listening_stats::LOADING_MESSAGE
+            } else {
+                listening_stats::EMPTY_MESSAGE
+            };
+            frame.render_widget(
+                Paragraph::new(Line::styled(
+                    message,
+                    Style::default().fg(style.track_duration_color()),
+                )),
+                inner,
+            );
+            return;
+        }
+    };
+
+    let heading = Style::default()
+        .fg(style.album_color())
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(style.text_color());
+
+    let rows = listening_stats::summary_rows(stats);
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(format!("{label:<label_width$}  "), heading),
+                Span::styled(value, text),
+            ])
+        })
+        .collect();
+
+    for (title, entries) in listening_stats::top_sections(stats) {
+        lines.push(Line::default());
+        lines.push(Line::styled(title, heading));
+        lines.extend(entries.into_iter().map(|entry| Line::styled(entry, text)));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+pub fn handle_key(action: Action) -> Option<ListeningStatsAction> {
+    match action {
+        Action::Back => Some(ListeningStatsAction::ToggleListeningStats),
+        Action::Quit => Some(ListeningStatsAction::Quit),
+        _ => None,
+    }
+}
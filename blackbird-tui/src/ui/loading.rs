@@ -2,7 +2,11 @@
 //!
 //! Renders a flock of small bird glyphs drifting in a wave pattern,
 //! with the "blackbird" title and track-count status centered below.
+//! [`LoadingStyle`] can swap the animation for plain fetch progress, as does
+//! reduced motion.
 
+use blackbird_client_shared::accessibility::loading_progress_text;
+use blackbird_core::blackbird_state::FetchProgress;
 use ratatui::{
     Frame,
     buffer::Buffer,
@@ -13,6 +17,7 @@ use ratatui::{
 };
 
 use super::StyleExt;
+use crate::config::LoadingStyle;
 
 /// Bird glyph: modifier letter voiceless (U+02EC), a soft bird-like silhouette.
 const BIRD: char = '\u{02EC}';
@@ -43,17 +48,47 @@ const FLOCK_HEIGHT: u16 = 5;
 /// Total height of the loading display: flock + blank line + title + status.
 const TOTAL_HEIGHT: u16 = FLOCK_HEIGHT + 1 + 1 + 1;
 
-/// Draws the animated loading screen centered in `area`.
+/// Draws the loading screen in `loading_style` centered in `area`. With
+/// `reduced_motion`, nothing on it animates.
+#[allow(clippy::too_many_arguments)]
 pub fn draw(
     frame: &mut Frame,
     tick_count: u64,
     style: &blackbird_client_shared::style::Style,
+    loading_style: LoadingStyle,
+    reduced_motion: bool,
     track_count: usize,
+    progress: Option<FetchProgress>,
     area: Rect,
 ) {
+    if reduced_motion {
+        match loading_style {
+            LoadingStyle::Animation | LoadingStyle::Text => draw_text(frame, style, progress, area),
+            LoadingStyle::Minimal => {
+                let text = loading_progress_text(progress);
+                let paragraph =
+                    Paragraph::new(text).style(Style::default().fg(style.track_duration_color()));
+                frame.render_widget(paragraph, area);
+            }
+        }
+        return;
+    }
+
+    match loading_style {
+        LoadingStyle::Animation => {}
+        LoadingStyle::Text => {
+            draw_text(frame, style, progress, area);
+            return;
+        }
+        LoadingStyle::Minimal => {
+            draw_minimal(frame, style, track_count, None, area);
+            return;
+        }
+    }
+
     if area.width < 4 || area.height < TOTAL_HEIGHT {
         // Area too small for the animation; fall back to simple text.
-        draw_minimal(frame, style, track_count, tick_count, area);
+        draw_minimal(frame, style, track_count, Some(tick_count), area);
         return;
     }
 
@@ -84,7 +119,7 @@ pub fn draw(
     let status_y = title_y + 1;
     if status_y < area.y + area.height {
         let status_area = Rect::new(area.x, status_y, area.width, 1);
-        let status_text = loading_status_text(track_count, tick_count);
+        let status_text = loading_status_text(track_count, Some(tick_count));
         let status = Paragraph::new(Line::from(Span::styled(
             status_text,
             Style::default().fg(dim),
@@ -128,11 +163,11 @@ fn draw_flock(
     }
 }
 
-/// Generates the status text with animated dots or a track count.
-/// The result is padded to a fixed width so centered text doesn't jitter
-/// as the dot count cycles.
-fn loading_status_text(track_count: usize, tick_count: u64) -> String {
-    let dot_count = (tick_count / 5 % 4 + 1) as usize;
+/// Generates the status text with a track count, and dots that cycle with
+/// `tick_count` or hold still without it. The result is padded to a fixed
+/// width so centered text doesn't jitter as the dot count cycles.
+fn loading_status_text(track_count: usize, tick_count: Option<u64>) -> String {
+    let dot_count = tick_count.map_or(3, |tick_count| (tick_count / 5 % 4 + 1) as usize);
     let dots = ".".repeat(dot_count);
     let pad = " ".repeat(4 - dot_count);
     if track_count > 0 {
@@ -142,12 +177,61 @@ fn loading_status_text(track_count: usize, tick_count: u64) -> String {
     }
 }
 
-/// Minimal fallback when the area is too small for the full animation.
+/// Draws the title and the fetch phase and counts, centered in `area`.
+fn draw_text(
+    frame: &mut Frame,
+    style: &blackbird_client_shared::style::Style,
+    progress: Option<FetchProgress>,
+    area: Rect,
+) {
+    let accent = style.track_name_playing_color();
+    let dim = style.track_duration_color();
+
+    let mut lines = vec![Line::from(Span::styled(
+        "blackbird",
+        Style::default().fg(accent).add_modifier(Modifier::BOLD),
+    ))];
+    lines.extend(
+        progress_lines(progress)
+            .into_iter()
+            .map(|line| Line::from(Span::styled(line, Style::default().fg(dim)))),
+    );
+
+    let height = (lines.len() as u16).min(area.height);
+    let top_y = area.y + area.height.saturating_sub(height) / 2;
+    let text_area = Rect::new(area.x, top_y, area.width, height);
+    frame.render_widget(Paragraph::new(lines).centered(), text_area);
+}
+
+/// The fetch phase, followed by its counts once any have arrived.
+fn progress_lines(progress: Option<FetchProgress>) -> Vec<String> {
+    match progress {
+        None => vec!["Connecting".into()],
+        Some(p) if p.tracks_fetched == 0 => vec![
+            "Fetching albums".into(),
+            format!(
+                "{} albums, {} tracks expected",
+                p.albums_fetched, p.expected_tracks
+            ),
+        ],
+        Some(p) => vec![
+            "Fetching tracks".into(),
+            format!(
+                "{}/{} tracks from {} albums",
+                p.tracks_fetched, p.expected_tracks, p.albums_fetched
+            ),
+        ],
+    }
+}
+
+/// A single status line, for the minimal style and as the fallback when the
+/// area is too small for the full animation. It only animates when given
+/// `tick_count`.
 fn draw_minimal(
     frame: &mut Frame,
     style: &blackbird_client_shared::style::Style,
     track_count: usize,
-    tick_count: u64,
+    tick_count: Option<u64>,
     area: Rect,
 ) {
     let dim = style.track_duration_color();
@@ -155,3 +239,40 @@ fn draw_minimal(
     let paragraph = Paragraph::new(text).style(Style::default().fg(dim));
     frame.render_widget(paragraph, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_lines_follow_the_fetch_phase() {
+        assert_eq!(progress_lines(None), ["Connecting"]);
+
+        let mut progress = FetchProgress {
+            albums_fetched: 20,
+            expected_tracks: 240,
+            ..Default::default()
+        };
+        assert_eq!(
+            progress_lines(Some(progress)),
+            ["Fetching albums", "20 albums, 240 tracks expected"]
+        );
+
+        progress.tracks_fetched = 100;
+        assert_eq!(
+            progress_lines(Some(progress)),
+            ["Fetching tracks", "100/240 tracks from 20 albums"]
+        );
+    }
+
+    #[test]
+    fn status_text_only_animates_with_a_tick_count() {
+        assert_eq!(loading_status_text(0, Some(0)), "loading.   ");
+        assert_eq!(loading_status_text(0, Some(5)), "loading..  ");
+        assert_eq!(loading_status_text(0, None), "loading... ");
+        assert_eq!(
+            loading_status_text(10, None),
+            "10 tracks loaded, scanning... "
+        );
+    }
+}
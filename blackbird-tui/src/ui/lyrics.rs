@@ -18,6 +18,8 @@ pub enum LyricsAction {
     ToggleLyrics,
     Quit,
     SeekRelative(i64),
+    FocusNowPlaying,
+    Next,
 }
 
 /// TUI-specific lyrics view state wrapping the shared data state.
@@ -193,14 +195,17 @@ pub fn handle_key(
             super::layout::PAGE_SCROLL_SIZE as i32,
         ),
         Action::Select => seek_to_selected(lyrics, logic),
+        Action::GotoPlaying => return Some(LyricsAction::FocusNowPlaying),
         Action::SeekForward => {
             return Some(LyricsAction::SeekRelative(super::layout::SEEK_STEP_SECS));
         }
         Action::SeekBackward => {
             return Some(LyricsAction::SeekRelative(-super::layout::SEEK_STEP_SECS));
         }
+        Action::NextLyricLine => seek_adjacent_line(lyrics, logic, 1),
+        Action::PreviousLyricLine => seek_adjacent_line(lyrics, logic, -1),
         Action::PlayPause => logic.toggle_current(),
-        Action::Next => logic.next(),
+        Action::Next => return Some(LyricsAction::Next),
         Action::Previous => logic.previous(),
         Action::NextGroup => logic.next_group(),
         Action::PreviousGroup => logic.previous_group(),
@@ -306,6 +311,19 @@ pub fn seek_to_selected(lyrics: &mut LyricsViewState, logic: &bc::Logic) {
     }
 }
 
+/// Seek playback to the start of the synced lyrics line `direction` lines
+/// from the current one, if there is one.
+pub fn seek_adjacent_line(lyrics: &mut LyricsViewState, logic: &bc::Logic, direction: isize) {
+    if let Some(position) = lyrics.shared.adjacent_line_start(
+        logic.get_playing_track_id().as_ref(),
+        logic.get_playing_position(),
+        direction,
+    ) {
+        logic.seek_current(position);
+        lyrics.selected_index = None;
+    }
+}
+
 /// Seek playback to the timestamp of a lyrics line at the given index.
 pub fn seek_to_line(lyrics: &mut LyricsViewState, logic: &bc::Logic, line_index: usize) {
     let Some(lyrics_data) = &lyrics.shared.data else {
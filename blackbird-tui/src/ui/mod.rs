@@ -1,22 +1,29 @@
 pub mod album_art_overlay;
+pub(crate) mod command_palette;
 pub(crate) mod layout;
 pub(crate) mod library;
+pub(crate) mod listeners;
+pub(crate) mod listening_stats;
 pub(crate) mod loading;
 pub(crate) mod logs;
 pub(crate) mod lyrics;
 pub(crate) mod now_playing;
 pub(crate) mod queue;
+pub(crate) mod recent;
 pub(crate) mod scroll;
 pub(crate) mod search;
+pub(crate) mod server_info;
 pub(crate) mod settings;
+pub(crate) mod status_line;
+pub(crate) mod track_number;
 
 use blackbird_client_shared::style as shared_style;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Clear, Paragraph},
+    widgets::{Block, Clear, Padding, Paragraph},
 };
 
 use smol_str::ToSmolStr as _;
@@ -33,7 +40,7 @@ pub(crate) fn effective_bg(config: &crate::config::Config) -> Color {
     if config.layout.use_terminal_background {
         Color::Reset
     } else {
-        config.style.background_color()
+        config.style.base.background_color()
     }
 }
 
@@ -181,8 +188,17 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
     };
     frame.render_widget(Block::default().style(fill_style), size);
 
-    // Main layout: [NowPlaying] | [Scrub+Volume] | [Content] | [Help].
-    let main = layout::split_main(size);
+    // The layout can't fit in a tiny terminal, so say so instead of drawing
+    // it garbled.
+    let min_height = layout::min_terminal_height(app.config.layout.show_status_line);
+    if size.width < layout::MIN_TERMINAL_WIDTH || size.height < min_height {
+        draw_too_small(frame, app, size, min_height);
+        return;
+    }
+
+    // Main layout: [NowPlaying] | [Scrub+Volume] | [Content] | [Status line, if
+    // enabled] | [Help].
+    let main = layout::split_main(size, app.config.layout.show_status_line);
 
     let is_loading = !app.logic.has_loaded_all_tracks();
 
@@ -198,34 +214,58 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         FocusedPanel::Search => search::draw(
             frame,
             &mut app.search,
-            &app.config.style,
+            &app.config.style.base,
             &app.logic,
             main.content,
         ),
         FocusedPanel::Lyrics => lyrics::draw(
             frame,
             &app.lyrics,
-            &app.config.style,
+            &app.config.style.base,
             app.logic.get_playing_position(),
             main.content,
         ),
-        FocusedPanel::Logs => logs::draw(frame, &mut app.logs, &app.config.style, main.content),
+        FocusedPanel::Logs => {
+            logs::draw(frame, &mut app.logs, &app.config.style.base, main.content)
+        }
         FocusedPanel::Queue => queue::draw(
             frame,
             &app.queue,
-            &app.config.style,
+            &app.config.style.base,
             &app.logic,
             main.content,
         ),
+        FocusedPanel::Recent => recent::draw(
+            frame,
+            &app.recent,
+            &app.config.style.base,
+            &app.logic,
+            main.content,
+        ),
+        FocusedPanel::Listeners => {
+            listeners::draw(frame, &app.config.style.base, &app.logic, main.content)
+        }
+        FocusedPanel::ServerInfo => {
+            server_info::draw(frame, &app.config.style.base, &app.logic, main.content)
+        }
+        FocusedPanel::ListeningStats => listening_stats::draw(
+            frame,
+            &app.config.style.base,
+            app.logic.get_listening_stats().as_ref(),
+            main.content,
+        ),
         FocusedPanel::Settings => settings::draw(
             frame,
             &mut app.settings,
-            &app.config.style,
+            &app.config.style.base,
             &app.config,
             main.content,
         ),
     }
 
+    if let Some(status_line_area) = main.status_line {
+        status_line::draw(frame, app, status_line_area);
+    }
     draw_help_bar(frame, app, main.help_bar);
 
     // Draw inline lyrics as an overlay at the bottom of the content area.
@@ -237,16 +277,36 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         draw_inline_lyrics(frame, app, overlay);
     }
 
+    // Preview the album art the mouse is resting on.
+    if app.focused_panel == FocusedPanel::Library && app.album_art_overlay.is_none() {
+        album_art_overlay::draw_hover_preview(frame, app, main.content);
+    }
+
     // Draw playback mode dropdown if open.
     if app.playback_mode_dropdown {
         now_playing::draw_playback_mode_dropdown(frame, app, size);
     }
 
+    // Draw the command palette over the panels.
+    if app.command_palette.is_some() {
+        command_palette::draw(frame, app, size);
+    }
+
+    // Draw the track number entry over the library.
+    if app.track_number.is_some() {
+        track_number::draw(frame, app, size);
+    }
+
     // Draw album art overlay on top of everything if active.
     if app.album_art_overlay.is_some() {
         album_art_overlay::draw(frame, app, size);
     }
 
+    // Prompt for new credentials if the server rejected the current ones.
+    if app.reauth_prompt_visible() {
+        draw_reauth_prompt(frame, app, size);
+    }
+
     // Draw quit confirmation dialog on top of everything.
     if app.quit_confirming {
         let yes = keys::KEY_CONFIRM_YES.to_smolstr();
@@ -263,20 +323,87 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         frame.render_widget(clear, popup_area);
 
         let popup = Paragraph::new(format!(" {prompt}"))
-            .block(Block::bordered().style(Style::default().fg(app.config.style.text_color())))
-            .style(Style::default().fg(app.config.style.text_color()));
+            .block(Block::bordered().style(Style::default().fg(app.config.style.base.text_color())))
+            .style(Style::default().fg(app.config.style.base.text_color()));
         frame.render_widget(popup, popup_area);
     }
 }
 
+/// Draws the warning shown in place of the UI when the terminal is smaller
+/// than the layout needs.
+fn draw_too_small(frame: &mut Frame, app: &App, size: Rect, min_height: u16) {
+    let style = Style::default().fg(app.config.style.base.text_color());
+    let lines = vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            style.add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("{}\u{d7}{}", size.width, size.height),
+            style,
+        )),
+        Line::from(Span::styled(
+            format!("need {}\u{d7}{min_height}", layout::MIN_TERMINAL_WIDTH),
+            style,
+        )),
+    ];
+    let height = (lines.len() as u16).min(size.height);
+    let y = size.y + (size.height - height) / 2;
+    let area = Rect::new(size.x, y, size.width, height);
+    frame.render_widget(Paragraph::new(lines).centered(), area);
+}
+
+fn draw_reauth_prompt(frame: &mut Frame, app: &App, size: Rect) {
+    let style = &app.config.style.base;
+    let accent = Style::default().fg(style.track_name_playing_color());
+    let dim = Style::default().fg(style.track_duration_color());
+    let settings = keys::KEY_SETTINGS.to_smolstr();
+
+    let lines = vec![
+        Line::from(Span::styled("server rejected credentials", accent)),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Press ", dim),
+            Span::styled(settings, accent),
+            Span::styled(
+                " to update them in settings, any other key to dismiss.",
+                dim,
+            ),
+        ]),
+    ];
+    let width = (lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4).min(size.width);
+    let height = (lines.len() as u16 + 2).min(size.height);
+    let x = size.x + size.width.saturating_sub(width) / 2;
+    let y = size.y + size.height.saturating_sub(height) / 2;
+    let rect = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, rect);
+    let popup = Paragraph::new(lines).block(
+        Block::bordered()
+            .border_style(accent)
+            .style(
+                Style::default()
+                    .fg(style.text_color())
+                    .bg(effective_bg(&app.config)),
+            )
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(popup, rect);
+}
+
 /// Hashes a string to produce a pleasing colour (uses shared implementation).
 /// Uses gamma-corrected version to match egui's color rendering.
 pub fn string_to_color(s: &str) -> Color {
     hsv_to_color(shared_style::string_to_hsv(s))
 }
 
+/// The colour shown in place of the art of an album without any.
+pub fn missing_art_color(artist: &str, album: &str) -> Color {
+    hsv_to_color(shared_style::missing_art_hsv(artist, album))
+}
+
 fn draw_scrub_bar(frame: &mut Frame, app: &mut App, area: Rect) {
-    let style = &app.config.style;
+    let style = &app.config.style.base;
     let details = app.logic.get_track_display_details();
 
     let (position_secs, duration_secs) = details
@@ -305,7 +432,14 @@ fn draw_scrub_bar(frame: &mut Frame, app: &mut App, area: Rect) {
 
     let position_str =
         blackbird_core::util::seconds_to_hms_string(display_position_secs as u32, true);
-    let duration_str = blackbird_core::util::seconds_to_hms_string(duration_secs as u32, true);
+    // A track without a known length can't be seeked, so its bar is drawn as
+    // indeterminate.
+    let duration_unknown = details.as_ref().is_some_and(|d| d.track_duration.is_zero());
+    let duration_str = if duration_unknown {
+        blackbird_core::util::UNKNOWN_DURATION.to_string()
+    } else {
+        blackbird_core::util::seconds_to_hms_string(duration_secs as u32, true)
+    };
     let volume = app.logic.get_volume();
 
     let label = format!(" {position_str} / {duration_str} ");
@@ -322,6 +456,7 @@ fn draw_scrub_bar(frame: &mut Frame, app: &mut App, area: Rect) {
     let has_half = filled_half_blocks % 2 == 1;
 
     let fg = style.track_name_playing_color();
+    let indeterminate_fg = style.track_duration_color();
     let bg = effective_bg(&app.config);
     let buf = frame.buffer_mut();
     let y = sv.scrub_bar.y;
@@ -333,7 +468,10 @@ fn draw_scrub_bar(frame: &mut Frame, app: &mut App, area: Rect) {
             continue;
         }
         let cell = &mut buf[pos];
-        if col < full_cols {
+        if duration_unknown {
+            cell.set_char('░');
+            cell.set_style(Style::default().fg(indeterminate_fg).bg(bg));
+        } else if col < full_cols {
             cell.set_char('█');
             cell.set_style(Style::default().fg(fg));
         } else if col == full_cols && has_half {
@@ -393,7 +531,7 @@ fn draw_scrub_bar(frame: &mut Frame, app: &mut App, area: Rect) {
 }
 
 fn draw_inline_lyrics(frame: &mut Frame, app: &App, area: Rect) {
-    let style = &app.config.style;
+    let style = &app.config.style.base;
     let position = app.logic.get_playing_position();
     let lyrics_line = app.lyrics.shared.current_inline_line(position);
 
@@ -451,7 +589,13 @@ pub fn handle_scrub_volume_click(app: &mut App, scrub_area: Rect, x: u16) {
             let ratio = (x - bar_start) as f32 / (bar_width - 1) as f32;
             app.logic.set_volume(ratio.clamp(0.0, 1.0));
         }
-    } else if x >= sv.scrub_bar.x && x < sv.scrub_bar.x + sv.scrub_bar.width {
+    } else if x >= sv.scrub_bar.x
+        && x < sv.scrub_bar.x + sv.scrub_bar.width
+        && app
+            .logic
+            .get_track_display_details()
+            .is_some_and(|d| !d.track_duration.is_zero())
+    {
         // Set preview ratio for instant visual feedback; the actual seek
         // is deferred until mouse-up via `seek_current_immediate`.
         let ratio = (x - sv.scrub_bar.x) as f32 / sv.scrub_bar.width as f32;
@@ -460,7 +604,7 @@ pub fn handle_scrub_volume_click(app: &mut App, scrub_area: Rect, x: u16) {
 }
 
 fn draw_help_bar(frame: &mut Frame, app: &mut App, area: Rect) {
-    let style = &app.config.style;
+    let style = &app.config.style.base;
 
     let help_entries: &[keys::HelpEntry] = match app.focused_panel {
         FocusedPanel::Library => keys::LIBRARY_HELP,
@@ -468,6 +612,10 @@ fn draw_help_bar(frame: &mut Frame, app: &mut App, area: Rect) {
         FocusedPanel::Lyrics => keys::LYRICS_HELP,
         FocusedPanel::Logs => keys::LOGS_HELP,
         FocusedPanel::Queue => keys::QUEUE_HELP,
+        FocusedPanel::Recent => keys::RECENT_HELP,
+        FocusedPanel::Listeners => keys::LISTENERS_HELP,
+        FocusedPanel::ServerInfo => keys::SERVER_INFO_HELP,
+        FocusedPanel::ListeningStats => keys::LISTENING_STATS_HELP,
         FocusedPanel::Settings => keys::SETTINGS_HELP,
     };
 
@@ -18,10 +18,10 @@ use super::{StyleExt, string_to_color};
 
 pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
     // Extract style colors upfront to avoid borrow conflicts.
-    let text_color = app.config.style.text_color();
-    let album_color = app.config.style.album_color();
-    let track_name_playing_color = app.config.style.track_name_playing_color();
-    let track_duration_color = app.config.style.track_duration_color();
+    let text_color = app.config.style.base.text_color();
+    let album_color = app.config.style.base.album_color();
+    let track_name_playing_color = app.config.style.base.track_name_playing_color();
+    let track_duration_color = app.config.style.base.track_duration_color();
 
     let details = app.logic.get_track_display_details();
 
@@ -102,8 +102,22 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
 
     if let Some(play_count) = tdd.play_count {
         track_spans.push(Span::styled(
-            format!(" {play_count}"),
-            Style::default().fg(app.config.style.track_number_color()),
+            format!(
+                " {}",
+                blackbird_client_shared::format_play_count(
+                    play_count,
+                    app.config.layout.base.abbreviate_play_counts
+                )
+            ),
+            Style::default().fg(app.config.style.base.track_number_color()),
+        ));
+    }
+
+    let track_gain = app.logic.get_track_gain(&tdd.track_id);
+    if track_gain != 0.0 {
+        track_spans.push(Span::styled(
+            format!(" {track_gain:+.1} dB"),
+            Style::default().fg(track_duration_color),
         ));
     }
 
@@ -129,7 +143,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
 }
 
 fn draw_idle(frame: &mut Frame, app: &App, area: Rect) {
-    let style = &app.config.style;
+    let style = &app.config.style.base;
     let track_count = app
         .logic
         .get_state()
@@ -217,7 +231,7 @@ fn heart_to_tui(state: blackbird_client_shared::style::HeartState) -> (&'static
 }
 
 fn draw_transport(frame: &mut Frame, app: &App, area: Rect) {
-    let style = &app.config.style;
+    let style = &app.config.style.base;
     let is_playing = app.logic.get_playing_position().is_some();
     let mode = app.logic.get_playback_mode();
 
@@ -363,7 +377,9 @@ const DROPDOWN_MARKER_OTHER: &str = "   ";
 /// Computes the dropdown rect for the playback mode selector, anchored below
 /// the mode text in the transport area and right-aligned to the terminal.
 pub fn playback_mode_dropdown_rect(size: Rect) -> Rect {
-    let main = super::layout::split_main(size);
+    // The status line sits at the bottom, so it doesn't move the now-playing
+    // area.
+    let main = super::layout::split_main(size, false);
     let np = super::layout::split_now_playing(main.now_playing);
 
     let marker_width = DROPDOWN_MARKER_CURRENT.len() as u16;
@@ -389,7 +405,7 @@ pub fn playback_mode_dropdown_rect(size: Rect) -> Rect {
 
 /// Draws the playback mode dropdown overlay.
 pub fn draw_playback_mode_dropdown(frame: &mut Frame, app: &App, size: Rect) {
-    let style = &app.config.style;
+    let style = &app.config.style.base;
     let rect = playback_mode_dropdown_rect(size);
     let current_mode = app.logic.get_playback_mode();
 
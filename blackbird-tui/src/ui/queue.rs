@@ -15,12 +15,15 @@ use super::StyleExt;
 pub enum QueueAction {
     ToggleQueue,
     Quit,
+    Next,
 }
 
 pub struct QueueState {
     /// Keyboard-selected line index. `None` = auto-follow current track.
     pub selected_index: Option<usize>,
     pub scroll_offset: usize,
+    /// The play queue entry being dragged with the mouse.
+    dragging: Option<usize>,
 }
 
 impl QueueState {
@@ -28,18 +31,56 @@ impl QueueState {
         Self {
             selected_index: None,
             scroll_offset: 0,
+            dragging: None,
         }
     }
 
     pub fn reset(&mut self) {
         self.selected_index = None;
         self.scroll_offset = 0;
+        self.dragging = None;
     }
 }
 
 /// Number of tracks to show before and after the current track in the queue window.
 const QUEUE_RADIUS: usize = 50;
 
+/// Where a line of the queue panel comes from.
+#[derive(Clone, Copy, PartialEq)]
+enum RowKind {
+    Played,
+    Current,
+    /// The entry at this index of the play queue.
+    Queued(usize),
+    Upcoming,
+}
+
+/// The lines of the queue panel, in play order: the tracks before the current
+/// one, the current track, the play queue, then the computed order. Also
+/// returns the index of the current track's line, or of the first line if
+/// nothing is playing. `None` if nothing is playing or queued.
+fn queue_rows(logic: &bc::Logic) -> Option<(Vec<(TrackId, RowKind)>, usize)> {
+    let (before, current, after) = logic.get_queue_window(QUEUE_RADIUS);
+    let queued = logic.get_queue();
+    if current.is_none() && queued.is_empty() {
+        return None;
+    }
+    let current_list_index = if current.is_some() { before.len() } else { 0 };
+    let rows = before
+        .into_iter()
+        .map(|t| (t, RowKind::Played))
+        .chain(current.map(|t| (t, RowKind::Current)))
+        .chain(
+            queued
+                .into_iter()
+                .enumerate()
+                .map(|(i, t)| (t, RowKind::Queued(i))),
+        )
+        .chain(after.into_iter().map(|t| (t, RowKind::Upcoming)))
+        .collect();
+    Some((rows, current_list_index))
+}
+
 pub fn draw(
     frame: &mut Frame,
     queue_state: &QueueState,
@@ -56,14 +97,12 @@ pub fn draw(
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let (before, current, after) = logic.get_queue_window(QUEUE_RADIUS);
-
-    if current.is_none() {
+    let Some((rows, current_list_index)) = queue_rows(logic) else {
         let msg = ratatui::widgets::Paragraph::new("No tracks in the queue.")
             .style(Style::default().fg(style.track_duration_color()));
         frame.render_widget(msg, inner);
         return;
-    }
+    };
 
     let state = logic.get_state();
     let st = state.read().unwrap();
@@ -73,22 +112,14 @@ pub fn draw(
     let track_duration_color = style.track_duration_color();
     let track_name_playing_color = style.track_name_playing_color();
     let track_name_hovered_color = style.track_name_hovered_color();
+    let album_color = style.album_color();
 
-    // Build list: [before... | current | after...]
-    let total_items = before.len() + 1 + after.len();
-    let current_list_index = before.len();
     let selected_index = queue_state.selected_index;
 
-    let mut items: Vec<ListItem> = Vec::with_capacity(total_items);
+    let mut items: Vec<ListItem> = Vec::with_capacity(rows.len());
 
-    let all_tracks: Vec<&TrackId> = before
-        .iter()
-        .chain(current.iter())
-        .chain(after.iter())
-        .collect();
-
-    for (idx, track_id) in all_tracks.iter().enumerate() {
-        let is_current = idx == current_list_index;
+    for (idx, (track_id, kind)) in rows.iter().enumerate() {
+        let is_current = *kind == RowKind::Current;
         let is_selected = selected_index == Some(idx);
 
         let display = TrackDisplayDetails::from_track_id(track_id, &st);
@@ -111,7 +142,7 @@ pub fn draw(
             track_name_hovered_color
         } else if is_current {
             track_name_playing_color
-        } else if idx < current_list_index {
+        } else if *kind == RowKind::Played {
             // Previous tracks are dimmed.
             ratatui::style::Color::Rgb(128, 128, 128)
         } else {
@@ -135,6 +166,9 @@ pub fn draw(
                     .fg(track_name_playing_color)
                     .add_modifier(Modifier::BOLD),
             ));
+        } else if matches!(kind, RowKind::Queued(_)) {
+            // Explicitly queued tracks are marked.
+            spans.push(Span::styled("+ ", Style::default().fg(album_color)));
         } else {
             spans.push(Span::raw("  "));
         }
@@ -187,8 +221,16 @@ pub fn handle_key(
             move_selection(queue_state, logic, super::layout::PAGE_SCROLL_SIZE as i32);
         }
         Action::Select => play_selected(queue_state, logic),
+        Action::MoveQueuedUp => move_queued(queue_state, logic, -1),
+        Action::MoveQueuedDown => move_queued(queue_state, logic, 1),
+        Action::Dequeue => {
+            if let Some(index) = selected_queued(queue_state, logic) {
+                logic.dequeue_track(index);
+            }
+        }
+        Action::ClearQueue => logic.clear_queue(),
         Action::PlayPause => logic.toggle_current(),
-        Action::Next => logic.next(),
+        Action::Next => return Some(QueueAction::Next),
         Action::Previous => logic.previous(),
         Action::NextGroup => logic.next_group(),
         Action::PreviousGroup => logic.previous_group(),
@@ -205,7 +247,8 @@ pub fn handle_key(
     None
 }
 
-/// Handle a mouse click in the queue area — play the clicked track.
+/// Handle a mouse click in the queue area — play the clicked track, or start
+/// dragging a play queue entry to reorder it.
 pub fn handle_mouse_click(
     queue_state: &mut QueueState,
     logic: &bc::Logic,
@@ -213,44 +256,96 @@ pub fn handle_mouse_click(
     _x: u16,
     y: u16,
 ) {
-    let inner_y = area.y + 1;
-    let inner_height = area.height.saturating_sub(2);
-    if y < inner_y || y >= inner_y + inner_height {
+    let Some((rows, current_list_index)) = queue_rows(logic) else {
         return;
+    };
+    let Some(clicked_index) = row_at(queue_state, area, y, current_list_index) else {
+        return;
+    };
+
+    match rows.get(clicked_index) {
+        Some((_, RowKind::Queued(index))) => queue_state.dragging = Some(*index),
+        Some((track_id, _)) => {
+            logic.request_play_track(track_id);
+            queue_state.selected_index = None;
+        }
+        None => {}
     }
+}
 
-    let (before, current, after) = logic.get_queue_window(QUEUE_RADIUS);
-    if current.is_none() {
+/// Handle a mouse drag in the queue area — move the dragged play queue entry
+/// to the entry under the cursor.
+pub fn handle_mouse_drag(queue_state: &mut QueueState, logic: &bc::Logic, area: Rect, y: u16) {
+    let Some(from) = queue_state.dragging else {
         return;
+    };
+    let Some((rows, current_list_index)) = queue_rows(logic) else {
+        return;
+    };
+    let target = row_at(queue_state, area, y, current_list_index).and_then(|i| rows.get(i));
+    if let Some((_, RowKind::Queued(to))) = target
+        && *to != from
+        && logic.move_queued_track(from, *to)
+    {
+        queue_state.dragging = Some(*to);
+    }
+}
+
+/// Handle a mouse release — end any drag.
+pub fn handle_mouse_up(queue_state: &mut QueueState) {
+    queue_state.dragging = None;
+}
+
+/// The index of the line at row `y` of the queue panel, which may be past the
+/// last line.
+fn row_at(
+    queue_state: &QueueState,
+    area: Rect,
+    y: u16,
+    current_list_index: usize,
+) -> Option<usize> {
+    let inner_y = area.y + 1;
+    let inner_height = area.height.saturating_sub(2);
+    if y < inner_y || y >= inner_y + inner_height {
+        return None;
     }
-    let total_items = before.len() + 1 + after.len();
 
-    let current_list_index = before.len();
     let visible_height = inner_height as usize;
     let focus_line = queue_state.selected_index.unwrap_or(current_list_index);
     let scroll_offset = focus_line.saturating_sub(visible_height / 2);
+    Some(scroll_offset + (y - inner_y) as usize)
+}
 
-    let row_in_list = (y - inner_y) as usize;
-    let clicked_index = scroll_offset + row_in_list;
-
-    if clicked_index < total_items {
-        let all_tracks: Vec<TrackId> = before.into_iter().chain(current).chain(after).collect();
-        logic.request_play_track(&all_tracks[clicked_index]);
-        queue_state.selected_index = None;
+/// The play queue index of the selected line, if it is a play queue entry.
+fn selected_queued(queue_state: &QueueState, logic: &bc::Logic) -> Option<usize> {
+    let (rows, _) = queue_rows(logic)?;
+    match rows.get(queue_state.selected_index?)? {
+        (_, RowKind::Queued(index)) => Some(*index),
+        _ => None,
     }
 }
 
-fn move_selection(queue_state: &mut QueueState, logic: &bc::Logic, delta: i32) {
-    let (before, current, after) = logic.get_queue_window(QUEUE_RADIUS);
-    if current.is_none() {
+/// Moves the selected play queue entry `delta` places, keeping it selected.
+fn move_queued(queue_state: &mut QueueState, logic: &bc::Logic, delta: isize) {
+    let Some(from) = selected_queued(queue_state, logic) else {
         return;
-    }
-    let total_items = before.len() + 1 + after.len();
-    if total_items == 0 {
+    };
+    let Some(to) = from.checked_add_signed(delta) else {
         return;
+    };
+    if logic.move_queued_track(from, to)
+        && let Some(selected) = queue_state.selected_index.as_mut()
+    {
+        *selected = selected.wrapping_add_signed(delta);
     }
+}
+
+fn move_selection(queue_state: &mut QueueState, logic: &bc::Logic, delta: i32) {
+    let Some((rows, current_list_index)) = queue_rows(logic) else {
+        return;
+    };
+    let total_items = rows.len();
 
-    let current_list_index = before.len();
     let current_sel = queue_state.selected_index.unwrap_or(current_list_index);
     let new_index = (current_sel as i32 + delta).clamp(0, total_items as i32 - 1) as usize;
     queue_state.selected_index = Some(new_index);
@@ -261,14 +356,15 @@ fn play_selected(queue_state: &mut QueueState, logic: &bc::Logic) {
         return;
     };
 
-    let (before, current, after) = logic.get_queue_window(QUEUE_RADIUS);
-    if current.is_none() {
+    let Some((rows, _)) = queue_rows(logic) else {
         return;
-    }
-
-    let all_tracks: Vec<TrackId> = before.into_iter().chain(current).chain(after).collect();
+    };
 
-    if let Some(track_id) = all_tracks.get(selected) {
+    if let Some((track_id, kind)) = rows.get(selected) {
+        // A queued track played out of turn leaves the play queue.
+        if let RowKind::Queued(index) = kind {
+            logic.dequeue_track(*index);
+        }
         logic.request_play_track(track_id);
         queue_state.selected_index = None;
     }
@@ -0,0 +1,181 @@
+use blackbird_client_shared::{RECENT_ALBUM_COUNT, style as shared_style};
+use blackbird_core as bc;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+};
+
+use crate::keys::Action;
+
+use super::StyleExt;
+
+pub enum RecentAction {
+    ToggleRecent,
+    Quit,
+}
+
+pub struct RecentState {
+    /// Keyboard-selected album index.
+    pub selected_index: usize,
+}
+
+impl RecentState {
+    pub fn new() -> Self {
+        Self { selected_index: 0 }
+    }
+
+    pub fn reset(&mut self) {
+        self.selected_index = 0;
+    }
+}
+
+pub fn draw(
+    frame: &mut Frame,
+    recent_state: &RecentState,
+    style: &shared_style::Style,
+    logic: &bc::Logic,
+    area: Rect,
+) {
+    let block = Block::default()
+        .title(" Recently played ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(style.album_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let recent = logic.recent_albums(RECENT_ALBUM_COUNT);
+    if recent.is_empty() {
+        let msg = ratatui::widgets::Paragraph::new("No albums played yet.")
+            .style(Style::default().fg(style.track_duration_color()));
+        frame.render_widget(msg, inner);
+        return;
+    }
+
+    let text_color = style.text_color();
+    let album_color = style.album_color();
+    let track_name_hovered_color = style.track_name_hovered_color();
+    let selected_index = recent_state.selected_index.min(recent.len() - 1);
+
+    let items: Vec<ListItem> = recent
+        .iter()
+        .enumerate()
+        .map(|(idx, album)| {
+            let is_selected = idx == selected_index;
+            let (artist_style, album_style) = if is_selected {
+                let style = Style::default()
+                    .fg(track_name_hovered_color)
+                    .add_modifier(Modifier::BOLD);
+                (style, style)
+            } else {
+                (
+                    Style::default().fg(text_color),
+                    Style::default().fg(album_color),
+                )
+            };
+
+            let indicator = if is_selected {
+                Span::styled("> ", artist_style)
+            } else {
+                Span::raw("  ")
+            };
+
+            ListItem::new(Line::from(vec![
+                indicator,
+                Span::styled(album.group.artist.to_string(), artist_style),
+                Span::styled(" - ", artist_style),
+                Span::styled(album.group.album.to_string(), album_style),
+            ]))
+        })
+        .collect();
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected_index));
+    let visible_height = inner.height as usize;
+    *list_state.offset_mut() = selected_index.saturating_sub(visible_height / 2);
+
+    frame.render_stateful_widget(List::new(items), inner, &mut list_state);
+}
+
+pub fn handle_key(
+    recent_state: &mut RecentState,
+    logic: &bc::Logic,
+    action: Action,
+) -> Option<RecentAction> {
+    match action {
+        Action::Back => return Some(RecentAction::ToggleRecent),
+        Action::Quit => return Some(RecentAction::Quit),
+        Action::MoveUp => move_selection(recent_state, logic, -1),
+        Action::MoveDown => move_selection(recent_state, logic, 1),
+        Action::PageUp => {
+            move_selection(
+                recent_state,
+                logic,
+                -(super::layout::PAGE_SCROLL_SIZE as i32),
+            );
+        }
+        Action::PageDown => {
+            move_selection(recent_state, logic, super::layout::PAGE_SCROLL_SIZE as i32);
+        }
+        Action::Select => {
+            if play_album(logic, recent_state.selected_index) {
+                return Some(RecentAction::ToggleRecent);
+            }
+        }
+        Action::PlayPause => logic.toggle_current(),
+        _ => {}
+    }
+    None
+}
+
+/// Handle a mouse click in the recently played area — play the clicked album.
+pub fn handle_mouse_click(
+    recent_state: &mut RecentState,
+    logic: &bc::Logic,
+    area: Rect,
+    _x: u16,
+    y: u16,
+) -> Option<RecentAction> {
+    let inner_y = area.y + 1;
+    let inner_height = area.height.saturating_sub(2);
+    if y < inner_y || y >= inner_y + inner_height {
+        return None;
+    }
+
+    let scroll_offset = recent_state
+        .selected_index
+        .saturating_sub(inner_height as usize / 2);
+    let clicked_index = scroll_offset + (y - inner_y) as usize;
+    play_album(logic, clicked_index).then_some(RecentAction::ToggleRecent)
+}
+
+/// Move selection by `delta` (for scroll events).
+pub fn scroll_selection(recent_state: &mut RecentState, logic: &bc::Logic, delta: i32) {
+    move_selection(recent_state, logic, delta);
+}
+
+fn move_selection(recent_state: &mut RecentState, logic: &bc::Logic, delta: i32) {
+    let len = logic.recent_albums(RECENT_ALBUM_COUNT).len();
+    if len == 0 {
+        return;
+    }
+    recent_state.selected_index =
+        (recent_state.selected_index as i32 + delta).clamp(0, len as i32 - 1) as usize;
+}
+
+/// Resumes the album at `index` from its most recently played track. Returns
+/// `true` if there was an album to play.
+fn play_album(logic: &bc::Logic, index: usize) -> bool {
+    let Some(album) = logic
+        .recent_albums(RECENT_ALBUM_COUNT)
+        .into_iter()
+        .nth(index)
+    else {
+        return false;
+    };
+    logic.request_play_track(&album.track_id);
+    true
+}
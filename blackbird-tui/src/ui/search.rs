@@ -1,6 +1,14 @@
-use blackbird_client_shared::style as shared_style;
+use blackbird_client_shared::{
+    search_history::SearchHistory,
+    search_suggestions::{self, SuggestionDebouncer},
+    style as shared_style,
+};
+use std::collections::HashMap;
+
 use blackbird_core::{
-    self as bc, TrackDisplayDetails, blackbird_state::TrackId, util::seconds_to_hms_string,
+    self as bc, TrackDisplayDetails,
+    blackbird_state::{Track, TrackId},
+    util::seconds_to_hms_string,
 };
 use ratatui::{
     Frame,
@@ -17,11 +25,18 @@ use super::{StyleExt, string_to_color};
 pub enum SearchAction {
     ToggleSearch,
     GotoTrack(TrackId),
+    FocusNowPlaying,
 }
 
 pub struct SearchState {
     pub query: String,
+    /// Local results, followed by any further results from the server.
     pub results: Vec<TrackId>,
+    /// Where the server's results start in `results`, once they arrive.
+    pub server_start: Option<usize>,
+    /// The server's results that aren't in the library, shown from the
+    /// server's details.
+    pub remote: HashMap<TrackId, Track>,
     pub selected_index: usize,
 
     /// Shared scroll/drag/inertia mechanism. Each result is one line, so
@@ -32,22 +47,36 @@ pub struct SearchState {
     /// Pending click at `(x, y, result_index)`. Resolved on mouse-up: if no
     /// drag intervened, the track is played.
     pub click_pending: Option<(u16, u16, usize)>,
+
+    /// Paces requests for server search suggestions as the query changes.
+    pub suggestions: SuggestionDebouncer,
+
+    /// Past queries, recalled with Up from the top of the results.
+    pub history: SearchHistory,
 }
 
 impl SearchState {
-    pub fn new() -> Self {
+    pub fn new(history: SearchHistory) -> Self {
         Self {
             query: String::new(),
             results: Vec::new(),
+            server_start: None,
+            remote: HashMap::new(),
             selected_index: 0,
             viewport: super::scroll::Scroller::new(),
             click_pending: None,
+            suggestions: SuggestionDebouncer::default(),
+            history,
         }
     }
 
+    /// Clears the search, remembering its query in the history.
     pub fn reset(&mut self) {
+        self.history.record(&self.query);
         self.query.clear();
         self.results.clear();
+        self.server_start = None;
+        self.remote.clear();
         self.selected_index = 0;
         self.viewport = super::scroll::Scroller::new();
         self.click_pending = None;
@@ -61,11 +90,32 @@ impl SearchState {
         } else {
             self.results.clear();
         }
+        self.server_start = None;
+        self.remote.clear();
+        self.merge_server_results(logic);
         self.selected_index = 0;
         self.viewport.line = 0;
         self.viewport.cancel_inertia();
     }
 
+    /// Appends the server's results for the query once they arrive, after
+    /// the local ones.
+    pub fn merge_server_results(&mut self, logic: &bc::Logic) {
+        if self.server_start.is_some() || self.query.len() < search_suggestions::MIN_QUERY_LEN {
+            return;
+        }
+        if let Some(server) = logic.get_server_search(&self.query) {
+            self.remote = search_suggestions::remote_tracks(
+                &server,
+                &logic.get_state().read().unwrap().library,
+            );
+            self.server_start = Some(search_suggestions::append_server_results(
+                &mut self.results,
+                server.into_iter().map(|track| track.id),
+            ));
+        }
+    }
+
     /// Adjust the viewport so `selected_index` is in the visible window.
     fn ensure_selection_visible(&mut self) {
         let visible_height = self.viewport.visible_height;
@@ -157,7 +207,7 @@ impl SearchState {
         if let Some((_x, _y, index)) = pending
             && let Some(track_id) = self.results.get(index)
         {
-            logic.request_play_track(track_id);
+            logic.load_and_play_track(track_id);
             return Some(SearchAction::ToggleSearch);
         }
         None
@@ -182,18 +232,36 @@ impl SearchState {
     pub fn handle_key(&mut self, logic: &bc::Logic, action: Action) -> Option<SearchAction> {
         match action {
             Action::Back => return Some(SearchAction::ToggleSearch),
+            Action::FocusNowPlaying => return Some(SearchAction::FocusNowPlaying),
             Action::Select => {
                 if let Some(track_id) = self.results.get(self.selected_index) {
-                    logic.request_play_track(track_id);
+                    logic.load_and_play_track(track_id);
                     return Some(SearchAction::ToggleSearch);
                 }
             }
             Action::GotoSelected => {
-                if let Some(track_id) = self.results.get(self.selected_index) {
+                // Remote tracks aren't in the library to go to.
+                if let Some(track_id) = self.results.get(self.selected_index)
+                    && !self.remote.contains_key(track_id)
+                {
                     return Some(SearchAction::GotoTrack(track_id.clone()));
                 }
             }
-            Action::MoveUp if self.selected_index > 0 => {
+            // Up from the top of the results, and Down while recalling,
+            // step through past queries.
+            Action::MoveUp if self.selected_index == 0 || self.history.is_browsing() => {
+                if let Some(query) = self.history.older(&self.query) {
+                    self.query = query;
+                    self.update(logic);
+                }
+            }
+            Action::MoveDown if self.history.is_browsing() => {
+                if let Some(query) = self.history.newer() {
+                    self.query = query;
+                    self.update(logic);
+                }
+            }
+            Action::MoveUp => {
                 self.selected_index -= 1;
                 self.ensure_selection_visible();
             }
@@ -203,15 +271,28 @@ impl SearchState {
                 self.selected_index += 1;
                 self.ensure_selection_visible();
             }
+            Action::CompleteSuggestion => {
+                if let Some(suggestions) = logic.get_search_suggestions(&self.query)
+                    && let Some(suggestion) =
+                        search_suggestions::completion(&suggestions, &self.query)
+                {
+                    self.query = suggestion.text().to_string();
+                    self.history.stop_browsing();
+                    self.update(logic);
+                }
+            }
             Action::DeleteChar => {
+                self.history.stop_browsing();
                 self.query.pop();
                 self.update(logic);
             }
             Action::ClearLine => {
+                self.history.stop_browsing();
                 self.query.clear();
                 self.update(logic);
             }
             Action::Char(c) => {
+                self.history.stop_browsing();
                 self.query.push(c);
                 self.update(logic);
             }
@@ -238,10 +319,15 @@ pub fn draw(
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
         .split(inner);
+    let (suggestions_area, results_area) = (chunks[1], chunks[2]);
 
-    search.viewport.visible_height = chunks[1].height as usize;
+    search.viewport.visible_height = results_area.height as usize;
     search.viewport.clamp(search.results.len());
 
     // Search input
@@ -255,6 +341,27 @@ pub fn draw(
     ]));
     frame.render_widget(input, chunks[0]);
 
+    // Server suggestions, completed to with Tab.
+    if search.query.len() >= search_suggestions::MIN_QUERY_LEN
+        && let Some(suggestions) = logic.get_search_suggestions(&search.query)
+        && !suggestions.is_empty()
+    {
+        let mut spans = vec![Span::styled(
+            "tab: ",
+            Style::default().fg(style.track_duration_color()),
+        )];
+        for (idx, suggestion) in suggestions.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(
+                search_suggestions::label(suggestion),
+                Style::default().fg(style.album_color()),
+            ));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), suggestions_area);
+    }
+
     // Search results
     if search.query.len() < 3 {
         let hint = if search.query.is_empty() {
@@ -264,14 +371,14 @@ pub fn draw(
         };
         let hint_widget =
             Paragraph::new(hint).style(Style::default().fg(style.track_duration_color()));
-        frame.render_widget(hint_widget, chunks[1]);
+        frame.render_widget(hint_widget, results_area);
         return;
     }
 
     if search.results.is_empty() {
         let no_results = Paragraph::new("No results found.")
             .style(Style::default().fg(style.track_duration_color()));
-        frame.render_widget(no_results, chunks[1]);
+        frame.render_widget(no_results, results_area);
         return;
     }
 
@@ -292,20 +399,38 @@ pub fn draw(
             let is_selected = i == search.selected_index;
             let details = TrackDisplayDetails::from_track_id(track_id, &app_state);
 
-            let line = if let Some(d) = details {
-                let artist = d.artist();
-                let dur_str = seconds_to_hms_string(d.track_duration.as_secs() as u32, false);
+            // Results only the server found are marked as such, and those
+            // that aren't in the library as remote.
+            let fields = match (&details, search.remote.get(track_id)) {
+                (Some(d), _) => Some((
+                    if search.server_start.is_some_and(|start| i >= start) {
+                        "server: "
+                    } else {
+                        ""
+                    },
+                    d.artist(),
+                    d.track_title.as_str(),
+                    d.track_duration.as_secs() as u32,
+                )),
+                (None, Some(track)) => Some((
+                    "remote: ",
+                    track.artist.as_deref().unwrap_or_default(),
+                    track.title.as_str(),
+                    track.duration.unwrap_or_default(),
+                )),
+                (None, None) => None,
+            };
 
+            let line = if let Some((source, artist, title, duration_secs)) = fields {
+                let dur_str = seconds_to_hms_string(duration_secs, false);
                 Line::from(vec![
+                    Span::styled(source, Style::default().fg(track_duration_color)),
                     Span::styled(
                         artist.to_string(),
                         Style::default().fg(string_to_color(artist)),
                     ),
                     Span::raw(" - "),
-                    Span::styled(
-                        d.track_title.to_string(),
-                        Style::default().fg(track_name_color),
-                    ),
+                    Span::styled(title.to_string(), Style::default().fg(track_name_color)),
                     Span::styled(
                         format!(" [{dur_str}]"),
                         Style::default().fg(track_length_color),
@@ -335,11 +460,11 @@ pub fn draw(
     // auto-scroll from clobbering our offset when the selection is off-screen.
     *list_state.offset_mut() = search.viewport.line;
 
-    frame.render_stateful_widget(list, chunks[1], &mut list_state);
+    frame.render_stateful_widget(list, results_area, &mut list_state);
 
     search.viewport.render_scrollbar(
         frame,
-        chunks[1],
+        results_area,
         search.results.len(),
         style.track_duration_color(),
         style.track_name_playing_color(),
@@ -347,9 +472,10 @@ pub fn draw(
 }
 
 /// `(start_y, end_y)` row range covered by the results list, mirroring the
-/// layout in `draw` (border + 1-row input + content + border).
+/// layout in `draw` (border + 1-row input + 1-row suggestions + content +
+/// border).
 fn results_y_range(area: Rect) -> (u16, u16) {
-    let start = area.y + 2;
+    let start = area.y + 3;
     let end = area.y + area.height.saturating_sub(1);
     (start, end)
 }
@@ -0,0 +1,66 @@
+use blackbird_client_shared::{server_info, style as shared_style};
+use blackbird_core as bc;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::keys::Action;
+
+use super::StyleExt;
+
+pub enum ServerInfoAction {
+    ToggleServerInfo,
+    Quit,
+}
+
+pub fn draw(frame: &mut Frame, style: &shared_style::Style, logic: &bc::Logic, area: Rect) {
+    let block = Block::default()
+        .title(" Server info ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(style.album_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(info) = logic.server_info() else {
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                server_info::UNAVAILABLE_MESSAGE,
+                Style::default().fg(style.track_duration_color()),
+            )),
+            inner,
+        );
+        return;
+    };
+
+    let rows = server_info::rows(&info, &logic.get_server_username());
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{label:<label_width$}  "),
+                    Style::default()
+                        .fg(style.album_color())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(value, Style::default().fg(style.text_color())),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+pub fn handle_key(action: Action) -> Option<ServerInfoAction> {
+    match action {
+        Action::Back => Some(ServerInfoAction::ToggleServerInfo),
+        Action::Quit => Some(ServerInfoAction::Quit),
+        _ => None,
+    }
+}
@@ -4,8 +4,9 @@ use std::{
 };
 
 use blackbird_client_shared::{
-    config::{AlbumArtStyle, Layout, Playback},
-    style as shared_style,
+    Direction,
+    config::{AlbumArtStyle, FollowPlaying, Layout, MissingArt, Playback},
+    cycle, style as shared_style,
 };
 use blackbird_core::blackbird_state::{AlbumId, CoverArtId, TrackId};
 use ratatui::{
@@ -87,12 +88,17 @@ enum SettingsRow {
         min: u64,
         max: u64,
     },
+    /// An enum field, cycled through in place. Values are compared by their
+    /// labels, so that this serves any enum.
     EnumField {
         label: &'static str,
         section: Section,
-        get: fn(&crate::config::Config) -> AlbumArtStyle,
-        set: fn(&mut crate::config::Config, AlbumArtStyle),
-        default: fn() -> AlbumArtStyle,
+        get: fn(&crate::config::Config) -> &'static str,
+        /// Advances the field to its next value.
+        cycle: fn(&mut crate::config::Config),
+        default: fn() -> &'static str,
+        /// Resets the field to its default.
+        reset: fn(&mut crate::config::Config),
     },
     HsvField {
         label: &'static str,
@@ -167,6 +173,32 @@ impl SettingsState {
     }
 }
 
+/// A boolean setting that can be flipped outside the settings panel.
+#[derive(Debug, Clone, Copy)]
+pub struct SettingToggle {
+    pub label: &'static str,
+    pub get: fn(&crate::config::Config) -> bool,
+    pub set: fn(&mut crate::config::Config, bool),
+}
+
+/// The boolean settings, for the command palette. Server settings are left
+/// out, as changing them reloads the library.
+pub fn setting_toggles() -> Vec<SettingToggle> {
+    build_rows()
+        .into_iter()
+        .filter_map(|row| match row {
+            SettingsRow::BoolField {
+                label,
+                section,
+                get,
+                set,
+                ..
+            } if section != Section::Server => Some(SettingToggle { label, get, set }),
+            _ => None,
+        })
+        .collect()
+}
+
 fn build_rows() -> Vec<SettingsRow> {
     let mut rows = vec![
         // Server section (no spacer before the first section).
@@ -195,13 +227,6 @@ fn build_rows() -> Vec<SettingsRow> {
             default: || blackbird_shared::config::Server::default().password,
             password: true,
         },
-        SettingsRow::BoolField {
-            label: "Transcode",
-            section: Section::Server,
-            get: |c| c.server.transcode,
-            set: |c, v| c.server.transcode = v,
-            default: || blackbird_shared::config::Server::default().transcode,
-        },
         // Layout section.
         SettingsRow::SectionSpacer,
         SettingsRow::SectionHeader("Layout"),
@@ -215,9 +240,30 @@ fn build_rows() -> Vec<SettingsRow> {
         SettingsRow::EnumField {
             label: "Album art style",
             section: Section::Layout,
-            get: |c| c.layout.base.album_art_style,
-            set: |c, v| c.layout.base.album_art_style = v,
-            default: || Layout::default().album_art_style,
+            get: |c| c.layout.base.album_art_style.as_str(),
+            cycle: |c| {
+                c.layout.base.album_art_style = cycle(
+                    AlbumArtStyle::ALL,
+                    c.layout.base.album_art_style,
+                    Direction::Forward,
+                )
+            },
+            default: || Layout::default().album_art_style.as_str(),
+            reset: |c| c.layout.base.album_art_style = Layout::default().album_art_style,
+        },
+        SettingsRow::EnumField {
+            label: "Missing art",
+            section: Section::Layout,
+            get: |c| c.layout.base.missing_art.as_str(),
+            cycle: |c| {
+                c.layout.base.missing_art = cycle(
+                    MissingArt::ALL,
+                    c.layout.base.missing_art,
+                    Direction::Forward,
+                )
+            },
+            default: || Layout::default().missing_art.as_str(),
+            reset: |c| c.layout.base.missing_art = Layout::default().missing_art,
         },
         SettingsRow::UsizeField {
             label: "Album spacing",
@@ -228,6 +274,41 @@ fn build_rows() -> Vec<SettingsRow> {
             min: 0,
             max: 10,
         },
+        SettingsRow::BoolField {
+            label: "Abbreviate play counts",
+            section: Section::Layout,
+            get: |c| c.layout.base.abbreviate_play_counts,
+            set: |c, v| c.layout.base.abbreviate_play_counts = v,
+            default: || Layout::default().abbreviate_play_counts,
+        },
+        SettingsRow::EnumField {
+            label: "Follow playing",
+            section: Section::Layout,
+            get: |c| c.layout.base.follow_playing.as_str(),
+            cycle: |c| {
+                c.layout.base.follow_playing = cycle(
+                    FollowPlaying::ALL,
+                    c.layout.base.follow_playing,
+                    Direction::Forward,
+                )
+            },
+            default: || Layout::default().follow_playing.as_str(),
+            reset: |c| c.layout.base.follow_playing = Layout::default().follow_playing,
+        },
+        SettingsRow::BoolField {
+            label: "Show album gain",
+            section: Section::Layout,
+            get: |c| c.layout.base.show_album_gain,
+            set: |c, v| c.layout.base.show_album_gain = v,
+            default: || Layout::default().show_album_gain,
+        },
+        SettingsRow::BoolField {
+            label: "Reduced motion",
+            section: Section::Layout,
+            get: |c| c.layout.base.reduced_motion,
+            set: |c, v| c.layout.base.reduced_motion = v,
+            default: || Layout::default().reduced_motion,
+        },
         SettingsRow::BoolField {
             label: "Use terminal background",
             section: Section::Layout,
@@ -235,9 +316,44 @@ fn build_rows() -> Vec<SettingsRow> {
             set: |c, v| c.layout.use_terminal_background = v,
             default: || crate::config::Layout::default().use_terminal_background,
         },
+        SettingsRow::BoolField {
+            label: "Show status line",
+            section: Section::Layout,
+            get: |c| c.layout.show_status_line,
+            set: |c, v| c.layout.show_status_line = v,
+            default: || crate::config::Layout::default().show_status_line,
+        },
+        SettingsRow::BoolField {
+            label: "Preview art on hover",
+            section: Section::Layout,
+            get: |c| c.layout.art_hover_preview,
+            set: |c, v| c.layout.art_hover_preview = v,
+            default: || crate::config::Layout::default().art_hover_preview,
+        },
         // Playback section.
         SettingsRow::SectionSpacer,
         SettingsRow::SectionHeader("Playback"),
+        // Stored with the server settings, but applied without reloading the
+        // library.
+        SettingsRow::BoolField {
+            label: "Transcode",
+            section: Section::Playback,
+            get: |c| c.server.transcode,
+            set: |c, v| c.server.transcode = v,
+            default: || blackbird_shared::config::Server::default().transcode,
+        },
+        // Left empty to use the format the server reports for each track.
+        SettingsRow::StringField {
+            label: "Transcode format",
+            section: Section::Playback,
+            get: |c| c.server.transcode_format.clone().unwrap_or_default(),
+            set: |c, v| {
+                let v = v.trim();
+                c.server.transcode_format = (!v.is_empty()).then(|| v.to_string());
+            },
+            default: String::new,
+            password: false,
+        },
         SettingsRow::BoolField {
             label: "Apply ReplayGain",
             section: Section::Playback,
@@ -254,6 +370,13 @@ fn build_rows() -> Vec<SettingsRow> {
             min: -12.0,
             max: 12.0,
         },
+        SettingsRow::BoolField {
+            label: "Resume albums",
+            section: Section::Playback,
+            get: |c| c.playback.resume_albums,
+            set: |c, v| c.playback.resume_albums = v,
+            default: || Playback::default().resume_albums,
+        },
         // Colors section.
         SettingsRow::SectionSpacer,
         SettingsRow::SectionHeader("Colors"),
@@ -603,7 +726,7 @@ fn render_row(
                     Style::default().fg(if is_selected { highlight } else { text_fg }),
                 ),
                 Span::styled(
-                    format!("{label}: {}", value.as_str()),
+                    format!("{label}: {value}"),
                     Style::default().fg(if is_selected { highlight } else { text_fg }),
                 ),
             ];
@@ -613,7 +736,7 @@ fn render_row(
             Line::from(spans)
         }
         SettingsRow::HsvField { label, index } => {
-            let hsv = *config.style.field(*index);
+            let hsv = *config.style.base.field(*index);
             let default_hsv = shared_style::Style::default_field(*index);
             let is_default = hsv == default_hsv;
             let indicator = if is_selected { "> " } else { "  " };
@@ -812,7 +935,7 @@ pub fn handle_key(
             Action::MoveUp => {
                 let row = &state.rows[state.selected_index];
                 if let SettingsRow::HsvField { index, .. } = row {
-                    let hsv = config.style.field_mut(*index);
+                    let hsv = config.style.base.field_mut(*index);
                     let comp_idx = match state.hsv_component {
                         HsvComponent::H => 0,
                         HsvComponent::S => 1,
@@ -825,7 +948,7 @@ pub fn handle_key(
             Action::MoveDown => {
                 let row = &state.rows[state.selected_index];
                 if let SettingsRow::HsvField { index, .. } = row {
-                    let hsv = config.style.field_mut(*index);
+                    let hsv = config.style.base.field_mut(*index);
                     let comp_idx = match state.hsv_component {
                         HsvComponent::H => 0,
                         HsvComponent::S => 1,
@@ -838,7 +961,7 @@ pub fn handle_key(
             Action::PageUp => {
                 let row = &state.rows[state.selected_index];
                 if let SettingsRow::HsvField { index, .. } = row {
-                    let hsv = config.style.field_mut(*index);
+                    let hsv = config.style.base.field_mut(*index);
                     let comp_idx = match state.hsv_component {
                         HsvComponent::H => 0,
                         HsvComponent::S => 1,
@@ -851,7 +974,7 @@ pub fn handle_key(
             Action::PageDown => {
                 let row = &state.rows[state.selected_index];
                 if let SettingsRow::HsvField { index, .. } = row {
-                    let hsv = config.style.field_mut(*index);
+                    let hsv = config.style.base.field_mut(*index);
                     let comp_idx = match state.hsv_component {
                         HsvComponent::H => 0,
                         HsvComponent::S => 1,
@@ -902,14 +1025,8 @@ pub fn handle_key(
                     state.editing = true;
                     state.edit_buffer = get(config).to_string();
                 }
-                SettingsRow::EnumField {
-                    get, set, section, ..
-                } => {
-                    let current = get(config);
-                    let all = AlbumArtStyle::ALL;
-                    let idx = all.iter().position(|v| *v == current).unwrap_or(0);
-                    let next = (idx + 1) % all.len();
-                    set(config, all[next]);
+                SettingsRow::EnumField { cycle, section, .. } => {
+                    cycle(config);
                     if *section == Section::Server {
                         server_changed = true;
                     }
@@ -979,19 +1096,15 @@ pub fn handle_key(
                         server_changed = true;
                     }
                 }
-                SettingsRow::EnumField {
-                    default,
-                    set,
-                    section,
-                    ..
-                } => {
-                    set(config, default());
+                SettingsRow::EnumField { reset, section, .. } => {
+                    reset(config);
                     if *section == Section::Server {
                         server_changed = true;
                     }
                 }
                 SettingsRow::HsvField { index, .. } => {
-                    *config.style.field_mut(*index) = shared_style::Style::default_field(*index);
+                    *config.style.base.field_mut(*index) =
+                        shared_style::Style::default_field(*index);
                 }
                 SettingsRow::SectionSpacer | SettingsRow::SectionHeader(_) => {}
             }
@@ -1022,7 +1135,7 @@ pub fn handle_key(
                         config.playback = Playback::default();
                     }
                     Section::Colors => {
-                        config.style = shared_style::Style::default();
+                        config.style.base = shared_style::Style::default();
                     }
                     Section::General => {
                         let extra = config.general.extra.clone();
@@ -1180,6 +1293,8 @@ fn build_preview_entries(
             starred: album.starred,
             album_id: AlbumId(format!("preview-album-{album_idx}").into()),
             cover_art_id: Some(art_id.clone()),
+            missing_art_color: None,
+            gain: None,
         };
 
         let tracks: Vec<_> = album
@@ -1197,6 +1312,7 @@ fn build_preview_entries(
                 starred: false,
                 play_count: None,
                 cover_art_id: Some(art_id.clone()),
+                missing_art_color: None,
                 track_index_in_group: track_idx,
             })
             .collect();
@@ -1292,6 +1408,7 @@ fn draw_library_preview(
         track_name_hovered_color: style.track_name_hovered_color(),
         track_length_color: style.track_length_color(),
         track_duration_color: style.track_duration_color(),
+        abbreviate_play_counts: config.layout.base.abbreviate_play_counts,
         playing_track_id: playing_track_id.as_ref(),
         selected_index,
         underline_index: None,
@@ -0,0 +1,61 @@
+//! The optional status line above the help bar, summarising volume, playback
+//! mode, sort order and the state of the server connection.
+
+use blackbird_core::{self as bc, AppStateError};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::app::App;
+
+use super::StyleExt;
+
+const SEPARATOR: &str = "  \u{00B7}  ";
+
+pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
+    let style = &app.config.style.base;
+    let label_style = Style::default().fg(style.track_duration_color());
+    let value_style = Style::default().fg(style.text_color());
+
+    let volume = format!("{:.0}%", app.logic.get_volume() * 100.0);
+    let fields = [
+        ("vol ", volume),
+        ("mode ", app.logic.get_playback_mode().as_str().to_string()),
+        ("sort ", app.logic.get_sort_order().as_str().to_string()),
+        ("", connection_state(&app.logic)),
+    ];
+
+    let mut spans = vec![];
+    for (i, (label, value)) in fields.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(SEPARATOR, label_style));
+        }
+        spans.push(Span::styled(label, label_style));
+        spans.push(Span::styled(value, value_style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Describes the connection to the server: whether the library has loaded,
+/// is being scanned, or failed to load, and whether the server still accepts
+/// our credentials.
+fn connection_state(logic: &bc::Logic) -> String {
+    if logic.has_rejected_credentials() {
+        return "credentials rejected".to_string();
+    }
+    if let Some(AppStateError::InitialFetchFailed { .. }) = logic.get_error() {
+        return "disconnected".to_string();
+    }
+    if !logic.has_loaded_all_tracks() {
+        return "loading library".to_string();
+    }
+    let username = logic.get_server_username();
+    match logic.get_server_scan_count() {
+        Some(count) => format!("connected as {username}, scanning ({count})"),
+        None => format!("connected as {username}"),
+    }
+}
@@ -0,0 +1,84 @@
+//! Jumping to a track of the selected album by typing its number, opened
+//! with `#`.
+
+use blackbird_core::{self as bc, TrackNumber, blackbird_state::AlbumId};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+
+use crate::{app::App, keys::Action};
+
+/// Width of the popup, enough for the title and a `disc.track` number.
+const POPUP_WIDTH: u16 = 24;
+
+/// Typing a track number, as `track` or `disc.track`, to play that track of
+/// an album.
+pub struct TrackNumberState {
+    album_id: AlbumId,
+    pub number: String,
+}
+
+impl TrackNumberState {
+    pub fn new(album_id: AlbumId) -> Self {
+        Self {
+            album_id,
+            number: String::new(),
+        }
+    }
+
+    /// Returns true once the entry is finished and should close.
+    pub fn handle_key(&mut self, logic: &bc::Logic, action: Action) -> bool {
+        match action {
+            Action::Back => return true,
+            Action::Select => {
+                let played = TrackNumber::parse(&self.number)
+                    .is_some_and(|number| logic.play_track_number(&self.album_id, number));
+                if !played {
+                    tracing::info!("No track {:?} in this album", self.number);
+                }
+                return true;
+            }
+            Action::DeleteChar => {
+                self.number.pop();
+            }
+            Action::Char(c) if c.is_ascii_digit() || (c == '.' && !self.number.contains('.')) => {
+                self.number.push(c);
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+pub fn draw(frame: &mut Frame, app: &App, size: Rect) {
+    let Some(entry) = &app.track_number else {
+        return;
+    };
+    let style = &app.config.style.base;
+    let bg = super::effective_bg(&app.config);
+
+    let width = POPUP_WIDTH.min(size.width);
+    let height = 3.min(size.height);
+    let x = size.x + size.width.saturating_sub(width) / 2;
+    let y = size.y + size.height.saturating_sub(height) / 2;
+    let rect = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, rect);
+    let accent = Style::default().fg(style.track_name_playing_color());
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("# ", accent),
+        Span::styled(&entry.number, Style::default().fg(style.text_color())),
+        Span::styled("\u{2588}", accent),
+    ]))
+    .block(
+        Block::bordered()
+            .title(" track number ")
+            .border_style(accent)
+            .style(Style::default().fg(style.text_color()).bg(bg)),
+    );
+    frame.render_widget(input, rect);
+}
@@ -29,6 +29,14 @@ pub struct General {
     pub window_height: u32,
     pub volume: f32,
     pub incremental_search_timeout_ms: u64,
+    /// Whether to briefly show the new track's art and title over the
+    /// library when a track starts.
+    pub now_playing_osd: bool,
+    /// How long the now playing overlay stays up, in seconds.
+    pub now_playing_osd_secs: f32,
+    /// Whether the window title shows the playing track and whether it's
+    /// paused, rather than just "blackbird".
+    pub dynamic_window_title: bool,
     /// Catch-all for unknown fields (e.g. TUI-specific settings like tick_rate_ms).
     #[serde(flatten)]
     pub extra: toml::Table,
@@ -43,6 +51,9 @@ impl Default for General {
             window_height: 1280,
             volume: 1.0,
             incremental_search_timeout_ms: 5000,
+            now_playing_osd: false,
+            now_playing_osd_secs: 2.5,
+            dynamic_window_title: true,
             extra: toml::Table::new(),
         }
     }
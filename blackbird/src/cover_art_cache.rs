@@ -67,6 +67,21 @@ impl CoverArtCache {
         }
     }
 
+    /// Sets the size requested from the server for full-res art (`None` for
+    /// the original image). Full-res images already decoded by egui are
+    /// forgotten so the re-fetched bytes replace them.
+    pub fn set_full_res_size(&mut self, ctx: &egui::Context, size: Option<usize>) {
+        for cover_art_id in self.inner.set_full_res_size(size) {
+            ctx.forget_image(&format!("bytes://full/{}", cover_art_id.0));
+        }
+    }
+
+    /// Whether full-resolution art for `cover_art_id` has arrived.
+    pub fn is_full_res_loaded(&self, cover_art_id: &CoverArtId) -> bool {
+        self.inner
+            .is_resolution_loaded(cover_art_id, Resolution::Full)
+    }
+
     /// Record a `Nearby` demand for library-resolution art: albums just
     /// outside the viewport, kept warm so scrolling doesn't flash
     /// placeholder art.
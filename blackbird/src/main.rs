@@ -17,6 +17,9 @@ fn main() {
     #[cfg(feature = "tray-icon")]
     blackbird_client_shared::tray::init_platform();
 
+    // Check the platform's reduced-motion preference off the UI thread.
+    blackbird_client_shared::accessibility::detect_platform_reduced_motion();
+
     // Log to a file so that shutdown diagnostics are visible even when the
     // GUI window has closed.
     let log_dir = blackbird_shared::paths::data_dir();
@@ -53,12 +56,44 @@ fn main() {
         base_url: config.shared.server.base_url.clone(),
         username: config.shared.server.username.clone(),
         password: config.shared.server.password.clone(),
+        client_id: config
+            .shared
+            .server
+            .client_id("blackbird", env!("CARGO_PKG_VERSION")),
         transcode: config.shared.server.transcode,
+        transcode_format: config.shared.server.transcode_format.clone(),
+        api_version: config.shared.server.api_version.clone(),
+        fetch_options: bc::blackbird_state::FetchOptions {
+            album_page_size: config.shared.server.album_page_size,
+            track_page_size: config.shared.server.track_page_size,
+            merge_editions: config.shared.layout.merge_editions,
+        },
+        scrobble_targets: config.shared.playback.scrobbling.clone(),
         volume: config.general.volume,
         apply_replaygain: config.shared.playback.apply_replaygain,
         replaygain_preamp_db: config.shared.playback.replaygain_preamp_db,
+        track_gains: config
+            .shared
+            .playback
+            .track_gains_db
+            .iter()
+            .map(|(id, gain)| (id.clone(), *gain))
+            .collect(),
+        skipped_tracks: config
+            .shared
+            .playback
+            .skipped_tracks
+            .iter()
+            .cloned()
+            .collect(),
+        skip_silence_db: config.shared.playback.skip_silence_db(),
         sort_order: config.shared.last_playback.sort_order,
         playback_mode: config.shared.last_playback.playback_mode,
+        preferred_shuffle: config.shared.last_playback.preferred_shuffle,
+        end_behavior: config.shared.playback.end_behavior,
+        stop_behavior: config.shared.playback.stop_behavior,
+        previous_behavior: config.shared.playback.previous_behavior,
+        previous_restart_threshold: config.shared.playback.previous_restart_threshold(),
         last_playback: config.shared.last_playback.as_track_and_position(),
         cover_art_loaded_tx,
         lyrics_loaded_tx,
@@ -124,6 +159,8 @@ pub struct App {
     /// Suppresses the config reload thread while settings is open, preventing
     /// disk values from clobbering in-memory edits.
     config_reload_suppressed: Arc<AtomicBool>,
+    /// Applies playback setting changes from the config to the logic.
+    config_sync: blackbird_client_shared::config_sync::ConfigSync,
     _config_reload_thread: std::thread::JoinHandle<()>,
     _repaint_thread: std::thread::JoinHandle<()>,
     playback_to_logic_rx: bc::PlaybackToLogicRx,
@@ -193,7 +230,7 @@ impl App {
                     }
                 })
             },
-            logic.subscribe_to_playback_events(),
+            logic.subscribe_events(),
             logic.request_handle(),
             logic.get_state(),
         )
@@ -204,10 +241,7 @@ impl App {
         let ui_state = ui::initialize(cc, &config.read().unwrap());
 
         #[cfg(feature = "tray-icon")]
-        let (tray_icon, tray_menu) = {
-            let current_playback_mode = logic.get_playback_mode();
-            blackbird_client_shared::tray::TrayMenu::new(icon, current_playback_mode)
-        };
+        let (tray_icon, tray_menu) = blackbird_client_shared::tray::TrayMenu::new(icon, &logic);
 
         let global_hotkey_manager =
             GlobalHotKeyManager::new().expect("Failed to create global hotkey manager");
@@ -248,9 +282,10 @@ impl App {
 
             config,
             config_reload_suppressed,
+            config_sync: Default::default(),
             _config_reload_thread,
             _repaint_thread,
-            playback_to_logic_rx: logic.subscribe_to_playback_events(),
+            playback_to_logic_rx: logic.subscribe_events(),
             logic,
             cover_art_cache,
             lyrics_loaded_rx,
@@ -322,15 +357,27 @@ impl eframe::App for App {
         }
 
         #[cfg(feature = "media-controls")]
-        self.controls.update();
-        // Keep ReplayGain settings in sync with the config. Cheap: the
-        // setters are no-ops when the value is unchanged.
+        self.controls.update(&self.logic);
+        // Keep the playback settings in sync with the config.
         {
             let cfg = self.config.read().unwrap();
-            self.logic
-                .set_apply_replaygain(cfg.shared.playback.apply_replaygain);
-            self.logic
-                .set_replaygain_preamp_db(cfg.shared.playback.replaygain_preamp_db);
+            self.config_sync.apply(
+                &mut self.logic,
+                &cfg.shared.playback,
+                &cfg.shared.queue,
+                &cfg.shared.server,
+                &cfg.shared.layout,
+            );
+
+            // Input can't be seen while the window is in the background, so
+            // don't let that count as idling unless configured to.
+            let (had_input, focused) =
+                ctx.input(|i| (!i.events.is_empty() || i.pointer.is_moving(), i.focused));
+            if had_input || (!focused && !cfg.shared.playback.idle_pause_in_background) {
+                self.logic.note_user_activity();
+            }
+            self.cover_art_cache
+                .set_full_res_size(ctx, cfg.shared.layout.full_art_size);
         }
         self.logic.update();
         // Reconcile against the previous frame's demand, then start a new
@@ -356,6 +403,11 @@ impl eframe::App for App {
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // The controls are never dropped, so their cover art file is removed
+        // here.
+        #[cfg(feature = "media-controls")]
+        self.controls.remove_cover_art();
+
         let mut config = self.config.write().unwrap();
         if let Some((x, y)) = self.current_window_position {
             config.general.window_position_x = x;
@@ -372,7 +424,15 @@ impl eframe::App for App {
                 track_and_position.position.as_secs_f64();
         }
         config.shared.last_playback.playback_mode = self.logic.get_playback_mode();
+        config.shared.last_playback.preferred_shuffle = self.logic.get_preferred_shuffle();
         config.shared.last_playback.sort_order = self.logic.get_sort_order();
+        config.shared.playback.track_gains_db = self.logic.get_track_gains().into_iter().collect();
+        config.shared.playback.skipped_tracks =
+            self.logic.get_skipped_tracks().into_iter().collect();
+        config
+            .shared
+            .search
+            .store_history(&self.ui_state.search.history);
         config.save();
     }
 }
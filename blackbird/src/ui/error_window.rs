@@ -0,0 +1,92 @@
+//! The error window. When the initial fetch fails, it also offers to edit the
+//! server settings and retry, so a bad first-time setup can be fixed in place.
+use blackbird_shared::config::Server;
+use egui::{Context, Grid, RichText, TextEdit, Window};
+
+use crate::{bc, config::Config};
+
+#[derive(Default)]
+pub struct ErrorWindowState {
+    /// The server settings being edited, copied from the config when the
+    /// initial fetch fails.
+    server: Option<Server>,
+    /// Whether the password field is visible.
+    show_password: bool,
+}
+
+/// What the user did in the error window.
+pub enum ErrorWindowAction {
+    Close,
+    OpenSettings,
+    /// Retry the initial fetch with these server settings.
+    Retry(Server),
+}
+
+pub fn ui(
+    ctx: &Context,
+    logic: &bc::Logic,
+    config: &Config,
+    state: &mut ErrorWindowState,
+) -> Option<ErrorWindowAction> {
+    let Some(error) = logic.get_error() else {
+        state.server = None;
+        return None;
+    };
+    // Credentials rejected before the library loaded also stop the initial
+    // fetch, so they can be corrected and retried here too.
+    let fetch_failed = matches!(error, bc::AppStateError::InitialFetchFailed { .. })
+        || (matches!(error, bc::AppStateError::AuthenticationFailed { .. })
+            && !logic.has_loaded_all_tracks());
+    if !fetch_failed {
+        state.server = None;
+    } else if state.server.is_none() {
+        state.server = Some(config.shared.server.clone());
+    }
+
+    let mut open = true;
+    let mut action = None;
+    Window::new("Error").open(&mut open).show(ctx, |ui| {
+        ui.label(RichText::new(error.display_name()).heading());
+        ui.label(RichText::new(
+            error.display_message(&logic.get_state().read().unwrap()),
+        ));
+        if matches!(error, bc::AppStateError::AuthenticationFailed { .. })
+            && ui.button("Open settings").clicked()
+        {
+            action = Some(ErrorWindowAction::OpenSettings);
+        }
+
+        let Some(server) = &mut state.server else {
+            return;
+        };
+        ui.separator();
+        Grid::new("error_window_server")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Base URL");
+                ui.text_edit_singleline(&mut server.base_url);
+                ui.end_row();
+
+                ui.label("Username");
+                ui.text_edit_singleline(&mut server.username);
+                ui.end_row();
+
+                ui.label("Password");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut server.password).password(!state.show_password),
+                    );
+                    ui.checkbox(&mut state.show_password, "Show");
+                });
+                ui.end_row();
+            });
+        if ui.button("Retry").clicked() {
+            action = Some(ErrorWindowAction::Retry(server.clone()));
+        }
+    });
+
+    if !open {
+        action = Some(ErrorWindowAction::Close);
+    }
+    action
+}
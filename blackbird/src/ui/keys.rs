@@ -49,12 +49,45 @@ pub const KEY_GOTO_PLAYING: Key = Key::G;
 pub const KEY_SEARCH_INLINE: Key = Key::Slash;
 pub const KEY_LYRICS: Key = Key::L;
 pub const KEY_QUEUE: Key = Key::U;
+pub const KEY_RECENT_ALBUMS: Key = Key::H;
+pub const KEY_LISTENERS: Key = Key::W;
+pub const KEY_SERVER_INFO: Key = Key::Questionmark;
+pub const KEY_LISTENING_STATS: Key = Key::T;
 pub const KEY_QUIT: Key = Key::Q;
 pub const KEY_STAR: Key = Key::Num8; // '*' is Shift+8
+pub const KEY_NEXT_LYRIC_LINE: Key = Key::Num0; // ')' is Shift+0
+pub const KEY_PREVIOUS_LYRIC_LINE: Key = Key::Num9; // '(' is Shift+9
+pub const KEY_STAR_ALBUM: Key = Key::F;
+/// Toggles the focused heart. Not Space, which is play/pause everywhere.
+pub const KEY_TOGGLE_FOCUSED_HEART: Key = Key::Enter;
 pub const KEY_VOLUME_UP: Key = Key::ArrowUp;
 pub const KEY_VOLUME_DOWN: Key = Key::ArrowDown;
 pub const KEY_TOGGLE_SORT: Key = Key::O;
 pub const KEY_SETTINGS: Key = Key::I;
+pub const KEY_RESCAN: Key = Key::R;
+pub const KEY_ALBUM_RADIO: Key = Key::A;
+pub const KEY_COPY_NOW_PLAYING: Key = Key::C;
+pub const KEY_DOWNLOAD: Key = Key::D;
+pub const KEY_TOGGLE_TRANSCODE: Key = Key::X;
+/// Shift+H, as H opens the recent albums.
+pub const KEY_HIDE_UNSTARRED: Key = Key::H;
+pub const KEY_SKIP_TRACK: Key = Key::K;
+pub const KEY_TOGGLE_SHUFFLE: Key = Key::Z;
+/// Shift+Z.
+pub const KEY_CYCLE_SHUFFLE_SCOPE: Key = Key::Z;
+/// The digits, each seeking to that many tenths of the way through the track.
+pub const KEYS_SEEK_TENTHS: [Key; 10] = [
+    Key::Num0,
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+];
 
 /// Actions that can be triggered by keyboard shortcuts.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -66,18 +99,41 @@ pub enum Action {
     NextGroup,
     PreviousGroup,
     CyclePlaybackMode(Direction),
+    /// Switches between the preferred shuffle mode and sequential playback.
+    ToggleShuffle,
+    /// Switches to the next shuffle mode.
+    CycleShuffleScope,
     ToggleSortOrder(Direction),
     Star,
+    StarAlbum,
     SeekForward,
     SeekBackward,
+    /// Seeks to the start of the next synced lyrics line.
+    NextLyricLine,
+    /// Seeks to the start of the previous synced lyrics line.
+    PreviousLyricLine,
+    /// Seeks to the given number of tenths of the way through the track.
+    SeekTenths(u8),
     GotoPlaying,
     SearchInline,
     Lyrics,
     Queue,
+    RecentAlbums,
+    Listeners,
+    ServerInfo,
+    ListeningStats,
     Quit,
     VolumeUp,
     VolumeDown,
     Settings,
+    Rescan,
+    AlbumRadio,
+    CopyNowPlaying,
+    Download,
+    ToggleTranscode,
+    HideUnstarred,
+    /// Toggles whether the playing track is always skipped.
+    SkipTrack,
 }
 
 impl Action {
@@ -91,18 +147,35 @@ impl Action {
             Action::NextGroup => KEY_NEXT,
             Action::PreviousGroup => KEY_PREVIOUS,
             Action::CyclePlaybackMode(_) => KEY_CYCLE_MODE,
+            Action::ToggleShuffle => KEY_TOGGLE_SHUFFLE,
+            Action::CycleShuffleScope => KEY_CYCLE_SHUFFLE_SCOPE,
             Action::ToggleSortOrder(_) => KEY_TOGGLE_SORT,
             Action::Star => KEY_STAR,
+            Action::StarAlbum => KEY_STAR_ALBUM,
             Action::SeekForward => KEY_SEEK_FWD,
             Action::SeekBackward => KEY_SEEK_BACK,
+            Action::NextLyricLine => KEY_NEXT_LYRIC_LINE,
+            Action::PreviousLyricLine => KEY_PREVIOUS_LYRIC_LINE,
+            Action::SeekTenths(tenths) => KEYS_SEEK_TENTHS[usize::from(*tenths)],
             Action::GotoPlaying => KEY_GOTO_PLAYING,
             Action::SearchInline => KEY_SEARCH_INLINE,
             Action::Lyrics => KEY_LYRICS,
             Action::Queue => KEY_QUEUE,
+            Action::RecentAlbums => KEY_RECENT_ALBUMS,
+            Action::Listeners => KEY_LISTENERS,
+            Action::ServerInfo => KEY_SERVER_INFO,
+            Action::ListeningStats => KEY_LISTENING_STATS,
             Action::Quit => KEY_QUIT,
             Action::VolumeUp => KEY_VOLUME_UP,
             Action::VolumeDown => KEY_VOLUME_DOWN,
             Action::Settings => KEY_SETTINGS,
+            Action::Rescan => KEY_RESCAN,
+            Action::AlbumRadio => KEY_ALBUM_RADIO,
+            Action::CopyNowPlaying => KEY_COPY_NOW_PLAYING,
+            Action::Download => KEY_DOWNLOAD,
+            Action::ToggleTranscode => KEY_TOGGLE_TRANSCODE,
+            Action::HideUnstarred => KEY_HIDE_UNSTARRED,
+            Action::SkipTrack => KEY_SKIP_TRACK,
         }
     }
 
@@ -129,10 +202,15 @@ impl Action {
         let key_label: Cow<'static, str> = match self {
             // Star is Shift+8, so we display '*' instead of '8'.
             Action::Star => "*".into(),
+            // Likewise Shift+0 and Shift+9.
+            Action::NextLyricLine => ")".into(),
+            Action::PreviousLyricLine => "(".into(),
+            Action::SeekTenths(_) => "0-9".into(),
             // Shifted actions: display the key in uppercase.
-            Action::NextGroup | Action::PreviousGroup => {
-                self.key().symbol_or_name().to_string().into()
-            }
+            Action::NextGroup
+            | Action::PreviousGroup
+            | Action::HideUnstarred
+            | Action::CycleShuffleScope => self.key().symbol_or_name().to_string().into(),
             // Cycle pairs combine forward (lowercase) and backward (uppercase).
             Action::CyclePlaybackMode(Direction::Forward)
             | Action::ToggleSortOrder(Direction::Forward) => {
@@ -158,20 +236,64 @@ impl Action {
             Action::CyclePlaybackMode(Direction::Forward) => {
                 format!("mode ({})", logic.get_playback_mode().as_str()).into()
             }
+            Action::ToggleShuffle => {
+                if logic.get_playback_mode().is_shuffle() {
+                    "unshuffle".into()
+                } else {
+                    format!("shuffle ({})", logic.get_preferred_shuffle()).into()
+                }
+            }
+            Action::CycleShuffleScope => "shuffle scope".into(),
             Action::ToggleSortOrder(Direction::Forward) => {
                 format!("sort ({})", logic.get_sort_order().as_str()).into()
             }
             Action::Star => "star".into(),
+            Action::StarAlbum => "star album".into(),
             Action::SeekForward => "seek+".into(),
             Action::SeekBackward => "seek-".into(),
+            Action::NextLyricLine => "next line".into(),
+            Action::PreviousLyricLine => "prev line".into(),
+            Action::SeekTenths(_) => "seek %".into(),
             Action::GotoPlaying => "goto".into(),
             Action::SearchInline => "search".into(),
             Action::Lyrics => "lyrics".into(),
             Action::Queue => "queue".into(),
+            Action::RecentAlbums => "recent".into(),
+            Action::Listeners => "listeners".into(),
+            Action::ServerInfo => "server".into(),
+            Action::ListeningStats => "stats".into(),
             Action::Quit => "quit".into(),
             Action::VolumeUp => "vol+".into(),
             Action::VolumeDown => "vol-".into(),
             Action::Settings => "settings".into(),
+            Action::Rescan => match logic.get_server_scan_count() {
+                Some(count) => format!("scanning ({count})").into(),
+                None => "rescan".into(),
+            },
+            Action::AlbumRadio => "radio".into(),
+            Action::CopyNowPlaying => "copy".into(),
+            Action::Download => "download".into(),
+            Action::ToggleTranscode => {
+                let state = if logic.get_transcode() { "on" } else { "off" };
+                format!("transcode ({state})").into()
+            }
+            Action::HideUnstarred => {
+                if logic.get_hide_unstarred() {
+                    "show all".into()
+                } else {
+                    "starred only".into()
+                }
+            }
+            Action::SkipTrack => {
+                let skipped = logic
+                    .get_playing_track_id()
+                    .is_some_and(|track_id| logic.is_track_skipped(&track_id));
+                if skipped {
+                    "unskip".into()
+                } else {
+                    "always skip".into()
+                }
+            }
             // Hidden via the early return above.
             Action::CyclePlaybackMode(Direction::Backward)
             | Action::ToggleSortOrder(Direction::Backward) => unreachable!(),
@@ -189,14 +311,34 @@ pub const LIBRARY_HELP: &[HelpEntry] = &[
     HelpEntry::Pair(Action::NextGroup, Action::PreviousGroup, "next/prev group"),
     HelpEntry::Single(Action::Stop),
     HelpEntry::Pair(Action::SeekBackward, Action::SeekForward, "seek-/+"),
+    HelpEntry::Single(Action::SeekTenths(0)),
+    HelpEntry::Pair(
+        Action::PreviousLyricLine,
+        Action::NextLyricLine,
+        "prev/next line",
+    ),
     HelpEntry::Single(Action::Star),
+    HelpEntry::Single(Action::StarAlbum),
+    HelpEntry::Single(Action::SkipTrack),
+    HelpEntry::Single(Action::HideUnstarred),
     HelpEntry::Single(Action::GotoPlaying),
     HelpEntry::Single(Action::SearchInline),
     HelpEntry::Single(Action::Lyrics),
     HelpEntry::Single(Action::Queue),
+    HelpEntry::Single(Action::RecentAlbums),
+    HelpEntry::Single(Action::Listeners),
+    HelpEntry::Single(Action::AlbumRadio),
+    HelpEntry::Single(Action::CopyNowPlaying),
+    HelpEntry::Single(Action::Download),
     HelpEntry::Pair(Action::VolumeUp, Action::VolumeDown, "vol+/-"),
     HelpEntry::Single(Action::CyclePlaybackMode(Direction::Forward)),
+    HelpEntry::Single(Action::ToggleShuffle),
+    HelpEntry::Single(Action::CycleShuffleScope),
     HelpEntry::Single(Action::ToggleSortOrder(Direction::Forward)),
+    HelpEntry::Single(Action::ToggleTranscode),
+    HelpEntry::Single(Action::Rescan),
+    HelpEntry::Single(Action::ServerInfo),
+    HelpEntry::Single(Action::ListeningStats),
     HelpEntry::Single(Action::Settings),
 ];
 
@@ -212,6 +354,9 @@ pub fn library_action(key: Key, shift: bool) -> Option<Action> {
         KEY_PLAY_PAUSE => Some(Action::PlayPause),
         KEY_STOP => Some(Action::Stop),
         KEY_NEXT if shift => Some(Action::NextGroup),
+        KEY_HIDE_UNSTARRED if shift => Some(Action::HideUnstarred),
+        KEY_CYCLE_SHUFFLE_SCOPE if shift => Some(Action::CycleShuffleScope),
+        KEY_TOGGLE_SHUFFLE => Some(Action::ToggleShuffle),
         KEY_NEXT => Some(Action::Next),
         KEY_PREVIOUS if shift => Some(Action::PreviousGroup),
         KEY_PREVIOUS => Some(Action::Previous),
@@ -223,12 +368,101 @@ pub fn library_action(key: Key, shift: bool) -> Option<Action> {
         KEY_SEARCH_INLINE => Some(Action::SearchInline),
         KEY_LYRICS => Some(Action::Lyrics),
         KEY_QUEUE => Some(Action::Queue),
+        KEY_RECENT_ALBUMS => Some(Action::RecentAlbums),
+        KEY_LISTENERS => Some(Action::Listeners),
+        KEY_SERVER_INFO => Some(Action::ServerInfo),
+        KEY_LISTENING_STATS => Some(Action::ListeningStats),
         KEY_QUIT => Some(Action::Quit),
         // '*' is Shift+8.
         KEY_STAR if shift => Some(Action::Star),
+        KEY_NEXT_LYRIC_LINE if shift => Some(Action::NextLyricLine),
+        KEY_PREVIOUS_LYRIC_LINE if shift => Some(Action::PreviousLyricLine),
+        KEY_STAR_ALBUM => Some(Action::StarAlbum),
         KEY_VOLUME_UP => Some(Action::VolumeUp),
         KEY_VOLUME_DOWN => Some(Action::VolumeDown),
         KEY_SETTINGS => Some(Action::Settings),
+        KEY_RESCAN => Some(Action::Rescan),
+        KEY_ALBUM_RADIO => Some(Action::AlbumRadio),
+        KEY_COPY_NOW_PLAYING => Some(Action::CopyNowPlaying),
+        KEY_DOWNLOAD => Some(Action::Download),
+        KEY_TOGGLE_TRANSCODE => Some(Action::ToggleTranscode),
+        KEY_SKIP_TRACK => Some(Action::SkipTrack),
+        // Shift+8, Shift+9 and Shift+0 are '*', '(' and ')', matched above.
+        _ if !shift => KEYS_SEEK_TENTHS
+            .iter()
+            .position(|&k| k == key)
+            .map(|tenths| Action::SeekTenths(tenths as u8)),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn help_actions() -> Vec<Action> {
+        LIBRARY_HELP
+            .iter()
+            .flat_map(|entry| match *entry {
+                HelpEntry::Single(a) => vec![a],
+                HelpEntry::Pair(a, b, _) => vec![a, b],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_help_entry_is_reachable_from_its_key() {
+        for action in help_actions() {
+            assert!(
+                [false, true]
+                    .into_iter()
+                    .any(|shift| library_action(action.key(), shift) == Some(action)),
+                "{action:?} is not bound to {:?}",
+                action.key()
+            );
+        }
+    }
+
+    #[test]
+    fn digits_seek_unless_shifted() {
+        assert_eq!(
+            library_action(Key::Num0, false),
+            Some(Action::SeekTenths(0))
+        );
+        assert_eq!(
+            library_action(Key::Num8, false),
+            Some(Action::SeekTenths(8))
+        );
+        assert_eq!(library_action(Key::Num8, true), Some(Action::Star));
+        assert_eq!(library_action(Key::Num0, true), Some(Action::NextLyricLine));
+        assert_eq!(library_action(Key::Num5, true), None);
+    }
+
+    #[test]
+    fn focused_hearts_are_not_toggled_by_play_pause() {
+        assert_ne!(KEY_TOGGLE_FOCUSED_HEART, KEY_PLAY_PAUSE);
+    }
+
+    /// Keyboard-only checklist: each of these is otherwise done by clicking
+    /// a heart, a transport icon or the scrub bar.
+    #[test]
+    fn mouse_controls_have_keyboard_equivalents() {
+        let actions = help_actions();
+        for action in [
+            Action::Star,
+            Action::StarAlbum,
+            Action::PlayPause,
+            Action::Stop,
+            Action::Next,
+            Action::Previous,
+            Action::SeekForward,
+            Action::SeekBackward,
+            Action::VolumeUp,
+            Action::VolumeDown,
+            Action::GotoPlaying,
+            Action::CyclePlaybackMode(Direction::Forward),
+        ] {
+            assert!(actions.contains(&action), "{action:?} has no shortcut");
+        }
+    }
+}
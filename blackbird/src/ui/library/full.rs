@@ -12,6 +12,10 @@ pub struct FullLibraryState {
     pub search_open: bool,
     pub lyrics_open: bool,
     pub queue_open: bool,
+    pub recent_open: bool,
+    pub listeners_open: bool,
+    pub server_info_open: bool,
+    pub listening_stats_open: bool,
 }
 
 /// Main library UI.
@@ -30,8 +34,13 @@ pub fn ui(
     ui_state: &FullLibraryState,
 ) -> Option<(CoverArtId, Rect)> {
     // Only capture keyboard input if search modal and lyrics window are not open
-    let can_handle_incremental_search =
-        !ui_state.search_open && !ui_state.lyrics_open && !ui_state.queue_open;
+    let can_handle_incremental_search = !ui_state.search_open
+        && !ui_state.lyrics_open
+        && !ui_state.queue_open
+        && !ui_state.recent_open
+        && !ui_state.listeners_open
+        && !ui_state.server_info_open
+        && !ui_state.listening_stats_open;
 
     render_library_view(
         ui,
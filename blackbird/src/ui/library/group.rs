@@ -1,10 +1,13 @@
-use blackbird_client_shared::config::AlbumArtStyle;
+use blackbird_client_shared::{
+    accessibility::StarTarget,
+    config::{AlbumArtStyle, MissingArt},
+};
 use blackbird_core::{AppState, Logic};
 use egui::{Align, Align2, Label, Layout, RichText, TextFormat, TextStyle, Ui, pos2, vec2};
 
 use crate::{
     bc::{
-        blackbird_state::{Group, TrackId},
+        blackbird_state::{AlbumId, Group, TrackId},
         util,
     },
     cover_art_cache::{CachePriority, CoverArtCache},
@@ -22,6 +25,8 @@ pub const GROUP_ALBUM_ART_LINE_COUNT: usize = 8;
 
 pub struct GroupResponse<'a> {
     pub clicked_track: Option<&'a TrackId>,
+    /// Whether the album name was clicked, to play the album.
+    pub clicked_album: bool,
     pub clicked_heart: bool,
     /// When set, the user is hovering over album art. Contains the cover art ID
     /// and the screen-space rect of the thumbnail.
@@ -38,8 +43,12 @@ pub fn ui<'a>(
     incremental_search_target: Option<&TrackId>,
     cover_art_cache: &mut CoverArtCache,
     album_art_style: AlbumArtStyle,
+    missing_art: MissingArt,
+    show_album_gain: bool,
+    abbreviate_play_counts: bool,
 ) -> GroupResponse<'a> {
     let mut clicked_track = None;
+    let mut clicked_album = false;
     let mut clicked_heart = false;
     let mut hovered_art: Option<(blackbird_core::blackbird_state::CoverArtId, egui::Rect)> = None;
 
@@ -68,11 +77,7 @@ pub fn ui<'a>(
             ui.add_space(LEFT_OF_ALBUM_ART_LEFT_MARGIN);
             let art_rect =
                 egui::Rect::from_min_size(ui.cursor().left_top(), vec2(art_size, art_size));
-            egui::Image::new(
-                cover_art_cache.get(group.cover_art_id.as_ref(), CachePriority::Visible),
-            )
-            .show_loading_spinner(false)
-            .paint_at(ui, art_rect);
+            paint_art(ui, style, group, art_rect, cover_art_cache, missing_art);
             // Sense hover on the art area.
             let art_response = ui.allocate_rect(art_rect, egui::Sense::hover());
             if art_response.hovered()
@@ -131,19 +136,28 @@ pub fn ui<'a>(
                             );
                         }
                     }
-                    ui.add(Label::new(layout_job).selectable(false));
+                    let album_response = ui.add(
+                        Label::new(layout_job)
+                            .selectable(false)
+                            .sense(egui::Sense::click()),
+                    );
+                    if album_response.hovered() {
+                        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                    }
+                    clicked_album = album_response.clicked();
                 });
 
                 ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                    let (heart_response, _) = ui_util::draw_heart(
+                    let (_, heart_toggled, _) = ui_util::draw_heart(
                         ui,
                         TextStyle::Body.resolve(ui.style()),
                         ui_util::HeartPlacement::Space,
                         group.starred,
                         false,
+                        StarTarget::Album,
                     );
 
-                    if heart_response.clicked() {
+                    if heart_toggled {
                         clicked_heart = true;
                     }
 
@@ -154,6 +168,18 @@ pub fn ui<'a>(
                         )
                         .selectable(false),
                     );
+
+                    let gain = show_album_gain
+                        .then(|| logic.get_state().read().unwrap().library.group_gain(group))
+                        .flatten();
+                    if let Some(gain) = gain {
+                        ui.add(
+                            Label::new(
+                                RichText::new(gain.to_string()).color(style.album_year_color32()),
+                            )
+                            .selectable(false),
+                        );
+                    }
                 });
             });
         });
@@ -196,11 +222,7 @@ pub fn ui<'a>(
                     max: image_pos + vec2(image_size, image_size),
                 };
 
-                egui::Image::new(
-                    cover_art_cache.get(group.cover_art_id.as_ref(), CachePriority::Visible),
-                )
-                .show_loading_spinner(false)
-                .paint_at(ui, art_rect);
+                paint_art(ui, style, group, art_rect, cover_art_cache, missing_art);
                 ui.allocate_rect(art_rect, egui::Sense::hover());
 
                 let track_x = image_pos.x + image_size + image_right_margin;
@@ -222,6 +244,7 @@ pub fn ui<'a>(
                             max_track_length_width,
                             spaced_row_height,
                             total_spacing,
+                            abbreviate_play_counts,
                             &mut clicked_track,
                         );
                     },
@@ -259,6 +282,7 @@ pub fn ui<'a>(
                             max_track_length_width,
                             spaced_row_height,
                             total_spacing,
+                            abbreviate_play_counts,
                             &mut clicked_track,
                         );
                     },
@@ -269,11 +293,41 @@ pub fn ui<'a>(
 
     GroupResponse {
         clicked_track,
+        clicked_album,
         clicked_heart,
         hovered_art,
     }
 }
 
+/// Paints the group's art into `rect`, or its own colour if it has no art
+/// and that is configured, shaped as the style asks.
+fn paint_art(
+    ui: &Ui,
+    style: &style::Style,
+    group: &Group,
+    rect: egui::Rect,
+    cover_art_cache: &mut CoverArtCache,
+    missing_art: MissingArt,
+) {
+    if missing_art.shows_color(group) {
+        ui.painter().rect_filled(
+            rect,
+            style.album_art_corner_radius,
+            style::missing_art_colour(&group.artist, &group.album),
+        );
+    } else {
+        style::album_art_image(
+            style,
+            egui::Image::new(
+                cover_art_cache.get(group.cover_art_id.as_ref(), CachePriority::Visible),
+            ),
+        )
+        .show_loading_spinner(false)
+        .paint_at(ui, rect);
+    }
+    style::paint_album_art_border(ui, style, rect);
+}
+
 #[allow(clippy::too_many_arguments)]
 fn render_tracks<'a>(
     ui: &mut Ui,
@@ -287,6 +341,7 @@ fn render_tracks<'a>(
     max_track_length_width: f32,
     spaced_row_height: f32,
     total_spacing: f32,
+    abbreviate_play_counts: bool,
     clicked_track: &mut Option<&'a TrackId>,
 ) {
     for (track_index, track_id) in tracks.iter().enumerate() {
@@ -316,6 +371,7 @@ fn render_tracks<'a>(
                 incremental_search_target: incremental_search_target == Some(&track.id),
                 track_y,
                 track_row_height: spaced_row_height - total_spacing,
+                abbreviate_play_counts,
             },
         );
 
@@ -342,6 +398,22 @@ pub fn line_count_for_group_and_track(group: &Group, track_id: &TrackId) -> usiz
         + group.tracks.iter().take_while(|id| *id != track_id).count()
 }
 
+/// Returns the height of the library above the given album's group.
+pub fn scroll_height_for_album(
+    state: &AppState,
+    spaced_row_height: f32,
+    album_id: &AlbumId,
+    album_art_style: AlbumArtStyle,
+    album_spacing: usize,
+) -> Option<f32> {
+    let index = state.library.shown_group_index(album_id)?;
+    let rows: usize = state.library.shown_groups()[..index]
+        .iter()
+        .map(|group| line_count(group, album_art_style, album_spacing))
+        .sum();
+    Some(rows as f32 * spaced_row_height)
+}
+
 pub fn target_scroll_height_for_track(
     state: &AppState,
     spaced_row_height: f32,
@@ -352,15 +424,16 @@ pub fn target_scroll_height_for_track(
     let track = state.library.track_map.get(track_id)?;
     let album_id = track.album_id.as_ref()?;
 
+    // The track's group may be hidden, in which case there is nowhere to go.
     let mut scroll_to_rows = 0;
-    for group in &state.library.groups {
+    for group in state.library.shown_groups() {
         if group.album_id == *album_id {
             scroll_to_rows += line_count_for_group_and_track(group, track_id);
-            break;
+            return Some(scroll_to_rows as f32 * spaced_row_height);
         }
 
         scroll_to_rows += line_count(group, album_art_style, album_spacing);
     }
 
-    Some(scroll_to_rows as f32 * spaced_row_height)
+    None
 }
@@ -26,8 +26,9 @@ pub fn compute_positions(
     let app_state = app_state.read().unwrap();
 
     state.positions.clear();
+    state.hide_unstarred = app_state.library.hide_unstarred();
 
-    if app_state.library.groups.is_empty() {
+    if app_state.library.shown_groups().is_empty() {
         return;
     }
 
@@ -36,7 +37,7 @@ pub fn compute_positions(
     // Convert groups to (label, line_count) pairs based on sort order.
     let group_data: Vec<(Cow<'_, str>, usize)> = app_state
         .library
-        .groups
+        .shown_groups()
         .iter()
         .map(|grp| {
             let label: Cow<'_, str> = match sort_order {
@@ -148,7 +149,7 @@ fn compute_track_position_fraction(
     let mut current_row = 0;
     let mut track_row = None;
 
-    for group in &app_state.library.groups {
+    for group in app_state.library.shown_groups() {
         if group.album_id == *album_id {
             track_row = Some(current_row + group::line_count_for_group_and_track(group, track_id));
             break;
@@ -160,7 +161,7 @@ fn compute_track_position_fraction(
     let track_row = track_row?;
     let total_rows: usize = app_state
         .library
-        .groups
+        .shown_groups()
         .iter()
         .map(|g| group::line_count(g, album_art_style, album_spacing))
         .sum();
@@ -81,7 +81,14 @@ pub fn ui(
                     close_window = true;
                 }
 
-                render_player_controls(ui, logic, config, has_loaded_all_tracks, cover_art_cache);
+                render_player_controls(
+                    ui,
+                    logic,
+                    config,
+                    has_loaded_all_tracks,
+                    cover_art_cache,
+                    None,
+                );
 
                 // Take the scroll target (only scrolls once)
                 let scroll_target = state.scroll_to_track.take();
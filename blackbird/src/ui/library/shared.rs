@@ -1,6 +1,7 @@
 use std::time::Instant;
 
-use blackbird_core::blackbird_state::{CoverArtId, TrackId};
+use blackbird_client_shared::{accessibility, config::FollowPlaying};
+use blackbird_core::blackbird_state::{AlbumId, CoverArtId, TrackId};
 use egui::{Align, Pos2, Rect, ScrollArea, Spinner, Ui, pos2, style::ScrollStyle, vec2};
 
 use crate::{
@@ -29,15 +30,34 @@ pub struct IncrementalSearchState {
 pub struct LibraryScrollState {
     pub(crate) positions: Vec<(String, f32)>,
     pub(crate) needs_update: bool,
+    /// Whether unstarred tracks were hidden when `positions` were computed.
+    pub(crate) hide_unstarred: bool,
     pub(crate) cached_playing_track_id: Option<TrackId>,
     pub(crate) cached_playing_track_position: Option<f32>,
 }
 
+/// The group at the top of the viewport, and how far into it the view is
+/// scrolled.
+pub struct ScrollAnchor {
+    album_id: AlbumId,
+    offset: f32,
+}
+
 /// Shared state for library view rendering (used by both main library and mini-library)
 #[derive(Default)]
 pub struct LibraryViewState {
     pub(crate) library_scroll: LibraryScrollState,
     pub(crate) incremental_search: IncrementalSearchState,
+    /// Updated every frame, so that a refresh can keep the same group at
+    /// the top of the view.
+    pub(crate) scroll_anchor: Option<ScrollAnchor>,
+    pub(crate) reanchor_pending: bool,
+    /// Whether the user has scrolled the view since the last track change,
+    /// which suspends following the playing track.
+    pub(crate) user_scrolled: bool,
+    /// Whether the next scroll request is an explicit request to go to the
+    /// playing track, which is honoured however following is configured.
+    pub(crate) goto_requested: bool,
 }
 
 impl LibraryViewState {
@@ -46,6 +66,19 @@ impl LibraryViewState {
         self.library_scroll.cached_playing_track_id = None;
         self.library_scroll.cached_playing_track_position = None;
     }
+
+    /// Invalidates the view after the library was replaced or updated
+    /// underneath it, and scrolls back to the group that was at the top of
+    /// the view on the next frame, wherever it ended up.
+    pub fn reanchor_after_refresh(&mut self) {
+        self.invalidate_library_scroll();
+        self.reanchor_pending = true;
+    }
+
+    /// Scrolls to the playing track, and resumes following it.
+    pub fn goto_playing(&mut self, logic: &bc::Logic) {
+        self.goto_requested = logic.goto_playing().is_some();
+    }
 }
 
 // ============================================================================
@@ -63,7 +96,8 @@ pub(crate) struct LibraryViewConfig<'a> {
 }
 
 /// Render player controls: mouse button handling, now playing, scrub bar,
-/// and separator.
+/// and separator. `follow_playing`, if given, is shown as a toggle and
+/// updated when it's clicked.
 /// Returns track_to_scroll_to if the user clicked on the playing track info.
 pub(crate) fn render_player_controls(
     ui: &mut Ui,
@@ -71,6 +105,7 @@ pub(crate) fn render_player_controls(
     config: &Config,
     has_loaded_all_tracks: bool,
     cover_art_cache: &mut CoverArtCache,
+    follow_playing: Option<&mut FollowPlaying>,
 ) -> Option<TrackId> {
     ui.input(|i| {
         if let Some(button) = config
@@ -97,6 +132,7 @@ pub(crate) fn render_player_controls(
         has_loaded_all_tracks,
         &mut track_to_scroll_to,
         cover_art_cache,
+        follow_playing,
     );
 
     crate::ui::scrub_bar::ui(ui, logic, config);
@@ -123,7 +159,26 @@ pub(crate) fn render_library_view(
     let mut art_hover_request: Option<(CoverArtId, Rect)> = None;
     ui.scope(|ui| {
         if !has_loaded_all_tracks {
-            ui.add_sized(ui.available_size(), Spinner::new());
+            if config.shared.layout.wants_reduced_motion() {
+                let progress = accessibility::loading_progress_text(logic.get_fetch_progress());
+                ui.vertical_centered(|ui| {
+                    ui.add_space(ui.available_height() / 3.0);
+                    ui.label(progress);
+                });
+            } else {
+                ui.add_sized(ui.available_size(), Spinner::new());
+            }
+            return;
+        }
+
+        if logic.is_library_empty() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(ui.available_height() / 3.0);
+                ui.label(blackbird_client_shared::EMPTY_LIBRARY_MESSAGE);
+                if ui.button("Retry").clicked() {
+                    logic.refresh_library();
+                }
+            });
             return;
         }
 
@@ -131,7 +186,9 @@ pub(crate) fn render_library_view(
         let album_spacing = config.shared.layout.album_spacing;
 
         // Compute library scroll positions if library was populated
-        if view_state.library_scroll.needs_update {
+        if view_state.library_scroll.needs_update
+            || view_state.library_scroll.hide_unstarred != logic.get_hide_unstarred()
+        {
             library_scroll::compute_positions(
                 logic,
                 &mut view_state.library_scroll,
@@ -170,6 +227,22 @@ pub(crate) fn render_library_view(
         let area_offset_y = ui.cursor().top();
         let playing_track_id = logic.get_playing_track_id();
 
+        // Where the anchor group's view position is now, after a refresh.
+        let reanchor_height = if std::mem::take(&mut view_state.reanchor_pending) {
+            view_state.scroll_anchor.as_ref().and_then(|anchor| {
+                let height = group::scroll_height_for_album(
+                    &logic.get_state().read().unwrap(),
+                    spaced_row_height,
+                    &anchor.album_id,
+                    album_art_style,
+                    album_spacing,
+                )?;
+                Some(height + anchor.offset)
+            })
+        } else {
+            None
+        };
+
         ScrollArea::vertical()
             .auto_shrink(false)
             .show_viewport(ui, |ui, viewport| {
@@ -204,6 +277,15 @@ pub(crate) fn render_library_view(
                         },
                         Some(Align::Center),
                     );
+                } else if let Some(reanchor_height) = reanchor_height {
+                    let target_height = area_offset_y + reanchor_height - viewport.min.y;
+                    ui.scroll_to_rect(
+                        Rect {
+                            min: Pos2::new(viewport.min.x, target_height),
+                            max: Pos2::new(viewport.max.x, target_height + spaced_row_height),
+                        },
+                        Some(Align::Min),
+                    );
                 }
 
                 // Set the total height for the virtual content
@@ -241,6 +323,10 @@ pub(crate) fn render_library_view(
                 });
 
                 let mut current_row = visible_groups.start_row;
+                view_state.scroll_anchor = visible_groups.groups.first().map(|grp| ScrollAnchor {
+                    album_id: grp.album_id.clone(),
+                    offset: viewport.min.y - current_row as f32 * spaced_row_height,
+                });
 
                 for grp in visible_groups.groups {
                     let group_lines = group::line_count(&grp, album_art_style, album_spacing);
@@ -267,6 +353,9 @@ pub(crate) fn render_library_view(
                                 current_search_match.as_ref(),
                                 cover_art_cache,
                                 album_art_style,
+                                config.shared.layout.missing_art,
+                                config.shared.layout.show_album_gain,
+                                config.shared.layout.abbreviate_play_counts,
                             )
                         })
                         .inner;
@@ -276,6 +365,10 @@ pub(crate) fn render_library_view(
                         logic.request_play_track(track_id);
                     }
 
+                    if group_response.clicked_album {
+                        logic.play_group(&grp.album_id);
+                    }
+
                     if group_response.clicked_heart {
                         logic.set_album_starred(&grp.album_id, !grp.starred);
                     }
@@ -288,6 +381,22 @@ pub(crate) fn render_library_view(
                 }
             });
 
+        // Wheel and touchpad scrolling over the library, or dragging its scroll
+        // bar, count as the user scrolling; scrolling to a target doesn't.
+        let library_rect = ui.min_rect();
+        let scroll_bar_left = library_rect.right() - ui.spacing().scroll.allocated_width();
+        if ui.rect_contains_pointer(library_rect)
+            && ui.input(|i| {
+                i.raw_scroll_delta.y != 0.0
+                    || (i.pointer.primary_down()
+                        && i.pointer
+                            .press_origin()
+                            .is_some_and(|p| library_rect.contains(p) && p.x > scroll_bar_left))
+            })
+        {
+            view_state.user_scrolled = true;
+        }
+
         // Render library scroll indicator
         library_scroll::render(
             ui,
@@ -1,5 +1,9 @@
-use blackbird_core::Logic;
-use egui::{Align2, Rect, Sense, TextStyle, Ui, WidgetText, epaint::PathStroke, pos2, vec2};
+use blackbird_client_shared::accessibility::StarTarget;
+use blackbird_core::{Logic, PlayScope};
+use egui::{
+    Align2, Rect, Sense, TextStyle, Ui, WidgetInfo, WidgetText, WidgetType, epaint::PathStroke,
+    pos2, vec2,
+};
 
 use crate::{
     bc::{blackbird_state::Track, util},
@@ -23,6 +27,8 @@ pub struct TrackParams {
     pub incremental_search_target: bool,
     pub track_y: f32,
     pub track_row_height: f32,
+    /// Whether to show play counts as e.g. "1.2k".
+    pub abbreviate_play_counts: bool,
 }
 
 pub fn ui(
@@ -43,7 +49,7 @@ pub fn ui(
     let text_y = params.track_y + (actual_row_height - params.track_row_height) / 2.0;
 
     // Draw heart to the left of play count
-    let (heart_response, heart_size) = ui_util::draw_heart(
+    let (heart_response, heart_toggled, heart_size) = ui_util::draw_heart(
         ui,
         default_font.clone(),
         ui_util::HeartPlacement::Position {
@@ -52,9 +58,10 @@ pub fn ui(
         },
         track.starred,
         false,
+        StarTarget::Track,
     );
     right_x -= heart_size;
-    if heart_response.clicked() {
+    if heart_toggled {
         logic.set_track_starred(&track.id, !track.starred);
     }
 
@@ -68,6 +75,46 @@ pub fn ui(
 
     // Check for interactions with this track area
     let track_response = ui.allocate_rect(track_rect, Sense::click());
+    track_response.widget_info(|| {
+        let label = match track.artist.as_ref() {
+            Some(artist) => format!("Play {} by {artist}", track.title),
+            None => format!("Play {}", track.title),
+        };
+        WidgetInfo::labeled(WidgetType::Button, true, label)
+    });
+    track_response.context_menu(|ui| {
+        if ui.button("Play from here").clicked() {
+            logic.play_from(&track.id, PlayScope::Library);
+            ui.close();
+        }
+        if ui.button("Play album from here").clicked() {
+            logic.play_from(&track.id, PlayScope::Album);
+            ui.close();
+        }
+        if ui.button("Play next").clicked() {
+            logic.enqueue_next(&track.id);
+            ui.close();
+        }
+        if ui.button("Add to queue").clicked() {
+            logic.enqueue_last(&track.id);
+            ui.close();
+        }
+        let path = logic.track_path(&track.id);
+        let reveal = ui
+            .add_enabled(path.is_some(), egui::Button::new("Reveal in file manager"))
+            .on_disabled_hover_text("The server doesn't report a local path for this track");
+        if reveal.clicked()
+            && let Some(path) = path
+        {
+            if let Err(e) = blackbird_client_shared::reveal::reveal_in_file_manager(&path) {
+                tracing::warn!(
+                    "Failed to open the file manager for {}: {e}",
+                    path.display()
+                );
+            }
+            ui.close();
+        }
+    });
 
     // Get track information
     let track_number = track.track.unwrap_or(0);
@@ -89,7 +136,7 @@ pub fn ui(
 
     // Draw track title
     let title_x = track_x + 8.0; // Small gap after track number
-    let title_color = if track_response.hovered() {
+    let title_color = if track_response.hovered() || track_response.has_focus() {
         style.track_name_hovered_color32()
     } else if params.playing {
         style.track_name_playing_color32()
@@ -109,7 +156,7 @@ pub fn ui(
         ui.painter().text(
             pos2(title_rect.right() + 4.0, text_y),
             Align2::LEFT_TOP,
-            play_count.to_string(),
+            blackbird_client_shared::format_play_count(play_count, params.abbreviate_play_counts),
             default_font.clone(),
             style.track_number_color32(),
         );
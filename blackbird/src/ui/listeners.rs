@@ -0,0 +1,67 @@
+use blackbird_client_shared::listeners::{self, RefreshTimer};
+use egui::{Align2, Context, RichText, ScrollArea, Vec2, Vec2b, Window};
+
+use crate::{
+    bc,
+    ui::{style, style::StyleExt},
+};
+
+#[derive(Default)]
+pub struct ListenersState {
+    pub(crate) open: bool,
+    pub(crate) refresh: RefreshTimer,
+}
+
+pub fn ui(logic: &bc::Logic, ctx: &Context, style: &style::Style, state: &mut ListenersState) {
+    if state.refresh.due() {
+        logic.refresh_server_now_playing();
+    }
+    ctx.request_repaint_after(listeners::REFRESH_INTERVAL);
+
+    let rows = listeners::rows(
+        &logic.get_server_now_playing(),
+        &logic.get_server_username(),
+    );
+
+    Window::new("Listening on the server")
+        .open(&mut state.open)
+        .default_pos(ctx.screen_rect().center())
+        .default_size(ctx.screen_rect().size() * Vec2::new(0.4, 0.4))
+        .pivot(Align2::CENTER_CENTER)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            ScrollArea::vertical()
+                .auto_shrink(Vec2b::FALSE)
+                .show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+
+                    for row in &rows {
+                        ui.horizontal(|ui| {
+                            let username = if row.is_you {
+                                format!("{} (you)", row.username)
+                            } else {
+                                row.username.clone()
+                            };
+                            ui.label(
+                                RichText::new(username)
+                                    .color(style.album_color32())
+                                    .strong(),
+                            );
+                            ui.label(RichText::new(&row.track).color(style.text_color32()));
+                            ui.label(
+                                RichText::new(format!("[{}]", row.detail))
+                                    .color(style.track_duration_color32()),
+                            );
+                        });
+                    }
+
+                    if let Some(message) = listeners::status_message(&rows) {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(10.0);
+                            ui.label(message);
+                            ui.add_space(10.0);
+                        });
+                    }
+                });
+        });
+}
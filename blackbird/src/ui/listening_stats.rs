@@ -0,0 +1,72 @@
+use blackbird_client_shared::listening_stats;
+use egui::{Align2, Context, Grid, RichText, ScrollArea, Vec2, Vec2b, Window};
+
+use crate::{
+    bc,
+    ui::{style, style::StyleExt},
+};
+
+#[derive(Default)]
+pub struct ListeningStatsState {
+    pub(crate) open: bool,
+}
+impl ListeningStatsState {
+    /// Toggles the window, aggregating the stats afresh when it opens.
+    pub fn toggle(&mut self, logic: &bc::Logic) {
+        self.open = !self.open;
+        if self.open {
+            logic.refresh_listening_stats();
+        }
+    }
+}
+
+pub fn ui(ctx: &Context, style: &style::Style, logic: &bc::Logic, state: &mut ListeningStatsState) {
+    let stats = logic.get_listening_stats();
+
+    Window::new("Listening stats")
+        .open(&mut state.open)
+        .default_pos(ctx.screen_rect().center())
+        .default_size(ctx.screen_rect().size() * Vec2::new(0.4, 0.5))
+        .pivot(Align2::CENTER_CENTER)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let stats = match &stats {
+                Some(stats) if stats.total_plays > 0 => stats,
+                Some(_) => {
+                    ui.label(listening_stats::EMPTY_MESSAGE);
+                    return;
+                }
+                None => {
+                    ui.label(listening_stats::LOADING_MESSAGE);
+                    return;
+                }
+            };
+
+            ScrollArea::vertical()
+                .auto_shrink(Vec2b::FALSE)
+                .show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+
+                    Grid::new("listening_stats_grid")
+                        .num_columns(2)
+                        .spacing([16.0, 4.0])
+                        .show(ui, |ui| {
+                            for (label, value) in listening_stats::summary_rows(stats) {
+                                ui.label(
+                                    RichText::new(label).color(style.album_color32()).strong(),
+                                );
+                                ui.label(RichText::new(value).color(style.text_color32()));
+                                ui.end_row();
+                            }
+                        });
+
+                    for (title, lines) in listening_stats::top_sections(stats) {
+                        ui.add_space(8.0);
+                        ui.label(RichText::new(title).color(style.album_color32()).strong());
+                        for line in lines {
+                            ui.label(RichText::new(line).color(style.text_color32()).monospace());
+                        }
+                    }
+                });
+        });
+}
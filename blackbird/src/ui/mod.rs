@@ -1,19 +1,30 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+mod error_window;
 mod keys;
 mod library;
+mod listeners;
+mod listening_stats;
 mod lyrics;
+mod now_playing_osd;
 mod playing_track;
 mod queue;
+mod recent;
 mod scrub_bar;
 mod search;
+mod server_info;
 mod settings;
 mod style;
 mod util;
+mod window_title;
 
 pub use style::{Style, StyleExt};
 
+use blackbird_client_shared::{
+    config::StartupPanel, double_press::NextPress, search_history::SearchHistory,
+    search_suggestions::SuggestionDebouncer,
+};
 use blackbird_core::blackbird_state::CoverArtId;
 use blackbird_shared::config::ConfigFile as _;
 use egui::{
@@ -27,6 +38,9 @@ use crate::{App, bc, config::Config, cover_art_cache::CachePriority};
 pub struct SearchState {
     pub(crate) open: bool,
     pub(crate) query: String,
+    pub(crate) suggestions: SuggestionDebouncer,
+    /// Past queries, recalled with Up and Down in the search input.
+    pub(crate) history: SearchHistory,
 }
 
 #[derive(Default)]
@@ -41,6 +55,16 @@ pub struct QueueState {
     pub(crate) open: bool,
 }
 
+#[derive(Default)]
+pub struct RecentState {
+    pub(crate) open: bool,
+}
+
+#[derive(Default)]
+pub struct ServerInfoState {
+    pub(crate) open: bool,
+}
+
 /// State for the hover-based full-res album art preview.
 pub struct ArtHoverState {
     pub cover_art_id: CoverArtId,
@@ -58,10 +82,19 @@ pub struct UiState {
     pub search: SearchState,
     pub lyrics: LyricsState,
     pub queue: QueueState,
+    pub recent: RecentState,
+    pub listeners: listeners::ListenersState,
+    pub server_info: ServerInfoState,
+    pub listening_stats: listening_stats::ListeningStatsState,
     pub settings: settings::SettingsState,
     pub library_view: library::LibraryViewState,
     pub mini_library: library::MiniLibraryState,
     pub quit_confirming: bool,
+    pub now_playing_osd: now_playing_osd::NowPlayingOsdState,
+    pub window_title: window_title::WindowTitleState,
+    pub error_window: error_window::ErrorWindowState,
+    /// Upgrades a quick second press of Next to going to the next album.
+    pub next_press: NextPress,
     /// When set, a full-res album art preview popup is shown near the hovered
     /// thumbnail.
     pub art_hover: Option<ArtHoverState>,
@@ -109,7 +142,16 @@ pub fn initialize(cc: &eframe::CreationContext<'_>, config: &Config) -> UiState
 
     egui_extras::install_image_loaders(&cc.egui_ctx);
 
-    UiState::default()
+    let mut ui_state = UiState::default();
+    ui_state.search.history = config.shared.search.initial_history();
+    match config.shared.layout.startup_panel {
+        StartupPanel::Library => {}
+        StartupPanel::Search => ui_state.search.open = true,
+        StartupPanel::Lyrics => ui_state.lyrics.open = true,
+        StartupPanel::Queue => ui_state.queue.open = true,
+        StartupPanel::Recent => ui_state.recent.open = true,
+    }
+    ui_state
 }
 
 impl App {
@@ -118,15 +160,33 @@ impl App {
         let config_guard = self.config.read().unwrap();
         let config = &config_guard;
 
-        let mut track_to_scroll_to = logic
+        let follow_playing = config.shared.layout.follow_playing;
+        let requested_track = logic
             .get_state()
             .write()
             .unwrap()
             .last_requested_track_for_ui_scroll
             .take();
+        let library_view = &mut self.ui_state.library_view;
+        let mut track_to_scroll_to = if std::mem::take(&mut library_view.goto_requested) {
+            library_view.user_scrolled = false;
+            requested_track
+        } else {
+            requested_track.filter(|_| follow_playing.should_follow(library_view.user_scrolled))
+        };
         while let Ok(event) = self.playback_to_logic_rx.try_recv() {
             if let bc::PlaybackToLogicMessage::TrackStarted(track_and_position) = event {
-                track_to_scroll_to = Some(track_and_position.track_id.clone());
+                let library_view = &mut self.ui_state.library_view;
+                if follow_playing.should_follow(library_view.user_scrolled) {
+                    track_to_scroll_to = Some(track_and_position.track_id.clone());
+                }
+                library_view.user_scrolled = false;
+                self.ui_state.now_playing_osd.on_track_started(
+                    bc::TrackDisplayDetails::from_track_and_position(
+                        &track_and_position,
+                        &logic.get_state().read().unwrap(),
+                    ),
+                );
 
                 // Request lyrics if inline lyrics are enabled or the panel is open.
                 if self.ui_state.lyrics.shared.on_track_started(
@@ -140,17 +200,17 @@ impl App {
             }
         }
 
-        if let Some(error) = logic.get_error() {
-            let mut open = true;
-            egui::Window::new("Error").open(&mut open).show(ctx, |ui| {
-                ui.label(RichText::new(error.display_name()).heading());
-                ui.label(RichText::new(
-                    error.display_message(&logic.get_state().read().unwrap()),
-                ));
-            });
-            if !open {
+        // Applied once the config read guard is dropped, as they're saved first.
+        let mut follow_playing_choice = follow_playing;
+        let mut retry_server = None;
+        match error_window::ui(ctx, logic, config, &mut self.ui_state.error_window) {
+            Some(error_window::ErrorWindowAction::Close) => logic.clear_error(),
+            Some(error_window::ErrorWindowAction::OpenSettings) => {
+                self.ui_state.settings.open = true;
                 logic.clear_error();
             }
+            Some(error_window::ErrorWindowAction::Retry(server)) => retry_server = Some(server),
+            None => {}
         }
 
         ctx.input(|i| {
@@ -199,6 +259,10 @@ impl App {
         let can_handle_shortcuts = !self.ui_state.search.open
             && !self.ui_state.lyrics.open
             && !self.ui_state.queue.open
+            && !self.ui_state.recent.open
+            && !self.ui_state.listeners.open
+            && !self.ui_state.server_info.open
+            && !self.ui_state.listening_stats.open
             && !self.ui_state.settings.open
             && !self.ui_state.quit_confirming
             && !search_active;
@@ -237,31 +301,50 @@ impl App {
             }
         }
 
-        // Q closes any open sub-window when shortcuts are blocked by one.
+        // Q closes any open sub-window when shortcuts are blocked by one. G
+        // does the same from the lyrics window, then scrolls the library to
+        // the playing track.
         if !can_handle_shortcuts && !self.ui_state.quit_confirming && !search_active {
             ctx.input(|i| {
                 for event in &i.events {
-                    if let egui::Event::Key {
-                        key: keys::KEY_QUIT,
+                    let egui::Event::Key {
+                        key,
                         pressed: true,
                         modifiers,
                         ..
                     } = event
-                        && !modifiers.command
-                        && !modifiers.alt
-                        && !modifiers.ctrl
-                        && !modifiers.shift
-                    {
-                        self.ui_state.search.open = false;
-                        self.ui_state.lyrics.open = false;
-                        self.ui_state.queue.open = false;
-                        self.ui_state.settings.open = false;
+                    else {
+                        continue;
+                    };
+                    if modifiers.command || modifiers.alt || modifiers.ctrl || modifiers.shift {
+                        continue;
+                    }
+                    let goto_playing = *key == keys::KEY_GOTO_PLAYING
+                        && self.ui_state.lyrics.open
+                        && !self.ui_state.settings.open;
+                    if *key != keys::KEY_QUIT && !goto_playing {
+                        continue;
+                    }
+                    self.ui_state.search.open = false;
+                    self.ui_state.lyrics.open = false;
+                    self.ui_state.queue.open = false;
+                    self.ui_state.recent.open = false;
+                    self.ui_state.listeners.open = false;
+                    self.ui_state.server_info.open = false;
+                    self.ui_state.listening_stats.open = false;
+                    self.ui_state.settings.open = false;
+                    if goto_playing {
+                        self.ui_state.library_view.goto_playing(logic);
                     }
                 }
             });
         }
 
+        // Applied once the config read guard has been dropped.
+        let mut toggle_transcode = false;
         if can_handle_shortcuts {
+            // Copied after the input closure, which holds the context lock.
+            let mut copy_text = None;
             ctx.input(|i| {
                 for event in &i.events {
                     let egui::Event::Key {
@@ -284,7 +367,10 @@ impl App {
                     match action {
                         keys::Action::PlayPause => logic.toggle_current(),
                         keys::Action::Stop => logic.stop_current(),
-                        keys::Action::Next => logic.next(),
+                        keys::Action::Next => self
+                            .ui_state
+                            .next_press
+                            .press(logic, config.shared.playback.double_press_next_window()),
                         keys::Action::Previous => logic.previous(),
                         keys::Action::NextGroup => logic.next_group(),
                         keys::Action::PreviousGroup => logic.previous_group(),
@@ -309,13 +395,7 @@ impl App {
                                 .library_view
                                 .invalidate_library_scroll();
                             // Re-center on the playing track after re-sorting.
-                            if let Some(track_id) = logic.get_playing_track_id() {
-                                logic
-                                    .get_state()
-                                    .write()
-                                    .unwrap()
-                                    .last_requested_track_for_ui_scroll = Some(track_id);
-                            }
+                            self.ui_state.library_view.goto_playing(logic);
                         }
                         keys::Action::SeekBackward => {
                             seek_relative(logic, -blackbird_client_shared::SEEK_STEP_SECS);
@@ -323,13 +403,20 @@ impl App {
                         keys::Action::SeekForward => {
                             seek_relative(logic, blackbird_client_shared::SEEK_STEP_SECS);
                         }
-                        keys::Action::GotoPlaying => {
-                            if let Some(track_id) = logic.get_playing_track_id() {
-                                let state = logic.get_state();
-                                let mut state = state.write().unwrap();
-                                state.last_requested_track_for_ui_scroll = Some(track_id);
+                        keys::Action::NextLyricLine => {
+                            seek_lyric_line(logic, &self.ui_state.lyrics.shared, 1);
+                        }
+                        keys::Action::PreviousLyricLine => {
+                            seek_lyric_line(logic, &self.ui_state.lyrics.shared, -1);
+                        }
+                        keys::Action::SeekTenths(tenths) => {
+                            if config.shared.playback.seek_with_digits {
+                                logic.seek_fraction(f32::from(tenths) / 10.0);
                             }
                         }
+                        keys::Action::GotoPlaying => {
+                            self.ui_state.library_view.goto_playing(logic);
+                        }
                         keys::Action::SearchInline => {
                             self.ui_state.library_view.incremental_search.active = true;
                         }
@@ -352,6 +439,19 @@ impl App {
                         keys::Action::Queue => {
                             self.ui_state.queue.open = !self.ui_state.queue.open;
                         }
+                        keys::Action::RecentAlbums => {
+                            self.ui_state.recent.open = !self.ui_state.recent.open;
+                        }
+                        keys::Action::Listeners => {
+                            self.ui_state.listeners.open = !self.ui_state.listeners.open;
+                            self.ui_state.listeners.refresh.reset();
+                        }
+                        keys::Action::ServerInfo => {
+                            self.ui_state.server_info.open = !self.ui_state.server_info.open;
+                        }
+                        keys::Action::ListeningStats => {
+                            self.ui_state.listening_stats.toggle(logic);
+                        }
                         keys::Action::Quit => {
                             self.ui_state.quit_confirming = true;
                         }
@@ -369,6 +469,20 @@ impl App {
                             drop(state);
                             logic.set_track_starred(&track_id, !starred);
                         }
+                        keys::Action::StarAlbum => {
+                            let Some(album_id) = logic.get_playing_album_id() else {
+                                continue;
+                            };
+                            let starred = logic
+                                .get_state()
+                                .read()
+                                .unwrap()
+                                .library
+                                .albums
+                                .get(&album_id)
+                                .is_some_and(|a| a.starred);
+                            logic.set_album_starred(&album_id, !starred);
+                        }
                         keys::Action::VolumeUp => {
                             let vol = (logic.get_volume() + blackbird_client_shared::VOLUME_STEP)
                                 .min(1.0);
@@ -382,9 +496,56 @@ impl App {
                         keys::Action::Settings => {
                             self.ui_state.settings.open = !self.ui_state.settings.open;
                         }
+                        keys::Action::Rescan => logic.trigger_server_scan(),
+                        keys::Action::AlbumRadio => {
+                            if let Some(album_id) = logic.get_playing_album_id() {
+                                logic.start_album_radio(&album_id);
+                            }
+                        }
+                        keys::Action::CopyNowPlaying => {
+                            if let Some(details) = logic.get_track_display_details() {
+                                copy_text =
+                                    Some(blackbird_client_shared::now_playing_text::format(
+                                        &config.shared.clipboard.now_playing_template,
+                                        &details,
+                                    ));
+                            }
+                        }
+                        keys::Action::Download => {
+                            if let Some(track_id) = logic.get_playing_track_id() {
+                                logic.download_track_to(
+                                    &track_id,
+                                    &config.shared.downloads.directory(),
+                                );
+                            }
+                        }
+                        keys::Action::ToggleTranscode => toggle_transcode = true,
+                        keys::Action::SkipTrack => {
+                            if let Some(track_id) = logic.get_playing_track_id() {
+                                let skipped = !logic.is_track_skipped(&track_id);
+                                logic.set_track_skipped(&track_id, skipped);
+                                // Marking the playing track moves on from it.
+                                if skipped {
+                                    logic.next();
+                                }
+                            }
+                        }
+                        keys::Action::ToggleShuffle => logic.toggle_shuffle(),
+                        keys::Action::CycleShuffleScope => logic.cycle_shuffle_scope(),
+                        keys::Action::HideUnstarred => {
+                            logic.set_hide_unstarred(!logic.get_hide_unstarred());
+                            self.ui_state.library_view.reanchor_after_refresh();
+                            self.ui_state
+                                .mini_library
+                                .library_view
+                                .reanchor_after_refresh();
+                        }
                     }
                 }
             });
+            if let Some(text) = copy_text {
+                ctx.copy_text(text);
+            }
         }
 
         // Process incoming lyrics data.
@@ -394,11 +555,11 @@ impl App {
 
         // Process library population signal
         while let Ok(()) = self.library_populated_rx.try_recv() {
-            self.ui_state.library_view.invalidate_library_scroll();
+            self.ui_state.library_view.reanchor_after_refresh();
             self.ui_state
                 .mini_library
                 .library_view
-                .invalidate_library_scroll();
+                .reanchor_after_refresh();
 
             // Populate the background art prefetch queue with all album cover art IDs.
             let state = logic.get_state();
@@ -420,6 +581,8 @@ impl App {
                 &config.style,
                 &mut self.ui_state.search.open,
                 &mut self.ui_state.search.query,
+                &mut self.ui_state.search.suggestions,
+                &mut self.ui_state.search.history,
             );
         }
 
@@ -439,6 +602,32 @@ impl App {
             queue::ui(logic, ctx, &config.style, &mut self.ui_state.queue.open);
         }
 
+        if self.ui_state.recent.open {
+            recent::ui(logic, ctx, &config.style, &mut self.ui_state.recent.open);
+        }
+
+        if self.ui_state.listeners.open {
+            listeners::ui(logic, ctx, &config.style, &mut self.ui_state.listeners);
+        }
+
+        if self.ui_state.server_info.open {
+            server_info::ui(
+                logic,
+                ctx,
+                &config.style,
+                &mut self.ui_state.server_info.open,
+            );
+        }
+
+        if self.ui_state.listening_stats.open {
+            listening_stats::ui(
+                ctx,
+                &config.style,
+                logic,
+                &mut self.ui_state.listening_stats,
+            );
+        }
+
         let margin = 8;
         let scroll_margin = 4;
         let has_loaded_all_tracks = logic.has_loaded_all_tracks();
@@ -514,8 +703,10 @@ impl App {
                     config,
                     has_loaded_all_tracks,
                     &mut self.cover_art_cache,
+                    Some(&mut follow_playing_choice),
                 ) {
                     track_to_scroll_to = Some(id);
+                    self.ui_state.library_view.user_scrolled = false;
                 }
 
                 let art_hover_result = library::full::ui(
@@ -531,6 +722,10 @@ impl App {
                         search_open: self.ui_state.search.open,
                         lyrics_open: self.ui_state.lyrics.open,
                         queue_open: self.ui_state.queue.open,
+                        recent_open: self.ui_state.recent.open,
+                        listeners_open: self.ui_state.listeners.open,
+                        server_info_open: self.ui_state.server_info.open,
+                        listening_stats_open: self.ui_state.listening_stats.open,
                     },
                 );
                 if let Some((id, rect)) = art_hover_result {
@@ -649,6 +844,15 @@ impl App {
                 });
         }
 
+        window_title::update(ctx, config, logic, &mut self.ui_state.window_title);
+
+        now_playing_osd::ui(
+            ctx,
+            config,
+            &mut self.ui_state.now_playing_osd,
+            &mut self.cover_art_cache,
+        );
+
         // Dismiss the hover popup if the thumbnail has scrolled off-screen.
         if let Some(ref hover) = self.ui_state.art_hover
             && !ctx.screen_rect().intersects(hover.art_screen_rect)
@@ -665,6 +869,7 @@ impl App {
                 .cover_art_cache
                 .get(Some(&hover.cover_art_id), CachePriority::Visible);
             let full_res_source = self.cover_art_cache.get_full_res(Some(&hover.cover_art_id));
+            let full_res_loaded = self.cover_art_cache.is_full_res_loaded(&hover.cover_art_id);
 
             let screen = ctx.screen_rect();
             let popup_max_width = screen.width() * 0.4;
@@ -713,6 +918,17 @@ impl App {
                             egui::Image::new(full_res_source)
                                 .show_loading_spinner(false)
                                 .paint_at(ui, response.rect);
+                            // Until the full-res art arrives, mark the
+                            // enlarged fallback as loading.
+                            if !full_res_loaded {
+                                let size = 16.0;
+                                let spinner_rect = egui::Rect::from_min_size(
+                                    response.rect.right_bottom()
+                                        - egui::vec2(size + 4.0, size + 4.0),
+                                    egui::vec2(size, size),
+                                );
+                                egui::Spinner::new().size(size).paint_at(ui, spinner_rect);
+                            }
                         });
                 });
 
@@ -772,6 +988,25 @@ impl App {
         // which needs a write lock to apply changes.
         drop(config_guard);
 
+        if follow_playing_choice != follow_playing {
+            let mut cfg = self.config.write().unwrap();
+            cfg.shared.layout.follow_playing = follow_playing_choice;
+            cfg.save();
+        }
+
+        if toggle_transcode {
+            let mut cfg = self.config.write().unwrap();
+            cfg.shared.server.transcode = !cfg.shared.server.transcode;
+            cfg.save();
+        }
+
+        if let Some(server) = retry_server {
+            let mut cfg = self.config.write().unwrap();
+            cfg.shared.server = server;
+            cfg.save();
+            self.logic.reconfigure_server(&cfg.shared.server);
+        }
+
         // Settings window — drawn last so it can work with a cloned config
         // and write changes back without conflicting with the read guard above.
         // Suppress config auto-reload while settings is open to prevent disk
@@ -802,12 +1037,7 @@ impl App {
                     // Save immediately for server changes that trigger a reload.
                     cfg.save();
 
-                    self.logic.reload_library(
-                        cfg.shared.server.base_url,
-                        cfg.shared.server.username,
-                        cfg.shared.server.password,
-                        cfg.shared.server.transcode,
-                    );
+                    self.logic.reconfigure_server(&cfg.shared.server);
                 }
             }
         }
@@ -833,3 +1063,19 @@ fn seek_relative(logic: &mut bc::Logic, seconds: i64) {
     };
     logic.seek_current(new_pos);
 }
+
+/// Seek to the start of the synced lyrics line `direction` lines from the
+/// current one, if there is one.
+fn seek_lyric_line(
+    logic: &bc::Logic,
+    lyrics: &blackbird_client_shared::lyrics::LyricsState,
+    direction: isize,
+) {
+    if let Some(position) = lyrics.adjacent_line_start(
+        logic.get_playing_track_id().as_ref(),
+        logic.get_playing_position(),
+        direction,
+    ) {
+        logic.seek_current(position);
+    }
+}
@@ -0,0 +1,103 @@
+//! A brief overlay announcing each new track with its art and title, fading
+//! out after a configurable time, for noticing track changes at a glance.
+use std::time::{Duration, Instant};
+
+use egui::{Align2, Context, Frame, Image, Label, Margin, RichText, vec2};
+
+use crate::{
+    bc,
+    config::Config,
+    cover_art_cache::{CachePriority, CoverArtCache},
+    ui::{style, style::StyleExt},
+};
+
+/// How long the overlay takes to fade out at the end of its time.
+const FADE_DURATION: Duration = Duration::from_millis(500);
+/// Side length of the album art in the overlay.
+const ART_SIZE: f32 = 96.0;
+/// Distance between the overlay and the bottom of the window.
+const BOTTOM_OFFSET: f32 = 48.0;
+
+#[derive(Default)]
+pub struct NowPlayingOsdState {
+    /// The track being announced, and when it started.
+    shown: Option<(bc::TrackDisplayDetails, Instant)>,
+}
+impl NowPlayingOsdState {
+    /// Announces a newly started track, replacing any track still shown.
+    pub fn on_track_started(&mut self, details: Option<bc::TrackDisplayDetails>) {
+        self.shown = details.map(|details| (details, Instant::now()));
+    }
+}
+
+pub fn ui(
+    ctx: &Context,
+    config: &Config,
+    state: &mut NowPlayingOsdState,
+    cover_art_cache: &mut CoverArtCache,
+) {
+    let duration =
+        Duration::try_from_secs_f32(config.general.now_playing_osd_secs).unwrap_or_default();
+    let remaining = state
+        .shown
+        .as_ref()
+        .and_then(|(_, shown_at)| duration.checked_sub(shown_at.elapsed()))
+        .filter(|_| config.general.now_playing_osd);
+    let (Some(remaining), Some((details, _))) = (remaining, &state.shown) else {
+        state.shown = None;
+        return;
+    };
+    let opacity = (remaining.as_secs_f32() / FADE_DURATION.as_secs_f32()).min(1.0);
+    let art = cover_art_cache.get(details.cover_art_id.as_ref(), CachePriority::Visible);
+    let artist = details
+        .track_artist
+        .as_ref()
+        .unwrap_or(&details.album_artist);
+
+    egui::Area::new(egui::Id::new("now_playing_osd"))
+        .anchor(Align2::CENTER_BOTTOM, vec2(0.0, -BOTTOM_OFFSET))
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .show(ctx, |ui| {
+            ui.multiply_opacity(opacity);
+            Frame::popup(ui.style())
+                .fill(config.style.background_color32())
+                .inner_margin(Margin::same(12))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            Image::new(art)
+                                .fit_to_exact_size(vec2(ART_SIZE, ART_SIZE))
+                                .show_loading_spinner(false),
+                        );
+                        ui.vertical(|ui| {
+                            ui.add(
+                                Label::new(
+                                    RichText::new(details.track_title.as_str())
+                                        .heading()
+                                        .color(config.style.track_name_playing_color32()),
+                                )
+                                .selectable(false),
+                            );
+                            ui.add(
+                                Label::new(
+                                    RichText::new(artist.as_str())
+                                        .color(style::string_to_colour(artist)),
+                                )
+                                .selectable(false),
+                            );
+                            ui.add(
+                                Label::new(
+                                    RichText::new(details.album_name.as_str())
+                                        .color(config.style.track_duration_color32()),
+                                )
+                                .selectable(false),
+                            );
+                        });
+                    });
+                });
+        });
+
+    // Keep repainting while the overlay fades.
+    ctx.request_repaint();
+}
@@ -1,6 +1,11 @@
+use blackbird_client_shared::{
+    accessibility::{self, StarTarget},
+    config::FollowPlaying,
+};
 use blackbird_core::{PlaybackMode, blackbird_state::TrackId};
 use egui::{
-    Align, Color32, Label, Layout, RichText, Sense, Spinner, TextStyle, Ui, UiBuilder, Vec2, vec2,
+    Align, Color32, Label, Layout, RichText, Sense, Slider, Spinner, TextStyle, Ui, UiBuilder,
+    Vec2, WidgetInfo, WidgetType, vec2,
 };
 
 use crate::{
@@ -19,6 +24,7 @@ pub fn ui(
     has_loaded_all_tracks: bool,
     track_to_scroll_to: &mut Option<TrackId>,
     cover_art_cache: &mut CoverArtCache,
+    follow_playing: Option<&mut FollowPlaying>,
 ) {
     let track_display_details = logic.get_track_display_details();
     let track_id = track_display_details
@@ -57,28 +63,33 @@ pub fn ui(
                         .sense(Sense::click());
                     let r = ui.scope_builder(ui_builder, |ui| {
                         let image_size = ui.text_style_height(&TextStyle::Body) * 2.5;
-                        ui.add_sized(
+                        let art = ui.add_sized(
                             vec2(image_size, image_size),
-                            egui::Image::new(
-                                cover_art_cache
-                                    .get(tdd.cover_art_id.as_ref(), CachePriority::Visible),
+                            style::album_art_image(
+                                &config.style,
+                                egui::Image::new(
+                                    cover_art_cache
+                                        .get(tdd.cover_art_id.as_ref(), CachePriority::Visible),
+                                ),
                             )
                             .show_loading_spinner(false),
                         );
+                        style::paint_album_art_border(ui, &config.style, art.rect);
 
                         ui.add_space(6.0);
 
                         ui.vertical(|ui| {
                             ui.horizontal(|ui| {
                                 // Add heart for track
-                                let (heart_response, _) = util::draw_heart(
+                                let (_, heart_toggled, _) = util::draw_heart(
                                     ui,
                                     TextStyle::Body.resolve(ui.style()),
                                     util::HeartPlacement::Space,
                                     tdd.starred,
                                     true,
+                                    StarTarget::Track,
                                 );
-                                if heart_response.clicked() {
+                                if heart_toggled {
                                     track_heart_clicked = true;
                                 }
                                 ui.add_space(4.0);
@@ -104,17 +115,28 @@ pub fn ui(
                                     )
                                     .selectable(false),
                                 );
+                                let track_gain = logic.get_track_gain(&tdd.track_id);
+                                if track_gain != 0.0 {
+                                    ui.add(
+                                        Label::new(
+                                            RichText::new(format!(" {track_gain:+.1} dB"))
+                                                .color(config.style.track_duration_color32()),
+                                        )
+                                        .selectable(false),
+                                    );
+                                }
                             });
                             ui.horizontal(|ui| {
                                 // Add heart for album
-                                let (heart_response, _) = util::draw_heart(
+                                let (_, heart_toggled, _) = util::draw_heart(
                                     ui,
                                     TextStyle::Body.resolve(ui.style()),
                                     util::HeartPlacement::Space,
                                     album_starred,
                                     true,
+                                    StarTarget::Album,
                                 );
-                                if heart_response.clicked() {
+                                if heart_toggled {
                                     album_heart_clicked = true;
                                 }
                                 ui.add_space(4.0);
@@ -137,7 +159,31 @@ pub fn ui(
                             });
                         });
                     });
+                    // Announce the whole now-playing block as one readable
+                    // sentence; activating it scrolls to the track.
+                    let description =
+                        accessibility::now_playing_description(&tdd, logic.get_playback_state());
+                    r.response.widget_info(|| {
+                        WidgetInfo::labeled(WidgetType::Button, true, &description)
+                    });
                     track_clicked = r.response.clicked();
+                    // Right-clicking trims the volume of a track that's too
+                    // loud or quiet, independently of ReplayGain.
+                    r.response.context_menu(|ui| {
+                        let mut gain = logic.get_track_gain(&tdd.track_id);
+                        let response = ui.add(
+                            Slider::new(&mut gain, blackbird_client_shared::TRACK_GAIN_RANGE_DB)
+                                .step_by(f64::from(blackbird_client_shared::TRACK_GAIN_STEP_DB))
+                                .suffix(" dB")
+                                .text("Track gain"),
+                        );
+                        if response.changed() {
+                            logic.set_track_gain(&tdd.track_id, gain);
+                        }
+                        if ui.button("Reset").clicked() {
+                            logic.set_track_gain(&tdd.track_id, 0.0);
+                        }
+                    });
                 } else {
                     ui.vertical(|ui| {
                         ui.horizontal(|ui| {
@@ -204,6 +250,28 @@ pub fn ui(
 
                 ui.add_space(24.0);
 
+                if let Some(follow_playing) = follow_playing {
+                    let button_color = if *follow_playing == FollowPlaying::Never {
+                        default
+                    } else {
+                        active
+                    };
+                    if control_button(
+                        ui,
+                        egui_phosphor::regular::CROSSHAIR,
+                        button_color,
+                        active,
+                        &format!("Follow playing: {}", follow_playing.as_str()),
+                    ) {
+                        *follow_playing = blackbird_client_shared::cycle(
+                            FollowPlaying::ALL,
+                            *follow_playing,
+                            blackbird_client_shared::Direction::Forward,
+                        );
+                    }
+                    ui.separator();
+                }
+
                 // Playback mode buttons
                 let playback = logic.get_playback_mode();
                 for (mode, icon, render_separator) in [
@@ -240,6 +308,11 @@ pub fn ui(
                     (
                         PlaybackMode::LikedGroupShuffle,
                         egui_phosphor::regular::DISC,
+                        true,
+                    ),
+                    (
+                        PlaybackMode::ArtistShuffle,
+                        egui_phosphor::regular::USER,
                         false,
                     ),
                 ]
@@ -305,13 +378,16 @@ fn control_button(
         visuals.widgets.inactive.fg_stroke.color = text_color;
         visuals.widgets.hovered.fg_stroke.color = hover_color;
         visuals.widgets.active.fg_stroke.color = hover_color;
-        ui.add(
-            Label::new(RichText::new(icon).size(CONTROL_BUTTON_SIZE))
-                .selectable(false)
-                .sense(Sense::click()),
-        )
-        .on_hover_text(tooltip)
-        .clicked()
+        let response = ui
+            .add(
+                Label::new(RichText::new(icon).size(CONTROL_BUTTON_SIZE))
+                    .selectable(false)
+                    .sense(Sense::click()),
+            )
+            .on_hover_text(tooltip);
+        // The icon glyph means nothing to a screen reader; use the tooltip.
+        response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, true, tooltip));
+        response.clicked()
     })
     .inner
 }
@@ -0,0 +1,78 @@
+use blackbird_client_shared::RECENT_ALBUM_COUNT;
+use egui::{Align2, Context, Label, RichText, ScrollArea, Sense, Vec2, Vec2b, Window};
+
+use crate::{
+    bc,
+    ui::{style, style::StyleExt},
+};
+
+pub fn ui(logic: &mut bc::Logic, ctx: &Context, style: &style::Style, recent_open: &mut bool) {
+    let recent = logic.recent_albums(RECENT_ALBUM_COUNT);
+    let playing_track_id = logic.get_playing_track_id();
+    let mut clicked_track = None;
+
+    Window::new("Recently played")
+        .open(recent_open)
+        .default_pos(ctx.screen_rect().center())
+        .default_size(ctx.screen_rect().size() * Vec2::new(0.4, 0.4))
+        .pivot(Align2::CENTER_CENTER)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            if recent.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(10.0);
+                    ui.label("No albums played yet.");
+                    ui.add_space(10.0);
+                });
+                return;
+            }
+
+            ScrollArea::vertical()
+                .auto_shrink(Vec2b::FALSE)
+                .show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+
+                    for (idx, album) in recent.iter().enumerate() {
+                        // The album being played is always the most recent.
+                        let is_playing = idx == 0
+                            && playing_track_id
+                                .as_ref()
+                                .is_some_and(|id| album.group.tracks.contains(id));
+
+                        let row_text = format!(
+                            "{}{} - {}",
+                            if is_playing { "\u{25b6} " } else { "  " },
+                            album.group.artist,
+                            album.group.album,
+                        );
+                        let color = if is_playing {
+                            style.track_name_playing_color32()
+                        } else {
+                            style.text_color32()
+                        };
+                        let response = ui.add(
+                            Label::new(RichText::new(row_text).color(color)).selectable(false),
+                        );
+
+                        let row_interaction = ui.interact(
+                            response.rect,
+                            ui.id().with(("recent_album", idx)),
+                            Sense::click(),
+                        );
+
+                        if row_interaction.clicked() {
+                            clicked_track = Some(album.track_id.clone());
+                        }
+
+                        if row_interaction.hovered() {
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                        }
+                    }
+                });
+        });
+
+    if let Some(track_id) = clicked_track {
+        logic.request_play_track(&track_id);
+        *recent_open = false;
+    }
+}
@@ -1,14 +1,16 @@
 use std::time::Duration;
 
-use blackbird_core::util::seconds_to_hms_string;
-use egui::{Align, Label, Layout, RichText, Slider, Ui, style::HandleShape};
+use blackbird_client_shared::accessibility;
+use blackbird_core::util::{UNKNOWN_DURATION, seconds_to_hms_string};
+use egui::{Align, Label, Layout, RichText, Slider, Ui, WidgetInfo, style::HandleShape};
 
 use crate::{bc, config::Config, ui::style::StyleExt};
 
 pub fn ui(ui: &mut Ui, logic: &mut bc::Logic, config: &Config) {
     ui.horizontal(|ui| {
-        let (position_secs, duration_secs) = logic
-            .get_track_display_details()
+        let details = logic.get_track_display_details();
+        let (position_secs, duration_secs) = details
+            .as_ref()
             .map(|pi| {
                 (
                     pi.track_position.as_secs_f32(),
@@ -17,9 +19,17 @@ pub fn ui(ui: &mut Ui, logic: &mut bc::Logic, config: &Config) {
             })
             .unwrap_or_default();
 
+        // A track without a known length can't be seeked, so its scrub bar is
+        // shown as indeterminate.
+        let duration_known = details.as_ref().is_none_or(|d| !d.track_duration.is_zero());
+
         // Position/duration text
-        let [position_hms, duration_hms] =
-            [position_secs, duration_secs].map(|s| seconds_to_hms_string(s as u32, true));
+        let position_hms = seconds_to_hms_string(position_secs as u32, true);
+        let duration_hms = if duration_known {
+            seconds_to_hms_string(duration_secs as u32, true)
+        } else {
+            UNKNOWN_DURATION.to_string()
+        };
         ui.add(
             Label::new(
                 RichText::new(format!("{position_hms} / {duration_hms}"))
@@ -38,6 +48,7 @@ pub fn ui(ui: &mut Ui, logic: &mut bc::Logic, config: &Config) {
                     .show_value(false)
                     .handle_shape(HandleShape::Rect { aspect_ratio: 0.75 }),
             );
+            volume_response.widget_info(|| WidgetInfo::slider(true, f64::from(volume), "Volume"));
             if volume_response.changed() {
                 logic.set_volume(volume);
             }
@@ -47,14 +58,26 @@ pub fn ui(ui: &mut Ui, logic: &mut bc::Logic, config: &Config) {
             ui.separator();
 
             // Scrub bar
-            let mut slider_position = position_secs;
+            let mut slider_position = if duration_known { position_secs } else { 0.0 };
             let slider_duration = duration_secs.max(1.0);
             ui.style_mut().spacing.slider_width = ui.available_width();
-            let slider_response = ui.add(
+            let slider_response = ui.add_enabled(
+                duration_known,
                 Slider::new(&mut slider_position, 0.0..=slider_duration)
                     .show_value(false)
                     .handle_shape(HandleShape::Rect { aspect_ratio: 2.0 }),
             );
+            slider_response.widget_info(|| {
+                let position = accessibility::position_description(
+                    Duration::from_secs_f32(slider_position),
+                    Duration::from_secs_f32(duration_secs),
+                );
+                WidgetInfo::slider(
+                    duration_known,
+                    f64::from(slider_position),
+                    format!("Seek, {position}"),
+                )
+            });
             if slider_response.changed() {
                 let seek_position = Duration::from_secs_f32(slider_position);
                 logic.seek_current(seek_position);
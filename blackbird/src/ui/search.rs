@@ -1,11 +1,19 @@
-use std::ops::Range;
+use std::{collections::HashMap, ops::Range};
 
+use blackbird_client_shared::{
+    search_history::SearchHistory,
+    search_suggestions::{self, SuggestionDebouncer},
+};
 use blackbird_core::{
-    AppState, TrackDisplayDetails, blackbird_state::TrackId, util::seconds_to_hms_string,
+    AppState, TrackDisplayDetails,
+    blackbird_state::{Track, TrackId},
+    util::seconds_to_hms_string,
 };
 use egui::{
-    CentralPanel, Color32, Context, Key, Sense, TextEdit, TextFormat, TextStyle, Ui, Vec2, Vec2b,
-    ViewportId, vec2,
+    CentralPanel, Color32, Context, Key, Modifiers, Sense, TextEdit, TextFormat, TextStyle, Ui,
+    Vec2, Vec2b, ViewportId,
+    text::{CCursor, CCursorRange},
+    vec2,
 };
 
 use crate::{bc, ui::style, ui::style::StyleExt, ui::util::global_window_builder};
@@ -17,6 +25,8 @@ pub fn ui(
     style: &style::Style,
     search_open: &mut bool,
     search_query: &mut String,
+    suggestion_debouncer: &mut SuggestionDebouncer,
+    history: &mut SearchHistory,
 ) {
     if !*search_open {
         // Close the viewport if it exists
@@ -33,17 +43,61 @@ pub fn ui(
 
     ctx.show_viewport_immediate(search_viewport_id(), viewport_builder, |ctx, _class| {
         CentralPanel::default().show(ctx, |ui| {
+            // Up and Down step through past queries. Consumed before the
+            // input sees them, as it always has focus.
+            let recalled = ui.input_mut(|i| {
+                if i.consume_key(Modifiers::NONE, Key::ArrowUp) {
+                    history.older(search_query)
+                } else if i.consume_key(Modifiers::NONE, Key::ArrowDown) {
+                    history.newer()
+                } else {
+                    None
+                }
+            });
+            if let Some(query) = &recalled {
+                *search_query = query.clone();
+            }
+
             let response = ui.add_sized(
                 Vec2::new(ui.available_width(), ui.text_style_height(&TextStyle::Body)),
                 TextEdit::singleline(search_query).hint_text("Your search here..."),
             );
             response.request_focus();
+            if response.changed() {
+                history.stop_browsing();
+            }
+            if recalled.is_some()
+                && let Some(mut state) = TextEdit::load_state(ctx, response.id)
+            {
+                let end = CCursor::new(search_query.chars().count());
+                state.cursor.set_char_range(Some(CCursorRange::one(end)));
+                state.store(ctx, response.id);
+            }
+
+            if let Some(query) = suggestion_debouncer.poll(search_query) {
+                logic.request_search_suggestions(query);
+                logic.search_server(query);
+            }
+            if suggestion_debouncer.is_waiting() {
+                ctx.request_repaint_after(search_suggestions::DEBOUNCE);
+            }
+            if search_query.len() >= search_suggestions::MIN_QUERY_LEN
+                && let Some(suggestions) = logic.get_search_suggestions(search_query)
+                && let Some(picked) = render_suggestions(ui, &suggestions, style)
+            {
+                *search_query = picked;
+                history.stop_browsing();
+            }
 
             let mut play_first_track = false;
             let mut goto_first_track = false;
             if response.has_focus() {
                 if ui.input(|i| i.key_pressed(Key::Escape)) {
                     clear = true;
+                } else if ui.input(|i| i.modifiers.command && i.key_pressed(Key::G)) {
+                    // Back to the library, at the playing track.
+                    goto_track_id = logic.get_playing_track_id();
+                    clear = true;
                 } else if ui.input(|i| i.key_pressed(Key::Enter)) {
                     if ui.input(|i| i.modifiers.shift) {
                         goto_first_track = true;
@@ -65,9 +119,19 @@ pub fn ui(
                     return;
                 }
 
+                // Fetched before taking the write lock below.
+                let server_results = logic.get_server_search(search_query);
                 let app_state = logic.get_state();
                 let mut app_state = app_state.write().unwrap();
-                let results = app_state.library.search(search_query);
+                let mut results = app_state.library.search(search_query);
+                let mut remote = HashMap::new();
+                let server_start = server_results.map(|server| {
+                    remote = search_suggestions::remote_tracks(&server, &app_state.library);
+                    search_suggestions::append_server_results(
+                        &mut results,
+                        server.into_iter().map(|track| track.id),
+                    )
+                });
                 if results.is_empty() {
                     ui.label("No results found...");
                     return;
@@ -76,7 +140,7 @@ pub fn ui(
                 // If Enter was pressed and we have results, select the first item
                 if play_first_track && !results.is_empty() {
                     requested_track_id = Some(results[0].clone());
-                } else if goto_first_track && !results.is_empty() {
+                } else if goto_first_track && !remote.contains_key(&results[0]) {
                     goto_track_id = Some(results[0].clone());
                 }
 
@@ -87,7 +151,15 @@ pub fn ui(
                         ui.text_style_height(&TextStyle::Body),
                         results.len(),
                         |ui, row_indices| {
-                            render_search_results(ui, row_indices, &results, &app_state, style)
+                            render_search_results(
+                                ui,
+                                row_indices,
+                                &results,
+                                server_start,
+                                &remote,
+                                &app_state,
+                                style,
+                            )
                         },
                     );
 
@@ -102,35 +174,83 @@ pub fn ui(
             }
 
             if let Some(track_id) = &requested_track_id {
-                logic.request_play_track(track_id);
+                logic.load_and_play_track(track_id);
                 clear = true;
             } else if let Some(track_id) = goto_track_id.take() {
                 let state = logic.get_state();
                 let mut state = state.write().unwrap();
                 state.last_requested_track_for_ui_scroll = Some(track_id);
                 clear = true;
+                ctx.send_viewport_cmd_to(ViewportId::ROOT, egui::ViewportCommand::Focus);
             }
 
             if clear {
                 *search_open = false;
+                history.record(search_query);
                 search_query.clear();
             }
         });
     });
 }
 
-/// Renders search result rows and returns the clicked track ID if any
+/// Renders the server's artist/album suggestions as a row of links and
+/// returns the text of the clicked suggestion, if any.
+fn render_suggestions(
+    ui: &mut Ui,
+    suggestions: &[bc::SearchSuggestion],
+    style: &style::Style,
+) -> Option<String> {
+    if suggestions.is_empty() {
+        return None;
+    }
+
+    let mut picked = None;
+    ui.horizontal_wrapped(|ui| {
+        for suggestion in suggestions {
+            let text = egui::RichText::new(search_suggestions::label(suggestion))
+                .color(style.album_color32());
+            if ui.link(text).clicked() {
+                picked = Some(suggestion.text().to_string());
+            }
+        }
+    });
+    picked
+}
+
+/// Renders search result rows and returns the clicked track ID if any.
+/// Rows from `server_start` on are marked as found only by the server, and
+/// those in `remote` as missing from the library.
 fn render_search_results(
     ui: &mut Ui,
     row_indices: Range<usize>,
     results: &[TrackId],
+    server_start: Option<usize>,
+    remote: &HashMap<TrackId, Track>,
     app_state: &AppState,
     style: &style::Style,
 ) -> Option<TrackId> {
     let mut requested_track_id = None;
-    for id in &results[row_indices] {
-        let Some(details) = TrackDisplayDetails::from_track_id(id, app_state) else {
-            continue;
+    for index in row_indices {
+        let id = &results[index];
+        let details = TrackDisplayDetails::from_track_id(id, app_state);
+        let (source, artist, title, duration_secs) = match (&details, remote.get(id)) {
+            (Some(details), _) => (
+                if server_start.is_some_and(|start| index >= start) {
+                    "server: "
+                } else {
+                    ""
+                },
+                details.artist(),
+                details.track_title.as_str(),
+                details.track_duration.as_secs() as u32,
+            ),
+            (None, Some(track)) => (
+                "remote: ",
+                track.artist.as_deref().unwrap_or_default(),
+                track.title.as_str(),
+                track.duration.unwrap_or_default(),
+            ),
+            (None, None) => continue,
         };
 
         let font_id = TextStyle::Body.resolve(ui.style());
@@ -153,7 +273,6 @@ fn render_search_results(
         };
 
         let is_hovered = response.hovered();
-        let artist = details.artist();
         let [artist_color, track_color, length_color] = [
             style::string_to_colour(artist).into(),
             style.track_name_color32(),
@@ -162,6 +281,17 @@ fn render_search_results(
         .map(|color| if is_hovered { color } else { darken(color) });
         let layout_job = {
             let mut layout_job = egui::text::LayoutJob::default();
+            if !source.is_empty() {
+                layout_job.append(
+                    source,
+                    0.0,
+                    TextFormat {
+                        color: length_color,
+                        font_id: font_id.clone(),
+                        ..Default::default()
+                    },
+                );
+            }
             layout_job.append(
                 artist,
                 0.0,
@@ -180,7 +310,7 @@ fn render_search_results(
                 },
             );
             layout_job.append(
-                &details.track_title,
+                title,
                 0.0,
                 TextFormat {
                     color: track_color,
@@ -189,10 +319,7 @@ fn render_search_results(
                 },
             );
             layout_job.append(
-                &format!(
-                    " [{}]",
-                    seconds_to_hms_string(details.track_duration.as_secs() as u32, false)
-                ),
+                &format!(" [{}]", seconds_to_hms_string(duration_secs, false)),
                 0.0,
                 TextFormat {
                     color: length_color,
@@ -0,0 +1,36 @@
+use blackbird_client_shared::server_info;
+use egui::{Align2, Context, Grid, RichText, Window};
+
+use crate::{
+    bc,
+    ui::{style, style::StyleExt},
+};
+
+pub fn ui(logic: &bc::Logic, ctx: &Context, style: &style::Style, server_info_open: &mut bool) {
+    let info = logic.server_info();
+    let username = logic.get_server_username();
+
+    Window::new("Server info")
+        .open(server_info_open)
+        .default_pos(ctx.screen_rect().center())
+        .pivot(Align2::CENTER_CENTER)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let Some(info) = info else {
+                ui.label(server_info::UNAVAILABLE_MESSAGE);
+                return;
+            };
+
+            Grid::new("server_info_grid")
+                .num_columns(2)
+                .spacing([16.0, 4.0])
+                .show(ui, |ui| {
+                    for (label, value) in server_info::rows(&info, &username) {
+                        ui.label(RichText::new(label).color(style.album_color32()).strong());
+                        ui.label(RichText::new(value).color(style.text_color32()));
+                        ui.end_row();
+                    }
+                });
+        });
+}
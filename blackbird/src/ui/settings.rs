@@ -3,7 +3,10 @@ use egui::{
     Window, ecolor::Hsva,
 };
 
-use blackbird_client_shared::{config::AlbumArtStyle, style as shared_style};
+use blackbird_client_shared::{
+    config::{AlbumArtStyle, FollowPlaying, MissingArt},
+    style as shared_style,
+};
 
 use crate::config::{Config, General, Keybindings};
 
@@ -62,12 +65,6 @@ pub fn ui(ctx: &Context, config: &mut Config, settings: &mut SettingsState) -> b
                             &server_default.password,
                             &mut settings.show_password,
                         );
-                        server_changed |= bool_row(
-                            ui,
-                            "Transcode",
-                            &mut config.shared.server.transcode,
-                            &server_default.transcode,
-                        );
 
                         reset_section_button(ui, config.shared.server != server_default, || {
                             config.shared.server = server_default;
@@ -89,6 +86,16 @@ pub fn ui(ctx: &Context, config: &mut Config, settings: &mut SettingsState) -> b
                             "Album art style",
                             &mut config.shared.layout.album_art_style,
                             &layout_default.album_art_style,
+                            AlbumArtStyle::ALL,
+                            AlbumArtStyle::as_str,
+                        );
+                        changed |= enum_row(
+                            ui,
+                            "Missing art",
+                            &mut config.shared.layout.missing_art,
+                            &layout_default.missing_art,
+                            MissingArt::ALL,
+                            MissingArt::as_str,
                         );
                         changed |= usize_row(
                             ui,
@@ -98,6 +105,32 @@ pub fn ui(ctx: &Context, config: &mut Config, settings: &mut SettingsState) -> b
                             0,
                             10,
                         );
+                        changed |= bool_row(
+                            ui,
+                            "Abbreviate play counts",
+                            &mut config.shared.layout.abbreviate_play_counts,
+                            &layout_default.abbreviate_play_counts,
+                        );
+                        changed |= enum_row(
+                            ui,
+                            "Follow playing",
+                            &mut config.shared.layout.follow_playing,
+                            &layout_default.follow_playing,
+                            FollowPlaying::ALL,
+                            FollowPlaying::as_str,
+                        );
+                        changed |= bool_row(
+                            ui,
+                            "Show album gain",
+                            &mut config.shared.layout.show_album_gain,
+                            &layout_default.show_album_gain,
+                        );
+                        changed |= bool_row(
+                            ui,
+                            "Reduced motion",
+                            &mut config.shared.layout.reduced_motion,
+                            &layout_default.reduced_motion,
+                        );
 
                         reset_section_button(ui, config.shared.layout != layout_default, || {
                             config.shared.layout = layout_default;
@@ -108,6 +141,14 @@ pub fn ui(ctx: &Context, config: &mut Config, settings: &mut SettingsState) -> b
                     // ── Playback ────────────────────────────────────
                     let playback_default = blackbird_client_shared::config::Playback::default();
                     section(ui, "Playback", |ui| {
+                        // Stored with the server settings, but applied without
+                        // reloading the library.
+                        changed |= bool_row(
+                            ui,
+                            "Transcode",
+                            &mut config.shared.server.transcode,
+                            &blackbird_shared::config::Server::default().transcode,
+                        );
                         changed |= bool_row(
                             ui,
                             "Apply ReplayGain",
@@ -123,6 +164,12 @@ pub fn ui(ctx: &Context, config: &mut Config, settings: &mut SettingsState) -> b
                             12.0,
                             0.5,
                         );
+                        changed |= bool_row(
+                            ui,
+                            "Resume albums",
+                            &mut config.shared.playback.resume_albums,
+                            &playback_default.resume_albums,
+                        );
 
                         reset_section_button(
                             ui,
@@ -150,7 +197,7 @@ pub fn ui(ctx: &Context, config: &mut Config, settings: &mut SettingsState) -> b
                                     for i in start..end {
                                         let (_, human_label) = shared_style::Style::FIELD_NAMES[i];
                                         let default_hsv = shared_style::Style::default_field(i);
-                                        let current = config.style.field_mut(i);
+                                        let current = config.style.base.field_mut(i);
                                         let label = human_readable_label(human_label);
 
                                         col.horizontal(|ui| {
@@ -178,8 +225,8 @@ pub fn ui(ctx: &Context, config: &mut Config, settings: &mut SettingsState) -> b
                                 }
                             });
 
-                            reset_section_button(ui, config.style != style_default, || {
-                                config.style = style_default;
+                            reset_section_button(ui, config.style.base != style_default, || {
+                                config.style.base = style_default;
                                 changed = true;
                             });
                         });
@@ -220,6 +267,27 @@ pub fn ui(ctx: &Context, config: &mut Config, settings: &mut SettingsState) -> b
                                 100,
                                 30000,
                             );
+                            changed |= bool_row(
+                                ui,
+                                "Now playing overlay",
+                                &mut config.general.now_playing_osd,
+                                &general_default.now_playing_osd,
+                            );
+                            changed |= f32_row(
+                                ui,
+                                "Overlay duration (s)",
+                                &mut config.general.now_playing_osd_secs,
+                                &general_default.now_playing_osd_secs,
+                                0.5,
+                                10.0,
+                                0.1,
+                            );
+                            changed |= bool_row(
+                                ui,
+                                "Track in window title",
+                                &mut config.general.dynamic_window_title,
+                                &general_default.dynamic_window_title,
+                            );
 
                             reset_section_button(
                                 ui,
@@ -227,13 +295,25 @@ pub fn ui(ctx: &Context, config: &mut Config, settings: &mut SettingsState) -> b
                                     != layout_default.scroll_multiplier
                                     || config.general.repaint_secs != general_default.repaint_secs
                                     || config.general.incremental_search_timeout_ms
-                                        != general_default.incremental_search_timeout_ms,
+                                        != general_default.incremental_search_timeout_ms
+                                    || config.general.now_playing_osd
+                                        != general_default.now_playing_osd
+                                    || config.general.now_playing_osd_secs
+                                        != general_default.now_playing_osd_secs
+                                    || config.general.dynamic_window_title
+                                        != general_default.dynamic_window_title,
                                 || {
                                     config.shared.layout.scroll_multiplier =
                                         layout_default.scroll_multiplier;
                                     config.general.repaint_secs = general_default.repaint_secs;
                                     config.general.incremental_search_timeout_ms =
                                         general_default.incremental_search_timeout_ms;
+                                    config.general.now_playing_osd =
+                                        general_default.now_playing_osd;
+                                    config.general.now_playing_osd_secs =
+                                        general_default.now_playing_osd_secs;
+                                    config.general.dynamic_window_title =
+                                        general_default.dynamic_window_title;
                                     changed = true;
                                 },
                             );
@@ -446,21 +526,23 @@ fn bool_row(ui: &mut egui::Ui, label: &str, value: &mut bool, default: &bool) ->
 }
 
 /// An enum field row (label | combo box | reset). Returns `true` if the value changed.
-fn enum_row(
+fn enum_row<T: Copy + PartialEq>(
     ui: &mut egui::Ui,
     label: &str,
-    value: &mut AlbumArtStyle,
-    default: &AlbumArtStyle,
+    value: &mut T,
+    default: &T,
+    variants: &[T],
+    as_str: fn(&T) -> &'static str,
 ) -> bool {
     let mut changed = false;
     ui.horizontal(|ui| {
         label_cell(ui, label);
         ComboBox::from_id_salt(label)
-            .selected_text(value.as_str())
+            .selected_text(as_str(value))
             .show_ui(ui, |ui| {
-                for variant in AlbumArtStyle::ALL {
+                for variant in variants {
                     if ui
-                        .selectable_value(value, *variant, variant.as_str())
+                        .selectable_value(value, *variant, as_str(variant))
                         .changed()
                     {
                         changed = true;
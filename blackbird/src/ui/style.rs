@@ -1,8 +1,36 @@
 use blackbird_client_shared::style as shared_style;
-use egui::{Color32, ecolor::Hsva};
+use egui::{Color32, Rect, Stroke, StrokeKind, Ui, ecolor::Hsva};
+use serde::{Deserialize, Serialize};
 
-/// Re-export the shared Style type.
-pub use shared_style::Style;
+/// egui style configuration, extending the shared [`shared_style::Style`]
+/// with egui-specific fields. Unknown fields from other clients are
+/// preserved via the catch-all.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Style {
+    /// Radius of the album art's corners, in points. Zero keeps them square.
+    #[serde(default)]
+    pub album_art_corner_radius: f32,
+    /// Whether to draw a subtle border around album art.
+    #[serde(default)]
+    pub album_art_border: bool,
+    /// Shared style settings.
+    #[serde(flatten)]
+    pub base: shared_style::Style,
+    /// Catch-all for unknown fields from other clients.
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            album_art_corner_radius: 0.0,
+            album_art_border: false,
+            base: shared_style::Style::default(),
+            extra: toml::Table::new(),
+        }
+    }
+}
 
 /// Extension trait for Style to get egui Color32 values.
 pub trait StyleExt {
@@ -23,37 +51,54 @@ fn hsv_to_egui_color32([h, s, v]: shared_style::Hsv) -> Color32 {
 }
 impl StyleExt for Style {
     fn background_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.background_hsv)
+        hsv_to_egui_color32(self.base.background_hsv)
     }
     fn text_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.text_hsv)
+        hsv_to_egui_color32(self.base.text_hsv)
     }
     fn album_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.album_hsv)
+        hsv_to_egui_color32(self.base.album_hsv)
     }
     fn album_length_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.album_length_hsv)
+        hsv_to_egui_color32(self.base.album_length_hsv)
     }
     fn album_year_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.album_year_hsv)
+        hsv_to_egui_color32(self.base.album_year_hsv)
     }
     fn track_number_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.track_number_hsv)
+        hsv_to_egui_color32(self.base.track_number_hsv)
     }
     fn track_length_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.track_length_hsv)
+        hsv_to_egui_color32(self.base.track_length_hsv)
     }
     fn track_name_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.track_name_hsv)
+        hsv_to_egui_color32(self.base.track_name_hsv)
     }
     fn track_name_hovered_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.track_name_hovered_hsv)
+        hsv_to_egui_color32(self.base.track_name_hovered_hsv)
     }
     fn track_name_playing_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.track_name_playing_hsv)
+        hsv_to_egui_color32(self.base.track_name_playing_hsv)
     }
     fn track_duration_color32(&self) -> Color32 {
-        hsv_to_egui_color32(self.track_duration_hsv)
+        hsv_to_egui_color32(self.base.track_duration_hsv)
+    }
+}
+
+/// Rounds the corners of album art as configured.
+pub fn album_art_image<'a>(style: &Style, image: egui::Image<'a>) -> egui::Image<'a> {
+    image.corner_radius(style.album_art_corner_radius)
+}
+
+/// Draws the configured border around album art shown in `rect`, if any.
+pub fn paint_album_art_border(ui: &Ui, style: &Style, rect: Rect) {
+    if style.album_art_border {
+        ui.painter().rect_stroke(
+            rect,
+            style.album_art_corner_radius,
+            Stroke::new(1.0, style.text_color32().gamma_multiply(0.25)),
+            StrokeKind::Inside,
+        );
     }
 }
 
@@ -62,3 +107,9 @@ pub fn string_to_colour(s: &str) -> Hsva {
     let hsv = shared_style::string_to_hsv(s);
     Hsva::new(hsv[0], hsv[1], hsv[2], 1.0)
 }
+
+/// The colour shown in place of the art of an album without any.
+pub fn missing_art_colour(artist: &str, album: &str) -> Hsva {
+    let hsv = shared_style::missing_art_hsv(artist, album);
+    Hsva::new(hsv[0], hsv[1], hsv[2], 1.0)
+}
@@ -1,4 +1,10 @@
-use egui::{Align2, Context, Pos2, Rect, Sense, TextStyle, Ui, Vec2, ViewportBuilder, pos2, vec2};
+use blackbird_client_shared::accessibility::{StarTarget, star_label};
+use egui::{
+    Align2, Context, PointerButton, Pos2, Rect, Sense, TextStyle, Ui, Vec2, ViewportBuilder,
+    WidgetInfo, WidgetType, pos2, vec2,
+};
+
+use crate::ui::keys::KEY_TOGGLE_FOCUSED_HEART;
 
 /// Calculate the total spacing between tracks (base egui spacing + extra spacing)
 pub fn track_spacing(ui: &Ui) -> f32 {
@@ -11,18 +17,24 @@ pub fn spaced_row_height(ui: &Ui) -> f32 {
     row_height + track_spacing(ui)
 }
 
-/// Draw a clickable heart.
+/// Where [`draw_heart`] puts the heart.
 pub enum HeartPlacement {
     Position { pos: Pos2, right_aligned: bool },
     Space,
 }
+
+/// Draw a clickable heart. It is keyboard-focusable, shows itself while
+/// focused, and is announced as a star toggle for `target`. Returns its
+/// response, whether it was toggled (by a click, or by
+/// [`KEY_TOGGLE_FOCUSED_HEART`] while focused) and its size.
 pub fn draw_heart(
     ui: &mut Ui,
     font: egui::FontId,
     placement: HeartPlacement,
     active: bool,
     show_outline_when_inactive: bool,
-) -> (egui::Response, f32) {
+    target: StarTarget,
+) -> (egui::Response, bool, f32) {
     let size = ui.fonts(|f| f.row_height(&font));
 
     let rect = if let HeartPlacement::Position { pos, right_aligned } = placement {
@@ -32,8 +44,22 @@ pub fn draw_heart(
         ui.allocate_space(vec2(size, size)).1
     };
     let response = ui.allocate_rect(rect, Sense::click());
+    response.widget_info(|| {
+        WidgetInfo::selected(
+            WidgetType::Checkbox,
+            true,
+            active,
+            star_label(target, active),
+        )
+    });
+
+    // egui also clicks focused widgets on Space, which is play/pause, so
+    // keyboard toggles are taken from their own key instead.
+    let toggled = response.clicked_by(PointerButton::Primary)
+        || (response.has_focus() && ui.input(|i| i.key_pressed(KEY_TOGGLE_FOCUSED_HEART)));
 
-    let hovered = response.hovered();
+    // Treat focus like hover so keyboard users can see what they're on.
+    let hovered = response.hovered() || response.has_focus();
 
     let state = blackbird_client_shared::style::HeartState::from_interaction(active, hovered);
     let visible = state.visible() || show_outline_when_inactive;
@@ -61,7 +87,7 @@ pub fn draw_heart(
         );
     }
 
-    (response, size)
+    (response, toggled, size)
 }
 
 /// Create a viewport builder for a global popup window, centered on the monitor.
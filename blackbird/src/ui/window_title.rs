@@ -0,0 +1,50 @@
+//! Keeps the window title in sync with the playing track, so the taskbar and
+//! alt-tab show what's playing.
+use blackbird_core::blackbird_state::TrackId;
+use egui::{Context, ViewportCommand};
+
+use crate::{bc, config::Config};
+
+/// The title used when nothing is playing, or when the title is static.
+const STATIC_TITLE: &str = "blackbird";
+
+/// What the title was last built from, so it's only rebuilt when the track or
+/// playback state changes.
+type TitleKey = Option<(TrackId, bc::PlaybackState)>;
+
+#[derive(Default)]
+pub struct WindowTitleState {
+    /// `None` until the first update, so the initial title is always sent.
+    last_key: Option<TitleKey>,
+}
+
+pub fn update(ctx: &Context, config: &Config, logic: &bc::Logic, state: &mut WindowTitleState) {
+    let key = if config.general.dynamic_window_title {
+        logic
+            .get_playing_track_id()
+            .map(|track_id| (track_id, logic.get_playback_state()))
+    } else {
+        None
+    };
+    if state.last_key.as_ref() == Some(&key) {
+        return;
+    }
+
+    let title = key
+        .as_ref()
+        .and_then(|(_, playback_state)| {
+            let details = logic.get_track_display_details()?;
+            let glyph = match playback_state {
+                bc::PlaybackState::Playing => "\u{25B6}",
+                bc::PlaybackState::Paused | bc::PlaybackState::Stopped => "\u{23F8}",
+            };
+            let artist = details
+                .track_artist
+                .as_ref()
+                .unwrap_or(&details.album_artist);
+            Some(format!("{glyph} {artist} - {}", details.track_title))
+        })
+        .unwrap_or_else(|| STATIC_TITLE.to_string());
+    ctx.send_viewport_cmd(ViewportCommand::Title(title));
+    state.last_key = Some(key);
+}